@@ -0,0 +1,64 @@
+//! Atomic file writes (ATOMIC-001).
+//!
+//! Config and state files are written to a temporary file in the same
+//! directory, synced, and renamed over the target, so a crash mid-write
+//! leaves either the old or the new content, never a truncated file.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use crate::error::Error;
+
+/// Atomically replace `path` with `contents`, keeping its permissions.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), Error> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = dir.join(format!(".{}.{}.tmp", name, std::process::id()));
+
+    let result = (|| {
+        let mut file = File::create(&tmp)?;
+        file.write_all(contents.as_ref())?;
+        if let Ok(meta) = fs::metadata(path) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        // Persist the rename itself; not possible on every platform
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+        Ok(())
+    })();
+
+    result.map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        Error::IoAt {
+            path: path.to_path_buf(),
+            source: e,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_replaces_content_and_leaves_no_temp_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        write(&path, "{}").unwrap();
+        write(&path, "{\"a\": 1}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"a\": 1}");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
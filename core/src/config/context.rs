@@ -0,0 +1,54 @@
+//! Pinned memories in agent context files.
+
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Pinned memories written into agent context files, for tools that don't use MCP.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContextConfig {
+    /// Keep a section of pinned memories in `files`, refreshed on commit and `sqrl apply`.
+    #[serde(default)]
+    pub section: bool,
+
+    /// Files holding the section, relative to the project root. Created if missing.
+    #[serde(default = "default_context_files")]
+    pub files: Vec<String>,
+}
+
+fn default_context_files() -> Vec<String> {
+    vec!["CLAUDE.md".to_string()]
+}
+
+impl Default for ContextConfig {
+    fn default() -> Self {
+        Self {
+            section: false,
+            files: default_context_files(),
+        }
+    }
+}
+
+impl ContextConfig {
+    /// Reject files that could point outside the project: absolute paths and
+    /// `..` segments.
+    pub fn check(&self) -> Result<(), Error> {
+        for file in &self.files {
+            let normalized = crate::paths::normalize(file);
+            let escapes = normalized.starts_with('/')
+                || Path::new(file).is_absolute()
+                || Path::new(file).has_root()
+                || normalized.split('/').any(|segment| segment == "..");
+            if escapes || normalized.is_empty() {
+                return Err(Error::ConfigParse(format!(
+                    "context.files: '{}' must be a file path inside the project",
+                    file
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! Which files count as documentation (CONFIG-006).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Documentation file settings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DocsConfig {
+    /// File extensions considered documentation.
+    #[serde(default = "default_extensions")]
+    pub extensions: Vec<String>,
+
+    /// Paths to include (relative to project root).
+    #[serde(default = "default_include_paths")]
+    pub include_paths: Vec<String>,
+
+    /// Paths to exclude.
+    #[serde(default = "default_exclude_paths")]
+    pub exclude_paths: Vec<String>,
+
+    /// Paths that are docs whatever their extension (`docs/`, `README`).
+    #[serde(default = "default_doc_paths")]
+    pub doc_paths: Vec<String>,
+}
+
+fn default_extensions() -> Vec<String> {
+    vec![
+        "md".to_string(),
+        "mdc".to_string(),
+        "txt".to_string(),
+        "rst".to_string(),
+    ]
+}
+
+fn default_include_paths() -> Vec<String> {
+    vec![
+        "specs/".to_string(),
+        "docs/".to_string(),
+        ".claude/".to_string(),
+        ".cursor/".to_string(),
+    ]
+}
+
+fn default_doc_paths() -> Vec<String> {
+    vec![
+        "docs/".to_string(),
+        "README".to_string(),
+        "CHANGELOG".to_string(),
+        "CONTRIBUTING".to_string(),
+        "AUTHORS".to_string(),
+    ]
+}
+
+fn default_exclude_paths() -> Vec<String> {
+    vec![
+        "node_modules/".to_string(),
+        "target/".to_string(),
+        ".git/".to_string(),
+        "vendor/".to_string(),
+        "dist/".to_string(),
+    ]
+}
+
+impl Default for DocsConfig {
+    fn default() -> Self {
+        Self {
+            extensions: default_extensions(),
+            include_paths: default_include_paths(),
+            exclude_paths: default_exclude_paths(),
+            doc_paths: default_doc_paths(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Project config file formats (CONFIG-002).
+//!
+//! `.sqrl/config.yaml` (default) or `.sqrl/config.toml`, auto-detected.
+
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// Supported config file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ConfigFormat {
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// File name inside `.sqrl/`.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ConfigFormat::Yaml => "config.yaml",
+            ConfigFormat::Toml => "config.toml",
+        }
+    }
+
+    /// Config file path for a project in this format.
+    pub fn path(self, project_root: &Path) -> PathBuf {
+        project_root.join(".sqrl").join(self.file_name())
+    }
+
+    /// Detect which format a project uses. Errors if both files exist.
+    pub fn detect(project_root: &Path) -> Result<Option<Self>, Error> {
+        let yaml = ConfigFormat::Yaml.path(project_root).exists();
+        let toml = ConfigFormat::Toml.path(project_root).exists();
+        match (yaml, toml) {
+            (true, true) => Err(Error::ConfigParse(
+                "Both .sqrl/config.yaml and .sqrl/config.toml exist; remove one".to_string(),
+            )),
+            (true, false) => Ok(Some(ConfigFormat::Yaml)),
+            (false, true) => Ok(Some(ConfigFormat::Toml)),
+            (false, false) => Ok(None),
+        }
+    }
+
+    /// Parse file content.
+    pub fn parse<T: DeserializeOwned>(self, content: &str) -> Result<T, Error> {
+        match self {
+            ConfigFormat::Yaml => {
+                serde_yaml::from_str(content).map_err(|e| Error::ConfigParse(e.to_string()))
+            }
+            ConfigFormat::Toml => {
+                toml::from_str(content).map_err(|e| Error::ConfigParse(e.to_string()))
+            }
+        }
+    }
+
+    /// Render file content with a header comment.
+    pub fn render<T: Serialize>(self, value: &T) -> Result<String, Error> {
+        let body = match self {
+            ConfigFormat::Yaml => {
+                serde_yaml::to_string(value).map_err(|e| Error::ConfigParse(e.to_string()))?
+            }
+            ConfigFormat::Toml => {
+                toml::to_string_pretty(value).map_err(|e| Error::ConfigParse(e.to_string()))?
+            }
+        };
+        Ok(format!("# Squirrel project configuration\n\n{}", body))
+    }
+}
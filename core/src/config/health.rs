@@ -0,0 +1,140 @@
+//! Health score settings (CONFIG-011).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Health score settings for `sqrl status --score` and the badge (CONFIG-011).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HealthConfig {
+    /// Relative weight of each part of the score; 0 leaves a part out.
+    #[serde(default)]
+    pub weights: HealthWeights,
+
+    /// Days since the first code commit without a doc change that bring doc debt to 0.
+    #[serde(default = "default_doc_debt_days")]
+    pub doc_debt_days: u32,
+
+    /// Code commits without a doc change that bring doc debt to 0.
+    #[serde(default = "default_doc_debt_commits")]
+    pub doc_debt_commits: u32,
+
+    /// Memories not stored, edited or reinforced for this many days are stale.
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u32,
+}
+
+/// Relative weights of the health score's parts.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HealthWeights {
+    /// Doc debt: code committed since docs last changed.
+    #[serde(default = "default_docs_weight")]
+    pub docs: u32,
+
+    /// Share of memories still in use.
+    #[serde(default = "default_memories_weight")]
+    pub memories: u32,
+
+    /// Git hooks installed and current.
+    #[serde(default = "default_hooks_weight")]
+    pub hooks: u32,
+}
+
+fn default_doc_debt_days() -> u32 {
+    30
+}
+
+fn default_doc_debt_commits() -> u32 {
+    20
+}
+
+fn default_stale_days() -> u32 {
+    90
+}
+
+fn default_docs_weight() -> u32 {
+    40
+}
+
+fn default_memories_weight() -> u32 {
+    30
+}
+
+fn default_hooks_weight() -> u32 {
+    30
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            weights: HealthWeights::default(),
+            doc_debt_days: default_doc_debt_days(),
+            doc_debt_commits: default_doc_debt_commits(),
+            stale_days: default_stale_days(),
+        }
+    }
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            docs: default_docs_weight(),
+            memories: default_memories_weight(),
+            hooks: default_hooks_weight(),
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Reject settings the score can't use: all weights 0, or a 0 limit.
+    pub fn check(&self) -> Result<(), Error> {
+        let weights = &self.weights;
+        let total = weights
+            .docs
+            .saturating_add(weights.memories)
+            .saturating_add(weights.hooks);
+        if total == 0 {
+            return Err(Error::ConfigParse(
+                "health.weights: at least one weight must be above 0".to_string(),
+            ));
+        }
+        for (key, value) in [
+            ("doc_debt_days", self.doc_debt_days),
+            ("doc_debt_commits", self.doc_debt_commits),
+            ("stale_days", self.stale_days),
+        ] {
+            if value == 0 {
+                return Err(Error::ConfigParse(format!(
+                    "health.{}: must be above 0",
+                    key
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_weights_checked_without_overflow() {
+        let mut health = HealthConfig {
+            weights: HealthWeights {
+                docs: u32::MAX,
+                memories: 1,
+                hooks: 0,
+            },
+            ..HealthConfig::default()
+        };
+        assert!(health.check().is_ok());
+        health.weights = HealthWeights {
+            docs: 0,
+            memories: 0,
+            hooks: 0,
+        };
+        assert!(health.check().is_err());
+    }
+}
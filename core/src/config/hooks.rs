@@ -0,0 +1,53 @@
+//! Git hook behavior.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::default_true;
+
+/// Git hooks behavior.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HooksConfig {
+    /// Auto-install hooks when git detected.
+    #[serde(default = "default_true")]
+    pub auto_install: bool,
+
+    /// Fail the pre-push hook when code changes without any doc update.
+    #[serde(default)]
+    pub pre_push_block: bool,
+
+    /// Record the active memories on each commit, for `sqrl memory at`.
+    #[serde(default)]
+    pub snapshots: bool,
+
+    /// Regenerate files derived from memories when switching branches.
+    #[serde(default)]
+    pub on_checkout: bool,
+
+    /// Language of the pre-push messages: `auto` (from `LANG`), `en` or `de`.
+    #[serde(default = "default_locale")]
+    pub locale: String,
+
+    /// Pre-push message overrides by key, e.g. `push_blocked_hint` (CONFIG-010).
+    #[serde(default)]
+    pub messages: BTreeMap<String, String>,
+}
+
+fn default_locale() -> String {
+    "auto".to_string()
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            auto_install: true,
+            pre_push_block: false,
+            snapshots: false,
+            on_checkout: false,
+            locale: default_locale(),
+            messages: BTreeMap::new(),
+        }
+    }
+}
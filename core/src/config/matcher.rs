@@ -0,0 +1,235 @@
+//! Precompiled doc path matching (CONFIG-006).
+//!
+//! `docs.include_paths`, `docs.exclude_paths` and `docs.doc_paths` are
+//! compiled into glob sets once per config load. An entry ending in `/` is
+//! a directory: includes are anchored at the project root, excludes match
+//! at any depth, and doc paths are anchored unless they are a bare file
+//! name (`README`), which matches at any depth. Entries and the paths
+//! matched against them are normalized first (PATH-001).
+
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::config::DocsConfig;
+use crate::error::Error;
+use crate::paths;
+
+/// Compiled form of the `docs` section.
+#[derive(Debug, Clone)]
+pub struct DocMatcher {
+    extensions: Vec<String>,
+    exclude: GlobSet,
+    exclude_paths: Vec<String>,
+    include: GlobSet,
+    include_paths: Vec<String>,
+    doc: GlobSet,
+    doc_paths: Vec<String>,
+}
+
+/// The `docs` setting that decided whether a path is a doc (`sqrl rules test`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocRule {
+    /// Extension in `docs.extensions`, and not excluded: a doc.
+    Extension(String),
+    /// Matched by this `docs.doc_paths` entry, and not excluded: a doc
+    /// whatever its extension.
+    Path(String),
+    /// Doc extension, but under this `docs.exclude_paths` entry.
+    Excluded(String),
+    /// Extension not in `docs.extensions`, and no `docs.doc_paths` entry matches.
+    OtherExtension,
+}
+
+impl DocMatcher {
+    /// Compile the configured patterns. Invalid globs are config errors.
+    pub fn new(docs: &DocsConfig) -> Result<Self, Error> {
+        let include_globs: Vec<String> = docs
+            .include_paths
+            .iter()
+            .map(|p| to_glob(p, false))
+            .collect();
+        let exclude_globs: Vec<String> = docs
+            .exclude_paths
+            .iter()
+            .map(|p| to_glob(p, true))
+            .collect();
+        let doc_globs: Vec<String> = docs
+            .doc_paths
+            .iter()
+            .map(|p| {
+                let entry = paths::normalize(p);
+                to_glob(
+                    &entry,
+                    !entry.trim_end_matches('/').contains('/') && !entry.ends_with('/'),
+                )
+            })
+            .collect();
+        Ok(Self {
+            extensions: docs.extensions.clone(),
+            exclude: build_set("docs.exclude_paths", &docs.exclude_paths, &exclude_globs)?,
+            exclude_paths: docs.exclude_paths.clone(),
+            include: build_set("docs.include_paths", &docs.include_paths, &include_globs)?,
+            include_paths: docs.include_paths.clone(),
+            doc: build_set("docs.doc_paths", &docs.doc_paths, &doc_globs)?,
+            doc_paths: docs.doc_paths.clone(),
+        })
+    }
+
+    /// Whether a project-relative path is a doc: doc extension or doc path,
+    /// and not excluded.
+    pub fn is_doc(&self, path: &str) -> bool {
+        matches!(self.explain(path), DocRule::Extension(_) | DocRule::Path(_))
+    }
+
+    /// Which setting makes a project-relative path a doc or not.
+    pub fn explain(&self, path: &str) -> DocRule {
+        let path = paths::normalize(path);
+        let path = path.as_str();
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .filter(|ext| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)));
+        let rule = match extension {
+            Some(extension) => DocRule::Extension(extension.to_string()),
+            None => match self.doc.matches(path).first() {
+                Some(&index) => DocRule::Path(self.doc_paths[index].clone()),
+                None => return DocRule::OtherExtension,
+            },
+        };
+        match self.exclude.matches(path).first() {
+            Some(&index) => DocRule::Excluded(self.exclude_paths[index].clone()),
+            None => rule,
+        }
+    }
+
+    /// Whether docguard lists a path among the project's docs: a top-level
+    /// doc, a doc under `docs.include_paths`, or any doc path.
+    pub fn is_listed(&self, path: &str) -> bool {
+        let path = paths::normalize(path);
+        match self.explain(&path) {
+            DocRule::Path(_) => true,
+            DocRule::Extension(_) => !path.contains('/') || self.included_by(&path).is_some(),
+            _ => false,
+        }
+    }
+
+    /// The `docs.include_paths` entry that lists a path among the project's docs.
+    pub fn included_by(&self, path: &str) -> Option<&str> {
+        let index = *self.include.matches(paths::normalize(path)).first()?;
+        Some(&self.include_paths[index])
+    }
+}
+
+/// Turn a config entry into a glob pattern.
+fn to_glob(entry: &str, anywhere: bool) -> String {
+    let entry = paths::normalize(entry);
+    let pattern = match entry.strip_suffix('/') {
+        Some(dir) => format!("{}/**/*", dir),
+        None => entry.to_string(),
+    };
+    if anywhere && !pattern.starts_with("**/") && !pattern.starts_with('/') {
+        format!("**/{}", pattern)
+    } else {
+        pattern.trim_start_matches('/').to_string()
+    }
+}
+
+fn build_set(key: &str, entries: &[String], globs: &[String]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+    for (entry, glob) in entries.iter().zip(globs) {
+        let glob = Glob::new(glob).map_err(|e| {
+            Error::ConfigParse(format!(
+                "{}: invalid pattern '{}': {}",
+                key,
+                entry,
+                e.kind()
+            ))
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| Error::ConfigParse(format!("{}: {}", key, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_docs_matching() {
+        let matcher = DocMatcher::new(&DocsConfig::default()).unwrap();
+        assert!(matcher.is_doc("README.md"));
+        assert!(matcher.is_doc("specs/INTERFACES.md"));
+        assert!(!matcher.is_doc("src/main.rs"));
+        assert!(!matcher.is_doc("web/node_modules/pkg/README.md"));
+        assert_eq!(matcher.included_by("docs/guide/setup.md"), Some("docs/"));
+
+        assert_eq!(matcher.explain("src/main.rs"), DocRule::OtherExtension);
+        assert_eq!(
+            matcher.explain("web/node_modules/pkg/README.md"),
+            DocRule::Excluded("node_modules/".to_string())
+        );
+        assert_eq!(matcher.included_by("docs/guide/intro.md"), Some("docs/"));
+    }
+
+    #[test]
+    fn test_doc_paths_match_without_extension() {
+        let matcher = DocMatcher::new(&DocsConfig::default()).unwrap();
+        for doc in [
+            "README",
+            "CHANGELOG",
+            "crates/core/README",
+            "docs/diagram.svg",
+        ] {
+            assert!(matcher.is_doc(doc), "{}", doc);
+            assert!(matcher.is_listed(doc), "{}", doc);
+        }
+        for other in ["LICENSE", "Makefile", "src/docs/mod.rs"] {
+            assert!(!matcher.is_doc(other), "{}", other);
+        }
+        assert_eq!(
+            matcher.explain("docs/diagram.svg"),
+            DocRule::Path("docs/".to_string())
+        );
+        assert_eq!(
+            matcher.explain("README.md"),
+            DocRule::Extension("md".to_string())
+        );
+        assert_eq!(
+            matcher.explain("vendor/lib/README"),
+            DocRule::Excluded("vendor/".to_string())
+        );
+        assert!(!matcher.is_listed("src/notes.md"));
+    }
+
+    #[test]
+    fn test_windows_paths_match_like_posix() {
+        let docs = DocsConfig {
+            include_paths: vec![".\\guides\\".to_string()],
+            ..Default::default()
+        };
+        let matcher = DocMatcher::new(&docs).unwrap();
+        assert!(matcher.is_listed("guides\\setup\\install.md"));
+        assert_eq!(
+            matcher.included_by("./guides/setup/install.md"),
+            Some(".\\guides\\")
+        );
+        assert_eq!(
+            matcher.explain("vendor\\lib\\README"),
+            DocRule::Excluded("vendor/".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_config_error() {
+        let docs = DocsConfig {
+            include_paths: vec!["docs/[".to_string()],
+            ..Default::default()
+        };
+        let err = DocMatcher::new(&docs).unwrap_err();
+        assert_eq!(err.code(), "config_invalid");
+        assert!(err.to_string().contains("docs.include_paths"));
+    }
+}
@@ -0,0 +1,134 @@
+//! What the MCP server offers in a project.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// MCP server settings for this project.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct McpServerConfig {
+    #[serde(default)]
+    pub tools: McpToolsConfig,
+
+    /// Most memory text in one `squirrel_get_memory` response, in characters.
+    /// A client profile's `max_chars` replaces it.
+    #[serde(default = "default_context_chars")]
+    pub context_chars: usize,
+
+    /// Caps on `squirrel_store_memory`, against runaway agent loops.
+    #[serde(default)]
+    pub limits: McpLimits,
+
+    /// Queue new memories from `squirrel_store_memory` for `sqrl memory approve`
+    /// instead of storing them.
+    #[serde(default)]
+    pub require_approval: bool,
+}
+
+fn default_context_chars() -> usize {
+    crate::storage::CONTEXT_BUDGET_CHARS
+}
+
+impl Default for McpServerConfig {
+    fn default() -> Self {
+        Self {
+            tools: McpToolsConfig::default(),
+            context_chars: default_context_chars(),
+            limits: McpLimits::default(),
+            require_approval: false,
+        }
+    }
+}
+
+/// Write limits per MCP session (one server process). 0 means no limit.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct McpLimits {
+    /// Stores accepted in any 60-second window, reinforcements included.
+    #[serde(default = "default_writes_per_minute")]
+    pub writes_per_minute: u32,
+
+    /// New (not deduplicated) memories accepted per session.
+    #[serde(default = "default_new_per_session")]
+    pub new_per_session: u32,
+}
+
+fn default_writes_per_minute() -> u32 {
+    30
+}
+
+fn default_new_per_session() -> u32 {
+    100
+}
+
+impl Default for McpLimits {
+    fn default() -> Self {
+        Self {
+            writes_per_minute: default_writes_per_minute(),
+            new_per_session: default_new_per_session(),
+        }
+    }
+}
+
+/// Every tool the MCP server offers; projects can disable some (`mcp.tools.disabled`).
+pub const TOOL_NAMES: &[&str] = &[
+    "squirrel_store_memory",
+    "squirrel_get_memory",
+    "squirrel_rate_memory",
+];
+
+/// Squirrel MCP tools available in this project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct McpToolsConfig {
+    /// Tools hidden from clients and rejected if called, e.g. `store_memory`
+    /// for retrieval only. The `squirrel_` prefix is optional.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
+impl McpToolsConfig {
+    /// Whether the tool named in `tools/call` (`squirrel_store_memory`) is disabled.
+    pub fn is_disabled(&self, tool: &str) -> bool {
+        let short = tool.strip_prefix("squirrel_").unwrap_or(tool);
+        self.disabled
+            .iter()
+            .any(|name| name.strip_prefix("squirrel_").unwrap_or(name) == short)
+    }
+
+    /// Reject names that are not Squirrel tools, so a typo can't leave one enabled.
+    pub(super) fn check(&self) -> Result<(), Error> {
+        for name in &self.disabled {
+            let known = TOOL_NAMES
+                .iter()
+                .any(|tool| tool == name || tool.strip_prefix("squirrel_") == Some(name));
+            if !known {
+                return Err(Error::ConfigParse(format!(
+                    "mcp.tools.disabled: unknown tool '{}' (expected one of {})",
+                    name,
+                    TOOL_NAMES.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_mcp_tools() {
+        let mut tools = McpToolsConfig::default();
+        assert!(!tools.is_disabled("squirrel_store_memory"));
+        assert!(tools.check().is_ok());
+
+        tools.disabled = vec!["store_memory".to_string()];
+        assert!(tools.is_disabled("squirrel_store_memory"));
+        assert!(!tools.is_disabled("squirrel_get_memory"));
+        assert!(tools.check().is_ok());
+
+        tools.disabled = vec!["delete_memory".to_string()];
+        assert!(tools.check().is_err());
+    }
+}
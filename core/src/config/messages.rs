@@ -0,0 +1,384 @@
+//! Pre-push hook messages (CONFIG-010).
+//!
+//! Each message has built-in text per locale. `hooks.messages` overrides any
+//! of them by key, e.g. to point a blocked push at a team's docs process;
+//! `{commits}` and `{files}` in the text are filled in, and `{count}` in the
+//! messages that count something. Overrides are checked once per config
+//! load, so a typo fails there rather than in a hook.
+
+use std::collections::BTreeMap;
+
+use crate::config::HooksConfig;
+use crate::error::Error;
+
+/// Locales with built-in text. `auto` picks one from the environment.
+pub const LOCALES: [&str; 2] = ["en", "de"];
+
+/// Placeholders any message may use.
+pub const PLACEHOLDERS: [&str; 2] = ["commits", "files"];
+
+/// Placeholder for what a counting message counts (`Message::counts`).
+pub const COUNT_PLACEHOLDER: &str = "count";
+
+/// A message the pre-push hook prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Message {
+    /// Heading of the summary.
+    Title,
+    /// Line with the number of commits.
+    CommitsToPush,
+    /// Heading of the changed files.
+    FilesChanged,
+    /// Heading of the repo's doc files.
+    DocFiles,
+    /// Heading of docs naming moved or deleted files.
+    StaleDocs,
+    /// Heading of memories about the changed files.
+    MemoriesHeading,
+    /// Memories left out of the list; counts them.
+    MoreMemories,
+    /// Memories naming old paths of moved files; counts them.
+    MovedMemories,
+    /// Summary under `--quiet` when docs changed.
+    QuietDocsUpdated,
+    /// Summary under `--quiet` when no doc changed.
+    QuietNoDocs,
+    /// Summary under `--quiet` when docs name moved or deleted files; counts them.
+    QuietStaleDocs,
+    /// Desktop notification for a blocked push.
+    NotifyPushBlocked,
+    /// Closing line when the push goes ahead.
+    Review,
+    /// Push blocked because no doc changed (`hooks.pre_push_block`).
+    PushBlocked,
+    /// What to do about it.
+    PushBlockedHint,
+    /// Both in one line, under `--quiet`.
+    PushBlockedQuiet,
+    /// Push blocked by a plugin (PLUGIN-001).
+    PluginBlocked,
+    /// What to do about it.
+    PluginBlockedHint,
+    /// Both in one line, under `--quiet`.
+    PluginBlockedQuiet,
+}
+
+impl Message {
+    pub const ALL: [Message; 19] = [
+        Message::Title,
+        Message::CommitsToPush,
+        Message::FilesChanged,
+        Message::DocFiles,
+        Message::StaleDocs,
+        Message::MemoriesHeading,
+        Message::MoreMemories,
+        Message::MovedMemories,
+        Message::QuietDocsUpdated,
+        Message::QuietNoDocs,
+        Message::QuietStaleDocs,
+        Message::NotifyPushBlocked,
+        Message::Review,
+        Message::PushBlocked,
+        Message::PushBlockedHint,
+        Message::PushBlockedQuiet,
+        Message::PluginBlocked,
+        Message::PluginBlockedHint,
+        Message::PluginBlockedQuiet,
+    ];
+
+    /// Key under `hooks.messages`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Message::Title => "title",
+            Message::CommitsToPush => "commits_to_push",
+            Message::FilesChanged => "files_changed",
+            Message::DocFiles => "doc_files",
+            Message::StaleDocs => "stale_docs",
+            Message::MemoriesHeading => "memories",
+            Message::MoreMemories => "more_memories",
+            Message::MovedMemories => "moved_memories",
+            Message::QuietDocsUpdated => "quiet_docs_updated",
+            Message::QuietNoDocs => "quiet_no_docs",
+            Message::QuietStaleDocs => "quiet_stale_docs",
+            Message::NotifyPushBlocked => "notify_push_blocked",
+            Message::Review => "review",
+            Message::PushBlocked => "push_blocked",
+            Message::PushBlockedHint => "push_blocked_hint",
+            Message::PushBlockedQuiet => "push_blocked_quiet",
+            Message::PluginBlocked => "plugin_blocked",
+            Message::PluginBlockedHint => "plugin_blocked_hint",
+            Message::PluginBlockedQuiet => "plugin_blocked_quiet",
+        }
+    }
+
+    /// Whether the text may use `{count}`.
+    pub fn counts(self) -> bool {
+        matches!(
+            self,
+            Message::MoreMemories | Message::MovedMemories | Message::QuietStaleDocs
+        )
+    }
+
+    /// Built-in text in `locale` (one of `LOCALES`).
+    fn builtin(self, locale: &str) -> &'static str {
+        match (locale, self) {
+            ("de", Message::Title) => "Squirrel: Änderungen vor dem Push prüfen",
+            ("de", Message::CommitsToPush) => "Zu pushende Commits: {commits}",
+            ("de", Message::FilesChanged) => "Geänderte Dateien:",
+            ("de", Message::DocFiles) => "Dokumentationsdateien im Repo:",
+            ("de", Message::StaleDocs) => {
+                "Dokumentation, die noch verschobene oder gelöschte Dateien nennt:"
+            }
+            ("de", Message::MemoriesHeading) => "Erinnerungen zu geänderten Dateien:",
+            ("de", Message::MoreMemories) => "… und {count} weitere",
+            ("de", Message::MovedMemories) => {
+                "{count} Erinnerungen nennen alte Pfade verschobener Dateien; führe 'sqrl memory lint --fix' aus."
+            }
+            ("de", Message::QuietDocsUpdated) => {
+                "Squirrel: {commits} Commits, {files} Dateien geändert, Dokumentation angepasst."
+            }
+            ("de", Message::QuietNoDocs) => {
+                "Squirrel: {commits} Commits, {files} Dateien geändert, keine Dokumentation angepasst."
+            }
+            ("de", Message::QuietStaleDocs) => {
+                "Squirrel: {commits} Commits, {files} Dateien geändert, {count} Dokumente nennen verschobene oder gelöschte Dateien."
+            }
+            ("de", Message::NotifyPushBlocked) => {
+                "Push blockiert: Code ohne Anpassung der Dokumentation geändert"
+            }
+            ("de", Message::Review) => {
+                "Prüfe, ob Dokumentation wegen dieser Änderungen angepasst werden muss."
+            }
+            ("de", Message::PushBlocked) => {
+                "Push blockiert: Code wurde geändert, aber keine Dokumentation."
+            }
+            ("de", Message::PushBlockedHint) => {
+                "Passe die betroffene Dokumentation an oder umgehe die Prüfung mit 'git push --no-verify'."
+            }
+            ("de", Message::PushBlockedQuiet) => {
+                "Squirrel: Push blockiert, keine Dokumentation geändert; umgehen mit 'git push --no-verify'."
+            }
+            ("de", Message::PluginBlocked) => {
+                "Push von einem Plugin blockiert (siehe Ausgabe oben)."
+            }
+            ("de", Message::PluginBlockedHint) => "Umgehen mit 'git push --no-verify'.",
+            ("de", Message::PluginBlockedQuiet) => {
+                "Squirrel: Push von einem Plugin blockiert; umgehen mit 'git push --no-verify'."
+            }
+            (_, Message::Title) => "Squirrel: Review changes before push",
+            (_, Message::CommitsToPush) => "Commits to push: {commits}",
+            (_, Message::FilesChanged) => "Files changed:",
+            (_, Message::DocFiles) => "Doc files in repo:",
+            (_, Message::StaleDocs) => "Docs still mentioning moved or deleted files:",
+            (_, Message::MemoriesHeading) => "Memories about changed files:",
+            (_, Message::MoreMemories) => "… and {count} more",
+            (_, Message::MovedMemories) => {
+                "{count} memories name the old paths of moved files; run 'sqrl memory lint --fix'."
+            }
+            (_, Message::QuietDocsUpdated) => {
+                "Squirrel: {commits} commits, {files} files changed, docs updated."
+            }
+            (_, Message::QuietNoDocs) => {
+                "Squirrel: {commits} commits, {files} files changed, no docs updated."
+            }
+            (_, Message::QuietStaleDocs) => {
+                "Squirrel: {commits} commits, {files} files changed, {count} docs mention moved or deleted files."
+            }
+            (_, Message::NotifyPushBlocked) => "Push blocked: code changed without doc updates",
+            (_, Message::Review) => "Review if any docs need updating based on these changes.",
+            (_, Message::PushBlocked) => "Push blocked: code changed but no docs were updated.",
+            (_, Message::PushBlockedHint) => {
+                "Update the relevant docs, or bypass with 'git push --no-verify'."
+            }
+            (_, Message::PushBlockedQuiet) => {
+                "Squirrel: push blocked, no docs updated; bypass with 'git push --no-verify'."
+            }
+            (_, Message::PluginBlocked) => "Push blocked by a plugin (see its output above).",
+            (_, Message::PluginBlockedHint) => "Bypass with 'git push --no-verify'.",
+            (_, Message::PluginBlockedQuiet) => {
+                "Squirrel: push blocked by a plugin; bypass with 'git push --no-verify'."
+            }
+        }
+    }
+}
+
+/// Messages for one config: the locale's text with `hooks.messages` on top.
+#[derive(Debug, Clone)]
+pub struct HookMessages {
+    locale: &'static str,
+    overrides: BTreeMap<String, String>,
+}
+
+impl HookMessages {
+    /// Check `hooks.locale` and `hooks.messages`. Errors name the key, e.g.
+    /// `hooks.messages.push_blocked: unknown placeholder {branch}`.
+    pub fn new(hooks: &HooksConfig) -> Result<Self, Error> {
+        let locale = match hooks.locale.as_str() {
+            "auto" => from_environment(),
+            locale => LOCALES.into_iter().find(|l| *l == locale).ok_or_else(|| {
+                Error::ConfigParse(format!(
+                    "hooks.locale: unknown locale '{}' (expected auto, {})",
+                    locale,
+                    LOCALES.join(", ")
+                ))
+            })?,
+        };
+        for (key, text) in &hooks.messages {
+            let Some(message) = Message::ALL.into_iter().find(|m| m.key() == key) else {
+                let keys: Vec<&str> = Message::ALL.iter().map(|m| m.key()).collect();
+                return Err(Error::ConfigParse(format!(
+                    "hooks.messages.{}: unknown message (expected one of: {})",
+                    key,
+                    keys.join(", ")
+                )));
+            };
+            let mut allowed = PLACEHOLDERS.to_vec();
+            if message.counts() {
+                allowed.push(COUNT_PLACEHOLDER);
+            }
+            if let Some(name) = placeholders(text).find(|name| !allowed.contains(name)) {
+                return Err(Error::ConfigParse(format!(
+                    "hooks.messages.{}: unknown placeholder {{{}}} (expected {{{}}})",
+                    key,
+                    name,
+                    allowed.join("}, {")
+                )));
+            }
+        }
+        Ok(Self {
+            locale,
+            overrides: hooks.messages.clone(),
+        })
+    }
+
+    /// Text of `message` with the placeholders filled in. May span lines.
+    pub fn render(&self, message: Message, commits: usize, files: usize) -> String {
+        self.render_count(message, commits, files, 0)
+    }
+
+    /// `render` for a message that counts something (`Message::counts`).
+    pub fn render_count(
+        &self,
+        message: Message,
+        commits: usize,
+        files: usize,
+        count: usize,
+    ) -> String {
+        self.overrides
+            .get(message.key())
+            .map(String::as_str)
+            .unwrap_or_else(|| message.builtin(self.locale))
+            .trim_end()
+            .replace("{commits}", &commits.to_string())
+            .replace("{files}", &files.to_string())
+            .replace("{count}", &count.to_string())
+    }
+}
+
+/// Names inside `{...}` in `text`.
+fn placeholders(text: &str) -> impl Iterator<Item = &str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+}
+
+/// Locale from `LC_ALL`, `LC_MESSAGES` or `LANG` (`de_DE.UTF-8` gives `de`),
+/// English if none names a built-in one.
+fn from_environment() -> &'static str {
+    let language = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    let language = language.split(['_', '.', '@']).next().unwrap_or_default();
+    LOCALES.into_iter().find(|l| *l == language).unwrap_or("en")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_fill_placeholders_and_reject_typos() {
+        let mut hooks = HooksConfig {
+            locale: "de".to_string(),
+            ..Default::default()
+        };
+        hooks.messages.insert(
+            "push_blocked_hint".to_string(),
+            "See https://wiki.example.com/docs ({files} files)".to_string(),
+        );
+        let messages = HookMessages::new(&hooks).unwrap();
+        assert_eq!(
+            messages.render(Message::PushBlockedHint, 3, 12),
+            "See https://wiki.example.com/docs (12 files)"
+        );
+        assert!(messages
+            .render(Message::PushBlocked, 3, 12)
+            .starts_with("Push blockiert"));
+
+        hooks
+            .messages
+            .insert("push_blocked".to_string(), "{branch} blocked".to_string());
+        let err = HookMessages::new(&hooks).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("hooks.messages.push_blocked: unknown placeholder {branch}"));
+
+        hooks.messages.clear();
+        hooks
+            .messages
+            .insert("title".to_string(), "{count} pushes".to_string());
+        assert!(HookMessages::new(&hooks).is_err());
+        hooks
+            .messages
+            .insert("title".to_string(), "Push".to_string());
+        hooks
+            .messages
+            .insert("more_memories".to_string(), "+{count}".to_string());
+        assert_eq!(
+            HookMessages::new(&hooks)
+                .unwrap()
+                .render_count(Message::MoreMemories, 3, 12, 5),
+            "+5"
+        );
+
+        hooks.messages.clear();
+        hooks
+            .messages
+            .insert("blocked".to_string(), "x".to_string());
+        assert!(HookMessages::new(&hooks).is_err());
+        hooks.messages.clear();
+        hooks.locale = "xx".to_string();
+        assert!(HookMessages::new(&hooks).is_err());
+    }
+
+    #[test]
+    fn test_de_locale_translates_every_message() {
+        let hooks = HooksConfig {
+            locale: "de".to_string(),
+            ..Default::default()
+        };
+        let messages = HookMessages::new(&hooks).unwrap();
+        for message in Message::ALL {
+            assert_ne!(
+                message.builtin("de"),
+                message.builtin("en"),
+                "{} has no German text",
+                message.key()
+            );
+        }
+        assert_eq!(
+            messages.render(Message::CommitsToPush, 3, 12),
+            "Zu pushende Commits: 3"
+        );
+        assert_eq!(
+            messages.render_count(Message::QuietStaleDocs, 3, 12, 2),
+            "Squirrel: 3 Commits, 12 Dateien geändert, 2 Dokumente nennen verschobene oder gelöschte Dateien."
+        );
+        assert_eq!(
+            messages.render_count(Message::MoreMemories, 3, 12, 4),
+            "… und 4 weitere"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Config version migration (CONFIG-005).
+//!
+//! Config files carry a `version` field. Older layouts are upgraded in memory
+//! on load; `sqrl config migrate` writes the upgraded file back.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::error::Error;
+
+/// Rewrites a config tree from one version to the next.
+pub type Migration = fn(&mut Mapping);
+
+/// A config file type with a versioned layout.
+pub trait Versioned: Default + Serialize + DeserializeOwned {
+    /// `MIGRATIONS[i]` upgrades version `i` to `i + 1`.
+    const MIGRATIONS: &'static [Migration];
+
+    /// Current layout version.
+    const VERSION: u32 = Self::MIGRATIONS.len() as u32;
+}
+
+/// Version stamped in a config tree. Unversioned files are version 0.
+pub fn version_of(tree: &Value) -> u32 {
+    tree.get("version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0)
+}
+
+/// Upgrade a config tree to the current layout. Returns the original version.
+pub fn migrate<T: Versioned>(tree: &mut Value) -> Result<u32, Error> {
+    let from = version_of(tree);
+    if from > T::VERSION {
+        return Err(Error::ConfigParse(format!(
+            "config version {} is newer than this sqrl supports ({}); upgrade sqrl",
+            from,
+            T::VERSION
+        )));
+    }
+    let Value::Mapping(map) = tree else {
+        return Ok(from);
+    };
+    for migration in &T::MIGRATIONS[from as usize..] {
+        migration(map);
+    }
+    map.insert(Value::from("version"), Value::from(T::VERSION));
+    Ok(from)
+}
+
+/// 0 -> 1: unversioned files. Layout unchanged; only the stamp is added.
+pub fn stamp_version(_: &mut Mapping) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_migrate_stamps_unversioned_tree() {
+        let mut tree: Value = serde_yaml::from_str("hooks:\n  auto_install: false\n").unwrap();
+        assert_eq!(migrate::<Config>(&mut tree).unwrap(), 0);
+        assert_eq!(version_of(&tree), Config::VERSION);
+
+        let config: Config = serde_yaml::from_value(tree).unwrap();
+        assert!(!config.hooks.auto_install);
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut tree: Value = serde_yaml::from_str("version: 999\n").unwrap();
+        assert!(migrate::<Config>(&mut tree).is_err());
+    }
+}
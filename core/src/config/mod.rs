@@ -0,0 +1,313 @@
+//! Project configuration (CONFIG-001).
+//!
+//! Handles loading and saving `.sqrl/config.yaml` (or `.sqrl/config.toml`).
+
+pub mod format;
+pub mod matcher;
+pub mod messages;
+pub mod migrate;
+pub mod redact;
+pub mod resolve;
+pub mod validate;
+
+mod context;
+mod docs;
+mod health;
+mod hooks;
+mod mcp;
+mod retention;
+mod roots;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::atomic;
+use crate::error::Error;
+use format::ConfigFormat;
+use matcher::DocMatcher;
+use messages::HookMessages;
+use migrate::{Migration, Versioned};
+use redact::Redactor;
+
+pub use context::ContextConfig;
+pub use docs::DocsConfig;
+pub use health::{HealthConfig, HealthWeights};
+pub use hooks::HooksConfig;
+pub use mcp::{McpLimits, McpServerConfig, McpToolsConfig, TOOL_NAMES};
+pub use redact::RedactionConfig;
+pub use retention::RetentionConfig;
+pub use roots::{find_project_root, project_root};
+
+/// Project configuration stored in `.sqrl/config.yaml`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Config {
+    /// Config layout version (CONFIG-005).
+    #[serde(default = "Config::current_version")]
+    pub version: u32,
+
+    /// Merge configs of enclosing Squirrel projects under this one (monorepos).
+    #[serde(default)]
+    pub inherit: bool,
+
+    /// AI tools enabled for this project.
+    #[serde(default)]
+    pub tools: ToolsConfig,
+
+    /// Documentation file settings.
+    #[serde(default)]
+    pub docs: DocsConfig,
+
+    /// Git hooks behavior.
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    /// What the MCP server offers in this project.
+    #[serde(default)]
+    pub mcp: McpServerConfig,
+
+    /// Squirrel-maintained section of agent context files.
+    #[serde(default)]
+    pub context: ContextConfig,
+
+    /// Secrets removed from memories MCP clients store.
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+
+    /// How long local-only records are kept.
+    #[serde(default)]
+    pub retention: RetentionConfig,
+
+    /// Subdirectories whose memories are scoped to them, e.g. `services/billing` (CONFIG-009).
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+
+    /// How the health score weighs its parts (CONFIG-011).
+    #[serde(default)]
+    pub health: HealthConfig,
+
+    /// Internal state (not user-editable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub internal: Option<InternalConfig>,
+}
+
+/// AI tools configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolsConfig {
+    #[serde(default = "default_true")]
+    pub claude_code: bool,
+    #[serde(default)]
+    pub cursor: bool,
+    #[serde(default)]
+    pub codex: bool,
+}
+
+/// Internal state (managed by sqrl, not user).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InternalConfig {
+    pub initialized_at: String,
+}
+
+// Default value functions
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ToolsConfig {
+    fn default() -> Self {
+        Self {
+            claude_code: true,
+            cursor: false,
+            codex: false,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            inherit: false,
+            tools: ToolsConfig::default(),
+            docs: DocsConfig::default(),
+            hooks: HooksConfig::default(),
+            mcp: McpServerConfig::default(),
+            context: ContextConfig::default(),
+            redaction: RedactionConfig::default(),
+            retention: RetentionConfig::default(),
+            namespaces: Vec::new(),
+            health: HealthConfig::default(),
+            internal: Some(InternalConfig {
+                initialized_at: chrono::Utc::now().to_rfc3339(),
+            }),
+        }
+    }
+}
+
+impl Versioned for Config {
+    const MIGRATIONS: &'static [Migration] = &[migrate::stamp_version];
+}
+
+impl Config {
+    fn current_version() -> u32 {
+        Self::VERSION
+    }
+
+    /// Get the config file path for a project (detected format, YAML if none).
+    pub fn path(project_root: &Path) -> PathBuf {
+        ConfigFormat::detect(project_root)
+            .ok()
+            .flatten()
+            .unwrap_or(ConfigFormat::Yaml)
+            .path(project_root)
+    }
+
+    /// Machine-local overlay merged over the shared config, never committed.
+    pub fn local_path(project_root: &Path) -> PathBuf {
+        project_root.join(".sqrl").join("config.local.json")
+    }
+
+    /// Check if a project has a config file.
+    pub fn exists(project_root: &Path) -> bool {
+        matches!(ConfigFormat::detect(project_root), Ok(Some(_)))
+    }
+
+    /// Load config from a project directory.
+    pub fn load(project_root: &Path) -> Result<Self, Error> {
+        let format = ConfigFormat::detect(project_root)?
+            .ok_or_else(|| Error::ConfigNotFound(ConfigFormat::Yaml.path(project_root)))?;
+        Self::load_file(&format.path(project_root))
+    }
+
+    /// Load a config file at any path, YAML unless it ends in `.toml`.
+    pub fn load_file(path: &Path) -> Result<Self, Error> {
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => ConfigFormat::Toml,
+            _ => ConfigFormat::Yaml,
+        };
+        let content = fs::read_to_string(path).map_err(Error::at(path))?;
+        let config: Self = validate::parse(path, &content, format)?;
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Checks the types can't express: doc and redaction patterns compile
+    /// (CONFIG-006, CONFIG-007), context files stay in the project, disabled
+    /// MCP tools exist, and health settings are usable (CONFIG-011).
+    pub fn check(&self) -> Result<(), Error> {
+        self.doc_matcher()?;
+        self.redactor()?;
+        self.context.check()?;
+        self.health.check()?;
+        self.mcp.tools.check()
+    }
+
+    /// Whether anything runs on commit (`hooks.snapshots`, `context.section`).
+    pub fn wants_post_commit_hook(&self) -> bool {
+        self.hooks.snapshots || self.context.section
+    }
+
+    /// Compile the doc include/exclude patterns (CONFIG-006).
+    pub fn doc_matcher(&self) -> Result<DocMatcher, Error> {
+        DocMatcher::new(&self.docs)
+    }
+
+    /// Compile the redaction rules (CONFIG-007).
+    pub fn redactor(&self) -> Result<Redactor, Error> {
+        Redactor::new(&self.redaction)
+    }
+
+    /// Check the pre-push message overrides (CONFIG-010).
+    pub fn hook_messages(&self) -> Result<HookMessages, Error> {
+        HookMessages::new(&self.hooks)
+    }
+
+    /// Resolve the effective config for a project (CONFIG-003).
+    pub fn effective(project_root: &Path) -> Result<Self, Error> {
+        Ok(resolve::resolve(project_root)?.config)
+    }
+
+    /// Save config to a project directory, keeping its current format.
+    pub fn save(&self, project_root: &Path) -> Result<(), Error> {
+        let format = ConfigFormat::detect(project_root)?.unwrap_or(ConfigFormat::Yaml);
+        self.save_as(project_root, format)
+    }
+
+    /// Save config in a specific format.
+    pub fn save_as(&self, project_root: &Path, format: ConfigFormat) -> Result<(), Error> {
+        atomic::write(&format.path(project_root), format.render(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert!(config.tools.claude_code);
+        assert!(!config.tools.cursor);
+        assert_eq!(config.docs.extensions, vec!["md", "mdc", "txt", "rst"]);
+        assert!(config.hooks.auto_install);
+        assert!(!config.hooks.pre_push_block);
+        assert!(config.health.check().is_ok());
+    }
+
+    #[test]
+    fn test_check_invalid_redaction_pattern_rejected() {
+        let mut config = Config::default();
+        config.redaction.patterns = vec![r"corp\.internal".to_string()];
+        assert!(config.check().is_ok());
+        config.redaction.patterns.push("(unclosed".to_string());
+        assert!(config.check().is_err());
+    }
+
+    #[test]
+    fn test_schema_excludes_internal_state() {
+        let schema = serde_json::to_value(schemars::schema_for!(Config)).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("hooks"));
+        assert!(!properties.contains_key("internal"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = TempDir::new().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+
+        let config = Config::default();
+        config.save(dir.path()).unwrap();
+
+        let loaded = Config::load(dir.path()).unwrap();
+        assert!(loaded.tools.claude_code);
+        assert!(loaded.internal.is_some());
+    }
+
+    #[test]
+    fn test_toml_config_detected_and_loaded() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+
+        let mut config = Config::default();
+        config.tools.cursor = true;
+        config.save_as(dir.path(), ConfigFormat::Toml).unwrap();
+
+        assert_eq!(
+            ConfigFormat::detect(dir.path()).unwrap(),
+            Some(ConfigFormat::Toml)
+        );
+        let loaded = Config::load(dir.path()).unwrap();
+        assert!(loaded.tools.cursor);
+
+        // Both formats present is ambiguous
+        config.save_as(dir.path(), ConfigFormat::Yaml).unwrap();
+        assert!(Config::load(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Layered configuration resolution (CONFIG-003).
+//!
+//! Precedence, lowest to highest: defaults, global config (overlapping keys),
+//! inherited ancestor project configs, project config, machine-local overlay,
+//! `SQRL_*` environment variables, `-c key=value` flags.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::config::format::ConfigFormat;
+use crate::config::{migrate, validate, Config};
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+
+/// Prefix for environment overrides. `SQRL_HOOKS__AUTO_INSTALL` maps to `hooks.auto_install`.
+pub const ENV_PREFIX: &str = "SQRL_";
+
+static FLAG_OVERRIDES: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Register `-c key=value` overrides from the command line.
+pub fn set_flag_overrides(overrides: Vec<String>) {
+    let _ = FLAG_OVERRIDES.set(overrides);
+}
+
+/// Where an effective value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    Global,
+    Ancestor(PathBuf),
+    Project,
+    Local,
+    Env(String),
+    Flag,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::Global => write!(f, "global (~/.sqrl/config.yaml)"),
+            Source::Ancestor(root) => write!(f, "inherited ({}/.sqrl/config)", root.display()),
+            Source::Project => write!(f, "project (.sqrl/config)"),
+            Source::Local => write!(f, "local (.sqrl/config.local.json)"),
+            Source::Env(var) => write!(f, "env ({})", var),
+            Source::Flag => write!(f, "flag (-c)"),
+        }
+    }
+}
+
+/// Effective configuration with the source of every value.
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub config: Config,
+    pub sources: BTreeMap<String, (Value, Source)>,
+}
+
+/// Resolve the effective project configuration.
+pub fn resolve(project_root: &Path) -> Result<ResolvedConfig, Error> {
+    resolve_with_env(project_root, std::env::vars())
+}
+
+/// Resolve with `env` standing in for the process environment.
+fn resolve_with_env(
+    project_root: &Path,
+    env: impl IntoIterator<Item = (String, String)>,
+) -> Result<ResolvedConfig, Error> {
+    let defaults = to_tree(&Config::default())?;
+    let mut sources = BTreeMap::new();
+    for (key, value) in flatten(&defaults) {
+        sources.insert(key, (value, Source::Default));
+    }
+
+    // Global config only contributes keys the project schema also has
+    if let Ok(global) = GlobalConfig::load().map(GlobalConfig::with_profile) {
+        let tree = serde_yaml::to_value(&global).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        for (key, value) in flatten(&tree) {
+            if sources.contains_key(&key) {
+                sources.insert(key, (value, Source::Global));
+            }
+        }
+    }
+
+    for root in Config::inherited_roots(project_root)? {
+        for (key, value) in flatten(&file_tree(&root)?) {
+            sources.insert(key, (value, Source::Ancestor(root.clone())));
+        }
+    }
+
+    if Config::exists(project_root) {
+        let tree = file_tree(project_root)?;
+        for (key, value) in flatten(&tree) {
+            sources.insert(key, (value, Source::Project));
+        }
+    }
+
+    if let Some(tree) = local_tree(project_root)? {
+        for (key, value) in flatten(&tree) {
+            sources.insert(key, (value, Source::Local));
+        }
+    }
+
+    for (var, raw) in env {
+        let Some(suffix) = var.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let key = suffix.to_lowercase().replace("__", ".");
+        if sources.contains_key(&key) {
+            sources.insert(key, (parse_scalar(&raw), Source::Env(var)));
+        }
+    }
+
+    for raw in FLAG_OVERRIDES.get().into_iter().flatten() {
+        let (key, value) = raw
+            .split_once('=')
+            .ok_or_else(|| Error::ConfigParse(format!("Override '{}' must be KEY=VALUE", raw)))?;
+        if !sources.contains_key(key) {
+            return Err(Error::ConfigParse(format!("Unknown config key '{}'", key)));
+        }
+        sources.insert(key.to_string(), (parse_scalar(value), Source::Flag));
+    }
+
+    let mut tree = Value::Mapping(Mapping::new());
+    for (key, (value, _)) in &sources {
+        set_path(&mut tree, key, value.clone());
+    }
+    let config: Config = serde_yaml::from_value(tree)
+        .map_err(|e| Error::ConfigParse(format!("Invalid effective config: {}", e)))?;
+
+    Ok(ResolvedConfig { config, sources })
+}
+
+/// Parse a raw string the way YAML would (`true`, `3`, `[a, b]`), falling back to a string.
+pub fn parse_scalar(raw: &str) -> Value {
+    serde_yaml::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Serialize a config without internal state or version stamp.
+fn to_tree(config: &Config) -> Result<Value, Error> {
+    let tree = serde_yaml::to_value(config).map_err(|e| Error::ConfigParse(e.to_string()))?;
+    Ok(strip(tree))
+}
+
+/// Keys explicitly set in a project's config file, so unset keys fall through.
+fn file_tree(project_root: &Path) -> Result<Value, Error> {
+    let format = ConfigFormat::detect(project_root)?
+        .ok_or_else(|| Error::ConfigNotFound(ConfigFormat::Yaml.path(project_root)))?;
+    let path = format.path(project_root);
+    let content = std::fs::read_to_string(&path).map_err(Error::at(&path))?;
+    // Validating first reports problems the way loading does
+    validate::parse::<Config>(&path, &content, format)?.check()?;
+    let mut tree: Value = format.parse(&content)?;
+    migrate::migrate::<Config>(&mut tree)?;
+    Ok(strip(tree))
+}
+
+/// Keys set in the machine-local overlay, if present.
+pub fn local_tree(project_root: &Path) -> Result<Option<Value>, Error> {
+    let path = Config::local_path(project_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    // JSON is read as YAML so validation reports the same way
+    let content = std::fs::read_to_string(&path)?;
+    validate::parse::<Config>(&path, &content, ConfigFormat::Yaml)?;
+    let tree: Value = ConfigFormat::Yaml.parse(&content)?;
+    Ok(Some(strip(tree)))
+}
+
+fn strip(mut tree: Value) -> Value {
+    if let Value::Mapping(map) = &mut tree {
+        map.remove("internal");
+        map.remove("version");
+    }
+    tree
+}
+
+/// Flatten a YAML tree into dotted keys. Sequences are leaves.
+pub fn flatten(tree: &Value) -> Vec<(String, Value)> {
+    let mut out = Vec::new();
+    flatten_into(tree, String::new(), &mut out);
+    out
+}
+
+fn flatten_into(value: &Value, prefix: String, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Mapping(map) => {
+            for (k, v) in map {
+                let Some(k) = k.as_str() else { continue };
+                let key = if prefix.is_empty() {
+                    k.to_string()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_into(v, key, out);
+            }
+        }
+        _ => out.push((prefix, value.clone())),
+    }
+}
+
+/// Set a dotted key in a YAML tree, creating intermediate mappings.
+pub fn set_path(tree: &mut Value, key: &str, value: Value) {
+    let mut current = tree;
+    let parts: Vec<&str> = key.split('.').collect();
+    for (i, part) in parts.iter().enumerate() {
+        if !current.is_mapping() {
+            *current = Value::Mapping(Mapping::new());
+        }
+        let map = current.as_mapping_mut().expect("mapping");
+        if i == parts.len() - 1 {
+            map.insert(Value::from(*part), value);
+            return;
+        }
+        current = map
+            .entry(Value::from(*part))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_flatten_and_set_path_roundtrip() {
+        let mut tree = Value::Mapping(Mapping::new());
+        set_path(&mut tree, "hooks.auto_install", Value::Bool(false));
+        set_path(&mut tree, "docs.extensions", parse_scalar("[md, txt]"));
+        let flat = flatten(&tree);
+        assert_eq!(
+            flat[0],
+            ("hooks.auto_install".to_string(), Value::Bool(false))
+        );
+        assert_eq!(flat[1].0, "docs.extensions");
+        assert!(flat[1].1.is_sequence());
+    }
+
+    #[test]
+    fn test_resolve_env_overrides_project() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        Config::default().save(dir.path()).unwrap();
+
+        let env = [("SQRL_TOOLS__CODEX".to_string(), "true".to_string())];
+        let resolved = resolve_with_env(dir.path(), env).unwrap();
+
+        assert!(resolved.config.tools.codex);
+        assert_eq!(
+            resolved.sources["tools.codex"].1,
+            Source::Env("SQRL_TOOLS__CODEX".to_string())
+        );
+        assert_eq!(resolved.sources["hooks.auto_install"].1, Source::Project);
+    }
+
+    #[test]
+    fn test_resolve_local_overlay_beats_project() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        Config::default().save(dir.path()).unwrap();
+        std::fs::write(
+            Config::local_path(dir.path()),
+            r#"{"hooks": {"auto_install": false}}"#,
+        )
+        .unwrap();
+
+        let resolved = resolve(dir.path()).unwrap();
+        assert!(!resolved.config.hooks.auto_install);
+        assert_eq!(resolved.sources["hooks.auto_install"].1, Source::Local);
+        assert_eq!(resolved.sources["tools.claude_code"].1, Source::Project);
+    }
+
+    #[test]
+    fn test_resolve_merges_inherited_ancestor() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("pkg");
+        std::fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        std::fs::create_dir_all(package.join(".sqrl")).unwrap();
+
+        let mut root_config = Config::default();
+        root_config.docs.extensions = vec!["adoc".to_string()];
+        root_config.save(dir.path()).unwrap();
+
+        // Package only sets what it overrides
+        std::fs::write(
+            package.join(".sqrl/config.yaml"),
+            "version: 1\ninherit: true\nhooks:\n  auto_install: false\n",
+        )
+        .unwrap();
+
+        let resolved = resolve(&package).unwrap();
+        assert!(!resolved.config.hooks.auto_install);
+        assert_eq!(resolved.config.docs.extensions, vec!["adoc"]);
+        assert_eq!(
+            resolved.sources["docs.extensions"].1,
+            Source::Ancestor(dir.path().to_path_buf())
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! How long local-only records are kept (CONFIG-008).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Age limits for local-only records, applied by `sqrl memory compact` (CONFIG-008).
+/// 0 keeps them forever.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RetentionConfig {
+    /// Days of MCP activity kept (`sqrl stats`, `sqrl memory show --trace`).
+    #[serde(default = "default_activity_days")]
+    pub activity_days: u32,
+
+    /// Days of commit snapshots kept (`sqrl memory at`).
+    #[serde(default = "default_snapshot_days")]
+    pub snapshot_days: u32,
+}
+
+fn default_activity_days() -> u32 {
+    90
+}
+
+fn default_snapshot_days() -> u32 {
+    365
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            activity_days: default_activity_days(),
+            snapshot_days: default_snapshot_days(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Finding the project a directory belongs to, and the projects it inherits from.
+
+use std::path::{Path, PathBuf};
+
+use super::Config;
+use crate::error::Error;
+
+/// Find the nearest enclosing Squirrel project, starting at `start`.
+/// The global `~/.sqrl` directory is not a project.
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    let global_dir = crate::global_config::GlobalConfig::dir().ok();
+    start
+        .ancestors()
+        .find(|dir| Config::exists(dir) && global_dir.as_deref() != Some(&dir.join(".sqrl")))
+        .map(Path::to_path_buf)
+}
+
+/// Project root for the current directory: the nearest project, or cwd if none.
+pub fn project_root() -> Result<PathBuf, Error> {
+    let cwd = std::env::current_dir()?;
+    Ok(find_project_root(&cwd).unwrap_or(cwd))
+}
+
+impl Config {
+    /// Enclosing projects whose configs apply when `inherit` is set, outermost first.
+    pub fn inherited_roots(project_root: &Path) -> Result<Vec<PathBuf>, Error> {
+        let mut roots = Vec::new();
+        if !Self::exists(project_root) {
+            return Ok(roots);
+        }
+        let mut config = Self::load(project_root)?;
+        let mut current = project_root.to_path_buf();
+        while config.inherit {
+            let Some(parent) = current.parent().and_then(find_project_root) else {
+                break;
+            };
+            config = Self::load(&parent)?;
+            roots.push(parent.clone());
+            current = parent;
+        }
+        roots.reverse();
+        Ok(roots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_project_root_picks_nearest() {
+        let dir = TempDir::new().unwrap();
+        let package = dir.path().join("packages").join("web");
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        fs::create_dir_all(package.join(".sqrl")).unwrap();
+        fs::create_dir_all(package.join("src")).unwrap();
+        Config::default().save(dir.path()).unwrap();
+
+        // Package has a .sqrl dir but no config yet: root wins
+        assert_eq!(
+            find_project_root(&package.join("src")).as_deref(),
+            Some(dir.path())
+        );
+
+        Config::default().save(&package).unwrap();
+        assert_eq!(
+            find_project_root(&package.join("src")).as_deref(),
+            Some(package.as_path())
+        );
+    }
+
+    #[test]
+    fn test_inherited_roots_follow_opt_in() {
+        let dir = TempDir::new().unwrap();
+        let package = dir.path().join("pkg");
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        fs::create_dir_all(package.join(".sqrl")).unwrap();
+        Config::default().save(dir.path()).unwrap();
+
+        let mut config = Config::default();
+        config.save(&package).unwrap();
+        assert!(Config::inherited_roots(&package).unwrap().is_empty());
+
+        config.inherit = true;
+        config.save(&package).unwrap();
+        assert_eq!(
+            Config::inherited_roots(&package).unwrap(),
+            vec![dir.path().to_path_buf()]
+        );
+    }
+}
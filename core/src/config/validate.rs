@@ -0,0 +1,602 @@
+//! Config validation with precise problem reports (CONFIG-004).
+//!
+//! Checks a config file against the schema implied by its defaults:
+//! unknown keys, wrong types, and values rejected on deserialization.
+//! MCP configs in `~/.sqrl/mcps/` have required fields, so they get their
+//! own checks (MCP-CONFIG-001).
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+use crate::config::format::ConfigFormat;
+use crate::config::migrate::{self, Versioned};
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::{remote_problem, GlobalConfig, McpConfig, Transport, MCP_SCOPES};
+
+/// Keys of an MCP config file.
+const MCP_KEYS: &[&str] = &[
+    "name",
+    "command",
+    "args",
+    "env",
+    "scope",
+    "cwd",
+    "transport",
+    "url",
+];
+
+/// Keys only `stdio` servers take.
+const STDIO_KEYS: &[&str] = &["command", "args", "env", "cwd"];
+
+/// A single problem found in a config file.
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    pub file: PathBuf,
+    pub key: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: ", self.file.display(), line)?,
+            None => write!(f, "{}: ", self.file.display())?,
+        }
+        if self.key.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.key, self.message)
+        }
+    }
+}
+
+/// Validate the project config, if present.
+pub fn validate_project(project_root: &Path) -> Result<Vec<Problem>, Error> {
+    let Some(format) = ConfigFormat::detect(project_root)? else {
+        return Ok(vec![]);
+    };
+    let path = format.path(project_root);
+    let content = fs::read_to_string(&path)?;
+    let mut problems = check_file::<Config>(&path, &content, format);
+    if problems.is_empty() {
+        // Patterns must compile, e.g. "docs.include_paths: invalid pattern ...",
+        // message overrides name known keys and placeholders, and some
+        // health weight counts
+        let compiled = parse::<Config>(&path, &content, format).and_then(|config| {
+            config
+                .doc_matcher()
+                .and(config.redactor())
+                .and(config.hook_messages())
+                .map(drop)
+                .and(config.health.check())
+                .and(config.context.check())
+        });
+        if let Err(Error::ConfigParse(msg)) = compiled {
+            let (key, message) = msg.split_once(": ").unwrap_or(("", &msg));
+            problems.push(Problem {
+                file: path.clone(),
+                key: key.to_string(),
+                line: find_line(&content, key, format),
+                message: message.to_string(),
+            });
+        }
+    }
+
+    let local = Config::local_path(project_root);
+    if local.exists() {
+        let content = fs::read_to_string(&local)?;
+        problems.extend(validate_content::<Config>(
+            &local,
+            &content,
+            ConfigFormat::Yaml,
+        ));
+    }
+    Ok(problems)
+}
+
+/// Validate the global config, if present.
+pub fn validate_global() -> Result<Vec<Problem>, Error> {
+    let path = GlobalConfig::path()?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(check_file::<GlobalConfig>(
+        &path,
+        &content,
+        ConfigFormat::Yaml,
+    ))
+}
+
+/// Validate every MCP config in `~/.sqrl/mcps/`, in name order.
+pub fn validate_mcps() -> Result<Vec<Problem>, Error> {
+    let dir = GlobalConfig::mcps_dir()?;
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    paths.sort();
+    let mut problems = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(&path).map_err(Error::at(&path))?;
+        problems.extend(validate_mcp(&path, &content));
+    }
+    Ok(problems)
+}
+
+/// Parse an MCP config file. Failures list every problem.
+pub fn parse_mcp(path: &Path, content: &str) -> Result<McpConfig, Error> {
+    let problems = validate_mcp(path, content);
+    if !problems.is_empty() {
+        return Err(into_error(&problems));
+    }
+    Ok(serde_json::from_str(content)?)
+}
+
+/// Check an MCP config file: JSON object, required `name` matching the file
+/// name, non-empty `command` for `stdio` servers or an `http(s)` `url` for
+/// remote ones, string `args`, string `env` values, a known `scope` and a
+/// string `cwd`.
+pub fn validate_mcp(path: &Path, content: &str) -> Vec<Problem> {
+    let problem = |key: &str, message: String| Problem {
+        file: path.to_path_buf(),
+        key: key.to_string(),
+        line: find_line(content, key, ConfigFormat::Yaml),
+        message,
+    };
+    let tree: serde_json::Value = match serde_json::from_str(content) {
+        Ok(tree) => tree,
+        Err(e) => return vec![problem("", format!("syntax error: {}", e))],
+    };
+    let Some(map) = tree.as_object() else {
+        return vec![problem(
+            "",
+            format!("expected an object, got {}", json_type(&tree)),
+        )];
+    };
+
+    let mut problems = Vec::new();
+    for key in map.keys().filter(|k| !MCP_KEYS.contains(&k.as_str())) {
+        problems.push(problem(
+            key,
+            format!("unknown key (expected one of: {})", MCP_KEYS.join(", ")),
+        ));
+    }
+
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    match map.get("name") {
+        None => problems.push(problem("name", "required".to_string())),
+        Some(serde_json::Value::String(name)) if !McpConfig::valid_name(name) => {
+            problems.push(problem(
+                "name",
+                format!(
+                    "'{}' is not a valid name (letters, digits, '.', '_', '-')",
+                    name
+                ),
+            ))
+        }
+        Some(serde_json::Value::String(name)) if name != stem => problems.push(problem(
+            "name",
+            format!("'{}' does not match the file name '{}.json'", name, stem),
+        )),
+        Some(serde_json::Value::String(_)) => {}
+        Some(other) => problems.push(problem(
+            "name",
+            format!("expected a string, got {}", json_type(other)),
+        )),
+    }
+
+    let transport = match map.get("transport") {
+        None => Some(Transport::Stdio),
+        Some(value) => {
+            let parsed = serde_json::from_value::<Transport>(value.clone()).ok();
+            if parsed.is_none() {
+                problems.push(problem(
+                    "transport",
+                    format!(
+                        "{} is not a transport (expected one of: stdio, sse, http)",
+                        value
+                    ),
+                ));
+            }
+            parsed
+        }
+    };
+    match (transport, map.get("url")) {
+        (Some(Transport::Stdio), Some(_)) => problems.push(problem(
+            "url",
+            "only applies to sse and http servers".to_string(),
+        )),
+        (Some(Transport::Sse | Transport::Http), url) => {
+            let url = url.map(|u| u.as_str().ok_or(u));
+            match url {
+                Some(Err(other)) => problems.push(problem(
+                    "url",
+                    format!("expected a string, got {}", json_type(other)),
+                )),
+                url => {
+                    if let Some(message) = remote_problem(url.and_then(Result::ok)) {
+                        problems.push(problem("url", message.to_string()));
+                    }
+                }
+            }
+            for key in STDIO_KEYS.iter().filter(|k| map.contains_key(**k)) {
+                problems.push(problem(key, "only applies to stdio servers".to_string()));
+            }
+        }
+        _ => {}
+    }
+    if transport == Some(Transport::Stdio) {
+        check_stdio(map, &problem, &mut problems);
+    }
+
+    match map.get("scope") {
+        None => {}
+        Some(serde_json::Value::String(scope)) if !MCP_SCOPES.contains(&scope.as_str()) => problems
+            .push(problem(
+                "scope",
+                format!(
+                    "'{}' is not a scope (expected one of: {})",
+                    scope,
+                    MCP_SCOPES.join(", ")
+                ),
+            )),
+        Some(serde_json::Value::String(_)) => {}
+        Some(other) => problems.push(problem(
+            "scope",
+            format!("expected a string, got {}", json_type(other)),
+        )),
+    }
+    problems
+}
+
+/// Checks of the keys `stdio` servers take.
+fn check_stdio(
+    map: &serde_json::Map<String, serde_json::Value>,
+    problem: &impl Fn(&str, String) -> Problem,
+    problems: &mut Vec<Problem>,
+) {
+    match map.get("command") {
+        None => problems.push(problem("command", "required".to_string())),
+        Some(serde_json::Value::String(command)) if command.trim().is_empty() => {
+            problems.push(problem("command", "must not be empty".to_string()))
+        }
+        Some(serde_json::Value::String(_)) => {}
+        Some(other) => problems.push(problem(
+            "command",
+            format!("expected a string, got {}", json_type(other)),
+        )),
+    }
+
+    match map.get("args") {
+        None => {}
+        Some(serde_json::Value::Array(args)) => {
+            for (i, arg) in args.iter().enumerate().filter(|(_, a)| !a.is_string()) {
+                problems.push(problem(
+                    &format!("args[{}]", i),
+                    format!("expected a string, got {}", json_type(arg)),
+                ));
+            }
+        }
+        Some(other) => problems.push(problem(
+            "args",
+            format!("expected a list, got {}", json_type(other)),
+        )),
+    }
+
+    match map.get("env") {
+        None => {}
+        Some(serde_json::Value::Object(env)) => {
+            for (name, value) in env.iter().filter(|(_, v)| !v.is_string()) {
+                problems.push(problem(
+                    &format!("env.{}", name),
+                    format!("expected a string, got {}", json_type(value)),
+                ));
+            }
+        }
+        Some(other) => problems.push(problem(
+            "env",
+            format!("expected a section, got {}", json_type(other)),
+        )),
+    }
+
+    match map.get("cwd") {
+        None | Some(serde_json::Value::String(_)) => {}
+        Some(other) => problems.push(problem(
+            "cwd",
+            format!("expected a string, got {}", json_type(other)),
+        )),
+    }
+}
+
+/// Validate a config file, also flagging layouts older than the current version.
+fn check_file<T: Versioned>(path: &Path, content: &str, format: ConfigFormat) -> Vec<Problem> {
+    let mut problems = validate_content::<T>(path, content, format);
+    if problems.is_empty() {
+        let from = format
+            .parse::<Value>(content)
+            .map(|tree| migrate::version_of(&tree))
+            .unwrap_or(T::VERSION);
+        if from < T::VERSION {
+            problems.push(Problem {
+                file: path.to_path_buf(),
+                key: "version".to_string(),
+                line: find_line(content, "version", format),
+                message: format!(
+                    "version {} is outdated (current {}); run 'sqrl config migrate'",
+                    from,
+                    T::VERSION
+                ),
+            });
+        }
+    }
+    problems
+}
+
+/// Render problems as a single error.
+pub fn into_error(problems: &[Problem]) -> Error {
+    let lines: Vec<String> = problems.iter().map(|p| format!("  {}", p)).collect();
+    Error::ConfigParse(format!("invalid configuration\n{}", lines.join("\n")))
+}
+
+/// Parse config content, migrating old layouts. Failures list every problem.
+pub fn parse<T: Versioned>(path: &Path, content: &str, format: ConfigFormat) -> Result<T, Error> {
+    let parsed = format.parse::<Value>(content).and_then(|mut tree| {
+        migrate::migrate::<T>(&mut tree)?;
+        serde_yaml::from_value(tree).map_err(|e| Error::ConfigParse(e.to_string()))
+    });
+    parsed.map_err(|e| {
+        let problems = validate_content::<T>(path, content, format);
+        if problems.is_empty() {
+            e
+        } else {
+            into_error(&problems)
+        }
+    })
+}
+
+/// Validate raw config content against the schema implied by its defaults.
+pub fn validate_content<T: Versioned>(
+    path: &Path,
+    content: &str,
+    format: ConfigFormat,
+) -> Vec<Problem> {
+    let problem = |key: &str, message: String| Problem {
+        file: path.to_path_buf(),
+        key: key.to_string(),
+        line: find_line(content, key, format),
+        message,
+    };
+
+    let mut tree: Value = match format.parse(content) {
+        Ok(tree) => tree,
+        Err(e) => return vec![problem("", format!("syntax error: {}", e))],
+    };
+    if let Err(e) = migrate::migrate::<T>(&mut tree) {
+        return vec![problem("version", e.to_string())];
+    }
+    let Ok(schema) = serde_yaml::to_value(T::default()) else {
+        return vec![];
+    };
+
+    let mut problems = Vec::new();
+    check_node(&tree, &schema, "", &mut |key, message| {
+        problems.push(problem(key, message))
+    });
+
+    // Types match but values may still be rejected (e.g. out of range)
+    if problems.is_empty() {
+        if let Err(e) = serde_yaml::from_value::<T>(tree) {
+            problems.push(problem("", e.to_string()));
+        }
+    }
+    problems
+}
+
+fn check_node(value: &Value, schema: &Value, key: &str, report: &mut impl FnMut(&str, String)) {
+    match (value, schema) {
+        // Empty in the defaults means user-named entries (e.g. profiles)
+        (Value::Mapping(_), Value::Mapping(schema_map)) if schema_map.is_empty() => {}
+        (Value::Mapping(map), Value::Mapping(schema_map)) => {
+            for (k, v) in map {
+                let name = k.as_str().unwrap_or_default();
+                let child = if key.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                match schema_map.get(k) {
+                    Some(s) => check_node(v, s, &child, report),
+                    None => {
+                        let known: Vec<&str> =
+                            schema_map.keys().filter_map(|k| k.as_str()).collect();
+                        report(
+                            &child,
+                            format!("unknown key (expected one of: {})", known.join(", ")),
+                        );
+                    }
+                }
+            }
+        }
+        (Value::Sequence(items), Value::Sequence(schema_items)) => {
+            if let Some(item_schema) = schema_items.first() {
+                for (i, item) in items.iter().enumerate() {
+                    check_node(item, item_schema, &format!("{}[{}]", key, i), report);
+                }
+            }
+        }
+        (_, Value::Null) => {}
+        _ if type_name(value) != type_name(schema) => report(
+            key,
+            format!("expected {}, got {}", type_name(schema), type_name(value)),
+        ),
+        _ => {}
+    }
+}
+
+/// Human-readable type of a value.
+pub fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Sequence(_) => "a list",
+        Value::Mapping(_) => "a section",
+        Value::Tagged(_) => "a tagged value",
+    }
+}
+
+/// Human-readable type of a JSON value, worded like [`type_name`].
+fn json_type(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "a list",
+        serde_json::Value::Object(_) => "a section",
+    }
+}
+
+/// Find the 1-based line defining a dotted key (best effort).
+fn find_line(content: &str, key: &str, format: ConfigFormat) -> Option<usize> {
+    if key.is_empty() {
+        return None;
+    }
+    let parts: Vec<&str> = key
+        .split('.')
+        .map(|p| p.split('[').next().unwrap_or(p))
+        .collect();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    let mut found = None;
+
+    for (depth, part) in parts.iter().enumerate() {
+        let is_last = depth == parts.len() - 1;
+        let idx = lines.iter().skip(start).position(|line| {
+            let t = line.trim_start();
+            match format {
+                ConfigFormat::Yaml => {
+                    t.starts_with(&format!("{}:", part)) || t.starts_with(&format!("\"{}\":", part))
+                }
+                ConfigFormat::Toml if is_last => {
+                    t.starts_with(&format!("{} ", part)) || t.starts_with(&format!("{}=", part))
+                }
+                ConfigFormat::Toml => t.starts_with('[') && t.contains(part),
+            }
+        })?;
+        found = Some(start + idx + 1);
+        start += idx + 1;
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_key_type_and_line() {
+        let content = "tools:\n  cursor: yes please\n  vim: true\nhooks:\n  auto_install: true\n";
+        let problems =
+            validate_content::<Config>(Path::new("config.yaml"), content, ConfigFormat::Yaml);
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].key, "tools.cursor");
+        assert_eq!(problems[0].line, Some(2));
+        assert!(problems[0].message.contains("expected a boolean"));
+        assert_eq!(problems[1].key, "tools.vim");
+        assert!(problems[1].message.contains("unknown key"));
+    }
+
+    #[test]
+    fn test_validate_global_rejects_out_of_range_port() {
+        let problems = validate_content::<GlobalConfig>(
+            Path::new("config.yaml"),
+            "ui:\n  port: 70000\n",
+            ConfigFormat::Yaml,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("u16"));
+    }
+
+    #[test]
+    fn test_validate_valid_config_has_no_problems() {
+        let content = ConfigFormat::Toml.render(&Config::default()).unwrap();
+        let problems =
+            validate_content::<Config>(Path::new("config.toml"), &content, ConfigFormat::Toml);
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_validate_mcp_reports_each_field() {
+        let content = r#"{
+  "name": "github",
+  "args": ["--port", 8080],
+  "env": {
+    "TOKEN": true
+  },
+  "scope": "global",
+  "cwd": 1,
+  "cmd": "npx"
+}"#;
+        let problems = validate_mcp(Path::new("mcps/github.json"), content);
+        let found: Vec<(&str, Option<usize>)> =
+            problems.iter().map(|p| (p.key.as_str(), p.line)).collect();
+        assert_eq!(
+            found,
+            [
+                ("cmd", Some(9)),
+                ("command", None),
+                ("args[1]", Some(3)),
+                ("env.TOKEN", Some(5)),
+                ("cwd", Some(8)),
+                ("scope", Some(7))
+            ]
+        );
+        assert!(problems[5].message.contains("project, user, local"));
+
+        let valid = r#"{"name": "github", "command": "npx", "args": ["-y"]}"#;
+        let mcp = parse_mcp(Path::new("mcps/github.json"), valid).unwrap();
+        assert_eq!(mcp.scope, "project");
+        assert!(parse_mcp(Path::new("mcps/other.json"), valid).is_err());
+
+        let remote = r#"{
+  "name": "docs",
+  "transport": "sse",
+  "url": "ftp://mcp.example.com",
+  "command": "npx"
+}"#;
+        let problems = validate_mcp(Path::new("mcps/docs.json"), remote);
+        let found: Vec<&str> = problems.iter().map(|p| p.key.as_str()).collect();
+        assert_eq!(found, ["url", "command"]);
+        let remote =
+            r#"{"name": "docs", "transport": "http", "url": "https://mcp.example.com/mcp"}"#;
+        let mcp = parse_mcp(Path::new("mcps/docs.json"), remote).unwrap();
+        assert_eq!(mcp.transport, Transport::Http);
+    }
+
+    #[test]
+    fn test_check_file_reports_outdated_version() {
+        let problems = check_file::<Config>(
+            Path::new("config.yaml"),
+            "hooks:\n  auto_install: true\n",
+            ConfigFormat::Yaml,
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].key, "version");
+        assert!(problems[0].message.contains("sqrl config migrate"));
+    }
+}
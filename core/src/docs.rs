@@ -0,0 +1,47 @@
+//! Doc detection shared by the pre-push check (CLI-008), `sqrl doctor` and
+//! MCP doc lookup (MCP-004).
+//!
+//! Which paths are docs is the project's `docs` config, compiled into a
+//! [`DocMatcher`](crate::config::matcher::DocMatcher).
+
+use std::path::Path;
+
+use tracing::debug;
+
+use crate::config::matcher::DocMatcher;
+use crate::walk;
+
+/// Find documentation files: top-level docs, docs under the include paths and
+/// doc paths, skipping ignored paths (WALK-001).
+pub fn find_doc_files(project_root: &Path, matcher: &DocMatcher) -> Vec<String> {
+    let docs: Vec<String> = walk::files(project_root, "", None)
+        .into_iter()
+        .filter(|path| matcher.is_listed(path))
+        .collect();
+
+    debug!(count = docs.len(), "Found doc files");
+    docs
+}
+
+/// Whether `text` contains `path` not as the tail of a longer path (`a.rs` in `src/a.rs`).
+pub fn mentions(text: &str, path: &str) -> bool {
+    text.match_indices(path).any(|(at, _)| {
+        !text[..at]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || "/._-".contains(c))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_skips_longer_paths() {
+        assert!(mentions("See src/a.rs for details", "src/a.rs"));
+        assert!(mentions("a.rs changed", "a.rs"));
+        assert!(!mentions("See src/a.rs for details", "a.rs"));
+        assert!(!mentions("data.rs", "a.rs"));
+    }
+}
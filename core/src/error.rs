@@ -0,0 +1,365 @@
+//! Error types for Squirrel.
+//!
+//! Every error has a stable code and, where there is an obvious next step,
+//! a hint. `main` renders both (ERROR-001).
+
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// Squirrel error type.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{}: {source}", path.display())]
+    IoAt {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Memory database not found: {}", .0.display())]
+    DatabaseNotFound(PathBuf),
+
+    #[error("Git error: {0}")]
+    Git(#[from] git2::Error),
+
+    #[error("'{command}' failed: {stderr}")]
+    Command { command: String, stderr: String },
+
+    #[error("MCP error: {0}")]
+    Mcp(String),
+
+    #[error("Home directory not found")]
+    HomeDirNotFound,
+
+    #[error("Config not found: {}", .0.display())]
+    ConfigNotFound(PathBuf),
+
+    #[error("Global config not found")]
+    GlobalConfigNotFound,
+
+    #[error("MCP config not found: {0}")]
+    McpNotFound(String),
+
+    #[error("Config parse error: {0}")]
+    ConfigParse(String),
+
+    #[error("Placeholder error: {0}")]
+    Placeholder(String),
+
+    #[error("Upgrade error: {0}")]
+    Upgrade(String),
+
+    #[error("Timed out waiting for lock: {}", .0.display())]
+    Locked(PathBuf),
+
+    #[error("Memory not found: {0}")]
+    MemoryNotFound(String),
+
+    #[error("ID prefix '{prefix}' matches {} memories", matches.len())]
+    AmbiguousId {
+        prefix: String,
+        matches: Vec<String>,
+    },
+
+    #[error("Sync failed: {0}")]
+    Sync(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error(
+        "memory.db schema version {0} is newer than this sqrl supports ({})",
+        crate::storage::SCHEMA_VERSION
+    )]
+    SchemaTooNew(i64),
+}
+
+/// Process exit codes (ERROR-002), stable so scripts can tell causes apart.
+/// Ranges group them: 10s not set up, 20s blocked by policy, 30s storage,
+/// 40s config, 50s integrations `sqrl status` checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Ok = 0,
+    /// The command ran and the answer is no: problems found, nothing matched.
+    Problems = 1,
+    /// Bad arguments, including unknown or ambiguous IDs. Also clap's code.
+    Usage = 2,
+    NotInitialized = 10,
+    NotConfigured = 11,
+    PushBlocked = 20,
+    Storage = 30,
+    SchemaTooNew = 31,
+    Locked = 32,
+    ConfigInvalid = 40,
+    HooksOutdated = 50,
+    McpUnregistered = 51,
+    /// Any other error: I/O, git, a command sqrl ran, the network.
+    Failure = 70,
+}
+
+impl ExitCode {
+    pub const ALL: [ExitCode; 13] = [
+        ExitCode::Ok,
+        ExitCode::Problems,
+        ExitCode::Usage,
+        ExitCode::NotInitialized,
+        ExitCode::NotConfigured,
+        ExitCode::PushBlocked,
+        ExitCode::Storage,
+        ExitCode::SchemaTooNew,
+        ExitCode::Locked,
+        ExitCode::ConfigInvalid,
+        ExitCode::HooksOutdated,
+        ExitCode::McpUnregistered,
+        ExitCode::Failure,
+    ];
+
+    /// The number the process exits with.
+    pub const fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Stable name, e.g. `not_initialized`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExitCode::Ok => "ok",
+            ExitCode::Problems => "problems",
+            ExitCode::Usage => "usage",
+            ExitCode::NotInitialized => "not_initialized",
+            ExitCode::NotConfigured => "not_configured",
+            ExitCode::PushBlocked => "push_blocked",
+            ExitCode::Storage => "storage",
+            ExitCode::SchemaTooNew => "schema_too_new",
+            ExitCode::Locked => "locked",
+            ExitCode::ConfigInvalid => "config_invalid",
+            ExitCode::HooksOutdated => "hooks_outdated",
+            ExitCode::McpUnregistered => "mcp_unregistered",
+            ExitCode::Failure => "failure",
+        }
+    }
+
+    /// One line for `sqrl status --explain-exit-codes`.
+    pub fn meaning(self) -> &'static str {
+        match self {
+            ExitCode::Ok => "Success",
+            ExitCode::Problems => {
+                "Ran, and found problems or nothing (doctor, lint, grep, rules test, apply --check)"
+            }
+            ExitCode::Usage => "Bad arguments, or an unknown or ambiguous memory ID",
+            ExitCode::NotInitialized => "Project not initialized (no .sqrl/)",
+            ExitCode::NotConfigured => "No global config, home directory or MCP config",
+            ExitCode::PushBlocked => "Pre-push check blocked the push",
+            ExitCode::Storage => "memory.db missing, corrupt or unreadable",
+            ExitCode::SchemaTooNew => "memory.db written by a newer sqrl",
+            ExitCode::Locked => "Another sqrl process held the lock",
+            ExitCode::ConfigInvalid => "Config syntax, key, value or placeholder error",
+            ExitCode::HooksOutdated => "Git hooks missing or out of date (sqrl status)",
+            ExitCode::McpUnregistered => "Enabled MCP not registered in a tool (sqrl status)",
+            ExitCode::Failure => "Any other error",
+        }
+    }
+
+    /// Error codes (ERROR-001) that exit with this code.
+    pub fn error_codes(self) -> impl Iterator<Item = &'static str> {
+        ERROR_EXITS
+            .into_iter()
+            .filter(move |(_, exit)| *exit == self)
+            .map(|(code, _)| code)
+    }
+}
+
+/// Exit code of each error code. Codes not listed exit `Failure`.
+const ERROR_EXITS: [(&str, ExitCode); 19] = [
+    ("io", ExitCode::Failure),
+    ("json", ExitCode::Failure),
+    ("database", ExitCode::Storage),
+    ("git", ExitCode::Failure),
+    ("command_failed", ExitCode::Failure),
+    ("mcp", ExitCode::Failure),
+    ("home_not_found", ExitCode::NotConfigured),
+    ("config_not_found", ExitCode::NotInitialized),
+    ("global_config_not_found", ExitCode::NotConfigured),
+    ("mcp_not_found", ExitCode::NotConfigured),
+    ("config_invalid", ExitCode::ConfigInvalid),
+    ("placeholder", ExitCode::ConfigInvalid),
+    ("upgrade_failed", ExitCode::Failure),
+    ("locked", ExitCode::Locked),
+    ("memory_not_found", ExitCode::Usage),
+    ("ambiguous_id", ExitCode::Usage),
+    ("sync_failed", ExitCode::Failure),
+    ("invalid_argument", ExitCode::Usage),
+    ("schema_too_new", ExitCode::SchemaTooNew),
+];
+
+impl Error {
+    /// Attach a path to an IO error, for `map_err`.
+    pub fn at(path: impl Into<PathBuf>) -> impl FnOnce(std::io::Error) -> Error {
+        let path = path.into();
+        move |source| Error::IoAt { path, source }
+    }
+
+    /// Stable machine-readable code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Io(_) | Error::IoAt { .. } => "io",
+            Error::Json(_) => "json",
+            Error::Sqlite(_) | Error::DatabaseNotFound(_) => "database",
+            Error::Git(_) => "git",
+            Error::Command { .. } => "command_failed",
+            Error::Mcp(_) => "mcp",
+            Error::HomeDirNotFound => "home_not_found",
+            Error::ConfigNotFound(_) => "config_not_found",
+            Error::GlobalConfigNotFound => "global_config_not_found",
+            Error::McpNotFound(_) => "mcp_not_found",
+            Error::ConfigParse(_) => "config_invalid",
+            Error::Placeholder(_) => "placeholder",
+            Error::Upgrade(_) => "upgrade_failed",
+            Error::Locked(_) => "locked",
+            Error::MemoryNotFound(_) => "memory_not_found",
+            Error::AmbiguousId { .. } => "ambiguous_id",
+            Error::Sync(_) => "sync_failed",
+            Error::InvalidArgument(_) => "invalid_argument",
+            Error::SchemaTooNew(_) => "schema_too_new",
+        }
+    }
+
+    /// Exit code for a command failing with this error (ERROR-002).
+    pub fn exit_code(&self) -> ExitCode {
+        let code = self.code();
+        ERROR_EXITS
+            .into_iter()
+            .find(|(c, _)| *c == code)
+            .map_or(ExitCode::Failure, |(_, exit)| exit)
+    }
+
+    /// What the user can try next, if anything.
+    pub fn hint(&self) -> Option<String> {
+        Some(match self {
+            Error::IoAt { source, .. } if source.kind() == std::io::ErrorKind::PermissionDenied => {
+                "Check the file's permissions.".to_string()
+            }
+            Error::Sqlite(_) => {
+                "Run 'sqrl status' to check the database, then 'sqrl init --repair'.".to_string()
+            }
+            Error::DatabaseNotFound(_) => "Run 'sqrl init --repair' to create it.".to_string(),
+            Error::Command { command, .. } => {
+                let program = command.split_whitespace().next().unwrap_or(command);
+                format!("Check that '{}' is installed and on PATH.", program)
+            }
+            Error::HomeDirNotFound => "Set the HOME environment variable.".to_string(),
+            Error::ConfigNotFound(_) => "Run 'sqrl init' in the project first.".to_string(),
+            Error::GlobalConfigNotFound => "Run 'sqrl setup' or 'sqrl config' first.".to_string(),
+            Error::McpNotFound(_) => {
+                "MCP configs live in ~/.sqrl/mcps/; add one with 'sqrl mcp import'.".to_string()
+            }
+            Error::ConfigParse(msg) if msg.starts_with("Unknown config key") => {
+                "Run 'sqrl config show' to see valid keys.".to_string()
+            }
+            Error::ConfigParse(_) => "Run 'sqrl doctor' to list every problem.".to_string(),
+            Error::Placeholder(_) => {
+                "Set the environment variable or keychain entry the placeholder names.".to_string()
+            }
+            Error::Upgrade(_) => {
+                "Retry later, or download the release manually from GitHub.".to_string()
+            }
+            Error::Locked(_) => {
+                "Another sqrl command is changing this directory; retry when it finishes."
+                    .to_string()
+            }
+            Error::MemoryNotFound(_) => {
+                "Run 'sqrl memory list' to see IDs (at least 4 characters).".to_string()
+            }
+            Error::AmbiguousId { .. } => "Use more characters of the ID.".to_string(),
+            Error::Sync(_) => {
+                "Pass a project directory, a memory.db path, or host:path for a machine reached over ssh."
+                    .to_string()
+            }
+            Error::SchemaTooNew(_) => {
+                "A newer sqrl wrote this database; run 'sqrl upgrade'.".to_string()
+            }
+            _ => return None,
+        })
+    }
+
+    /// Captured context (paths, commands, stderr).
+    pub fn context(&self) -> Value {
+        match self {
+            Error::IoAt { path, .. }
+            | Error::ConfigNotFound(path)
+            | Error::DatabaseNotFound(path)
+            | Error::Locked(path) => {
+                json!({ "path": path })
+            }
+            Error::Command { command, stderr } => json!({ "command": command, "stderr": stderr }),
+            Error::McpNotFound(name) => json!({ "mcp": name }),
+            Error::MemoryNotFound(id) => json!({ "id": id }),
+            Error::AmbiguousId { prefix, matches } => json!({ "id": prefix, "matches": matches }),
+            _ => json!({}),
+        }
+    }
+
+    /// JSON document for `--json` error output.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "error": {
+                "code": self.code(),
+                "message": self.to_string(),
+                "hint": self.hint(),
+                "context": self.context(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_json_has_code_hint_and_context() {
+        let err = Error::ConfigNotFound(PathBuf::from("/p/.sqrl/config.yaml"));
+        let value = err.to_json();
+        assert_eq!(value["error"]["code"], "config_not_found");
+        assert!(value["error"]["hint"]
+            .as_str()
+            .unwrap()
+            .contains("sqrl init"));
+        assert_eq!(value["error"]["context"]["path"], "/p/.sqrl/config.yaml");
+    }
+
+    #[test]
+    fn test_exit_codes_are_distinct_by_cause() {
+        let err = Error::ConfigNotFound(PathBuf::from("/p/.sqrl/config.yaml"));
+        assert_eq!(err.exit_code().code(), 10);
+        assert_eq!(Error::SchemaTooNew(2).exit_code(), ExitCode::SchemaTooNew);
+        assert_eq!(
+            Error::DatabaseNotFound(PathBuf::from("memory.db")).exit_code(),
+            ExitCode::Storage
+        );
+        assert_eq!(
+            Error::InvalidArgument("x".to_string()).exit_code(),
+            ExitCode::Usage
+        );
+        assert_eq!(
+            Error::Upgrade("x".to_string()).exit_code(),
+            ExitCode::Failure
+        );
+
+        let mut codes: Vec<i32> = ExitCode::ALL.iter().map(|e| e.code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), ExitCode::ALL.len());
+        assert!(ExitCode::Storage.error_codes().eq(["database"]));
+    }
+}
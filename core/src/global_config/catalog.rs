@@ -0,0 +1,176 @@
+//! Built-in catalog of common MCP servers (MCP-CONFIG-003).
+//!
+//! Templates use `{{param}}` in args and env; instantiating fills them in.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::global_config::McpConfig;
+
+/// A template parameter shown as a form field.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateParam {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+    pub default: Option<&'static str>,
+}
+
+/// A catalog entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpTemplate {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub command: &'static str,
+    pub args: &'static [&'static str],
+    pub env: &'static [(&'static str, &'static str)],
+    pub params: &'static [TemplateParam],
+}
+
+const fn param(
+    name: &'static str,
+    description: &'static str,
+    default: Option<&'static str>,
+) -> TemplateParam {
+    TemplateParam {
+        name,
+        description,
+        required: default.is_none(),
+        default,
+    }
+}
+
+const TEMPLATES: &[McpTemplate] = &[
+    McpTemplate {
+        id: "filesystem",
+        description: "Read and write files under an allowed directory",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-filesystem", "{{path}}"],
+        env: &[],
+        params: &[param("path", "Directory the server may access", None)],
+    },
+    McpTemplate {
+        id: "git",
+        description: "Inspect and operate on a git repository",
+        command: "uvx",
+        args: &["mcp-server-git", "--repository", "{{repository}}"],
+        env: &[],
+        params: &[param("repository", "Path to the git repository", Some("."))],
+    },
+    McpTemplate {
+        id: "fetch",
+        description: "Fetch web pages and convert them to markdown",
+        command: "uvx",
+        args: &["mcp-server-fetch"],
+        env: &[],
+        params: &[],
+    },
+    McpTemplate {
+        id: "github",
+        description: "GitHub issues, pull requests and repositories",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-github"],
+        env: &[("GITHUB_PERSONAL_ACCESS_TOKEN", "{{token}}")],
+        params: &[param(
+            "token",
+            "Token or placeholder, e.g. ${env:GITHUB_TOKEN}",
+            Some("${env:GITHUB_TOKEN}"),
+        )],
+    },
+    McpTemplate {
+        id: "sqlite",
+        description: "Query a SQLite database",
+        command: "uvx",
+        args: &["mcp-server-sqlite", "--db-path", "{{db_path}}"],
+        env: &[],
+        params: &[param("db_path", "Path to the database file", None)],
+    },
+    McpTemplate {
+        id: "memory",
+        description: "Knowledge graph memory server",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-memory"],
+        env: &[],
+        params: &[],
+    },
+];
+
+/// List all catalog templates.
+pub fn templates() -> &'static [McpTemplate] {
+    TEMPLATES
+}
+
+/// Find a template by ID.
+pub fn get_template(id: &str) -> Option<&'static McpTemplate> {
+    TEMPLATES.iter().find(|t| t.id == id)
+}
+
+/// Instantiate a template into an MCP config.
+pub fn instantiate(
+    template: &McpTemplate,
+    name: Option<&str>,
+    params: &BTreeMap<String, String>,
+) -> Result<McpConfig, Error> {
+    let mut values = HashMap::new();
+    for p in template.params {
+        let value = params
+            .get(p.name)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+            .or(p.default)
+            .ok_or_else(|| Error::ConfigParse(format!("Missing parameter '{}'", p.name)))?;
+        values.insert(p.name, value);
+    }
+
+    let fill = |s: &str| {
+        values.iter().fold(s.to_string(), |acc, (k, v)| {
+            acc.replace(&format!("{{{{{}}}}}", k), v)
+        })
+    };
+
+    Ok(McpConfig {
+        name: name.unwrap_or(template.id).to_string(),
+        command: template.command.to_string(),
+        args: template.args.iter().map(|a| fill(a)).collect(),
+        env: template
+            .env
+            .iter()
+            .map(|(k, v)| (k.to_string(), fill(v)))
+            .collect(),
+        scope: "project".to_string(),
+        cwd: None,
+        transport: Default::default(),
+        url: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instantiate_fills_params_and_defaults() {
+        let template = get_template("git").unwrap();
+        let mcp = instantiate(template, None, &BTreeMap::new()).unwrap();
+        assert_eq!(mcp.name, "git");
+        assert_eq!(mcp.args, vec!["mcp-server-git", "--repository", "."]);
+
+        let template = get_template("github").unwrap();
+        let mcp = instantiate(template, Some("gh"), &BTreeMap::new()).unwrap();
+        assert_eq!(mcp.name, "gh");
+        assert_eq!(
+            mcp.env
+                .get("GITHUB_PERSONAL_ACCESS_TOKEN")
+                .map(String::as_str),
+            Some("${env:GITHUB_TOKEN}")
+        );
+    }
+
+    #[test]
+    fn test_instantiate_missing_required_param_errors() {
+        let template = get_template("filesystem").unwrap();
+        assert!(instantiate(template, None, &BTreeMap::new()).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Manifest of entries Squirrel wrote into third-party configs (MANAGED-001).
+//!
+//! Each entry is located by file + JSON pointer and fingerprinted with a hash,
+//! so hand-edited or deleted entries can be reported as drift, and taken
+//! back out again by `sqrl unapply` (CLI-031). Items of an array (Continue's
+//! server list) move when others are added or removed, so they are found by
+//! fingerprint, or by the fields in their `key`, rather than by index.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::atomic;
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+
+/// A config entry written by Squirrel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedEntry {
+    pub tool: String,
+    pub name: String,
+    pub file: PathBuf,
+    pub pointer: String,
+    pub hash: String,
+    pub applied_at: String,
+    /// Directory `sqrl apply` ran in, for entries that belong to a project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<PathBuf>,
+    /// For an array item, the fields that identify it, e.g. its command line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<Value>,
+}
+
+/// How a managed entry differs from what Squirrel wrote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftKind {
+    Modified,
+    Removed,
+}
+
+/// A managed entry that drifted.
+#[derive(Debug, Clone)]
+pub struct Drift {
+    pub entry: ManagedEntry,
+    pub kind: DriftKind,
+}
+
+/// Manifest stored in `~/.sqrl/managed.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub entries: Vec<ManagedEntry>,
+}
+
+impl Manifest {
+    /// Get the manifest file path.
+    pub fn path() -> Result<PathBuf, Error> {
+        Ok(GlobalConfig::dir()?.join("managed.json"))
+    }
+
+    /// Load the manifest, empty if missing.
+    pub fn load() -> Result<Self, Error> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Save the manifest.
+    pub fn save(&self) -> Result<(), Error> {
+        atomic::write(&Self::path()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record the current value at `pointer` in `file` as managed.
+    /// Does nothing if the file or pointer can't be read.
+    pub fn record(
+        &mut self,
+        tool: &str,
+        name: &str,
+        file: &Path,
+        pointer: &str,
+        project: Option<&Path>,
+    ) {
+        let Some(value) = read_pointer(file, pointer) else {
+            return;
+        };
+        self.entries.retain(|e| {
+            !(e.tool == tool && e.file == file && e.key.is_none() && e.pointer == pointer)
+        });
+        self.entries.push(ManagedEntry {
+            tool: tool.to_string(),
+            name: name.to_string(),
+            file: file.to_path_buf(),
+            pointer: pointer.to_string(),
+            hash: hash_value(&value),
+            applied_at: chrono::Utc::now().to_rfc3339(),
+            project: project.map(Path::to_path_buf),
+            key: None,
+        });
+    }
+
+    /// Record the first item of the array at `array` in `file` that matches
+    /// `key` as managed. Does nothing if there is none.
+    pub fn record_item(&mut self, tool: &str, name: &str, file: &Path, array: &str, key: Value) {
+        let Some(Value::Array(items)) = read_pointer(file, array) else {
+            return;
+        };
+        let Some((index, value)) = items.iter().enumerate().find(|(_, v)| matches(v, &key)) else {
+            return;
+        };
+        self.entries
+            .retain(|e| !(e.tool == tool && e.file == file && e.key.as_ref() == Some(&key)));
+        self.entries.push(ManagedEntry {
+            tool: tool.to_string(),
+            name: name.to_string(),
+            file: file.to_path_buf(),
+            pointer: format!("{}/{}", array, index),
+            hash: hash_value(value),
+            applied_at: chrono::Utc::now().to_rfc3339(),
+            project: None,
+            key: Some(key),
+        });
+    }
+
+    /// Compare every managed entry against its file.
+    pub fn check(&self) -> Vec<Drift> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                Some(Drift {
+                    entry: entry.clone(),
+                    kind: entry.drift()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Take the entry at `index` out of its file and forget it. An entry
+    /// already removed by hand is only forgotten.
+    pub fn unapply(&mut self, index: usize) -> Result<(), Error> {
+        let entry = self.entries.remove(index);
+        let Ok(content) = fs::read_to_string(&entry.file) else {
+            return Ok(());
+        };
+        let mut json: Value = serde_json::from_str(&content)?;
+        let Some(pointer) = entry.locate(&json) else {
+            return Ok(());
+        };
+        let (parent, key) = pointer.rsplit_once('/').unwrap_or(("", ""));
+        match json.pointer_mut(parent) {
+            Some(Value::Object(map)) => {
+                map.remove(&key.replace("~1", "/").replace("~0", "~"));
+            }
+            Some(Value::Array(items)) => {
+                let Ok(removed) = key.parse::<usize>() else {
+                    return Ok(());
+                };
+                items.remove(removed);
+            }
+            _ => return Ok(()),
+        }
+        atomic::write(&entry.file, serde_json::to_string_pretty(&json)?)?;
+        Ok(())
+    }
+}
+
+impl ManagedEntry {
+    /// How the entry differs from what Squirrel wrote, if it does.
+    pub fn drift(&self) -> Option<DriftKind> {
+        let Some(json) = read_pointer(&self.file, "") else {
+            return Some(DriftKind::Removed);
+        };
+        let Some(pointer) = self.locate(&json) else {
+            return Some(DriftKind::Removed);
+        };
+        match json.pointer(&pointer) {
+            Some(value) if hash_value(value) == self.hash => None,
+            _ => Some(DriftKind::Modified),
+        }
+    }
+
+    /// Where the entry is in `json` now. An array item is the one with the
+    /// recorded fingerprint, else the first matching `key`.
+    fn locate(&self, json: &Value) -> Option<String> {
+        let Some(key) = &self.key else {
+            return json.pointer(&self.pointer).map(|_| self.pointer.clone());
+        };
+        let (array, _) = self.pointer.rsplit_once('/')?;
+        let items = json.pointer(array)?.as_array()?;
+        let index = items
+            .iter()
+            .position(|item| hash_value(item) == self.hash)
+            .or_else(|| items.iter().position(|item| matches(item, key)))?;
+        Some(format!("{}/{}", array, index))
+    }
+}
+
+/// Whether `value` has every field of `key` with the same value.
+pub fn matches(value: &Value, key: &Value) -> bool {
+    match (value, key) {
+        (Value::Object(value), Value::Object(key)) => key
+            .iter()
+            .all(|(k, v)| value.get(k).is_some_and(|field| matches(field, v))),
+        _ => value == key,
+    }
+}
+
+/// Escape a JSON pointer segment (RFC 6901).
+pub fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Read the JSON value at `pointer` in `file`, if both exist.
+pub fn read_pointer(file: &Path, pointer: &str) -> Option<Value> {
+    let content = fs::read_to_string(file).ok()?;
+    let json: Value = serde_json::from_str(&content).ok()?;
+    json.pointer(pointer).cloned()
+}
+
+fn hash_value(value: &Value) -> String {
+    let digest = Sha256::digest(value.to_string().as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_check_detects_modified_and_removed() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("tool.json");
+        fs::write(
+            &file,
+            r#"{"mcpServers":{"a":{"command":"x"},"b":{"command":"y"}}}"#,
+        )
+        .unwrap();
+
+        let mut manifest = Manifest::default();
+        manifest.record("claude_code", "a", &file, "/mcpServers/a", None);
+        manifest.record("claude_code", "b", &file, "/mcpServers/b", None);
+        assert!(manifest.check().is_empty());
+
+        fs::write(&file, r#"{"mcpServers":{"a":{"command":"edited"}}}"#).unwrap();
+        let drift = manifest.check();
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].kind, DriftKind::Modified);
+        assert_eq!(drift[1].kind, DriftKind::Removed);
+    }
+
+    #[test]
+    fn test_array_items_are_found_wherever_they_moved() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("config.json");
+        let item = |command: &str| serde_json::json!({"transport": {"command": command}});
+        fs::write(
+            &file,
+            r#"{"servers":[{"transport":{"command":"a"}},{"transport":{"command":"b"}},{"transport":{"command":"c"}}],"keep":1}"#,
+        )
+        .unwrap();
+
+        let mut manifest = Manifest::default();
+        for name in ["a", "b", "c"] {
+            manifest.record_item("continue", name, &file, "/servers", item(name));
+        }
+        assert_eq!(manifest.entries[2].pointer, "/servers/2");
+        manifest.unapply(0).unwrap();
+        assert!(manifest.check().is_empty());
+
+        // A server added by hand in front shifts ours without drift
+        fs::write(
+            &file,
+            r#"{"servers":[{"transport":{"command":"mine"}},{"transport":{"command":"b"}},{"transport":{"command":"c"}}]}"#,
+        )
+        .unwrap();
+        assert!(manifest.check().is_empty());
+
+        // Edited in place, it is still found by its key
+        fs::write(
+            &file,
+            r#"{"servers":[{"transport":{"command":"mine"}},{"transport":{"command":"b","env":{"K":"v"}}}]}"#,
+        )
+        .unwrap();
+        let drift = manifest.check();
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].kind, DriftKind::Modified);
+        assert_eq!(drift[1].kind, DriftKind::Removed);
+
+        manifest.unapply(0).unwrap();
+        let json: Value = serde_json::from_str(&fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(json, serde_json::json!({"servers": [item("mine")]}));
+    }
+
+    #[test]
+    fn test_escape_pointer_escapes_slashes() {
+        assert_eq!(escape_pointer("/home/u/p"), "~1home~1u~1p");
+    }
+}
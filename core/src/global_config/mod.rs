@@ -0,0 +1,780 @@
+//! Global configuration (CONFIG-001).
+//!
+//! Handles loading and saving `~/.sqrl/config.yaml` and MCP configs.
+
+pub mod catalog;
+pub mod managed;
+pub mod projects;
+pub mod template;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::atomic;
+use crate::config::format::ConfigFormat;
+use crate::config::migrate::{self, Migration, Versioned};
+use crate::config::validate;
+use crate::error::Error;
+
+/// Global configuration stored in `~/.sqrl/config.yaml`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GlobalConfig {
+    /// Config layout version (CONFIG-005).
+    #[serde(default = "GlobalConfig::current_version")]
+    pub version: u32,
+
+    /// CLI tools enabled (applied to all projects).
+    #[serde(default)]
+    pub tools: GlobalToolsConfig,
+
+    /// Enabled MCP names (from uploaded config).
+    #[serde(default)]
+    pub mcps: Vec<String>,
+
+    /// Web UI settings.
+    #[serde(default)]
+    pub ui: UiConfig,
+
+    /// Persistent CLI log file.
+    #[serde(default)]
+    pub log: LogConfig,
+
+    /// Desktop notifications, per event.
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Trash for destructive operations (`sqrl undo`).
+    #[serde(default)]
+    pub trash: TrashConfig,
+
+    /// Local usage analytics (`sqrl stats --usage`).
+    #[serde(default)]
+    pub analytics: AnalyticsConfig,
+
+    /// External commands run on Squirrel events (PLUGIN-001).
+    #[serde(default)]
+    pub plugins: Vec<Plugin>,
+
+    /// Memory retrieval limits per MCP client, keyed by client name (MCP-002).
+    #[serde(default)]
+    pub retrieval: BTreeMap<String, RetrievalProfile>,
+
+    /// Active profile name, if any.
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// Named profiles (e.g. "work", "personal").
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named set of overrides for the global config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct Profile {
+    /// Replaces the top-level `tools` section while active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<GlobalToolsConfig>,
+
+    /// MCP names `sqrl apply` registers while active (all if unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcps: Option<Vec<String>>,
+}
+
+/// CLI tools configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GlobalToolsConfig {
+    #[serde(default = "default_true")]
+    pub claude_code: bool,
+    #[serde(default = "default_true")]
+    pub git: bool,
+    #[serde(default)]
+    pub cursor: bool,
+    #[serde(default)]
+    pub codex: bool,
+    #[serde(default)]
+    pub aider: bool,
+    #[serde(default)]
+    pub continue_dev: bool,
+}
+
+/// Web UI settings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UiConfig {
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default = "default_true")]
+    pub open_browser: bool,
+}
+
+/// CLI log file settings (`~/.sqrl/logs/cli.log`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LogConfig {
+    /// Write logs to the file on every run, not just with `--log-file`.
+    #[serde(default)]
+    pub file: bool,
+    /// Level for the file (`error`, `warn`, `info`, `debug`, `trace`).
+    #[serde(default = "default_log_level")]
+    pub level: String,
+    /// Rotate when the file exceeds this size.
+    #[serde(default = "default_log_max_size_kb")]
+    pub max_size_kb: u64,
+    /// Rotated files kept (`cli.log.1` … `cli.log.N`).
+    #[serde(default = "default_log_keep")]
+    pub keep: u32,
+}
+
+/// Desktop notifications, per event. All off by default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NotificationsConfig {
+    /// The pre-push hook blocked a push (`hooks.pre_push_block`).
+    #[serde(default)]
+    pub push_blocked: bool,
+}
+
+/// Trash settings (TRASH-001).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TrashConfig {
+    /// Days a trashed item can still be restored.
+    #[serde(default = "default_trash_retention_days")]
+    pub retention_days: u32,
+}
+
+/// Local usage analytics (CLI-023). Recorded in each project's database,
+/// never sent anywhere.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AnalyticsConfig {
+    /// Record commands, hook latencies and MCP tool calls.
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// An external command run with a JSON event on stdin (PLUGIN-001).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Plugin {
+    /// Name shown in warnings and logs.
+    pub name: String,
+    /// Events that run it.
+    pub events: Vec<PluginEvent>,
+    /// Program and arguments (not run through a shell).
+    pub command: Vec<String>,
+    /// Seconds before the command is killed and counted as failed.
+    #[serde(default = "default_plugin_timeout_secs")]
+    pub timeout_secs: u64,
+    /// What a failure (non-zero exit, timeout, missing program) does.
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+}
+
+/// Points where plugins run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginEvent {
+    /// A new memory was stored (MCP or `sqrl memory import`).
+    MemoryCreated,
+    /// The pre-push hook is checking the commits about to be pushed.
+    PrePushCheck,
+}
+
+/// How a plugin failure is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Log only.
+    Ignore,
+    /// Print a warning on stderr.
+    #[default]
+    Warn,
+    /// Warn, and fail the `pre_push_check` so the push is blocked.
+    Block,
+}
+
+/// What `squirrel_get_memory` returns to one MCP client.
+/// Unset fields fall back to the request's arguments and built-in defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RetrievalProfile {
+    /// Most memories per response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<i64>,
+
+    /// Most characters of memory text per response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_chars: Option<usize>,
+
+    /// Memory types to include (preference, project).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_types: Option<Vec<String>>,
+
+    /// Only memories with any of these tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// MCP configuration file (MCP-CONFIG-001).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpConfig {
+    pub name: String,
+    /// Command to launch a `stdio` server; empty for remote ones.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub env: std::collections::HashMap<String, String>,
+    #[serde(default = "default_scope")]
+    pub scope: String,
+    /// Directory to start the server in; relative paths are resolved against
+    /// the directory the tool launches it from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Transport::is_stdio")]
+    pub transport: Transport,
+    /// Endpoint of an `sse` or `http` server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// How a tool talks to an MCP server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// Launch `command` and talk over stdin/stdout.
+    #[default]
+    Stdio,
+    /// Remote server using Server-Sent Events.
+    Sse,
+    /// Remote server using streamable HTTP.
+    Http,
+}
+
+impl Transport {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Transport::Stdio => "stdio",
+            Transport::Sse => "sse",
+            Transport::Http => "http",
+        }
+    }
+
+    pub fn is_stdio(&self) -> bool {
+        *self == Transport::Stdio
+    }
+}
+
+/// Scopes an MCP config can have, as tools register them.
+pub const MCP_SCOPES: &[&str] = &["project", "user", "local"];
+
+impl McpConfig {
+    /// Whether `name` can be an MCP config name; names become file names.
+    pub fn valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+    }
+
+    /// Check the name, the fields its transport needs, and the scope
+    /// (MCP-CONFIG-001).
+    pub fn check(&self) -> Result<(), Error> {
+        let invalid = |message: String| Err(Error::InvalidArgument(message));
+        if !Self::valid_name(&self.name) {
+            return invalid(format!(
+                "invalid MCP name '{}': use letters, digits, '.', '_' and '-'",
+                self.name
+            ));
+        }
+        match (self.transport, &self.url) {
+            (Transport::Stdio, _) if self.command.trim().is_empty() => {
+                return invalid(format!("MCP '{}' has no command", self.name));
+            }
+            (Transport::Stdio, Some(_)) => {
+                return invalid(format!(
+                    "MCP '{}': url needs transport sse or http",
+                    self.name
+                ));
+            }
+            (Transport::Stdio, None) => {}
+            (transport, url) => {
+                if let Some(problem) = remote_problem(url.as_deref()) {
+                    return invalid(format!("MCP '{}': url {}", self.name, problem));
+                }
+                if !self.command.is_empty()
+                    || !self.args.is_empty()
+                    || !self.env.is_empty()
+                    || self.cwd.is_some()
+                {
+                    return invalid(format!(
+                        "MCP '{}': command, args, env and cwd don't apply to {} servers",
+                        self.name,
+                        transport.as_str()
+                    ));
+                }
+            }
+        }
+        if !MCP_SCOPES.contains(&self.scope.as_str()) {
+            return Err(Error::InvalidArgument(format!(
+                "invalid scope '{}': expected one of {}",
+                self.scope,
+                MCP_SCOPES.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What is wrong with a remote server's URL, if anything.
+pub fn remote_problem(url: Option<&str>) -> Option<&'static str> {
+    match url {
+        None | Some("") => Some("is required for sse and http servers"),
+        Some(url) if !url.starts_with("http://") && !url.starts_with("https://") => {
+            Some("must start with http:// or https://")
+        }
+        // Nothing runs locally to resolve them, and tool configs must not hold secrets
+        Some(url) if url.contains("${") => Some("can't contain placeholders"),
+        Some(_) => None,
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_port() -> u16 {
+    3333
+}
+
+fn default_scope() -> String {
+    "project".to_string()
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_log_max_size_kb() -> u64 {
+    1024
+}
+
+fn default_log_keep() -> u32 {
+    3
+}
+
+fn default_plugin_timeout_secs() -> u64 {
+    5
+}
+
+fn default_trash_retention_days() -> u32 {
+    7
+}
+
+impl Default for GlobalToolsConfig {
+    fn default() -> Self {
+        Self {
+            claude_code: true,
+            git: true,
+            cursor: false,
+            codex: false,
+            aider: false,
+            continue_dev: false,
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            port: 3333,
+            open_browser: true,
+        }
+    }
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            file: false,
+            level: default_log_level(),
+            max_size_kb: default_log_max_size_kb(),
+            keep: default_log_keep(),
+        }
+    }
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: default_trash_retention_days(),
+        }
+    }
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            version: Self::VERSION,
+            tools: GlobalToolsConfig::default(),
+            mcps: Vec::new(),
+            ui: UiConfig::default(),
+            log: LogConfig::default(),
+            notifications: NotificationsConfig::default(),
+            trash: TrashConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            plugins: Vec::new(),
+            retrieval: BTreeMap::new(),
+            profile: None,
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl Versioned for GlobalConfig {
+    const MIGRATIONS: &'static [Migration] = &[migrate::stamp_version];
+}
+
+impl GlobalConfig {
+    fn current_version() -> u32 {
+        Self::VERSION
+    }
+
+    /// Get the global sqrl directory path.
+    pub fn dir() -> Result<PathBuf, Error> {
+        dirs::home_dir()
+            .map(|h| h.join(".sqrl"))
+            .ok_or(Error::HomeDirNotFound)
+    }
+
+    /// Get the global config file path.
+    pub fn path() -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join("config.yaml"))
+    }
+
+    /// Get the MCPs directory path.
+    pub fn mcps_dir() -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join("mcps"))
+    }
+
+    /// Get the global memory database path.
+    pub fn memory_db_path() -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join("memory.db"))
+    }
+
+    /// Get the CLI log file path.
+    pub fn log_path() -> Result<PathBuf, Error> {
+        Ok(Self::dir()?.join("logs").join("cli.log"))
+    }
+
+    /// Initialize global config directory if not exists.
+    pub fn init() -> Result<(), Error> {
+        let dir = Self::dir()?;
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+
+        let mcps_dir = Self::mcps_dir()?;
+        if !mcps_dir.exists() {
+            fs::create_dir_all(&mcps_dir)?;
+        }
+
+        let config_path = Self::path()?;
+        if !config_path.exists() {
+            let config = GlobalConfig::default();
+            config.save()?;
+        }
+
+        // Create default squirrel MCP config
+        let squirrel_mcp = mcps_dir.join("squirrel.json");
+        if !squirrel_mcp.exists() {
+            let mcp = McpConfig {
+                name: "squirrel".to_string(),
+                command: "sqrl".to_string(),
+                args: vec!["mcp-serve".to_string()],
+                env: std::collections::HashMap::new(),
+                scope: "project".to_string(),
+                cwd: None,
+                transport: Transport::Stdio,
+                url: None,
+            };
+            let content = serde_json::to_string_pretty(&mcp)?;
+            atomic::write(&squirrel_mcp, content)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if global config exists.
+    pub fn exists() -> bool {
+        Self::path().map(|p| p.exists()).unwrap_or(false)
+    }
+
+    /// Load global config.
+    pub fn load() -> Result<Self, Error> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Err(Error::GlobalConfigNotFound);
+        }
+        let content = fs::read_to_string(&path).map_err(Error::at(&path))?;
+        validate::parse(&path, &content, ConfigFormat::Yaml)
+    }
+
+    /// Retrieval profile for an MCP client: `name@version`, then `name`, then `*`.
+    /// Names match case-insensitively.
+    pub fn retrieval_profile(&self, client: &str, version: &str) -> Option<&RetrievalProfile> {
+        let versioned = format!("{}@{}", client, version);
+        let profile = [versioned.as_str(), client, "*"]
+            .into_iter()
+            .find_map(|key| {
+                self.retrieval
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                    .map(|(_, profile)| profile)
+            });
+        profile
+    }
+
+    /// The active profile, if one is selected and defined.
+    pub fn active_profile(&self) -> Option<(&str, &Profile)> {
+        let name = self.profile.as_deref()?;
+        self.profiles.get(name).map(|p| (name, p))
+    }
+
+    /// This config with the active profile's overrides applied.
+    pub fn with_profile(mut self) -> Self {
+        if let Some((_, profile)) = self.active_profile() {
+            if let Some(tools) = profile.tools.clone() {
+                self.tools = tools;
+            }
+        }
+        self
+    }
+
+    /// Name of the active profile in the saved global config.
+    pub fn active_profile_name() -> Option<String> {
+        let config = Self::load().ok()?;
+        config.active_profile().map(|(name, _)| name.to_string())
+    }
+
+    /// Save global config.
+    pub fn save(&self) -> Result<(), Error> {
+        let path = Self::path()?;
+        let content = serde_yaml::to_string(self).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        let with_header = format!("# Squirrel global configuration\n\n{}", content);
+        atomic::write(&path, with_header)?;
+        Ok(())
+    }
+
+    /// List all MCP configs.
+    pub fn list_mcps() -> Result<Vec<McpConfig>, Error> {
+        let mcps_dir = Self::mcps_dir()?;
+        if !mcps_dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut mcps = vec![];
+        for entry in fs::read_dir(&mcps_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().map(|e| e == "json").unwrap_or(false) {
+                let content = fs::read_to_string(&path).map_err(Error::at(&path))?;
+                mcps.push(validate::parse_mcp(&path, &content)?);
+            }
+        }
+        Ok(mcps)
+    }
+
+    /// File holding the MCP config `name`. Names that could leave
+    /// `~/.sqrl/mcps/` are rejected.
+    pub fn mcp_path(name: &str) -> Result<PathBuf, Error> {
+        if !McpConfig::valid_name(name) {
+            return Err(Error::InvalidArgument(format!(
+                "invalid MCP name '{}': use letters, digits, '.', '_' and '-'",
+                name
+            )));
+        }
+        Ok(Self::mcps_dir()?.join(format!("{}.json", name)))
+    }
+
+    /// Get a specific MCP config by name.
+    pub fn get_mcp(name: &str) -> Result<McpConfig, Error> {
+        let path = Self::mcp_path(name)?;
+        if !path.exists() {
+            return Err(Error::McpNotFound(name.to_string()));
+        }
+        let content = fs::read_to_string(&path).map_err(Error::at(&path))?;
+        validate::parse_mcp(&path, &content)
+    }
+
+    /// Save an MCP config, rejecting ones that would not load back.
+    pub fn save_mcp(mcp: &McpConfig) -> Result<(), Error> {
+        mcp.check()?;
+        let path = Self::mcp_path(&mcp.name)?;
+        let content = serde_json::to_string_pretty(mcp)?;
+        atomic::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Parse an `mcpServers` map (Claude Code / Cursor format) into MCP configs.
+    /// Returns parsed configs and names of entries that could not be converted.
+    pub fn parse_mcp_servers(
+        servers: &serde_json::Value,
+        scope: &str,
+    ) -> (Vec<McpConfig>, Vec<String>) {
+        let mut mcps = vec![];
+        let mut skipped = vec![];
+
+        let Some(map) = servers.as_object() else {
+            return (mcps, skipped);
+        };
+
+        for (name, entry) in map {
+            // Remote servers: {"type": "sse" | "http", "url": ...}
+            let transport = match entry.get("type").and_then(|t| t.as_str()) {
+                Some("sse") => Some(Transport::Sse),
+                Some("http" | "streamable-http") => Some(Transport::Http),
+                _ => None,
+            };
+            if let (Some(transport), Some(url)) =
+                (transport, entry.get("url").and_then(|u| u.as_str()))
+            {
+                mcps.push(McpConfig {
+                    name: name.clone(),
+                    command: String::new(),
+                    args: Vec::new(),
+                    env: Default::default(),
+                    scope: scope.to_string(),
+                    cwd: None,
+                    transport,
+                    url: Some(url.to_string()),
+                });
+                continue;
+            }
+            let Some(command) = entry.get("command").and_then(|c| c.as_str()) else {
+                skipped.push(name.clone());
+                continue;
+            };
+            let args = entry
+                .get("args")
+                .and_then(|a| a.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let env = entry
+                .get("env")
+                .and_then(|e| e.as_object())
+                .map(|obj| {
+                    obj.iter()
+                        .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            mcps.push(McpConfig {
+                name: name.clone(),
+                command: command.to_string(),
+                args,
+                env,
+                scope: scope.to_string(),
+                cwd: entry.get("cwd").and_then(|c| c.as_str()).map(String::from),
+                transport: Transport::Stdio,
+                url: None,
+            });
+        }
+
+        (mcps, skipped)
+    }
+
+    /// Delete an MCP config.
+    pub fn delete_mcp(name: &str) -> Result<(), Error> {
+        let path = Self::mcp_path(name)?;
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_profile_overrides_tools() {
+        let mut config = GlobalConfig::default();
+        let work = Profile {
+            tools: Some(GlobalToolsConfig {
+                cursor: true,
+                ..GlobalToolsConfig::default()
+            }),
+            mcps: Some(vec!["github".to_string()]),
+        };
+        config.profiles.insert("work".to_string(), work);
+        assert!(!config.clone().with_profile().tools.cursor);
+
+        config.profile = Some("work".to_string());
+        assert_eq!(config.active_profile().map(|(name, _)| name), Some("work"));
+        assert!(config.with_profile().tools.cursor);
+    }
+
+    #[test]
+    fn test_retrieval_profile_prefers_versioned_name() {
+        let config: GlobalConfig = serde_yaml::from_str(
+            "retrieval:\n  cursor: {max_items: 10}\n  cursor@1.0: {max_items: 5}\n  '*': {max_chars: 4000}\n",
+        )
+        .unwrap();
+        let items = |name, version| {
+            config
+                .retrieval_profile(name, version)
+                .map(|p| (p.max_items, p.max_chars))
+        };
+        assert_eq!(items("Cursor", "1.0"), Some((Some(5), None)));
+        assert_eq!(items("cursor", "2.3"), Some((Some(10), None)));
+        assert_eq!(items("claude-code", "1.0"), Some((None, Some(4000))));
+    }
+
+    #[test]
+    fn test_mcp_path_traversal_name_rejected() {
+        for name in ["../projects", "..", "a/b", ""] {
+            assert!(matches!(
+                GlobalConfig::mcp_path(name),
+                Err(Error::InvalidArgument(_))
+            ));
+            assert!(GlobalConfig::get_mcp(name).is_err());
+            assert!(GlobalConfig::delete_mcp(name).is_err());
+        }
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = GlobalConfig::default();
+        assert!(config.tools.claude_code);
+        assert!(config.tools.git);
+        assert!(!config.tools.cursor);
+        assert_eq!(config.ui.port, 3333);
+        assert!(config.ui.open_browser);
+    }
+
+    #[test]
+    fn test_parse_mcp_servers_skips_unsupported_entries() {
+        let servers = serde_json::json!({
+            "local": { "command": "npx", "args": ["-y", "server"], "env": { "KEY": "x" } },
+            "remote": { "type": "sse", "url": "https://example.com/sse" },
+            "socket": { "type": "ws", "url": "wss://example.com/ws" }
+        });
+        let (mcps, skipped) = GlobalConfig::parse_mcp_servers(&servers, "user");
+        assert_eq!(mcps.len(), 2);
+        assert_eq!(mcps[0].name, "local");
+        assert_eq!(mcps[0].args, vec!["-y", "server"]);
+        assert_eq!(mcps[0].env.get("KEY").map(String::as_str), Some("x"));
+        assert_eq!(mcps[0].scope, "user");
+        assert_eq!(mcps[1].transport, Transport::Sse);
+        assert_eq!(mcps[1].url.as_deref(), Some("https://example.com/sse"));
+        assert_eq!(skipped, vec!["socket"]);
+    }
+}
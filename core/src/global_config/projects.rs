@@ -0,0 +1,115 @@
+//! Registry of initialized projects (REGISTRY-001).
+//!
+//! `~/.sqrl/projects.json` lists every project `sqrl init` set up, so
+//! commands and the web UI can work across projects.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::atomic;
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+
+/// A registered project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectEntry {
+    pub path: PathBuf,
+    pub added_at: String,
+    pub last_activity: String,
+}
+
+/// Registry stored in `~/.sqrl/projects.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default)]
+    pub projects: Vec<ProjectEntry>,
+}
+
+impl Registry {
+    /// Get the registry file path.
+    pub fn path() -> Result<PathBuf, Error> {
+        Ok(GlobalConfig::dir()?.join("projects.json"))
+    }
+
+    /// Load the registry, empty if missing.
+    pub fn load() -> Result<Self, Error> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Save the registry.
+    pub fn save(&self) -> Result<(), Error> {
+        fs::create_dir_all(GlobalConfig::dir()?)?;
+        atomic::write(&Self::path()?, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Add a project, or mark activity if already registered.
+    pub fn register(&mut self, project_root: &Path) {
+        let now = chrono::Utc::now().to_rfc3339();
+        match self.projects.iter_mut().find(|p| p.path == project_root) {
+            Some(entry) => entry.last_activity = now,
+            None => self.projects.push(ProjectEntry {
+                path: project_root.to_path_buf(),
+                added_at: now.clone(),
+                last_activity: now,
+            }),
+        }
+    }
+
+    /// Remove a project. Returns true if it was registered.
+    pub fn remove(&mut self, project_root: &Path) -> bool {
+        let before = self.projects.len();
+        self.projects.retain(|p| p.path != project_root);
+        self.projects.len() != before
+    }
+
+    /// Remove projects whose `.sqrl/` no longer exists. Returns removed paths.
+    pub fn prune(&mut self) -> Vec<PathBuf> {
+        let (kept, removed): (Vec<_>, Vec<_>) = self
+            .projects
+            .drain(..)
+            .partition(|p| p.path.join(".sqrl").exists());
+        self.projects = kept;
+        removed.into_iter().map(|p| p.path).collect()
+    }
+}
+
+/// Record activity for a project, ignoring registry errors.
+pub fn touch(project_root: &Path) {
+    let Ok(mut registry) = Registry::load() else {
+        return;
+    };
+    registry.register(project_root);
+    let _ = registry.save();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_register_is_idempotent_and_prune_drops_missing() {
+        let dir = tempdir().unwrap();
+        let live = dir.path().join("live");
+        fs::create_dir_all(live.join(".sqrl")).unwrap();
+        let gone = dir.path().join("gone");
+
+        let mut registry = Registry::default();
+        registry.register(&live);
+        registry.register(&live);
+        registry.register(&gone);
+        assert_eq!(registry.projects.len(), 2);
+
+        assert_eq!(registry.prune(), vec![gone]);
+        assert_eq!(registry.projects.len(), 1);
+        assert!(registry.remove(&live));
+        assert!(registry.projects.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Placeholder templating for MCP configs (MCP-CONFIG-002).
+//!
+//! Supports `${env:VAR}` and `${keychain:service/key}` in command, args, env
+//! and cwd.
+//! Placeholders are resolved at launch time so secrets never reach tool configs.
+
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::global_config::McpConfig;
+
+/// Check if any field of an MCP config contains a placeholder.
+pub fn has_placeholders(mcp: &McpConfig) -> bool {
+    std::iter::once(&mcp.command)
+        .chain(mcp.args.iter())
+        .chain(mcp.env.values())
+        .chain(mcp.cwd.iter())
+        .any(|s| s.contains("${"))
+}
+
+/// Resolve all placeholders in an MCP config.
+pub fn resolve_mcp(mcp: &McpConfig) -> Result<McpConfig, Error> {
+    let ctx = |e: Error| match e {
+        Error::Placeholder(msg) => Error::Placeholder(format!("MCP '{}': {}", mcp.name, msg)),
+        other => other,
+    };
+
+    let mut env = HashMap::new();
+    for (key, value) in &mcp.env {
+        env.insert(key.clone(), resolve_str(value).map_err(ctx)?);
+    }
+
+    Ok(McpConfig {
+        command: resolve_str(&mcp.command).map_err(ctx)?,
+        args: mcp
+            .args
+            .iter()
+            .map(|a| resolve_str(a))
+            .collect::<Result<_, _>>()
+            .map_err(ctx)?,
+        env,
+        cwd: mcp
+            .cwd
+            .as_deref()
+            .map(resolve_str)
+            .transpose()
+            .map_err(ctx)?,
+        ..mcp.clone()
+    })
+}
+
+/// Resolve placeholders in a single string.
+pub fn resolve_str(input: &str) -> Result<String, Error> {
+    let mut output = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}').ok_or_else(|| {
+            Error::Placeholder(format!("Unterminated placeholder in '{}'", input))
+        })?;
+        output.push_str(&resolve_placeholder(&after[..end])?);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+/// Resolve a single `kind:reference` placeholder body.
+fn resolve_placeholder(body: &str) -> Result<String, Error> {
+    let (kind, reference) = body
+        .split_once(':')
+        .ok_or_else(|| Error::Placeholder(format!("Invalid placeholder '${{{}}}'", body)))?;
+
+    match kind {
+        "env" => std::env::var(reference).map_err(|_| {
+            Error::Placeholder(format!("Environment variable '{}' is not set", reference))
+        }),
+        "keychain" => {
+            let (service, key) = reference.split_once('/').ok_or_else(|| {
+                Error::Placeholder(format!(
+                    "Keychain placeholder '{}' must be 'service/key'",
+                    reference
+                ))
+            })?;
+            keychain(service, key)
+        }
+        _ => Err(Error::Placeholder(format!(
+            "Unknown placeholder kind '{}' (expected 'env' or 'keychain')",
+            kind
+        ))),
+    }
+}
+
+#[cfg(feature = "keychain")]
+fn keychain(service: &str, key: &str) -> Result<String, Error> {
+    keyring::Entry::new(service, key)
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| {
+            Error::Placeholder(format!(
+                "Keychain entry '{}/{}' not available: {}",
+                service, key, e
+            ))
+        })
+}
+
+#[cfg(not(feature = "keychain"))]
+fn keychain(service: &str, key: &str) -> Result<String, Error> {
+    Err(Error::Placeholder(format!(
+        "Keychain entry '{}/{}' not available: built without the keychain feature",
+        service, key
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_str_env_substitutes_value() {
+        std::env::set_var("SQRL_TEST_TEMPLATE_TOKEN", "abc123");
+        let resolved = resolve_str("Bearer ${env:SQRL_TEST_TEMPLATE_TOKEN}!").unwrap();
+        assert_eq!(resolved, "Bearer abc123!");
+    }
+
+    #[test]
+    fn test_resolve_str_missing_env_errors() {
+        let err = resolve_str("${env:SQRL_TEST_TEMPLATE_MISSING}").unwrap_err();
+        assert!(err.to_string().contains("SQRL_TEST_TEMPLATE_MISSING"));
+    }
+
+    #[test]
+    fn test_resolve_str_unknown_kind_errors() {
+        assert!(resolve_str("${vault:foo}").is_err());
+        assert!(resolve_str("${env:FOO").is_err());
+    }
+
+    #[test]
+    fn test_resolve_str_plain_text_unchanged() {
+        assert_eq!(resolve_str("plain text").unwrap(), "plain text");
+    }
+}
@@ -0,0 +1,44 @@
+//! Squirrel core library.
+//!
+//! Memory storage, project and global config, and doc detection, without
+//! the CLI, MCP server or web UI, so editor extensions and CI bots can read
+//! and write Squirrel's memories without shelling out to `sqrl`.
+//!
+//! ```no_run
+//! use std::ops::ControlFlow;
+//! use std::path::Path;
+//!
+//! let root = Path::new("/path/to/project");
+//! // Project memories, best ranked first (SCHEMA-001)
+//! sqrl_core::storage::visit_memories(root, Some("project"), |memory| {
+//!     println!("{}", memory.content);
+//!     ControlFlow::Continue(())
+//! })?;
+//!
+//! // Docs as the pre-push check sees them (CLI-008)
+//! let config = sqrl_core::Config::effective(root)?;
+//! let docs = sqrl_core::docs::find_doc_files(root, &config.doc_matcher()?);
+//! # let _ = docs;
+//! # Ok::<(), sqrl_core::Error>(())
+//! ```
+//!
+//! Writers should hold [`lock::SqrlLock`] for the project, as the CLI does
+//! (LOCK-001).
+//!
+//! Features, both off by default: `cli` derives `clap::ValueEnum` on the
+//! enums `sqrl` takes as arguments, and `keychain` resolves
+//! `${keychain:service/key}` placeholders from the OS keychain.
+
+pub mod atomic;
+pub mod config;
+pub mod docs;
+pub mod error;
+pub mod global_config;
+pub mod lock;
+pub mod paths;
+pub mod storage;
+pub mod walk;
+
+pub use config::Config;
+pub use error::Error;
+pub use global_config::GlobalConfig;
@@ -0,0 +1,93 @@
+//! Cross-process advisory locks for `.sqrl` directories (LOCK-001).
+//!
+//! Commands that mutate `.sqrl/` or `~/.sqrl/` hold the lock for their
+//! duration so concurrent invocations (e.g. a hook firing mid-import)
+//! run one after another. The lock is released on drop or process exit.
+
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use fs4::fs_std::FileExt;
+use tracing::debug;
+
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+
+/// Lock file name inside a `.sqrl` directory.
+pub const LOCK_FILE: &str = ".lock";
+
+/// How long to wait for another process before giving up.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A held lock. Released on drop.
+#[derive(Debug)]
+pub struct SqrlLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl SqrlLock {
+    /// Lock a project's `.sqrl/`, creating the directory if needed.
+    pub fn project(project_root: &Path) -> Result<Self, Error> {
+        Self::acquire(&project_root.join(".sqrl"))
+    }
+
+    /// Lock `~/.sqrl/`.
+    pub fn global() -> Result<Self, Error> {
+        Self::acquire(&GlobalConfig::dir()?)
+    }
+
+    /// Lock the directory holding a `memory.db` anywhere, e.g. a sync peer's.
+    pub fn store(db_path: &Path) -> Result<Self, Error> {
+        Self::acquire(db_path.parent().unwrap_or(Path::new(".")))
+    }
+
+    fn acquire(dir: &Path) -> Result<Self, Error> {
+        fs::create_dir_all(dir).map_err(Error::at(dir))?;
+        let path = dir.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(Error::at(&path))?;
+
+        let start = Instant::now();
+        while !file.try_lock_exclusive().map_err(Error::at(&path))? {
+            if start.elapsed() > TIMEOUT {
+                return Err(Error::Locked(path));
+            }
+            debug!(path = %path.display(), "Waiting for lock");
+            thread::sleep(Duration::from_millis(50));
+        }
+        Ok(Self { file, path })
+    }
+}
+
+impl Drop for SqrlLock {
+    fn drop(&mut self) {
+        if let Err(e) = FileExt::unlock(&self.file) {
+            debug!(path = %self.path.display(), error = %e, "Failed to release lock");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let dir = TempDir::new().unwrap();
+        let lock = SqrlLock::project(dir.path()).unwrap();
+
+        let other = File::open(dir.path().join(".sqrl").join(LOCK_FILE)).unwrap();
+        assert!(!other.try_lock_exclusive().unwrap());
+
+        drop(lock);
+        assert!(other.try_lock_exclusive().unwrap());
+    }
+}
@@ -0,0 +1,60 @@
+//! One spelling for project-relative paths (PATH-001).
+//!
+//! The same file can reach Squirrel as `src/cli/mod.rs` from git,
+//! `src\cli\mod.rs` from a walk or config written on Windows, or
+//! `./src/cli/mod.rs` from a user. Everything that matches or compares paths
+//! (docguard, the doc globs, namespaces, paths mentioned in memories) does so
+//! on `normalize`d paths, so one rule set matches the same files in Windows
+//! and Unix checkouts of a repository.
+
+use std::path::Path;
+
+/// `path` with `/` separators and without `.` or empty segments, e.g.
+/// `.\docs\\guide.md` gives `docs/guide.md`. A leading `/` (anchored at the
+/// root in config entries) and a trailing `/` (a directory) are kept.
+///
+/// Backslashes always separate, so in config entries they can't escape glob
+/// characters; `[*]` matches a literal `*`.
+pub fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect();
+    let mut normalized = segments.join("/");
+    if path.starts_with('/') {
+        normalized.insert(0, '/');
+    }
+    if path.ends_with('/') && !segments.is_empty() {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// A filesystem path relative to the project root, normalized.
+pub fn from_path(path: &Path) -> String {
+    normalize(&path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_windows_and_posix_alike() {
+        for path in [
+            "src/cli/mod.rs",
+            "./src/cli/mod.rs",
+            "src\\cli\\mod.rs",
+            ".\\src\\cli\\mod.rs",
+            "src//cli/./mod.rs",
+        ] {
+            assert_eq!(normalize(path), "src/cli/mod.rs", "{}", path);
+        }
+        assert_eq!(normalize("docs\\"), "docs/");
+        assert_eq!(normalize("/docs/"), "/docs/");
+        assert_eq!(normalize("**\\node_modules\\"), "**/node_modules/");
+        assert_eq!(normalize("./"), "");
+        assert_eq!(from_path(Path::new("docs/guide.md")), "docs/guide.md");
+    }
+}
@@ -0,0 +1,175 @@
+//! Which MCP clients use the memory system (`sqrl stats`, CLI-023).
+//!
+//! Each tool call is logged with the name and version the client declared in
+//! `initialize`. Kept out of `memories`, like ratings, so sync and share ignore it.
+
+use rusqlite::{OptionalExtension, Result as SqliteResult};
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::storage::Storage;
+
+/// Client name logged when `initialize` declared none.
+pub const UNKNOWN_CLIENT: &str = "unknown";
+
+/// MCP tool call kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Store,
+    Get,
+    Rate,
+}
+
+impl Action {
+    fn as_str(self) -> &'static str {
+        match self {
+            Action::Store => "store",
+            Action::Get => "get",
+            Action::Rate => "rate",
+        }
+    }
+}
+
+/// One logged tool call about a memory (`sqrl memory show --trace`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemoryEvent {
+    pub client: String,
+    pub version: String,
+    /// `store` or `rate`.
+    pub action: String,
+    pub at: String,
+}
+
+/// Tool calls by one client.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ClientStats {
+    pub client: String,
+    /// Version seen most recently.
+    pub version: String,
+    pub stored: i64,
+    pub retrievals: i64,
+    pub rated: i64,
+    pub last_seen: String,
+}
+
+impl Storage {
+    /// Log a tool call. `memory_id` is the memory stored or rated.
+    pub fn record_activity(
+        &self,
+        client: &str,
+        version: &str,
+        action: Action,
+        memory_id: Option<&str>,
+    ) -> Result<(), Error> {
+        self.conn.execute(
+            "INSERT INTO mcp_activity (client, client_version, action, memory_id, at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                client,
+                version,
+                action.as_str(),
+                memory_id,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Totals per client, most active first.
+    pub fn client_stats(&self) -> Result<Vec<ClientStats>, Error> {
+        self.client_stats_since("")
+    }
+
+    /// Totals per client for calls at or after `since` (RFC 3339), most active first.
+    pub fn client_stats_since(&self, since: &str) -> Result<Vec<ClientStats>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT client,
+                    (SELECT client_version FROM mcp_activity l WHERE l.client = a.client
+                     ORDER BY at DESC LIMIT 1),
+                    SUM(action = 'store'), SUM(action = 'get'), SUM(action = 'rate'), MAX(at)
+             FROM mcp_activity a WHERE at >= ?1
+             GROUP BY client ORDER BY COUNT(*) DESC, client",
+        )?;
+        let rows = stmt.query_map([since], |row| {
+            Ok(ClientStats {
+                client: row.get(0)?,
+                version: row.get(1)?,
+                stored: row.get(2)?,
+                retrievals: row.get(3)?,
+                rated: row.get(4)?,
+                last_seen: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+
+    /// Tool calls that stored (or reinforced) and rated a memory, oldest first.
+    pub fn memory_events(&self, id: &str) -> Result<Vec<MemoryEvent>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT client, client_version, action, at FROM mcp_activity
+             WHERE memory_id = ?1 ORDER BY at, rowid",
+        )?;
+        let rows = stmt.query_map([id], |row| {
+            Ok(MemoryEvent {
+                client: row.get(0)?,
+                version: row.get(1)?,
+                action: row.get(2)?,
+                at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+
+    /// Client that first stored a memory, as `name version`.
+    pub fn created_by(&self, id: &str) -> Result<Option<String>, Error> {
+        let client: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT client, client_version FROM mcp_activity
+                 WHERE action = 'store' AND memory_id = ?1 ORDER BY at LIMIT 1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(client.map(|(name, version)| format!("{} {}", name, version).trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_client_stats_and_provenance() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let id = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        storage
+            .record_activity("claude-code", "1.0", Action::Store, Some(&id))
+            .unwrap();
+        storage
+            .record_activity("claude-code", "1.1", Action::Get, None)
+            .unwrap();
+        storage
+            .record_activity("cursor", "", Action::Rate, Some(&id))
+            .unwrap();
+
+        let stats = storage.client_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].client, "claude-code");
+        assert_eq!(stats[0].version, "1.1");
+        assert_eq!((stats[0].stored, stats[0].retrievals), (1, 1));
+        assert_eq!((stats[1].client.as_str(), stats[1].rated), ("cursor", 1));
+        assert_eq!(
+            storage.created_by(&id).unwrap().as_deref(),
+            Some("claude-code 1.0")
+        );
+        let events = storage.memory_events(&id).unwrap();
+        let actions: Vec<(&str, &str)> = events
+            .iter()
+            .map(|e| (e.client.as_str(), e.action.as_str()))
+            .collect();
+        assert_eq!(actions, [("claude-code", "store"), ("cursor", "rate")]);
+    }
+}
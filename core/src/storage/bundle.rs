@@ -0,0 +1,233 @@
+//! Team memory bundle committed to the repo (SHARE-001).
+//!
+//! `.sqrl/shared.json` holds the memories a team opted into version control.
+//! Use counts stay local, so the file only changes when the knowledge does.
+//! Both directions merge by ID: the later `updated_at` wins.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::atomic;
+use crate::error::Error;
+use crate::storage::sync::is_newer;
+use crate::storage::{Memory, Storage};
+
+/// Current bundle format.
+const BUNDLE_VERSION: u32 = 1;
+
+/// Path of the bundle in a project.
+pub fn path(project_root: &Path) -> PathBuf {
+    project_root.join(".sqrl").join("shared.json")
+}
+
+/// A memory as stored in the bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedMemory {
+    pub id: String,
+    pub memory_type: String,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<&Memory> for SharedMemory {
+    fn from(memory: &Memory) -> Self {
+        Self {
+            id: memory.id.clone(),
+            memory_type: memory.memory_type.clone(),
+            content: memory.content.clone(),
+            tags: memory.tags.clone(),
+            created_at: memory.created_at.clone(),
+            updated_at: memory.updated_at.clone(),
+        }
+    }
+}
+
+/// The bundle file, memories sorted by ID for stable diffs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    pub memories: Vec<SharedMemory>,
+}
+
+impl Bundle {
+    /// Read the bundle, or an empty one if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Self {
+                version: BUNDLE_VERSION,
+                memories: Vec::new(),
+            });
+        }
+        let content = fs::read_to_string(path).map_err(Error::at(path))?;
+        // Git hands merge drivers an empty base for files added on both sides
+        if content.trim().is_empty() {
+            return Ok(Self {
+                version: BUNDLE_VERSION,
+                memories: Vec::new(),
+            });
+        }
+        serde_json::from_str(&content)
+            .map_err(|e| Error::ConfigParse(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        atomic::write(path, serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// Merge memories into the bundle. Returns how many entries were added or updated.
+    pub fn merge(&mut self, memories: &[Memory]) -> usize {
+        let mut by_id: BTreeMap<String, SharedMemory> =
+            self.memories.drain(..).map(|m| (m.id.clone(), m)).collect();
+        let mut changed = 0;
+        for memory in memories {
+            let newer = by_id
+                .get(&memory.id)
+                .is_none_or(|existing| is_newer(&memory.updated_at, &existing.updated_at));
+            if newer {
+                by_id.insert(memory.id.clone(), memory.into());
+                changed += 1;
+            }
+        }
+        self.version = BUNDLE_VERSION;
+        self.memories = by_id.into_values().collect();
+        changed
+    }
+
+    /// Three-way merge of two edits of a bundle (the `sqrl-memories` merge
+    /// driver). Entries on both sides keep the later `updated_at`; an entry
+    /// one side removed stays removed unless the other side changed it.
+    pub fn merge_edits(base: &Bundle, ours: &Bundle, theirs: &Bundle) -> Bundle {
+        let by_id = |bundle: &Bundle| -> BTreeMap<String, SharedMemory> {
+            bundle
+                .memories
+                .iter()
+                .map(|m| (m.id.clone(), m.clone()))
+                .collect()
+        };
+        let (base, ours, theirs) = (by_id(base), by_id(ours), by_id(theirs));
+        let mut merged = BTreeMap::new();
+        for id in ours.keys().chain(theirs.keys()) {
+            let kept = match (ours.get(id), theirs.get(id)) {
+                (Some(a), Some(b)) if is_newer(&b.updated_at, &a.updated_at) => Some(b),
+                (Some(a), Some(_)) => Some(a),
+                (Some(one), None) | (None, Some(one)) => (base.get(id) != Some(one)).then_some(one),
+                (None, None) => None,
+            };
+            if let Some(memory) = kept {
+                merged.insert(id.clone(), memory.clone());
+            }
+        }
+        Bundle {
+            version: BUNDLE_VERSION,
+            memories: merged.into_values().collect(),
+        }
+    }
+
+    /// Merge the bundle into a database in one transaction. Returns how many
+    /// memories were added or updated. Memories deleted locally after their
+    /// last shared update stay deleted.
+    pub fn apply(&self, storage: &Storage) -> Result<usize, Error> {
+        storage.atomically(|| self.apply_each(storage))
+    }
+
+    fn apply_each(&self, storage: &Storage) -> Result<usize, Error> {
+        let tombstones = storage.tombstones()?;
+        let mut changed = 0;
+        for shared in &self.memories {
+            let local = storage.get_memory(&shared.id)?;
+            let wanted = match &local {
+                Some(local) => is_newer(&shared.updated_at, &local.updated_at),
+                None => tombstones
+                    .get(&shared.id)
+                    .is_none_or(|deleted_at| is_newer(&shared.updated_at, deleted_at)),
+            };
+            if !wanted {
+                continue;
+            }
+            storage.restore_memory(&Memory {
+                id: shared.id.clone(),
+                memory_type: shared.memory_type.clone(),
+                content: shared.content.clone(),
+                tags: shared.tags.clone(),
+                use_count: local.map_or(1, |m| m.use_count),
+                created_at: shared.created_at.clone(),
+                updated_at: shared.updated_at.clone(),
+            })?;
+            changed += 1;
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_push_then_pull_merges_by_id() {
+        let dir = TempDir::new().unwrap();
+        let alice = Storage::open(&dir.path().join("a.db")).unwrap();
+        let bob = Storage::open(&dir.path().join("b.db")).unwrap();
+        let shared = alice.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        bob.store_memory("project", "Bob's note", &[]).unwrap();
+
+        let mut bundle = Bundle::load(&dir.path().join("shared.json")).unwrap();
+        assert_eq!(bundle.merge(&alice.list_all_memories().unwrap()), 1);
+        assert_eq!(bundle.merge(&alice.list_all_memories().unwrap()), 0);
+        bundle.save(&dir.path().join("shared.json")).unwrap();
+
+        let bundle = Bundle::load(&dir.path().join("shared.json")).unwrap();
+        assert_eq!(bundle.apply(&bob).unwrap(), 1);
+        assert_eq!(bundle.apply(&bob).unwrap(), 0);
+        assert_eq!(bob.list_all_memories().unwrap().len(), 2);
+
+        // A local delete is not undone by the next pull
+        bob.delete_memory(&shared).unwrap();
+        assert_eq!(bundle.apply(&bob).unwrap(), 0);
+        assert!(bob.get_memory(&shared).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_merge_edits_keeps_both_sides() {
+        let memory = |id: &str, content: &str, updated_at: &str| SharedMemory {
+            id: id.to_string(),
+            memory_type: "project".to_string(),
+            content: content.to_string(),
+            tags: Vec::new(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            updated_at: updated_at.to_string(),
+        };
+        let bundle = |memories: Vec<SharedMemory>| Bundle {
+            version: BUNDLE_VERSION,
+            memories,
+        };
+        let old = "2026-01-01T00:00:00+00:00";
+        let new = "2026-02-01T00:00:00+00:00";
+        let base = bundle(vec![
+            memory("a", "Use pnpm", old),
+            memory("b", "Run clippy", old),
+            memory("c", "Squash merges", old),
+        ]);
+        // Ours edits a and removes b; theirs removes c and adds d
+        let ours = bundle(vec![
+            memory("a", "Use pnpm 9", new),
+            memory("c", "Squash merges", old),
+        ]);
+        let theirs = bundle(vec![
+            memory("a", "Use pnpm", old),
+            memory("b", "Run clippy", old),
+            memory("d", "Tag releases", new),
+        ]);
+
+        let merged = Bundle::merge_edits(&base, &ours, &theirs);
+        let contents: Vec<&str> = merged.memories.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["Use pnpm 9", "Tag releases"]);
+    }
+}
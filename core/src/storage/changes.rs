@@ -0,0 +1,119 @@
+//! Change feed of a memory database (SCHEMA-001 `changes`).
+//!
+//! Triggers log every insert, update and delete on the tracked tables with
+//! a sequence number, so a reader that remembers the last number it saw can
+//! ask what changed since: the MCP response cache, the web UI, or a sync
+//! peer. Writes from other processes (CLI, hooks, another server) show up
+//! the same way, since SQLite runs the triggers.
+
+use std::path::Path;
+
+use rusqlite::{OpenFlags, OptionalExtension};
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::storage::{db_path, Storage};
+
+/// Tables whose changes are logged.
+pub const TRACKED: [&str; 3] = ["memories", "ratings", "pending"];
+
+/// One logged change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub seq: i64,
+    /// `memories`, `ratings` or `pending`.
+    pub table: String,
+    /// Row ID: the memory's for `memories` and `ratings`, the proposal's for `pending`.
+    pub id: String,
+    /// `insert`, `update` or `delete`.
+    pub op: String,
+    pub at: String,
+}
+
+impl Storage {
+    /// Changes with a sequence number above `seq`, oldest first.
+    pub fn changes_since(&self, seq: i64) -> Result<Vec<Change>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, table_name, id, op, at FROM changes WHERE seq > ?1 ORDER BY seq",
+        )?;
+        let rows = stmt.query_map([seq], |row| {
+            Ok(Change {
+                seq: row.get(0)?,
+                table: row.get(1)?,
+                id: row.get(2)?,
+                op: row.get(3)?,
+                at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Sequence number of the latest change, 0 before any.
+    pub fn generation(&self) -> Result<i64, Error> {
+        generation_of(&self.conn)
+    }
+}
+
+/// Sequence number of the project database's latest change, read without
+/// taking the write path. None when there is no database, or it was last
+/// opened by a version without the feed.
+pub fn generation(project_root: &Path) -> Result<Option<i64>, Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let conn = rusqlite::Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let has_feed = conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'changes'",
+            [],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some();
+    if !has_feed {
+        return Ok(None);
+    }
+    generation_of(&conn).map(Some)
+}
+
+/// Read from `sqlite_sequence` rather than `MAX(seq)`: `changes` is
+/// AUTOINCREMENT, so the counter survives when expired changes are deleted
+/// (CONFIG-008) and a sequence number is never handed out twice.
+fn generation_of(conn: &rusqlite::Connection) -> Result<i64, Error> {
+    let seq: Option<i64> = conn
+        .query_row(
+            "SELECT seq FROM sqlite_sequence WHERE name = 'changes'",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(seq.unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_changes_since() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        assert_eq!(storage.generation().unwrap(), 0);
+
+        let id = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let seen = storage.generation().unwrap();
+        storage.rate_memory(&id, true).unwrap();
+        storage.delete_memory(&id).unwrap();
+
+        let changes = storage.changes_since(seen).unwrap();
+        let ops: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|c| (c.table.as_str(), c.op.as_str()))
+            .collect();
+        assert_eq!(ops, [("ratings", "insert"), ("memories", "delete")]);
+        assert!(changes.iter().all(|c| c.id == id));
+        assert_eq!(storage.generation().unwrap(), changes[1].seq);
+    }
+}
@@ -0,0 +1,192 @@
+//! Bounding the local-only tables (`sqrl memory compact`, CONFIG-008).
+//!
+//! Memories, ratings and tombstones are never expired: sync needs
+//! tombstones to keep deletes deleted. Only the MCP activity log, usage
+//! analytics, the change feed and commit snapshots grow without bound. Rows
+//! left pointing at nothing (ratings of memories that are gone, snapshots of
+//! commits the repository dropped) are removed whatever their age.
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::storage::Storage;
+
+/// Rows removed by a compaction.
+#[derive(Debug, Default, Serialize)]
+pub struct CompactReport {
+    /// `mcp_activity` rows.
+    pub activity: usize,
+    /// `usage` rows, expired with the activity log.
+    pub usage: usize,
+    /// `changes` rows, expired with the activity log.
+    pub changes: usize,
+    /// `snapshots` rows.
+    pub snapshots: usize,
+    /// `snapshots` rows of commits no longer reachable in the repository.
+    pub unreachable_snapshots: usize,
+    /// `memory_sets` no snapshot refers to any more.
+    pub memory_sets: usize,
+    /// `ratings` rows of memories that no longer exist and can't be restored.
+    pub ratings: usize,
+}
+
+impl Storage {
+    /// Delete activity, usage and changes recorded before an RFC 3339 time.
+    /// The generation is kept in `sqlite_sequence`, so it doesn't go back.
+    pub fn expire_activity(&self, before: &str) -> Result<CompactReport, Error> {
+        Ok(CompactReport {
+            activity: self
+                .conn
+                .execute("DELETE FROM mcp_activity WHERE at < ?1", [before])?,
+            usage: self
+                .conn
+                .execute("DELETE FROM usage WHERE at < ?1", [before])?,
+            changes: self
+                .conn
+                .execute("DELETE FROM changes WHERE at < ?1", [before])?,
+            ..CompactReport::default()
+        })
+    }
+
+    /// Delete activity, usage, changes and snapshots recorded before the given
+    /// RFC 3339 times (None keeps them), snapshots of the `unreachable`
+    /// commits, and orphaned rows, then reclaim the space. Ratings of memories
+    /// deleted since `restorable_since` are kept for `sqrl undo`.
+    pub fn compact(
+        &self,
+        activity_before: Option<&str>,
+        snapshots_before: Option<&str>,
+        unreachable: &[String],
+        restorable_since: &str,
+    ) -> Result<CompactReport, Error> {
+        let mut report = match activity_before {
+            Some(before) => self.expire_activity(before)?,
+            None => CompactReport::default(),
+        };
+        if let Some(before) = snapshots_before {
+            report.snapshots = self
+                .conn
+                .execute("DELETE FROM snapshots WHERE taken_at < ?1", [before])?;
+        }
+        for commit in unreachable {
+            report.unreachable_snapshots += self
+                .conn
+                .execute("DELETE FROM snapshots WHERE commit_id = ?1", [commit])?;
+        }
+        report.memory_sets = self.conn.execute(
+            "DELETE FROM memory_sets WHERE hash NOT IN (SELECT set_hash FROM snapshots)",
+            [],
+        )?;
+        report.ratings = self.conn.execute(
+            "DELETE FROM ratings WHERE id NOT IN (SELECT id FROM memories)
+             AND id NOT IN (SELECT id FROM tombstones WHERE deleted_at >= ?1)",
+            [restorable_since],
+        )?;
+        self.conn.execute_batch("VACUUM")?;
+        Ok(report)
+    }
+
+    /// Commit IDs that have a snapshot.
+    pub fn snapshot_commits(&self) -> Result<Vec<String>, Error> {
+        let mut stmt = self.conn.prepare("SELECT commit_id FROM snapshots")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::activity::Action;
+    use crate::storage::usage::UsageKind;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compact_drops_only_expired_rows() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        storage.store_memory("project", "Use pnpm", &[]).unwrap();
+        storage.record_snapshot("c1").unwrap();
+        storage
+            .record_activity("claude-code", "1.0", Action::Get, None)
+            .unwrap();
+        storage
+            .record_usage(UsageKind::Command, "status", Duration::from_millis(20))
+            .unwrap();
+
+        let past = "2000-01-01T00:00:00+00:00";
+        let report = storage.compact(Some(past), Some(past), &[], past).unwrap();
+        assert_eq!(
+            (report.activity, report.snapshots, report.memory_sets),
+            (0, 0, 0)
+        );
+
+        let future = "2999-01-01T00:00:00+00:00";
+        let generation = storage.generation().unwrap();
+        let report = storage.compact(Some(future), None, &[], past).unwrap();
+        assert_eq!(
+            (
+                report.activity,
+                report.usage,
+                report.changes,
+                report.snapshots
+            ),
+            (1, 1, generation as usize, 0)
+        );
+        assert!(storage.snapshot("c1").unwrap().is_some());
+        assert!(storage.changes_since(0).unwrap().is_empty());
+        assert_eq!(storage.generation().unwrap(), generation);
+        storage.store_memory("project", "Use bun", &[]).unwrap();
+        assert_eq!(
+            storage.changes_since(generation).unwrap()[0].seq,
+            generation + 1
+        );
+
+        let report = storage.compact(None, Some(future), &[], past).unwrap();
+        assert_eq!((report.snapshots, report.memory_sets), (1, 1));
+        assert_eq!(storage.list_all_memories().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_compact_drops_orphans() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let kept = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let gone = storage.store_memory("project", "Use npm", &[]).unwrap().id;
+        storage.rate_memory(&kept, true).unwrap();
+        storage.rate_memory(&gone, false).unwrap();
+        storage
+            .conn
+            .execute("DELETE FROM memories WHERE id = ?1", [&gone])
+            .unwrap();
+        storage.record_snapshot("c1").unwrap();
+        storage.record_snapshot("c2").unwrap();
+        assert_eq!(storage.snapshot_commits().unwrap().len(), 2);
+
+        let trashed = storage.store_memory("project", "Use yarn", &[]).unwrap().id;
+        storage.rate_memory(&trashed, false).unwrap();
+        storage.delete_memory(&trashed).unwrap();
+
+        let past = "2000-01-01T00:00:00+00:00";
+        let report = storage
+            .compact(None, None, &["c1".to_string()], past)
+            .unwrap();
+        assert_eq!(
+            (
+                report.unreachable_snapshots,
+                report.memory_sets,
+                report.ratings
+            ),
+            (1, 0, 1)
+        );
+        assert_eq!(storage.snapshot_commits().unwrap(), ["c2"]);
+        assert!(storage.ratings().unwrap().contains_key(&kept));
+        assert!(storage.ratings().unwrap().contains_key(&trashed));
+
+        let future = "2999-01-01T00:00:00+00:00";
+        let report = storage.compact(None, None, &[], future).unwrap();
+        assert_eq!(report.ratings, 1);
+        assert!(!storage.ratings().unwrap().contains_key(&trashed));
+    }
+}
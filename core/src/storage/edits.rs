@@ -0,0 +1,99 @@
+//! Editing, deleting and restoring memories. Deletions leave tombstones so
+//! sync doesn't bring the memories back.
+
+use std::collections::HashMap;
+
+use rusqlite::Result as SqliteResult;
+
+use crate::error::Error;
+use crate::storage::{Memory, Storage};
+
+impl Storage {
+    /// Update an existing memory; the given fields change together.
+    pub fn update_memory(
+        &self,
+        id: &str,
+        memory_type: Option<&str>,
+        content: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<(), Error> {
+        self.atomically(|| self.update_fields(id, memory_type, content, tags))
+    }
+    fn update_fields(
+        &self,
+        id: &str,
+        memory_type: Option<&str>,
+        content: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<(), Error> {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        if let Some(mt) = memory_type {
+            self.conn.execute(
+                "UPDATE memories SET memory_type = ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![mt, now, id],
+            )?;
+        }
+
+        if let Some(c) = content {
+            self.conn.execute(
+                "UPDATE memories SET content = ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![c, now, id],
+            )?;
+        }
+
+        if let Some(t) = tags {
+            let tags_json = serde_json::to_string(t)?;
+            self.conn.execute(
+                "UPDATE memories SET tags = ?1, updated_at = ?2 WHERE id = ?3",
+                rusqlite::params![tags_json, now, id],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Insert or replace a memory as given, keeping its ID, counts, and timestamps.
+    /// Clears any tombstone for the ID.
+    pub fn restore_memory(&self, memory: &Memory) -> Result<(), Error> {
+        self.conn
+            .execute("DELETE FROM tombstones WHERE id = ?1", [&memory.id])?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO memories (id, memory_type, content, tags, use_count, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                memory.id,
+                memory.memory_type,
+                memory.content,
+                serde_json::to_string(&memory.tags)?,
+                memory.use_count,
+                memory.created_at,
+                memory.updated_at
+            ],
+        )?;
+        Ok(())
+    }
+    /// Delete a memory by ID, leaving a tombstone.
+    pub fn delete_memory(&self, id: &str) -> Result<(), Error> {
+        let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+        self.apply_tombstone(id, &now)
+    }
+    /// Deleted IDs with their deletion time.
+    pub fn tombstones(&self) -> Result<HashMap<String, String>, Error> {
+        let mut stmt = self.conn.prepare("SELECT id, deleted_at FROM tombstones")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+    /// Delete a memory and record when, keeping the later of two deletion times.
+    /// Its ratings stay until compaction, so a restore brings its rank back.
+    pub fn apply_tombstone(&self, id: &str, deleted_at: &str) -> Result<(), Error> {
+        self.conn
+            .execute("DELETE FROM memories WHERE id = ?1", [id])?;
+        self.conn.execute(
+            "INSERT INTO tombstones (id, deleted_at) VALUES (?1, ?2)
+             ON CONFLICT(id) DO UPDATE SET deleted_at = MAX(deleted_at, excluded.deleted_at)",
+            [id, deleted_at],
+        )?;
+        Ok(())
+    }
+}
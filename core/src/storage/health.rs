@@ -0,0 +1,148 @@
+//! Creating a project database and checking one without touching it.
+
+use std::fs;
+use std::path::Path;
+
+use rusqlite::{Connection, OpenFlags};
+
+use crate::error::Error;
+use crate::storage::db_path;
+use crate::storage::schema::{ensure_memories_table, schema_version, SCHEMA_VERSION};
+
+/// Create the project database and its tables if missing.
+/// Returns true if anything had to be created.
+pub fn ensure_schema(project_root: &Path) -> Result<bool, Error> {
+    let path = db_path(project_root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let conn = Connection::open(&path)?;
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'memories')",
+        [],
+        |row| row.get(0),
+    )?;
+    ensure_memories_table(&conn)?;
+    Ok(!exists)
+}
+
+/// What is wrong with a project database (`check_health`).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DbProblem {
+    #[error("memory.db is missing")]
+    Missing,
+    #[error("cannot open memory.db: {0}")]
+    Unopenable(String),
+    #[error("memory.db is corrupt: {0}")]
+    Corrupt(String),
+    #[error("memory.db is unreadable: {0}")]
+    Unreadable(String),
+    #[error("memories table is missing")]
+    MissingTable,
+    /// Written by a newer sqrl; only an upgrade helps.
+    #[error("{}", Error::SchemaTooNew(*.0))]
+    TooNew(i64),
+}
+
+/// Check the project database without modifying it. Returns the problem, if any.
+pub fn check_health(project_root: &Path) -> Option<DbProblem> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Some(DbProblem::Missing);
+    }
+    let conn = match Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY) {
+        Ok(conn) => conn,
+        Err(e) => return Some(DbProblem::Unopenable(e.to_string())),
+    };
+    match conn.query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0)) {
+        Ok(result) if result == "ok" => {}
+        Ok(result) => return Some(DbProblem::Corrupt(result)),
+        Err(e) => return Some(DbProblem::Unreadable(e.to_string())),
+    }
+    let has_table = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'memories')",
+            [],
+            |row| row.get::<_, bool>(0),
+        )
+        .unwrap_or(false);
+    if !has_table {
+        return Some(DbProblem::MissingTable);
+    }
+    match schema_version(&conn) {
+        Ok(version) if version > SCHEMA_VERSION => Some(DbProblem::TooNew(version)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_ensure_schema_reports_creation_once() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        fs::write(db_path(dir.path()), "").unwrap();
+
+        assert!(ensure_schema(dir.path()).unwrap());
+        assert!(!ensure_schema(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn test_schema_version_migrates_old_and_refuses_newer() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        let path = db_path(dir.path());
+        {
+            // Unversioned layout with the index the type and use count index replaced
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE memories (id TEXT PRIMARY KEY, memory_type TEXT NOT NULL,
+                     content TEXT NOT NULL, tags TEXT DEFAULT '[]', use_count INTEGER DEFAULT 1,
+                     created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                 CREATE INDEX idx_memories_type ON memories(memory_type);",
+            )
+            .unwrap();
+        }
+
+        assert!(!ensure_schema(dir.path()).unwrap());
+        let conn = Connection::open(&path).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let old_index: bool = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = 'idx_memories_type')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!old_index);
+
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        assert!(matches!(
+            Storage::open(&path),
+            Err(Error::SchemaTooNew(v)) if v == SCHEMA_VERSION + 1
+        ));
+        assert_eq!(
+            check_health(dir.path()),
+            Some(DbProblem::TooNew(SCHEMA_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn test_check_health_flags_missing_table() {
+        let dir = tempdir().unwrap();
+        assert_eq!(check_health(dir.path()), Some(DbProblem::Missing));
+
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        fs::write(db_path(dir.path()), "").unwrap();
+        assert_eq!(check_health(dir.path()), Some(DbProblem::MissingTable));
+
+        ensure_schema(dir.path()).unwrap();
+        assert!(check_health(dir.path()).is_none());
+    }
+}
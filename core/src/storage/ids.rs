@@ -0,0 +1,112 @@
+//! Memory IDs: content-addressed, shown short, resolved from a prefix.
+
+use rusqlite::{Connection, Result as SqliteResult};
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+
+/// Characters shown for a memory ID in listings (`sqrl memory list`).
+pub const SHORT_ID_LEN: usize = 8;
+
+/// Shortest prefix accepted when resolving an ID.
+pub const MIN_PREFIX_LEN: usize = 4;
+
+/// Short form of a memory ID for display.
+pub fn short_id(id: &str) -> &str {
+    &id[..id.len().min(SHORT_ID_LEN)]
+}
+
+/// Content-addressed ID of a memory: the same memory gets the same ID on every machine.
+pub fn content_id(memory_type: &str, content: &str) -> String {
+    hash_id(memory_type, content, 0)
+}
+
+fn hash_id(memory_type: &str, content: &str, salt: u32) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(memory_type.as_bytes());
+    hasher.update([0]);
+    hasher.update(content.as_bytes());
+    if salt > 0 {
+        hasher.update(salt.to_le_bytes());
+    }
+    hasher.finalize()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// ID for a new memory: its `content_id`, salted only if that is already taken
+/// (content later edited).
+pub(super) fn new_memory_id(
+    conn: &Connection,
+    memory_type: &str,
+    content: &str,
+) -> Result<String, Error> {
+    for salt in 0u32.. {
+        let id = hash_id(memory_type, content, salt);
+        let taken: bool = conn
+            .prepare_cached("SELECT EXISTS(SELECT 1 FROM memories WHERE id = ?1)")?
+            .query_row([&id], |row| row.get(0))?;
+        if !taken {
+            return Ok(id);
+        }
+    }
+    unreachable!("salt space exhausted")
+}
+
+/// Resolve a full ID or unambiguous prefix to the stored ID.
+pub(super) fn resolve_id(conn: &Connection, prefix: &str) -> Result<String, Error> {
+    let prefix = prefix.trim().to_ascii_lowercase();
+    let valid =
+        prefix.len() >= MIN_PREFIX_LEN && prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    if !valid {
+        return Err(Error::MemoryNotFound(prefix));
+    }
+
+    let mut stmt = conn.prepare("SELECT id FROM memories WHERE id LIKE ?1 || '%' LIMIT 10")?;
+    let matches: Vec<String> = stmt
+        .query_map([&prefix], |row| row.get(0))?
+        .collect::<SqliteResult<_>>()?;
+    match matches.len() {
+        0 => Err(Error::MemoryNotFound(prefix)),
+        1 => Ok(matches.into_iter().next().unwrap_or_default()),
+        _ if matches.contains(&prefix) => Ok(prefix),
+        _ => Err(Error::AmbiguousId { prefix, matches }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{db_path, store_memory, Storage};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_ids_are_content_addressed_and_resolve_by_prefix() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        let storage = Storage::open(&db_path(dir.path())).unwrap();
+
+        let first = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let other = tempdir().unwrap();
+        fs::create_dir_all(other.path().join(".sqrl")).unwrap();
+        let (same, _, _) = store_memory(other.path(), "project", "Use pnpm", &[]).unwrap();
+        assert_eq!(first, same);
+        assert_eq!(first.len(), 32);
+
+        // The edited memory keeps its ID; re-storing the old content gets a salted one
+        storage
+            .update_memory(&first, None, Some("Use pnpm 9"), None)
+            .unwrap();
+        let second = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        assert_ne!(first, second);
+
+        assert_eq!(storage.resolve_id(&first[..6]).unwrap(), first);
+        assert_eq!(storage.resolve_id(&first.to_uppercase()).unwrap(), first);
+        assert!(matches!(
+            storage.resolve_id("abc"),
+            Err(Error::MemoryNotFound(_))
+        ));
+    }
+}
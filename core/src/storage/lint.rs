@@ -0,0 +1,295 @@
+//! Problems in stored memories (`sqrl memory lint`, CLI-019), and the file
+//! paths they mention.
+//!
+//! All checks are heuristics over the text; none of them reads a model.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::storage::Memory;
+
+/// Longest memory content before it is flagged. Memories are 1-2 sentences.
+pub const MAX_CONTENT_CHARS: usize = 400;
+
+/// Memory types the MCP tools accept (MCP-001).
+const KNOWN_TYPES: &[&str] = &["preference", "project"];
+
+/// File extensions that make a word look like a path.
+const PATH_EXTENSIONS: &[&str] = &[
+    "c", "cfg", "cpp", "css", "go", "h", "html", "java", "js", "json", "jsx", "kt", "lock", "md",
+    "py", "rb", "rs", "sh", "sql", "swift", "toml", "ts", "tsx", "txt", "yaml", "yml",
+];
+
+/// Words that flip or set a memory's polarity, stripped to find what it is about.
+const NEGATIONS: &[&str] = &["don't", "dont", "do not", "never", "avoid", "stop"];
+const AFFIRMATIONS: &[&str] = &["always", "use", "prefer", "do"];
+
+/// Kind of problem found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// Mentions a project file that doesn't exist. Fixable when git history
+    /// shows where it was moved.
+    MissingFile,
+    /// Says the opposite of another memory about the same thing.
+    Contradiction,
+    /// Longer than `MAX_CONTENT_CHARS`.
+    TooLong,
+    /// Has no tags. Only checked on request: MCP clients rarely tag.
+    Untagged,
+    /// `memory_type` is not one the tools use.
+    UnknownType,
+    /// Stray whitespace in content, or repeated tags. Fixable.
+    Untidy,
+}
+
+/// One problem with one memory.
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub id: String,
+    pub kind: IssueKind,
+    pub message: String,
+}
+
+impl IssueKind {
+    /// Whether `sqrl memory lint --fix` may repair it without changing meaning.
+    pub fn fixable(self) -> bool {
+        matches!(self, IssueKind::Untidy | IssueKind::MissingFile)
+    }
+}
+
+/// Check every memory, and with `untagged` flag those without tags too.
+/// Paths in project memories resolve against `project_root`.
+pub fn lint(memories: &[Memory], project_root: &Path, untagged: bool) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut issue = |memory: &Memory, kind, message: String| {
+        issues.push(Issue {
+            id: memory.id.clone(),
+            kind,
+            message,
+        })
+    };
+
+    let mut anchors: HashMap<String, (bool, &Memory)> = HashMap::new();
+    for memory in memories {
+        if !KNOWN_TYPES.contains(&memory.memory_type.as_str()) {
+            issue(
+                memory,
+                IssueKind::UnknownType,
+                format!("unknown type '{}'", memory.memory_type),
+            );
+        }
+        if untagged && memory.tags.is_empty() {
+            issue(memory, IssueKind::Untagged, "no tags".to_string());
+        }
+        let chars = memory.content.chars().count();
+        if chars > MAX_CONTENT_CHARS {
+            issue(
+                memory,
+                IssueKind::TooLong,
+                format!("{} characters (limit {})", chars, MAX_CONTENT_CHARS),
+            );
+        }
+        if tidy(memory).is_some() {
+            issue(
+                memory,
+                IssueKind::Untidy,
+                "stray whitespace or repeated tags".to_string(),
+            );
+        }
+        if memory.memory_type == "project" {
+            for path in mentioned_paths(&memory.content) {
+                if !project_root.join(&path).exists() {
+                    issue(
+                        memory,
+                        IssueKind::MissingFile,
+                        format!("'{}' does not exist", path),
+                    );
+                }
+            }
+        }
+
+        let (positive, anchor) = polarity(&memory.content);
+        if anchor.is_empty() {
+            continue;
+        }
+        match anchors.get(&anchor) {
+            Some((other_positive, other)) if *other_positive != positive => issue(
+                memory,
+                IssueKind::Contradiction,
+                format!(
+                    "contradicts {}: {}",
+                    super::short_id(&other.id),
+                    other.content
+                ),
+            ),
+            _ => {
+                anchors.entry(anchor).or_insert((positive, memory));
+            }
+        }
+    }
+    issues
+}
+
+/// The memory with whitespace collapsed and tags deduplicated, if that changes it.
+pub fn tidy(memory: &Memory) -> Option<Memory> {
+    let content = memory
+        .content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut tags: Vec<String> = Vec::new();
+    for tag in &memory.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    (content != memory.content || tags != memory.tags).then(|| Memory {
+        content,
+        tags,
+        ..memory.clone()
+    })
+}
+
+/// Words that look like relative file paths.
+pub fn mentioned_paths(content: &str) -> Vec<String> {
+    content
+        .split_whitespace()
+        .map(path_token)
+        .filter(|word| !word.contains("://") && !word.starts_with(['/', '~']))
+        .filter(|word| {
+            let Some((stem, ext)) = word.rsplit_once('.') else {
+                return word.len() > 1 && word.ends_with('/');
+            };
+            // "Node.js" is a product, "index.js" a file
+            let product = ext == "js" && stem.starts_with(char::is_uppercase);
+            !stem.is_empty()
+                && !product
+                && PATH_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// The memory with every mention of path `old` changed to `new`, if it has one.
+pub fn rename_path(memory: &Memory, old: &str, new: &str) -> Option<Memory> {
+    if !mentioned_paths(&memory.content)
+        .iter()
+        .any(|path| path == old)
+    {
+        return None;
+    }
+    let content = memory
+        .content
+        .split_inclusive(char::is_whitespace)
+        .map(|word| match path_token(word.trim_end()) {
+            token if token == old => word.replacen(old, new, 1),
+            _ => word.to_string(),
+        })
+        .collect();
+    Some(Memory {
+        content,
+        ..memory.clone()
+    })
+}
+
+/// A word without surrounding quotes, brackets, and sentence punctuation.
+fn path_token(word: &str) -> &str {
+    word.trim_matches(|c: char| "`'\"()[],;:!?".contains(c))
+        .trim_end_matches('.')
+}
+
+/// Whether a memory asks for or against something, and what that something is.
+/// "Use pnpm" and "Don't use pnpm." both anchor on "pnpm".
+fn polarity(content: &str) -> (bool, String) {
+    let mut text: String = content
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '\'')
+        .collect();
+    let mut positive = true;
+    loop {
+        let trimmed = text.trim_start();
+        let word = NEGATIONS
+            .iter()
+            .map(|w| (w, false))
+            .chain(AFFIRMATIONS.iter().map(|w| (w, true)))
+            .find(|(w, _)| {
+                trimmed
+                    .strip_prefix(**w)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+            });
+        let Some((word, affirms)) = word else {
+            break;
+        };
+        positive &= affirms;
+        text = trimmed[word.len()..].to_string();
+    }
+    (
+        positive,
+        text.split_whitespace().collect::<Vec<_>>().join(" "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn memory(id: &str, content: &str, tags: &[&str]) -> Memory {
+        Memory {
+            id: id.to_string(),
+            memory_type: "project".to_string(),
+            content: content.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            use_count: 1,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_lint_flags_each_kind() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        let memories = [
+            memory("a1", "Use pnpm", &["tools"]),
+            memory("b2", "Don't use pnpm.", &["tools"]),
+            memory(
+                "c3",
+                "Edit Cargo.toml, not src/gone.rs (see Node.js docs)",
+                &["build"],
+            ),
+            memory("d4", "Run  tests", &["ci", "ci"]),
+            memory("e5", &"x".repeat(MAX_CONTENT_CHARS + 1), &[]),
+        ];
+        let issues = |untagged| -> Vec<String> {
+            lint(&memories, dir.path(), untagged)
+                .iter()
+                .map(|i| format!("{} {:?}", i.id, i.kind))
+                .collect()
+        };
+        assert_eq!(
+            issues(false),
+            [
+                "b2 Contradiction",
+                "c3 MissingFile",
+                "d4 Untidy",
+                "e5 TooLong",
+            ]
+        );
+        assert!(issues(true).contains(&"e5 Untagged".to_string()));
+
+        let fixed = tidy(&memories[3]).unwrap();
+        assert_eq!((fixed.content.as_str(), fixed.tags.len()), ("Run tests", 1));
+
+        let moved = rename_path(&memories[2], "src/gone.rs", "src/gone/mod.rs").unwrap();
+        assert_eq!(
+            moved.content,
+            "Edit Cargo.toml, not src/gone/mod.rs (see Node.js docs)"
+        );
+        assert!(rename_path(&memories[2], "gone.rs", "x.rs").is_none());
+    }
+}
@@ -0,0 +1,177 @@
+//! Memories formatted for an MCP response, within a size budget.
+
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use crate::error::Error;
+use crate::storage::namespaces::namespace_rank;
+use crate::storage::retrieval::{visit_selected, Selection};
+use crate::storage::{short_id, Memory};
+
+/// Most memory text included in one formatted response, unless
+/// `mcp.context_chars` or a client profile says otherwise.
+pub const CONTEXT_BUDGET_CHARS: usize = 16_000;
+
+/// Format memories of `memory_types` (empty: all) as markdown grouped by
+/// type (for MCP response). Stops once `limit` memories or `max_chars` (default
+/// `CONTEXT_BUDGET_CHARS`) of memory text are collected, and then ends with
+/// a note saying more matched. With `files`, memories in their namespaces
+/// come first and those of other namespaces last.
+pub fn format_memories_as_markdown(
+    project_root: &Path,
+    memory_types: &[&str],
+    tags: Option<&[String]>,
+    limit: Option<i64>,
+    max_chars: Option<usize>,
+    profile: Option<&str>,
+    files: &[String],
+) -> Result<String, Error> {
+    let limit = limit.map(|l| l.max(0) as usize);
+    let max_chars = max_chars.unwrap_or(CONTEXT_BUDGET_CHARS);
+    let selection = Selection {
+        memory_types,
+        tags,
+        by_profile: true,
+        profile,
+        // Without files the rank order is final; one more row tells whether any were left out
+        limit: limit.filter(|_| files.is_empty()).map(|l| l + 1),
+    };
+    let mut candidates = Vec::new();
+    // Memories that sort first: all of them without files, else those in a
+    // file's namespace. Once these fill the limit or budget, later rows can't get in
+    let mut first = 0;
+    let mut read = 0;
+    let mut omitted = None;
+    visit_selected(project_root, &selection, |memory| {
+        if read > max_chars {
+            omitted = Some(format!("to fit {} characters", max_chars));
+            return ControlFlow::Break(());
+        }
+        if let Some(l) = limit.filter(|l| first > *l) {
+            omitted = Some(format!("past the limit of {}", l));
+            return ControlFlow::Break(());
+        }
+        if files.is_empty() || namespace_rank(&memory, files) == 0 {
+            first += 1;
+            read += memory.content.len();
+        }
+        candidates.push(memory);
+        ControlFlow::Continue(())
+    })?;
+    if !files.is_empty() {
+        candidates.sort_by_key(|memory| namespace_rank(memory, files));
+    }
+
+    let mut memories = Vec::new();
+    let mut used = 0;
+    for memory in candidates {
+        if let Some(l) = limit.filter(|l| memories.len() >= *l) {
+            omitted = Some(format!("past the limit of {}", l));
+            break;
+        }
+        if used + memory.content.len() > max_chars && !memories.is_empty() {
+            omitted = Some(format!("to fit {} characters", max_chars));
+            break;
+        }
+        used += memory.content.len();
+        memories.push(memory);
+    }
+
+    if memories.is_empty() {
+        return Ok("No memories found.".to_string());
+    }
+
+    // Group by type
+    let mut grouped: std::collections::BTreeMap<String, Vec<&Memory>> =
+        std::collections::BTreeMap::new();
+    for memory in &memories {
+        grouped
+            .entry(memory.memory_type.clone())
+            .or_default()
+            .push(memory);
+    }
+
+    let mut output = String::new();
+    for (mtype, mems) in &grouped {
+        output.push_str(&format!("## {} ({})\n", mtype, mems.len()));
+        for m in mems {
+            output.push_str(&format!(
+                "- {} [used {}x] {}\n",
+                short_id(&m.id),
+                m.use_count,
+                m.content
+            ));
+        }
+        output.push('\n');
+    }
+    if let Some(reason) = omitted {
+        output.push_str(&format!(
+            "(More memories match; less-used ones omitted {}. Filter by memory_type or tags to see more.)\n",
+            reason
+        ));
+    }
+
+    Ok(output.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::store_memory;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_format_memories_empty() {
+        let dir = tempdir().unwrap();
+        let result = format_memories_as_markdown(dir.path(), &[], None, None, None, None, &[]);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "No memories found.");
+    }
+
+    #[test]
+    fn test_format_memories_stops_at_budget() {
+        let dir = tempdir().unwrap();
+        let chunk = "x".repeat(CONTEXT_BUDGET_CHARS / 2);
+        for i in 0..3 {
+            store_memory(dir.path(), "project", &format!("{}{}", i, chunk), &[]).unwrap();
+        }
+
+        let output =
+            format_memories_as_markdown(dir.path(), &[], None, None, None, None, &[]).unwrap();
+        assert!(output.starts_with("## project (1)"));
+        assert!(output.ends_with(&format!(
+            "omitted to fit {} characters. Filter by memory_type or tags to see more.)",
+            CONTEXT_BUDGET_CHARS
+        )));
+
+        let limited =
+            format_memories_as_markdown(dir.path(), &[], None, Some(2), None, None, &[]).unwrap();
+        assert!(limited.starts_with("## project (1)"));
+
+        let short = format_memories_as_markdown(
+            dir.path(),
+            &[],
+            None,
+            Some(2),
+            Some(CONTEXT_BUDGET_CHARS * 2),
+            None,
+            &[],
+        )
+        .unwrap();
+        assert!(short.starts_with("## project (2)"));
+        assert!(short.contains("omitted past the limit of 2"));
+
+        let wide = format_memories_as_markdown(
+            dir.path(),
+            &[],
+            None,
+            None,
+            Some(CONTEXT_BUDGET_CHARS * 2),
+            None,
+            &[],
+        )
+        .unwrap();
+        assert!(wide.starts_with("## project (3)"));
+        assert!(!wide.contains("omitted"));
+    }
+}
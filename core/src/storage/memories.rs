@@ -0,0 +1,147 @@
+//! Reading stored memories back.
+
+use rusqlite::Result as SqliteResult;
+
+use crate::error::Error;
+use crate::storage::{Memory, Storage};
+
+/// Statement behind `project_memories_mentioning`; `?1` is a JSON array of paths.
+const MENTIONING_SQL: &str =
+    "SELECT id, memory_type, content, tags, use_count, created_at, updated_at
+     FROM memories
+     WHERE memory_type = 'project'
+       AND EXISTS (SELECT 1 FROM json_each(?1) WHERE instr(content, value) > 0)
+     ORDER BY use_count DESC";
+
+impl Storage {
+    /// List all memories.
+    pub fn list_all_memories(&self) -> Result<Vec<Memory>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, memory_type, content, tags, use_count, created_at, updated_at
+             FROM memories ORDER BY use_count DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let tags_json: String = row.get(3)?;
+            Ok(Memory {
+                id: row.get(0)?,
+                memory_type: row.get(1)?,
+                content: row.get(2)?,
+                tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+                use_count: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        })?;
+
+        let mut memories = Vec::new();
+        for row in rows {
+            memories.push(row?);
+        }
+        Ok(memories)
+    }
+    /// Project memories whose text contains any of `paths`, most used first.
+    /// One cached statement filtered by the type index, so the pre-push hook
+    /// (CLI-008) reads no other memories however many there are.
+    pub fn project_memories_mentioning(&self, paths: &[&str]) -> Result<Vec<Memory>, Error> {
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.conn.prepare_cached(MENTIONING_SQL)?;
+        let rows = stmt.query_map([serde_json::to_string(paths)?], |row| {
+            let tags_json: String = row.get(3)?;
+            Ok(Memory {
+                id: row.get(0)?,
+                memory_type: row.get(1)?,
+                content: row.get(2)?,
+                tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+                use_count: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+    /// Get a specific memory by ID.
+    pub fn get_memory(&self, id: &str) -> Result<Option<Memory>, Error> {
+        let result = self.conn.query_row(
+            "SELECT id, memory_type, content, tags, use_count, created_at, updated_at
+             FROM memories WHERE id = ?1",
+            [id],
+            |row| {
+                let tags_json: String = row.get(3)?;
+                Ok(Memory {
+                    id: row.get(0)?,
+                    memory_type: row.get(1)?,
+                    content: row.get(2)?,
+                    tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+                    use_count: row.get(4)?,
+                    created_at: row.get(5)?,
+                    updated_at: row.get(6)?,
+                })
+            },
+        );
+
+        match result {
+            Ok(memory) => Ok(Some(memory)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+    /// Whether a memory with exactly this content exists (`store_memory` would reinforce it).
+    pub fn has_content(&self, content: &str) -> Result<bool, Error> {
+        Ok(self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM memories WHERE content = ?1)",
+            [content],
+            |row| row.get(0),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::db_path;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_project_memories_mentioning_uses_type_index() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        let storage = Storage::open(&db_path(dir.path())).unwrap();
+        storage
+            .store_memory("project", "Keep src/api.rs free of I/O", &[])
+            .unwrap();
+        storage
+            .store_memory("project", "docs/setup.md is generated", &[])
+            .unwrap();
+        storage
+            .store_memory("preference", "Review src/api.rs twice", &[])
+            .unwrap();
+
+        let found = storage
+            .project_memories_mentioning(&["src/api.rs", "README.md"])
+            .unwrap();
+        let contents: Vec<&str> = found.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["Keep src/api.rs free of I/O"]);
+        assert!(storage.project_memories_mentioning(&[]).unwrap().is_empty());
+
+        let mut explain = storage
+            .conn
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", MENTIONING_SQL))
+            .unwrap();
+        let plan: Vec<String> = explain
+            .query_map(["[\"src/api.rs\"]"], |row| row.get(3))
+            .unwrap()
+            .collect::<SqliteResult<_>>()
+            .unwrap();
+        let plan = plan.join("\n");
+        assert!(
+            plan.contains("SEARCH memories USING INDEX idx_memories_type_use_count"),
+            "{}",
+            plan
+        );
+        assert!(!plan.contains("TEMP B-TREE"), "{}", plan);
+    }
+}
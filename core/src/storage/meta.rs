@@ -0,0 +1,109 @@
+//! Namespaced key-value metadata (`meta`, SCHEMA-001).
+//!
+//! Small bits of state a subsystem keeps about a store, such as when it
+//! last synced with a peer, live here rather than in files of their own
+//! under `.sqrl/`. Local only: sync and share leave the table alone.
+//! `sqrl db meta` (CLI-028) lists it.
+
+use rusqlite::{OptionalExtension, Result as SqliteResult};
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::storage::Storage;
+
+/// Namespace for `sqrl sync` cursors, keyed by peer database path.
+pub const SYNC_NAMESPACE: &str = "sync";
+
+/// Namespace for context-file bullets declined by `sqrl memory import`,
+/// keyed by content ID. Never a tombstone, which sync would spread.
+pub const DECLINED_NAMESPACE: &str = "import.declined";
+
+/// One stored value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetaEntry {
+    pub namespace: String,
+    pub key: String,
+    pub value: String,
+    pub updated_at: String,
+}
+
+impl Storage {
+    /// The value under `namespace`/`key`, if set.
+    pub fn meta_get(&self, namespace: &str, key: &str) -> Result<Option<String>, Error> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT value FROM meta WHERE namespace = ?1 AND key = ?2",
+                [namespace, key],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Set `namespace`/`key` to `value`, replacing any earlier value.
+    pub fn meta_set(&self, namespace: &str, key: &str, value: &str) -> Result<(), Error> {
+        self.conn.execute(
+            "INSERT INTO meta (namespace, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (namespace, key) DO UPDATE
+             SET value = excluded.value, updated_at = excluded.updated_at",
+            rusqlite::params![namespace, key, value, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Entries in `namespace` (all if `None`), by namespace then key.
+    pub fn meta_entries(&self, namespace: Option<&str>) -> Result<Vec<MetaEntry>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT namespace, key, value, updated_at FROM meta
+             WHERE ?1 IS NULL OR namespace = ?1 ORDER BY namespace, key",
+        )?;
+        let rows = stmt.query_map([namespace], |row| {
+            Ok(MetaEntry {
+                namespace: row.get(0)?,
+                key: row.get(1)?,
+                value: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_meta_set_replaces_value() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        assert_eq!(storage.meta_get(SYNC_NAMESPACE, "/peer").unwrap(), None);
+
+        storage
+            .meta_set(SYNC_NAMESPACE, "/peer", "2026-01-01")
+            .unwrap();
+        storage
+            .meta_set(SYNC_NAMESPACE, "/peer", "2026-02-01")
+            .unwrap();
+        storage.meta_set("hooks", "version", "2").unwrap();
+        assert_eq!(
+            storage
+                .meta_get(SYNC_NAMESPACE, "/peer")
+                .unwrap()
+                .as_deref(),
+            Some("2026-02-01")
+        );
+
+        let keys = |ns| -> Vec<String> {
+            storage
+                .meta_entries(ns)
+                .unwrap()
+                .into_iter()
+                .map(|e| format!("{}/{}", e.namespace, e.key))
+                .collect()
+        };
+        assert_eq!(keys(None), ["hooks/version", "sync//peer"]);
+        assert_eq!(keys(Some("hooks")), ["hooks/version"]);
+    }
+}
@@ -0,0 +1,130 @@
+//! SQLite storage for memories.
+//!
+//! SCHEMA-001: memories in <repo>/.sqrl/memory.db
+
+pub mod activity;
+pub mod bundle;
+pub mod changes;
+pub mod compact;
+pub mod lint;
+pub mod meta;
+pub mod pending;
+pub mod query;
+pub mod snapshot;
+pub mod sync;
+pub mod usage;
+
+mod edits;
+mod health;
+mod ids;
+mod markdown;
+mod memories;
+mod namespaces;
+mod profiles;
+mod ratings;
+mod retrieval;
+mod schema;
+mod store;
+mod visibility;
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+pub use health::{check_health, ensure_schema, DbProblem};
+pub use ids::{content_id, short_id, MIN_PREFIX_LEN, SHORT_ID_LEN};
+pub use markdown::{format_memories_as_markdown, CONTEXT_BUDGET_CHARS};
+pub use namespaces::{
+    derive_namespace, in_namespace, namespace, with_namespace, NAMESPACE_TAG_PREFIX,
+};
+pub use profiles::{visible_in_profile, PROFILE_TAG_PREFIX};
+pub use retrieval::{get_memory_counts, visit_memories};
+pub use schema::SCHEMA_VERSION;
+pub use store::store_memory;
+pub use visibility::{Visibility, VISIBILITY_TAG_PREFIX};
+
+// === Database Path ===
+
+/// Get the project database path.
+pub fn db_path(project_root: &Path) -> PathBuf {
+    project_root.join(".sqrl").join("memory.db")
+}
+
+// === Storage struct for web API ===
+
+/// Storage handle for a project database.
+pub struct Storage {
+    conn: Connection,
+}
+
+impl Storage {
+    /// Open a storage connection to a database file.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let conn = Connection::open(path)?;
+        schema::ensure_memories_table(&conn)?;
+        Ok(Self { conn })
+    }
+    /// Open an existing database for reading only: nothing is created or
+    /// migrated, so no write lock is needed (the pre-push hook, CLI-008).
+    pub fn open_read_only(path: &Path) -> Result<Self, Error> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        Ok(Self { conn })
+    }
+    /// Resolve a full ID or unambiguous prefix (e.g. `3f2a`) to the stored ID.
+    pub fn resolve_id(&self, prefix: &str) -> Result<String, Error> {
+        ids::resolve_id(&self.conn, prefix)
+    }
+    /// Run `write` in one transaction, committed only if it returns `Ok`.
+    /// Everything `write` does through this handle is rolled back on error.
+    /// Nested calls join the outer transaction.
+    pub fn atomically<T>(&self, write: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+        if !self.conn.is_autocommit() {
+            return write();
+        }
+        let tx = self.conn.unchecked_transaction()?;
+        let result = write()?;
+        tx.commit()?;
+        Ok(result)
+    }
+}
+
+/// A memory to store with `Storage::add_memories_batch`.
+#[derive(Debug, Clone)]
+pub struct NewMemory {
+    pub memory_type: String,
+    pub content: String,
+    pub tags: Vec<String>,
+}
+
+/// Result of storing a memory.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreResult {
+    pub stored: bool,
+    pub id: String,
+    pub deduplicated: bool,
+    pub use_count: i64,
+}
+
+// === Memory (SCHEMA-001) ===
+
+/// How often MCP clients found a memory useful (MCP-003).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Rating {
+    pub useful: i64,
+    pub unhelpful: i64,
+}
+
+/// A stored memory (behavioral correction).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Memory {
+    pub id: String,
+    pub memory_type: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    pub use_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
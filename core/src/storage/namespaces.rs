@@ -0,0 +1,110 @@
+//! Memories scoped to a subdirectory of a monorepo, kept as a tag.
+
+use crate::paths;
+use crate::storage::{lint, Memory};
+
+/// Tag prefix scoping a memory to a subdirectory ("namespace:services/billing").
+pub const NAMESPACE_TAG_PREFIX: &str = "namespace:";
+
+/// The subdirectory a memory is scoped to, if any.
+pub fn namespace(memory: &Memory) -> Option<&str> {
+    memory
+        .tags
+        .iter()
+        .find_map(|t| t.strip_prefix(NAMESPACE_TAG_PREFIX))
+}
+
+/// `tags` with their namespace tag replaced by `namespace` (none if None).
+pub fn with_namespace(tags: &[String], namespace: Option<&str>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .iter()
+        .filter(|t| !t.starts_with(NAMESPACE_TAG_PREFIX))
+        .cloned()
+        .collect();
+    if let Some(namespace) = namespace {
+        tags.push(format!(
+            "{}{}",
+            NAMESPACE_TAG_PREFIX,
+            paths::normalize(namespace).trim_matches('/')
+        ));
+    }
+    tags
+}
+
+/// The one namespace every path mentioned in `content` lies in, if there is one.
+pub fn derive_namespace(content: &str, namespaces: &[String]) -> Option<String> {
+    let mut found: Option<String> = None;
+    for path in lint::mentioned_paths(content) {
+        let namespace = namespaces
+            .iter()
+            .map(|n| paths::normalize(n).trim_matches('/').to_string())
+            .find(|n| in_namespace(&path, n))?;
+        if found.as_deref().is_some_and(|f| f != namespace) {
+            return None;
+        }
+        found = Some(namespace);
+    }
+    found
+}
+
+/// Whether a project-relative `path` lies under `namespace`, however either
+/// is spelled (PATH-001).
+pub fn in_namespace(path: &str, namespace: &str) -> bool {
+    let path = paths::normalize(path);
+    let namespace = paths::normalize(namespace);
+    path.strip_prefix(namespace.trim_matches('/'))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Order for retrieval around `files`: memories scoped to a directory holding
+/// one of them first, then unscoped ones, then those scoped elsewhere.
+pub(super) fn namespace_rank(memory: &Memory, files: &[String]) -> u8 {
+    match namespace(memory) {
+        None => 1,
+        Some(ns) if files.iter().any(|f| in_namespace(f, ns)) => 0,
+        Some(_) => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{format_memories_as_markdown, store_memory};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_namespaced_memories_follow_files() {
+        let dir = tempdir().unwrap();
+        let namespaces = [
+            "services/billing".to_string(),
+            "services\\api\\".to_string(),
+        ];
+        let scoped = |content: &str| {
+            let namespace = derive_namespace(content, &namespaces);
+            with_namespace(&[], namespace.as_deref())
+        };
+        store_memory(dir.path(), "project", "Use pnpm", &[]).unwrap();
+        let api = "Validate input in services/api/src/handlers.ts";
+        store_memory(dir.path(), "project", api, &scoped(api)).unwrap();
+        let billing = "Round amounts in services/billing/src/money.rs";
+        store_memory(dir.path(), "project", billing, &scoped(billing)).unwrap();
+        assert!(derive_namespace(
+            "Compare services/api/a.ts with services/billing/b.rs",
+            &namespaces
+        )
+        .is_none());
+
+        let files = ["services/billing/src/invoice.rs".to_string()];
+        let output =
+            format_memories_as_markdown(dir.path(), &[], None, None, None, None, &files).unwrap();
+        let lines: Vec<&str> = output.lines().skip(1).collect();
+        assert!(lines[0].ends_with(billing));
+        assert!(lines[1].ends_with("Use pnpm"));
+        assert!(lines[2].ends_with(api));
+
+        let first = format_memories_as_markdown(dir.path(), &[], None, Some(1), None, None, &files)
+            .unwrap();
+        assert!(first.lines().nth(1).unwrap().ends_with(billing));
+        assert!(first.contains("past the limit of 1"));
+    }
+}
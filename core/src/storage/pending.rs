@@ -0,0 +1,171 @@
+//! MCP writes waiting for a human (`mcp.require_approval`, CLI-019).
+//!
+//! Proposed memories live apart from `memories`, so retrieval, sync, and share
+//! never see them until `sqrl memory approve` moves them over.
+
+use rusqlite::{OptionalExtension, Result as SqliteResult};
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::storage::{self, Storage, MIN_PREFIX_LEN};
+
+/// A memory an MCP client asked to store.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingMemory {
+    /// `content_id` of the proposal; usually the ID it gets once approved.
+    pub id: String,
+    pub memory_type: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    /// Client that proposed it first.
+    pub client: String,
+    /// How many times it was proposed.
+    pub proposals: i64,
+    pub proposed_at: String,
+}
+
+impl Storage {
+    /// Queue a memory for approval. Returns its ID and whether it was queued.
+    /// Proposing the same memory again only counts it, and one already stored
+    /// with the same type and content isn't queued at all, so proposing the
+    /// same things twice never adds a second entry.
+    pub fn propose_memory(
+        &self,
+        memory_type: &str,
+        content: &str,
+        tags: &[String],
+        client: &str,
+    ) -> Result<(String, bool), Error> {
+        let id = storage::content_id(memory_type, content);
+        if self.get_memory(&id)?.is_some() {
+            return Ok((id, false));
+        }
+        self.conn.execute(
+            "INSERT INTO pending (id, memory_type, content, tags, client, proposals, proposed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6)
+             ON CONFLICT(id) DO UPDATE SET proposals = proposals + 1",
+            rusqlite::params![
+                id,
+                memory_type,
+                content,
+                serde_json::to_string(tags)?,
+                client,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok((id, true))
+    }
+
+    /// Memories awaiting approval, oldest first.
+    pub fn pending_memories(&self) -> Result<Vec<PendingMemory>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, memory_type, content, tags, client, proposals, proposed_at
+             FROM pending ORDER BY proposed_at, id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let tags: String = row.get(3)?;
+            Ok(PendingMemory {
+                id: row.get(0)?,
+                memory_type: row.get(1)?,
+                content: row.get(2)?,
+                tags: serde_json::from_str(&tags).unwrap_or_default(),
+                client: row.get(4)?,
+                proposals: row.get(5)?,
+                proposed_at: row.get(6)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+
+    /// Remove a pending memory by ID or unambiguous prefix, returning it.
+    pub fn take_pending(&self, prefix: &str) -> Result<PendingMemory, Error> {
+        let prefix = prefix.trim().to_ascii_lowercase();
+        if prefix.len() < MIN_PREFIX_LEN {
+            return Err(Error::MemoryNotFound(prefix));
+        }
+        let mut matches: Vec<PendingMemory> = self
+            .pending_memories()?
+            .into_iter()
+            .filter(|p| p.id.starts_with(&prefix))
+            .collect();
+        let pending = match matches.len() {
+            0 => return Err(Error::MemoryNotFound(prefix)),
+            1 => matches.remove(0),
+            _ => {
+                return Err(Error::AmbiguousId {
+                    prefix,
+                    matches: matches.into_iter().map(|p| p.id).collect(),
+                })
+            }
+        };
+        self.conn
+            .execute("DELETE FROM pending WHERE id = ?1", [&pending.id])?;
+        Ok(pending)
+    }
+
+    /// Number of memories awaiting approval.
+    pub fn pending_count(&self) -> Result<i64, Error> {
+        let count: Option<i64> = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM pending", [], |row| row.get(0))
+            .optional()?;
+        Ok(count.unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_proposals_wait_outside_memories() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let (id, queued) = storage
+            .propose_memory("project", "Use pnpm", &[], "claude-code")
+            .unwrap();
+        assert!(queued);
+        storage
+            .propose_memory("project", "Use pnpm", &[], "cursor")
+            .unwrap();
+
+        assert!(storage.list_all_memories().unwrap().is_empty());
+        let pending = storage.pending_memories().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            (pending[0].client.as_str(), pending[0].proposals),
+            ("claude-code", 2)
+        );
+
+        let taken = storage.take_pending(&id[..6]).unwrap();
+        assert_eq!(taken.content, "Use pnpm");
+        assert_eq!(storage.pending_count().unwrap(), 0);
+        assert!(storage.take_pending(&id).is_err());
+
+        storage.store_memory("project", "Use pnpm", &[]).unwrap();
+        let (_, queued) = storage
+            .propose_memory("project", "Use pnpm", &[], "cursor")
+            .unwrap();
+        assert!(!queued);
+        assert_eq!(storage.pending_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_propose_same_content_other_type_is_queued() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        storage.store_memory("project", "Use pnpm", &[]).unwrap();
+
+        let (id, queued) = storage
+            .propose_memory("preference", "Use pnpm", &[], "claude-code")
+            .unwrap();
+        assert!(queued);
+        let pending = storage.pending_memories().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            (pending[0].id.as_str(), pending[0].memory_type.as_str()),
+            (id.as_str(), "preference")
+        );
+    }
+}
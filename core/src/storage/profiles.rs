@@ -0,0 +1,75 @@
+//! Preferences scoped to a global profile.
+
+use crate::storage::Memory;
+
+/// Tag marking a preference as belonging to a global profile ("profile:work").
+pub const PROFILE_TAG_PREFIX: &str = "profile:";
+
+/// Whether a memory applies under the active profile.
+/// Preferences tagged for another profile (or any profile, when none is active) are hidden.
+pub fn visible_in_profile(memory: &Memory, profile: Option<&str>) -> bool {
+    let mut profiles = memory
+        .tags
+        .iter()
+        .filter_map(|t| t.strip_prefix(PROFILE_TAG_PREFIX))
+        .peekable();
+    memory.memory_type != "preference"
+        || profiles.peek().is_none()
+        || profile.is_some_and(|active| profiles.any(|p| p == active))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{format_memories_as_markdown, store_memory};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_profile_preferences_hidden_outside_profile() {
+        let dir = tempdir().unwrap();
+        store_memory(dir.path(), "preference", "Use tabs", &[]).unwrap();
+        store_memory(
+            dir.path(),
+            "preference",
+            "Sign commits",
+            &["profile:work".to_string()],
+        )
+        .unwrap();
+
+        let work =
+            format_memories_as_markdown(dir.path(), &[], None, None, None, Some("work"), &[])
+                .unwrap();
+        assert!(work.contains("Use tabs") && work.contains("Sign commits"));
+
+        let none =
+            format_memories_as_markdown(dir.path(), &[], None, None, None, None, &[]).unwrap();
+        assert!(none.contains("Use tabs") && !none.contains("Sign commits"));
+
+        let tagged = ["profile:work".to_string()];
+        let work_only = format_memories_as_markdown(
+            dir.path(),
+            &[],
+            Some(&tagged),
+            None,
+            None,
+            Some("work"),
+            &[],
+        )
+        .unwrap();
+        assert!(work_only.contains("Sign commits") && !work_only.contains("Use tabs"));
+
+        store_memory(dir.path(), "project", "Use pnpm", &[]).unwrap();
+        store_memory(dir.path(), "style", "Short names", &[]).unwrap();
+        let both = format_memories_as_markdown(
+            dir.path(),
+            &["preference", "project"],
+            None,
+            None,
+            None,
+            None,
+            &[],
+        )
+        .unwrap();
+        assert!(both.contains("Use tabs") && both.contains("Use pnpm"));
+        assert!(!both.contains("Short names"));
+    }
+}
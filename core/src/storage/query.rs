@@ -0,0 +1,109 @@
+//! Ad-hoc SQL against a memory database (`sqrl db query`, CLI-028).
+
+use std::path::Path;
+
+use rusqlite::types::ValueRef;
+use rusqlite::{Batch, Connection, OpenFlags};
+use serde_json::{json, Value};
+
+use crate::error::Error;
+
+/// Columns and rows a statement returned, and rows it changed.
+#[derive(Debug)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub changed: usize,
+}
+
+/// Run one SQL statement. The database is opened read-only, and statements
+/// that would write are refused, unless `writable`; then the caller holds the lock.
+pub fn query(path: &Path, sql: &str, writable: bool) -> Result<QueryResult, Error> {
+    if !path.exists() {
+        return Err(Error::DatabaseNotFound(path.to_path_buf()));
+    }
+    let flags = if writable {
+        OpenFlags::SQLITE_OPEN_READ_WRITE
+    } else {
+        OpenFlags::SQLITE_OPEN_READ_ONLY
+    };
+    let conn = Connection::open_with_flags(path, flags)?;
+    let mut batch = Batch::new(&conn, sql);
+    let Some(mut stmt) = batch.next()? else {
+        return Err(Error::InvalidArgument("no SQL statement given".to_string()));
+    };
+    if batch.next()?.is_some() {
+        return Err(Error::InvalidArgument(
+            "one statement at a time".to_string(),
+        ));
+    }
+    let readonly = stmt.readonly();
+    if !writable && !readonly {
+        return Err(Error::InvalidArgument(
+            "statement writes to the database; rerun with --unsafe-writes".to_string(),
+        ));
+    }
+
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    if columns.is_empty() {
+        let changed = stmt.execute([])?;
+        return Ok(QueryResult {
+            columns,
+            rows: Vec::new(),
+            changed,
+        });
+    }
+    let mut rows = Vec::new();
+    let mut results = stmt.query([])?;
+    while let Some(row) = results.next()? {
+        let values = (0..columns.len())
+            .map(|i| row.get_ref(i).map(to_json))
+            .collect::<Result<_, _>>()?;
+        rows.push(values);
+    }
+    Ok(QueryResult {
+        columns,
+        rows,
+        // Statements with RETURNING both return rows and write
+        changed: if readonly { 0 } else { conn.changes() as usize },
+    })
+}
+
+fn to_json(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => json!(i),
+        ValueRef::Real(f) => json!(f),
+        ValueRef::Text(text) => json!(String::from_utf8_lossy(text)),
+        ValueRef::Blob(blob) => json!(format!("<{} bytes>", blob.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_writes_need_opt_in() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("memory.db");
+        Storage::open(&path).unwrap();
+
+        let insert = "INSERT INTO tombstones (id, deleted_at) VALUES ('ab12', 'now')";
+        let err = query(&path, insert, false).unwrap_err();
+        assert_eq!(err.code(), "invalid_argument");
+        assert_eq!(query(&path, insert, true).unwrap().changed, 1);
+        assert!(query(&path, "SELECT 1; DELETE FROM tombstones", true).is_err());
+
+        let result = query(
+            &path,
+            "SELECT id, 1.5 AS n, NULL AS z FROM tombstones",
+            false,
+        )
+        .unwrap();
+        assert_eq!(result.columns, ["id", "n", "z"]);
+        assert_eq!(result.rows, [[json!("ab12"), json!(1.5), Value::Null]]);
+    }
+}
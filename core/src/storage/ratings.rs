@@ -0,0 +1,87 @@
+//! Verdicts MCP clients give on memories (MCP-003).
+
+use std::collections::HashMap;
+
+use rusqlite::Result as SqliteResult;
+
+use crate::error::Error;
+use crate::storage::{Rating, Storage};
+
+impl Storage {
+    /// Record an MCP client's verdict on a memory. Returns the updated totals.
+    pub fn rate_memory(&self, id: &str, useful: bool) -> Result<Rating, Error> {
+        let (useful, unhelpful) = if useful { (1, 0) } else { (0, 1) };
+        let now = chrono::Utc::now().to_rfc3339();
+        self.conn.execute(
+            "INSERT INTO ratings (id, useful, unhelpful, rated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET useful = useful + ?2, unhelpful = unhelpful + ?3,
+                 rated_at = ?4",
+            rusqlite::params![id, useful, unhelpful, now],
+        )?;
+        Ok(self.ratings()?.remove(id).unwrap_or_default())
+    }
+    /// Rating totals by memory ID. Unrated memories are absent.
+    pub fn ratings(&self) -> Result<HashMap<String, Rating>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, useful, unhelpful FROM ratings")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                Rating {
+                    useful: row.get(1)?,
+                    unhelpful: row.get(2)?,
+                },
+            ))
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::ops::ControlFlow;
+
+    use crate::storage::{db_path, visit_memories};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_unhelpful_ratings_lower_rank() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".sqrl")).unwrap();
+        let storage = Storage::open(&db_path(dir.path())).unwrap();
+        let noisy = storage.store_memory("project", "Noisy", &[]).unwrap().id;
+        storage.store_memory("project", "Noisy", &[]).unwrap();
+        storage.store_memory("project", "Quiet", &[]).unwrap();
+
+        let order = |root| {
+            let mut contents = Vec::new();
+            visit_memories(root, None, |memory| {
+                contents.push(memory.content);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            contents
+        };
+        assert_eq!(order(dir.path()), ["Noisy", "Quiet"]);
+
+        let rating = storage.rate_memory(&noisy, false).unwrap();
+        assert_eq!(
+            rating,
+            Rating {
+                useful: 0,
+                unhelpful: 1
+            }
+        );
+        assert_eq!(order(dir.path()), ["Quiet", "Noisy"]);
+
+        // Kept while deleted, so `sqrl undo` brings the rank back
+        let memory = storage.get_memory(&noisy).unwrap().unwrap();
+        storage.delete_memory(&noisy).unwrap();
+        assert!(storage.ratings().unwrap().contains_key(&noisy));
+        storage.restore_memory(&memory).unwrap();
+        assert_eq!(order(dir.path()), ["Quiet", "Noisy"]);
+    }
+}
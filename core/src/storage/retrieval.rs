@@ -0,0 +1,173 @@
+//! Reading memories in rank order, filtered in SQL.
+
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::error::Error;
+use crate::storage::schema::ensure_memories_table;
+use crate::storage::{db_path, Memory, PROFILE_TAG_PREFIX};
+
+/// Stream memories, best ranked first, until `visit` returns `Break`.
+/// Rank is `use_count` plus useful ratings minus twice the unhelpful ones (MCP-003).
+/// Rows are read one at a time, so stopping early never loads the rest.
+pub fn visit_memories(
+    project_root: &Path,
+    memory_type: Option<&str>,
+    visit: impl FnMut(Memory) -> ControlFlow<()>,
+) -> Result<(), Error> {
+    let memory_types: Vec<&str> = memory_type.into_iter().collect();
+    let selection = Selection {
+        memory_types: &memory_types,
+        ..Selection::default()
+    };
+    visit_selected(project_root, &selection, visit)
+}
+
+/// Which memories `visit_selected` reads. The filters run in SQL, so the
+/// limit can too.
+#[derive(Default)]
+pub(super) struct Selection<'a> {
+    /// Memories of any of these types (empty: all).
+    pub(super) memory_types: &'a [&'a str],
+    /// Memories with any of these tags (none or empty: all).
+    pub(super) tags: Option<&'a [String]>,
+    /// Hide preferences of other profiles, as `visible_in_profile` does.
+    pub(super) by_profile: bool,
+    pub(super) profile: Option<&'a str>,
+    pub(super) limit: Option<usize>,
+}
+
+pub(super) fn visit_selected(
+    project_root: &Path,
+    selection: &Selection,
+    mut visit: impl FnMut(Memory) -> ControlFlow<()>,
+) -> Result<(), Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let conn = Connection::open(&path)?;
+
+    // Check if table exists
+    let table_exists: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='memories'",
+        [],
+        |row| row.get(0),
+    )?;
+    if table_exists == 0 {
+        return Ok(());
+    }
+    // Databases older than the ratings table
+    ensure_memories_table(&conn)?;
+
+    let types = (!selection.memory_types.is_empty())
+        .then(|| serde_json::to_string(selection.memory_types))
+        .transpose()?;
+    let tags = selection
+        .tags
+        .filter(|tags| !tags.is_empty())
+        .map(serde_json::to_string)
+        .transpose()?;
+    let limit = selection.limit.map_or(-1, |l| l as i64);
+    let mut stmt = conn.prepare(
+        "SELECT m.id, m.memory_type, m.content, m.tags, m.use_count, m.created_at, m.updated_at
+         FROM memories m LEFT JOIN ratings r ON r.id = m.id
+         WHERE (?1 IS NULL OR m.memory_type IN (SELECT value FROM json_each(?1)))
+           AND (?2 IS NULL OR EXISTS (
+               SELECT 1 FROM json_each(m.tags) t JOIN json_each(?2) f ON t.value = f.value))
+           AND (NOT ?3 OR m.memory_type != 'preference'
+               OR NOT EXISTS (SELECT 1 FROM json_each(m.tags)
+                              WHERE substr(value, 1, length(?5)) = ?5)
+               OR EXISTS (SELECT 1 FROM json_each(m.tags) WHERE value = ?5 || ?4))
+         ORDER BY m.use_count + COALESCE(r.useful, 0) - 2 * COALESCE(r.unhelpful, 0) DESC,
+             m.use_count DESC
+         LIMIT ?6",
+    )?;
+    let mut rows = stmt.query(rusqlite::params![
+        types,
+        tags,
+        selection.by_profile,
+        selection.profile,
+        PROFILE_TAG_PREFIX,
+        limit
+    ])?;
+    while let Some(row) = rows.next()? {
+        let tags_json: String = row.get(3)?;
+        let memory = Memory {
+            id: row.get(0)?,
+            memory_type: row.get(1)?,
+            content: row.get(2)?,
+            tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            use_count: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        };
+        if visit(memory).is_break() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Get memory count by type.
+pub fn get_memory_counts(
+    project_root: &Path,
+) -> Result<std::collections::HashMap<String, i64>, Error> {
+    let path = db_path(project_root);
+    if !path.exists() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let conn = Connection::open(&path)?;
+
+    let table_exists: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='memories'",
+        [],
+        |row| row.get(0),
+    )?;
+    if table_exists == 0 {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let mut stmt =
+        conn.prepare("SELECT memory_type, COUNT(*) FROM memories GROUP BY memory_type")?;
+    let rows = stmt.query_map([], |row| {
+        let mtype: String = row.get(0)?;
+        let count: i64 = row.get(1)?;
+        Ok((mtype, count))
+    })?;
+
+    let mut counts = std::collections::HashMap::new();
+    for row in rows {
+        let (mtype, count) = row?;
+        counts.insert(mtype, count);
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn get_memories(project_root: &Path, memory_type: Option<&str>) -> Result<Vec<Memory>, Error> {
+        let mut memories = Vec::new();
+        visit_memories(project_root, memory_type, |memory| {
+            memories.push(memory);
+            ControlFlow::Continue(())
+        })?;
+        Ok(memories)
+    }
+
+    #[test]
+    fn test_get_memories_empty() {
+        let dir = tempdir().unwrap();
+        let result = get_memories(dir.path(), None);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+    }
+}
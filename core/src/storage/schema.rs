@@ -0,0 +1,172 @@
+//! Tables of the memory database and its version stamp.
+
+use rusqlite::{Connection, Result as SqliteResult};
+use tracing::info;
+
+use crate::error::Error;
+use crate::storage::changes;
+
+/// Schema version stamped in `PRAGMA user_version`. Bump it whenever
+/// `create_tables` gains a table, index or cleanup, so older databases are
+/// upgraded on first contact and older binaries refuse newer ones.
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// Bring a database up to `SCHEMA_VERSION`, refusing one written by a newer
+/// sqrl. Unversioned databases from before the stamp are version 0.
+pub(super) fn ensure_memories_table(conn: &Connection) -> Result<(), Error> {
+    let version = schema_version(conn)?;
+    if version > SCHEMA_VERSION {
+        return Err(Error::SchemaTooNew(version));
+    }
+    create_tables(conn)?;
+    if version < SCHEMA_VERSION {
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        let legacy: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM memories)", [], |row| {
+            row.get(0)
+        })?;
+        if legacy {
+            info!(
+                path = conn.path().unwrap_or_default(),
+                from = version,
+                to = SCHEMA_VERSION,
+                "Migrated memory.db schema"
+            );
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn schema_version(conn: &Connection) -> SqliteResult<i64> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+/// Create missing tables, indexes and triggers, and drop ones older versions
+/// left behind. Idempotent.
+fn create_tables(conn: &Connection) -> SqliteResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS memories (
+            id           TEXT PRIMARY KEY,
+            memory_type  TEXT NOT NULL,
+            content      TEXT NOT NULL,
+            tags         TEXT DEFAULT '[]',
+            use_count    INTEGER DEFAULT 1,
+            created_at   TEXT NOT NULL,
+            updated_at   TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Type lookups ordered by use; replaces the type-only index it covers
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_memories_type_use_count
+             ON memories(memory_type, use_count DESC);
+         DROP INDEX IF EXISTS idx_memories_type;",
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_memories_use_count ON memories(use_count DESC)",
+        [],
+    )?;
+    // Feedback from MCP clients (MCP-003), kept out of memories so sync and share ignore it
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ratings (
+            id          TEXT PRIMARY KEY,
+            useful      INTEGER NOT NULL DEFAULT 0,
+            unhelpful   INTEGER NOT NULL DEFAULT 0,
+            rated_at    TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Deleted IDs, so sync (SYNC-001) doesn't bring them back
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tombstones (
+            id          TEXT PRIMARY KEY,
+            deleted_at  TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // MCP writes awaiting approval (mcp.require_approval)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS pending (
+            id           TEXT PRIMARY KEY,
+            memory_type  TEXT NOT NULL,
+            content      TEXT NOT NULL,
+            tags         TEXT DEFAULT '[]',
+            client       TEXT NOT NULL,
+            proposals    INTEGER NOT NULL DEFAULT 1,
+            proposed_at  TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // MCP tool calls by client (CLI-023), local only
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS mcp_activity (
+            client          TEXT NOT NULL,
+            client_version  TEXT NOT NULL,
+            action          TEXT NOT NULL,
+            memory_id       TEXT,
+            at              TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Memories active at each commit (hooks.snapshots); identical sets stored once
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS memory_sets (
+            hash        TEXT PRIMARY KEY,
+            memories    TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS snapshots (
+            commit_id   TEXT PRIMARY KEY,
+            set_hash    TEXT NOT NULL,
+            taken_at    TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Commands, hooks and MCP tool calls with their durations (analytics.enabled), local only
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS usage (
+            kind         TEXT NOT NULL,
+            name         TEXT NOT NULL,
+            duration_ms  INTEGER NOT NULL,
+            at           TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Subsystem state by namespace and key (meta.rs), local only
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS meta (
+            namespace   TEXT NOT NULL,
+            key         TEXT NOT NULL,
+            value       TEXT NOT NULL,
+            updated_at  TEXT NOT NULL,
+            PRIMARY KEY (namespace, key)
+        )",
+        [],
+    )?;
+    // Every change to memories, ratings and proposals, whichever process makes
+    // it, for readers that poll instead of rereading everything (changes.rs)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS changes (
+            seq         INTEGER PRIMARY KEY AUTOINCREMENT,
+            table_name  TEXT NOT NULL,
+            id          TEXT NOT NULL,
+            op          TEXT NOT NULL,
+            at          TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+        )",
+        [],
+    )?;
+    for table in changes::TRACKED {
+        for (op, event, row) in [
+            ("insert", "INSERT", "NEW"),
+            ("update", "UPDATE", "NEW"),
+            ("delete", "DELETE", "OLD"),
+        ] {
+            conn.execute_batch(&format!(
+                "CREATE TRIGGER IF NOT EXISTS {table}_{op}_changes AFTER {event} ON {table}
+                 BEGIN INSERT INTO changes (table_name, id, op) VALUES ('{table}', {row}.id, '{op}'); END;"
+            ))?;
+        }
+    }
+    Ok(())
+}
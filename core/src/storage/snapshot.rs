@@ -0,0 +1,176 @@
+//! Memories active at each commit (`sqrl memory at`, CLI-019).
+//!
+//! The post-commit hook records the memory set under the new commit's ID.
+//! Identical sets are stored once, so commits that don't change memories
+//! cost one row.
+
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Error;
+use crate::storage::Storage;
+
+/// A memory as recorded in a snapshot. Usage counts are left out, so the set
+/// only changes when memories do.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotMemory {
+    pub id: String,
+    pub memory_type: String,
+    pub content: String,
+    pub tags: Vec<String>,
+}
+
+/// The memories recorded for one commit.
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub commit: String,
+    pub taken_at: String,
+    pub memories: Vec<SnapshotMemory>,
+}
+
+impl Storage {
+    /// Record the current memories under `commit`.
+    pub fn record_snapshot(&self, commit: &str) -> Result<(), Error> {
+        let mut memories: Vec<SnapshotMemory> = self
+            .list_all_memories()?
+            .into_iter()
+            .map(|m| SnapshotMemory {
+                id: m.id,
+                memory_type: m.memory_type,
+                content: m.content,
+                tags: m.tags,
+            })
+            .collect();
+        // By ID, so reordering by use doesn't make a new set
+        memories.sort_by(|a, b| a.id.cmp(&b.id));
+        let json = serde_json::to_string(&memories)?;
+        let hash: String = Sha256::digest(json.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        self.conn.execute(
+            "INSERT OR IGNORE INTO memory_sets (hash, memories) VALUES (?1, ?2)",
+            [&hash, &json],
+        )?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO snapshots (commit_id, set_hash, taken_at) VALUES (?1, ?2, ?3)",
+            [commit, &hash, &chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// The latest snapshot recorded at or before `at` (RFC 3339), if any.
+    pub fn snapshot_before(&self, at: &str) -> Result<Option<Snapshot>, Error> {
+        let commit: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT commit_id FROM snapshots WHERE taken_at <= ?1
+                 ORDER BY taken_at DESC LIMIT 1",
+                [at],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match commit {
+            Some(commit) => self.snapshot(&commit),
+            None => Ok(None),
+        }
+    }
+
+    /// The snapshot recorded for `commit` (full ID), if any.
+    pub fn snapshot(&self, commit: &str) -> Result<Option<Snapshot>, Error> {
+        let row: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT s.taken_at, m.memories FROM snapshots s
+                 JOIN memory_sets m ON m.hash = s.set_hash WHERE s.commit_id = ?1",
+                [commit],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((taken_at, memories)) = row else {
+            return Ok(None);
+        };
+        Ok(Some(Snapshot {
+            commit: commit.to_string(),
+            taken_at,
+            memories: serde_json::from_str(&memories)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_snapshots_share_unchanged_sets() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        storage.store_memory("project", "Use pnpm", &[]).unwrap();
+        storage.record_snapshot("aaa").unwrap();
+        // A use doesn't change the set
+        storage.store_memory("project", "Use pnpm", &[]).unwrap();
+        storage.record_snapshot("bbb").unwrap();
+        storage.store_memory("project", "Run clippy", &[]).unwrap();
+        storage.record_snapshot("ccc").unwrap();
+
+        let sets: i64 = storage
+            .conn
+            .query_row("SELECT COUNT(*) FROM memory_sets", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(sets, 2);
+        assert_eq!(storage.snapshot("aaa").unwrap().unwrap().memories.len(), 1);
+        assert_eq!(storage.snapshot("ccc").unwrap().unwrap().memories.len(), 2);
+        assert!(storage.snapshot("ddd").unwrap().is_none());
+
+        let latest = chrono::Utc::now().to_rfc3339();
+        assert_eq!(
+            storage
+                .snapshot_before(&latest)
+                .unwrap()
+                .unwrap()
+                .memories
+                .len(),
+            2
+        );
+        assert!(storage
+            .snapshot_before("2000-01-01T00:00:00+00:00")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_snapshot_before_edit_and_delete_keeps_earlier_state() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let pnpm = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let clippy = storage
+            .store_memory("project", "Run clippy", &[])
+            .unwrap()
+            .id;
+        storage.record_snapshot("aaa").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let as_of = chrono::Utc::now().to_rfc3339();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        storage
+            .update_memory(&pnpm, None, Some("Use pnpm 9"), None)
+            .unwrap();
+        storage.delete_memory(&clippy).unwrap();
+        storage.record_snapshot("bbb").unwrap();
+
+        let then = storage.snapshot_before(&as_of).unwrap().unwrap();
+        assert_eq!(then.commit, "aaa");
+        let contents: Vec<&str> = then.memories.iter().map(|m| m.content.as_str()).collect();
+        assert!(contents.contains(&"Use pnpm"));
+        assert!(contents.contains(&"Run clippy"));
+        assert_eq!(contents.len(), 2);
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let latest = storage.snapshot_before(&now).unwrap().unwrap();
+        assert_eq!(latest.memories.len(), 1);
+        assert_eq!(latest.memories[0].content, "Use pnpm 9");
+    }
+}
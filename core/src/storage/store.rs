@@ -0,0 +1,171 @@
+//! Storing and editing memories, deduplicated by content.
+
+use std::fs;
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::error::Error;
+use crate::storage::ids::new_memory_id;
+use crate::storage::schema::ensure_memories_table;
+use crate::storage::{db_path, NewMemory, Storage, StoreResult};
+
+impl Storage {
+    /// Store a new memory.
+    pub fn store_memory(
+        &self,
+        memory_type: &str,
+        content: &str,
+        tags: &[String],
+    ) -> Result<StoreResult, Error> {
+        store_in(&self.conn, memory_type, content, tags)
+    }
+    /// Store several memories in one transaction: all of them or, on error,
+    /// none. Deduplicates like `store_memory`, within the batch too.
+    pub fn add_memories_batch(&self, memories: &[NewMemory]) -> Result<Vec<StoreResult>, Error> {
+        self.atomically(|| {
+            memories
+                .iter()
+                .map(|m| store_in(&self.conn, &m.memory_type, &m.content, &m.tags))
+                .collect()
+        })
+    }
+}
+
+/// Store a memory. Deduplicates by content (increments use_count if exists).
+pub fn store_memory(
+    project_root: &Path,
+    memory_type: &str,
+    content: &str,
+    tags: &[String],
+) -> Result<(String, bool, i64), Error> {
+    let path = db_path(project_root);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let conn = Connection::open(&path)?;
+    ensure_memories_table(&conn)?;
+    let stored = store_in(&conn, memory_type, content, tags)?;
+    Ok((stored.id, stored.deduplicated, stored.use_count))
+}
+
+/// Store a memory on `conn`, reinforcing one with the same content.
+/// Statements are cached, so a transaction storing many prepares each once.
+fn store_in(
+    conn: &Connection,
+    memory_type: &str,
+    content: &str,
+    tags: &[String],
+) -> Result<StoreResult, Error> {
+    let existing: Option<(String, i64)> = conn
+        .prepare_cached("SELECT id, use_count FROM memories WHERE content = ?1")?
+        .query_row([content], |row| Ok((row.get(0)?, row.get(1)?)))
+        .ok();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    if let Some((id, use_count)) = existing {
+        let new_count = use_count + 1;
+        conn.prepare_cached("UPDATE memories SET use_count = ?1, updated_at = ?2 WHERE id = ?3")?
+            .execute(rusqlite::params![new_count, now, id])?;
+        Ok(StoreResult {
+            stored: true,
+            id,
+            deduplicated: true,
+            use_count: new_count,
+        })
+    } else {
+        let id = new_memory_id(conn, memory_type, content)?;
+        let tags_json = serde_json::to_string(tags)?;
+        conn.prepare_cached(
+            "INSERT INTO memories (id, memory_type, content, tags, use_count, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, 1, ?5, ?6)",
+        )?
+        .execute(rusqlite::params![id, memory_type, content, tags_json, now, now])?;
+        Ok(StoreResult {
+            stored: true,
+            id,
+            deduplicated: false,
+            use_count: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_store_and_get_memory() {
+        let dir = tempdir().unwrap();
+        let sqrl_dir = dir.path().join(".sqrl");
+        fs::create_dir_all(&sqrl_dir).unwrap();
+
+        let (id, deduped, count) = store_memory(
+            dir.path(),
+            "preference",
+            "No emojis",
+            &["style".to_string()],
+        )
+        .unwrap();
+        assert!(!deduped);
+        assert_eq!(count, 1);
+        assert!(!id.is_empty());
+
+        // Store same memory again - should dedup
+        let (id2, deduped2, count2) = store_memory(
+            dir.path(),
+            "preference",
+            "No emojis",
+            &["style".to_string()],
+        )
+        .unwrap();
+        assert!(deduped2);
+        assert_eq!(count2, 2);
+        assert_eq!(id, id2);
+
+        // Get memories
+        let memories = Storage::open(&db_path(dir.path()))
+            .unwrap()
+            .list_all_memories()
+            .unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].use_count, 2);
+    }
+
+    #[test]
+    fn test_batch_is_all_or_nothing() {
+        let dir = tempdir().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let new = |content: &str| NewMemory {
+            memory_type: "project".to_string(),
+            content: content.to_string(),
+            tags: Vec::new(),
+        };
+
+        let stored = storage
+            .add_memories_batch(&[new("Use pnpm"), new("Run clippy"), new("Use pnpm")])
+            .unwrap();
+        assert_eq!(
+            stored.iter().map(|s| s.deduplicated).collect::<Vec<_>>(),
+            [false, false, true]
+        );
+        assert_eq!(
+            storage
+                .get_memory(&stored[0].id)
+                .unwrap()
+                .unwrap()
+                .use_count,
+            2
+        );
+
+        let failed: Result<(), Error> = storage.atomically(|| {
+            storage.store_memory("project", "No emojis", &[])?;
+            Err(Error::InvalidArgument("stop".to_string()))
+        });
+        assert!(failed.is_err());
+        assert!(!storage.has_content("No emojis").unwrap());
+        assert_eq!(storage.list_all_memories().unwrap().len(), 2);
+    }
+}
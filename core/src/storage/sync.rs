@@ -0,0 +1,208 @@
+//! Merge two memory databases (SYNC-001).
+//!
+//! Records are matched by ID (content-addressed, SCHEMA-001). Every rule is
+//! deterministic, so syncing A with B and B with A converge to the same state:
+//!
+//! - On both sides: the later `updated_at` wins, ties broken by content;
+//!   `use_count` becomes the larger of the two.
+//! - On one side, tombstoned on the other: deleted if the tombstone is at
+//!   least as new as the record, otherwise copied over (it was restored).
+//! - On one side only: copied over.
+//! - Tombstones are copied both ways.
+//! - Memories private on either side (`visibility:private`) are left alone.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::storage::{Memory, Storage, Visibility};
+
+/// What a sync changed on each side.
+#[derive(Debug, Default, Serialize)]
+pub struct SyncReport {
+    /// Memories added or updated locally.
+    pub pulled: usize,
+    /// Memories added or updated on the peer.
+    pub pushed: usize,
+    /// Memories deleted locally because the peer deleted them.
+    pub deleted_local: usize,
+    /// Memories deleted on the peer because they were deleted locally.
+    pub deleted_peer: usize,
+    /// Memories left out because they are private on one side.
+    pub private: usize,
+}
+
+/// Bring `local` and `peer` to the same set of memories. Each side's writes
+/// commit together, so an interrupted sync leaves neither half-merged.
+pub fn sync(local: &Storage, peer: &Storage) -> Result<SyncReport, Error> {
+    local.atomically(|| peer.atomically(|| merge_into(local, peer)))
+}
+
+fn merge_into(local: &Storage, peer: &Storage) -> Result<SyncReport, Error> {
+    let local_memories = by_id(local.list_all_memories()?);
+    let peer_memories = by_id(peer.list_all_memories()?);
+    let local_tombstones = local.tombstones()?;
+    let peer_tombstones = peer.tombstones()?;
+
+    let ids: BTreeSet<&String> = local_memories
+        .keys()
+        .chain(peer_memories.keys())
+        .chain(local_tombstones.keys())
+        .chain(peer_tombstones.keys())
+        .collect();
+
+    let mut report = SyncReport::default();
+    for id in ids {
+        let (l, p) = (local_memories.get(id), peer_memories.get(id));
+        if l.into_iter().chain(p).any(|m| !Visibility::shareable(m)) {
+            report.private += 1;
+            continue;
+        }
+        match (l, p) {
+            (Some(l), Some(p)) => {
+                let merged = merge(l, p);
+                if !same(&merged, l) {
+                    local.restore_memory(&merged)?;
+                    report.pulled += 1;
+                }
+                if !same(&merged, p) {
+                    peer.restore_memory(&merged)?;
+                    report.pushed += 1;
+                }
+            }
+            (Some(l), None) => match peer_tombstones.get(id) {
+                Some(deleted_at) if !is_newer(&l.updated_at, deleted_at) => {
+                    local.apply_tombstone(id, deleted_at)?;
+                    report.deleted_local += 1;
+                }
+                _ => {
+                    peer.restore_memory(l)?;
+                    report.pushed += 1;
+                }
+            },
+            (None, Some(p)) => match local_tombstones.get(id) {
+                Some(deleted_at) if !is_newer(&p.updated_at, deleted_at) => {
+                    peer.apply_tombstone(id, deleted_at)?;
+                    report.deleted_peer += 1;
+                }
+                _ => {
+                    local.restore_memory(p)?;
+                    report.pulled += 1;
+                }
+            },
+            (None, None) => {
+                if let Some(deleted_at) = local_tombstones.get(id) {
+                    peer.apply_tombstone(id, deleted_at)?;
+                }
+                if let Some(deleted_at) = peer_tombstones.get(id) {
+                    local.apply_tombstone(id, deleted_at)?;
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn by_id(memories: Vec<Memory>) -> HashMap<String, Memory> {
+    memories.into_iter().map(|m| (m.id.clone(), m)).collect()
+}
+
+/// Deterministic merge of two versions of one memory.
+fn merge(a: &Memory, b: &Memory) -> Memory {
+    let a_wins = match timestamp(&a.updated_at).cmp(&timestamp(&b.updated_at)) {
+        std::cmp::Ordering::Equal => (&a.content, &a.memory_type) >= (&b.content, &b.memory_type),
+        order => order.is_gt(),
+    };
+    let mut merged = if a_wins { a.clone() } else { b.clone() };
+    merged.use_count = a.use_count.max(b.use_count);
+    merged
+}
+
+fn same(a: &Memory, b: &Memory) -> bool {
+    a.memory_type == b.memory_type
+        && a.content == b.content
+        && a.tags == b.tags
+        && a.use_count == b.use_count
+        && a.updated_at == b.updated_at
+}
+
+/// Whether `a` is strictly later than `b` (RFC 3339).
+pub(crate) fn is_newer(a: &str, b: &str) -> bool {
+    timestamp(a) > timestamp(b)
+}
+
+fn timestamp(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn open(dir: &TempDir) -> Storage {
+        Storage::open(&dir.path().join("memory.db")).unwrap()
+    }
+
+    #[test]
+    fn test_sync_converges_and_keeps_deletions() {
+        let (a_dir, b_dir) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let (a, b) = (open(&a_dir), open(&b_dir));
+
+        let shared = a.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let only_a = a.store_memory("project", "Run clippy", &[]).unwrap().id;
+        b.store_memory("project", "Use pnpm", &[]).unwrap();
+        b.store_memory("project", "Use pnpm", &[]).unwrap();
+        let only_b = b.store_memory("preference", "No emojis", &[]).unwrap().id;
+
+        let report = sync(&a, &b).unwrap();
+        assert_eq!((report.pulled, report.pushed), (2, 1));
+        assert_eq!(a.get_memory(&shared).unwrap().unwrap().use_count, 2);
+        assert!(a.get_memory(&only_b).unwrap().is_some());
+        assert!(b.get_memory(&only_a).unwrap().is_some());
+
+        // A deletion propagates instead of being resurrected
+        a.delete_memory(&only_a).unwrap();
+        let report = sync(&b, &a).unwrap();
+        assert_eq!(report.deleted_local, 1);
+        assert!(b.get_memory(&only_a).unwrap().is_none());
+
+        // Converged: nothing left to do in either direction
+        let report = sync(&a, &b).unwrap();
+        assert_eq!(report.pulled + report.pushed + report.deleted_local, 0);
+        let report = sync(&b, &a).unwrap();
+        assert_eq!(report.pulled + report.pushed + report.deleted_peer, 0);
+    }
+
+    #[test]
+    fn test_private_memories_stay_local() {
+        let (a_dir, b_dir) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let (a, b) = (open(&a_dir), open(&b_dir));
+        let private = [format!("{}private", crate::storage::VISIBILITY_TAG_PREFIX)];
+        let id = a
+            .store_memory("project", "Staging password hint", &private)
+            .unwrap()
+            .id;
+        a.store_memory("project", "Use pnpm", &[]).unwrap();
+
+        let report = sync(&a, &b).unwrap();
+        assert_eq!((report.pushed, report.private), (1, 1));
+        assert!(b.get_memory(&id).unwrap().is_none());
+
+        // Made private after an earlier sync: the peer's copy is not refreshed either
+        b.store_memory("project", "Use pnpm", &[]).unwrap();
+        let shared = a
+            .list_all_memories()
+            .unwrap()
+            .into_iter()
+            .find(|m| m.content == "Use pnpm")
+            .unwrap();
+        let tags = Visibility::Private.apply_to(&shared.tags);
+        a.update_memory(&shared.id, None, None, Some(&tags))
+            .unwrap();
+        let report = sync(&a, &b).unwrap();
+        assert_eq!((report.pulled, report.pushed, report.private), (0, 0, 2));
+    }
+}
@@ -0,0 +1,182 @@
+//! Local usage analytics (`sqrl stats --usage`, CLI-023).
+//!
+//! Off unless `analytics.enabled` is set in the global config. Commands, git
+//! hook handlers and MCP tool calls are logged with how long they took, in
+//! the project database only: nothing is ever sent anywhere, and like
+//! `mcp_activity` the rows are left out of sync and share.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::storage::{db_path, Storage};
+
+/// What was timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageKind {
+    /// A `sqrl` command, by its subcommand path (`memory list`).
+    Command,
+    /// A git hook handler (`post-commit`).
+    Hook,
+    /// An MCP tool call (`squirrel_get_memory`).
+    Tool,
+}
+
+impl UsageKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            UsageKind::Command => "command",
+            UsageKind::Hook => "hook",
+            UsageKind::Tool => "tool",
+        }
+    }
+}
+
+/// Totals for one command, hook or tool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageStats {
+    /// `command`, `hook` or `tool`.
+    pub kind: String,
+    pub name: String,
+    pub count: i64,
+    pub avg_ms: i64,
+    pub max_ms: i64,
+    pub last_used: String,
+}
+
+/// Uses of one kind on one day, for charts.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageDay {
+    /// `YYYY-MM-DD`, UTC.
+    pub day: String,
+    pub kind: String,
+    pub count: i64,
+}
+
+impl Storage {
+    /// Log one use taking `duration`.
+    pub fn record_usage(
+        &self,
+        kind: UsageKind,
+        name: &str,
+        duration: Duration,
+    ) -> Result<(), Error> {
+        self.conn.execute(
+            "INSERT INTO usage (kind, name, duration_ms, at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                kind.as_str(),
+                name,
+                i64::try_from(duration.as_millis()).unwrap_or(i64::MAX),
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Totals per command, hook and tool, most used first within each kind.
+    pub fn usage_stats(&self) -> Result<Vec<UsageStats>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT kind, name, COUNT(*), CAST(AVG(duration_ms) AS INTEGER), MAX(duration_ms),
+                    MAX(at)
+             FROM usage GROUP BY kind, name ORDER BY kind, COUNT(*) DESC, name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(UsageStats {
+                kind: row.get(0)?,
+                name: row.get(1)?,
+                count: row.get(2)?,
+                avg_ms: row.get(3)?,
+                max_ms: row.get(4)?,
+                last_used: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+
+    /// Uses per day and kind, oldest first.
+    pub fn usage_by_day(&self) -> Result<Vec<UsageDay>, Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT substr(at, 1, 10) AS day, kind, COUNT(*) FROM usage
+             GROUP BY day, kind ORDER BY day, kind",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(UsageDay {
+                day: row.get(0)?,
+                kind: row.get(1)?,
+                count: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<SqliteResult<_>>()?)
+    }
+}
+
+/// Whether `analytics.enabled` is set in the global config.
+pub fn enabled() -> bool {
+    GlobalConfig::load()
+        .map(|config| config.analytics.enabled)
+        .unwrap_or(false)
+}
+
+/// Log a use in the project database if analytics are on and the project
+/// has one. Failures are logged, never surfaced.
+pub fn record(project_root: &Path, kind: UsageKind, name: &str, duration: Duration) {
+    let path = db_path(project_root);
+    if !path.exists() || !enabled() {
+        return;
+    }
+    let recorded =
+        Storage::open(&path).and_then(|storage| storage.record_usage(kind, name, duration));
+    if let Err(e) = recorded {
+        debug!(error = %e, "Could not record usage");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_usage_stats() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let ms = Duration::from_millis;
+        storage
+            .record_usage(UsageKind::Hook, "post-commit", ms(100))
+            .unwrap();
+        storage
+            .record_usage(UsageKind::Hook, "post-commit", ms(300))
+            .unwrap();
+        storage
+            .record_usage(UsageKind::Tool, "squirrel_get_memory", ms(5))
+            .unwrap();
+
+        let stats = storage.usage_stats().unwrap();
+        let rows: Vec<(&str, &str, i64, i64, i64)> = stats
+            .iter()
+            .map(|s| {
+                (
+                    s.kind.as_str(),
+                    s.name.as_str(),
+                    s.count,
+                    s.avg_ms,
+                    s.max_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("hook", "post-commit", 2, 200, 300),
+                ("tool", "squirrel_get_memory", 1, 5, 5)
+            ]
+        );
+        let days = storage.usage_by_day().unwrap();
+        assert_eq!(days.iter().map(|d| d.count).sum::<i64>(), 3);
+    }
+}
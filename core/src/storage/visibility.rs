@@ -0,0 +1,67 @@
+//! How far a memory may travel, kept as a tag.
+
+use serde::Serialize;
+
+use crate::storage::Memory;
+
+/// Tag prefix setting a memory's visibility ("visibility:private"). Without
+/// one, a memory is `team`.
+pub const VISIBILITY_TAG_PREFIX: &str = "visibility:";
+
+/// How far a memory may travel beyond this machine (SCHEMA-001).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Never synced, shared, or written into repo files.
+    Private,
+    /// Synced and shared with the team (the default).
+    Team,
+    /// Also fine to publish outside the team.
+    Public,
+}
+
+impl Visibility {
+    /// A memory's visibility. An unrecognized level counts as private.
+    pub fn of(memory: &Memory) -> Self {
+        Self::from_tags(&memory.tags)
+    }
+
+    /// Visibility recorded in a memory's tags.
+    pub fn from_tags(tags: &[String]) -> Self {
+        let level = tags
+            .iter()
+            .find_map(|t| t.strip_prefix(VISIBILITY_TAG_PREFIX));
+        match level {
+            None | Some("team") => Visibility::Team,
+            Some("public") => Visibility::Public,
+            Some(_) => Visibility::Private,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Team => "team",
+            Visibility::Public => "public",
+        }
+    }
+
+    /// `tags` with their visibility tag replaced by this level's (none for team).
+    pub fn apply_to(self, tags: &[String]) -> Vec<String> {
+        let mut tags: Vec<String> = tags
+            .iter()
+            .filter(|t| !t.starts_with(VISIBILITY_TAG_PREFIX))
+            .cloned()
+            .collect();
+        if self != Visibility::Team {
+            tags.push(format!("{}{}", VISIBILITY_TAG_PREFIX, self.as_str()));
+        }
+        tags
+    }
+
+    /// Whether the memory may leave this machine (sync, share, repo files).
+    pub fn shareable(memory: &Memory) -> bool {
+        Self::of(memory) != Visibility::Private
+    }
+}
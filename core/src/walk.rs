@@ -0,0 +1,83 @@
+//! Ignore-aware file walking for repo scans (WALK-001).
+//!
+//! Every scan of the project tree (doc discovery for docguard and doctor,
+//! doc proximity for MCP) goes through here, so paths listed in `.gitignore`,
+//! `.ignore` or a Squirrel-only `.sqrlignore` are skipped everywhere alike.
+//! Hidden files are kept; `.git` and `.sqrl` never are.
+
+use std::path::Path;
+
+use ignore::WalkBuilder;
+
+use crate::paths;
+
+/// Squirrel-specific ignore file, gitignore syntax.
+pub const SQRLIGNORE: &str = ".sqrlignore";
+
+/// Directories never walked.
+const SKIPPED_DIRS: &[&str] = &[".git", ".sqrl"];
+
+/// Files under `dir` (project-relative, `""` for the root), as sorted
+/// project-relative paths. `max_depth` 1 lists only `dir` itself.
+pub fn files(project_root: &Path, dir: &str, max_depth: Option<usize>) -> Vec<String> {
+    let walker = WalkBuilder::new(project_root.join(dir))
+        .hidden(false)
+        .require_git(false)
+        .max_depth(max_depth)
+        .add_custom_ignore_filename(SQRLIGNORE)
+        .filter_entry(|entry| {
+            !(entry.depth() > 0
+                && entry.file_type().is_some_and(|t| t.is_dir())
+                && SKIPPED_DIRS.iter().any(|d| entry.file_name() == *d))
+        })
+        .build();
+
+    let mut files: Vec<String> = walker
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(project_root).ok()?;
+            Some(paths::from_path(relative))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_files_skip_ignored() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for path in [
+            "README.md",
+            "docs/guide.md",
+            "docs/generated/api.md",
+            "target/notes.md",
+            ".github/CONTRIBUTING.md",
+            ".sqrl/config.yaml",
+        ] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::write(root.join(path), "").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::write(root.join(SQRLIGNORE), "docs/generated/\n").unwrap();
+
+        assert_eq!(
+            files(root, "", None),
+            [
+                ".github/CONTRIBUTING.md",
+                ".gitignore",
+                ".sqrlignore",
+                "README.md",
+                "docs/guide.md"
+            ]
+        );
+        assert_eq!(files(root, "docs", Some(1)), ["docs/guide.md"]);
+    }
+}
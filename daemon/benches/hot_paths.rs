@@ -0,0 +1,108 @@
+//! Benchmarks for the paths that run on every push and memory lookup.
+//!
+//! Run with `cargo bench -p sqrl --bench hot_paths`. `sqrl doctor --bench` measures the same
+//! paths in a real repository against their budgets.
+
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use git2::{Repository, Signature};
+use tempfile::TempDir;
+
+use sqrl::cli::git;
+use sqrl::config::matcher::DocMatcher;
+use sqrl::config::DocsConfig;
+use sqrl::storage;
+
+const MEMORIES: usize = 2_000;
+const CHANGED_FILES: usize = 200;
+
+fn commit_all(repo: &Repository, message: &str) {
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = Signature::now("bench", "bench@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap();
+}
+
+/// Repo with one pushed commit and one unpushed commit touching many files.
+fn push_fixture() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    fs::write(dir.path().join("README.md"), "# bench\n").unwrap();
+    commit_all(&repo, "base");
+    let base = repo.head().unwrap().target().unwrap();
+    repo.reference("refs/remotes/origin/main", base, true, "bench")
+        .unwrap();
+
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    for i in 0..CHANGED_FILES {
+        let content = format!("pub fn f{}() -> usize {{\n    {}\n}}\n", i, i);
+        fs::write(dir.path().join("src").join(format!("f{}.rs", i)), content).unwrap();
+    }
+    commit_all(&repo, "many files");
+    dir
+}
+
+fn memory_fixture() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for i in 0..MEMORIES {
+        let memory_type = if i % 2 == 0 { "project" } else { "preference" };
+        let content = format!("Memory {} about the build and test workflow", i);
+        storage::store_memory(
+            dir.path(),
+            memory_type,
+            &content,
+            &[format!("tag{}", i % 10)],
+        )
+        .unwrap();
+    }
+    dir
+}
+
+fn bench_docguard(c: &mut Criterion) {
+    let repo = push_fixture();
+    c.bench_function("git::push_range", |b| {
+        b.iter(|| git::push_range(repo.path()).unwrap())
+    });
+
+    let matcher = DocMatcher::new(&DocsConfig::default()).unwrap();
+    let paths: Vec<String> = (0..CHANGED_FILES)
+        .flat_map(|i| [format!("src/f{}.rs", i), format!("docs/f{}.md", i)])
+        .collect();
+    c.bench_function("DocMatcher::is_doc", |b| {
+        b.iter(|| paths.iter().filter(|p| matcher.is_doc(p)).count())
+    });
+}
+
+fn bench_memories(c: &mut Criterion) {
+    let project = memory_fixture();
+    let root: &Path = project.path();
+    c.bench_function("storage::visit_memories", |b| {
+        b.iter(|| {
+            let mut count = 0;
+            storage::visit_memories(root, None, |_| {
+                count += 1;
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            count
+        })
+    });
+    c.bench_function("storage::format_memories_as_markdown", |b| {
+        b.iter(|| {
+            storage::format_memories_as_markdown(root, &[], None, Some(50), None, None, &[])
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_docguard, bench_memories);
+criterion_main!(benches);
@@ -1,61 +1,403 @@
 //! Apply global MCP configs to current project (CLI-004).
 
+use std::path::PathBuf;
 use std::process::Command;
 
 use tracing::{info, warn};
 
-use crate::error::Error;
-use crate::global_config::GlobalConfig;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cli::context;
+use crate::cli::output::{self, human};
+use crate::cli::targets;
+use crate::config::{find_project_root, Config};
+use crate::error::{Error, ExitCode};
+use crate::global_config::managed::{
+    escape_pointer, read_pointer, DriftKind, ManagedEntry, Manifest,
+};
+use crate::global_config::{template, GlobalConfig, McpConfig, Transport};
+use crate::lock::SqrlLock;
+
+/// What `sqrl apply` registered, per tool.
+#[derive(Debug, Default, Serialize)]
+pub struct ApplyReport {
+    pub profile: Option<String>,
+    pub claude_code: Vec<String>,
+    #[serde(rename = "continue")]
+    pub continue_dev: Vec<String>,
+    pub aider: Option<String>,
+    /// Context files whose pinned-memory section changed (`context.section`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub context: Vec<String>,
+}
+
+/// Where one MCP stands in one tool (`sqrl apply --check`).
+#[derive(Debug, Serialize)]
+pub struct EntryState {
+    /// `claude_code` or `continue`.
+    pub tool: String,
+    pub name: String,
+    /// `applied`, `modified`, `removed`, `unmanaged`, `missing`, `unresolved` or `stale`.
+    pub state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+impl EntryState {
+    /// Whether `sqrl apply` (or `sqrl unapply`, for stale entries) would change it.
+    fn out_of_date(&self) -> bool {
+        !matches!(self.state, "applied" | "unmanaged")
+    }
+}
 
 /// Run the apply command.
 pub fn run() -> Result<(), Error> {
+    let mut report = apply()?;
+    report.context = refresh_context()?;
+    output::emit(&report)
+}
+
+/// Compare what `sqrl apply` would register against the managed entries
+/// (MANAGED-001) without writing anything. Returns exit code.
+pub fn check() -> Result<i32, Error> {
+    if !GlobalConfig::exists() {
+        human!("Global config not found. Run 'sqrl config' first.");
+        return output::emit(&json!({ "ok": true, "entries": [] })).map(|()| 0);
+    }
+    let config = GlobalConfig::load()?;
+    let mcps = selected_mcps(&config)?;
+    let config = config.with_profile();
+    let manifest = Manifest::load()?;
+    let cwd = std::env::current_dir()?;
+
+    let mut states = Vec::new();
+    let mut tools = Vec::new();
+    if config.tools.claude_code {
+        tools.push("claude_code");
+        for mcp in &mcps {
+            let (file, pointer) = claude_entry_location(mcp)?;
+            let entry = manifest
+                .entries
+                .iter()
+                .find(|e| e.tool == "claude_code" && e.file == file && e.pointer == pointer);
+            let state = match launch(mcp) {
+                Err(_) => "unresolved",
+                Ok(_) => ledger_state(entry, read_pointer(&file, &pointer).is_some()),
+            };
+            states.push(EntryState {
+                tool: "claude_code".to_string(),
+                name: mcp.name.clone(),
+                state,
+                file: Some(file),
+            });
+        }
+    }
+    if config.tools.continue_dev {
+        tools.push("continue");
+        let file = targets::continue_config_path()?;
+        for mcp in &targets::continue_mcps(&mcps) {
+            let entry = manifest
+                .entries
+                .iter()
+                .find(|e| e.tool == "continue" && e.name == mcp.name);
+            let state = match launch(mcp) {
+                Err(_) => "unresolved",
+                Ok(launch) => ledger_state(entry, targets::continue_registered(&launch)),
+            };
+            states.push(EntryState {
+                tool: "continue".to_string(),
+                name: mcp.name.clone(),
+                state,
+                file: Some(file.clone()),
+            });
+        }
+    }
+
+    // Applied here or globally, but no longer selected
+    let continue_mcps = targets::continue_mcps(&mcps);
+    for entry in &manifest.entries {
+        let here = entry.project.as_ref().is_none_or(|p| *p == cwd);
+        let selected = match entry.tool.as_str() {
+            "continue" => &continue_mcps,
+            _ => &mcps,
+        }
+        .iter()
+        .any(|m| m.name == entry.name);
+        if here && !selected && tools.contains(&entry.tool.as_str()) {
+            states.push(EntryState {
+                tool: entry.tool.clone(),
+                name: entry.name.clone(),
+                state: "stale",
+                file: Some(entry.file.clone()),
+            });
+        }
+    }
+
+    for state in &states {
+        let mark = if state.out_of_date() { "!" } else { "=" };
+        let tool = if state.tool == "continue" {
+            "Continue"
+        } else {
+            "Claude Code"
+        };
+        human!("  {} {} ({}): {}", mark, state.name, tool, state.state);
+    }
+    let out_of_date = states.iter().filter(|s| s.out_of_date()).count();
+    if states.is_empty() {
+        human!("Nothing to apply.");
+    } else if out_of_date == 0 {
+        human!("Up to date.");
+    } else {
+        human!();
+        human!(
+            "{} out of date. Run 'sqrl apply', or 'sqrl unapply' for stale entries.",
+            out_of_date
+        );
+    }
+    output::emit(&json!({ "ok": out_of_date == 0, "entries": states }))?;
+    Ok(if out_of_date == 0 {
+        0
+    } else {
+        ExitCode::Problems.code()
+    })
+}
+
+/// State of an MCP from its managed entry, or whether the tool has it anyway.
+fn ledger_state(entry: Option<&ManagedEntry>, present: bool) -> &'static str {
+    match entry.map(ManagedEntry::drift) {
+        Some(None) => "applied",
+        Some(Some(DriftKind::Modified)) => "modified",
+        Some(Some(DriftKind::Removed)) => "removed",
+        None if present => "unmanaged",
+        None => "missing",
+    }
+}
+
+/// Refresh the current project's pinned-memory section, if it keeps one.
+fn refresh_context() -> Result<Vec<String>, Error> {
+    let Some(project_root) = find_project_root(&std::env::current_dir()?) else {
+        return Ok(Vec::new());
+    };
+    let config = Config::effective(&project_root)?;
+    if !config.context.section {
+        return Ok(Vec::new());
+    }
+    let _lock = SqrlLock::project(&project_root)?;
+    let files = context::refresh(&project_root, &config)?;
+    for file in &files {
+        human!("Pinned memories refreshed in {}.", file);
+    }
+    Ok(files)
+}
+
+/// Apply enabled MCP configs to the enabled tools.
+pub fn apply() -> Result<ApplyReport, Error> {
+    let mut report = ApplyReport::default();
+
     // Load global config
     if !GlobalConfig::exists() {
-        println!("Global config not found. Run 'sqrl config' first.");
-        return Ok(());
+        human!("Global config not found. Run 'sqrl config' first.");
+        return Ok(report);
     }
 
+    let _lock = SqrlLock::global()?;
     let config = GlobalConfig::load()?;
-    let mcps = GlobalConfig::list_mcps()?;
+    let mcps = selected_mcps(&config)?;
+    if let Some((name, _)) = config.active_profile() {
+        human!("Using profile '{}'.", name);
+        report.profile = Some(name.to_string());
+    }
+    let config = config.with_profile();
 
     if mcps.is_empty() {
-        println!("No MCP configs found in ~/.sqrl/mcps/");
-        return Ok(());
+        human!("No MCP configs found in ~/.sqrl/mcps/");
+        // Continue still gets Squirrel's own server
+        if !config.tools.continue_dev {
+            return Ok(report);
+        }
     }
 
-    println!("Applying MCP configs...");
+    human!("Applying MCP configs...");
+
+    let mut manifest = Manifest::load()?;
 
     let mut applied_claude = Vec::new();
+    let mut applied_continue = Vec::new();
+    let mut applied_aider = false;
 
     // Apply to Claude Code if enabled
     if config.tools.claude_code {
         for mcp in &mcps {
-            if apply_to_claude_code(mcp)? {
+            if apply_to_claude_code(mcp, &mut manifest)? {
                 applied_claude.push(mcp.name.clone());
             }
         }
     }
 
+    // Apply to Continue if enabled
+    if config.tools.continue_dev {
+        let mut launchable = Vec::new();
+        for mcp in &targets::continue_mcps(&mcps) {
+            match launch(mcp) {
+                Ok(launch) => launchable.push((mcp.clone(), launch)),
+                Err(e) => human!("  ! {} (failed: {})", mcp.name, e),
+            }
+        }
+        match targets::apply_to_continue(&launchable, &mut manifest) {
+            Ok(names) => applied_continue = names,
+            Err(e) => {
+                warn!(error = %e, "Failed to update Continue config");
+                human!("  ! Continue (failed: {})", e);
+            }
+        }
+    }
+
+    // Aider has no MCP support: generate a conventions file instead
+    if config.tools.aider {
+        if let Some(project_root) = find_project_root(&std::env::current_dir()?) {
+            targets::apply_to_aider(&project_root)?;
+            applied_aider = true;
+        } else {
+            human!("  - Aider skipped (project not initialized)");
+        }
+    }
+
     // Git hooks are installed separately by sqrl init
     if config.tools.git {
         info!("Git hooks are installed by 'sqrl init'");
     }
 
+    manifest.save()?;
+
     // Print summary
-    println!();
-    println!("Applied MCP configs:");
+    human!();
+    human!("Applied MCP configs:");
     if !applied_claude.is_empty() {
-        println!("  Claude Code: {}", applied_claude.join(", "));
+        human!("  Claude Code: {}", applied_claude.join(", "));
+    }
+    if !applied_continue.is_empty() {
+        human!("  Continue: {}", applied_continue.join(", "));
+    }
+    if applied_aider {
+        human!("  Aider: {}", targets::AIDER_CONVENTIONS);
     }
     if config.tools.git {
-        println!("  Git: (hooks managed by sqrl init)");
+        human!("  Git: (hooks managed by sqrl init)");
     }
 
-    Ok(())
+    report.claude_code = applied_claude;
+    report.continue_dev = applied_continue;
+    if applied_aider {
+        report.aider = Some(targets::AIDER_CONVENTIONS.to_string());
+    }
+    Ok(report)
+}
+
+/// MCP configs to apply: all of them, or those the active profile selects.
+pub fn selected_mcps(config: &GlobalConfig) -> Result<Vec<McpConfig>, Error> {
+    let mut mcps = GlobalConfig::list_mcps()?;
+    if let Some((_, profile)) = config.active_profile() {
+        if let Some(selected) = &profile.mcps {
+            mcps.retain(|mcp| selected.contains(&mcp.name));
+        }
+    }
+    Ok(mcps)
+}
+
+/// Whether tools launch an MCP through `sqrl _internal mcp-exec`: to resolve
+/// placeholders at serve time, or to start it in `cwd`, which no tool config
+/// can express.
+fn via_exec(mcp: &McpConfig) -> bool {
+    template::has_placeholders(mcp) || mcp.cwd.is_some()
+}
+
+/// How a tool should reach an MCP server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Launch {
+    /// Command line to launch, with its environment sorted by name.
+    /// The environment is empty when `mcp-exec` sets it instead.
+    Command {
+        command: Vec<String>,
+        env: Vec<(String, String)>,
+    },
+    /// Remote server to connect to.
+    Remote { transport: Transport, url: String },
+}
+
+/// Work out how a tool should reach an MCP.
+///
+/// Configs with placeholders are launched through `sqrl _internal mcp-exec`,
+/// which resolves them at serve time. Resolution is checked here first so
+/// missing values fail at apply time with a clear error.
+pub fn launch(mcp: &McpConfig) -> Result<Launch, Error> {
+    if let Some(url) = &mcp.url {
+        return Ok(Launch::Remote {
+            transport: mcp.transport,
+            url: url.clone(),
+        });
+    }
+    if via_exec(mcp) {
+        template::resolve_mcp(mcp)?;
+        return Ok(Launch::Command {
+            command: vec![
+                "sqrl".to_string(),
+                "_internal".to_string(),
+                "mcp-exec".to_string(),
+                mcp.name.clone(),
+            ],
+            env: Vec::new(),
+        });
+    }
+
+    let mut command = vec![mcp.command.clone()];
+    command.extend(mcp.args.clone());
+    let mut env: Vec<(String, String)> = mcp
+        .env
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    env.sort();
+    Ok(Launch::Command { command, env })
+}
+
+/// Locate where Claude Code stores an MCP entry: (file, JSON pointer).
+pub fn claude_entry_location(mcp: &McpConfig) -> Result<(std::path::PathBuf, String), Error> {
+    let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+    let cwd = std::env::current_dir()?;
+    let name = escape_pointer(&mcp.name);
+
+    Ok(match mcp.scope.as_str() {
+        "project" => (cwd.join(".mcp.json"), format!("/mcpServers/{}", name)),
+        "user" => (home.join(".claude.json"), format!("/mcpServers/{}", name)),
+        _ => (
+            home.join(".claude.json"),
+            format!(
+                "/projects/{}/mcpServers/{}",
+                escape_pointer(&cwd.to_string_lossy()),
+                name
+            ),
+        ),
+    })
+}
+
+/// `args` for display, with each `-e KEY=value` shown as `-e KEY=***` so
+/// secrets stay out of errors and logs.
+fn redacted(args: &[String]) -> String {
+    let mut shown = Vec::with_capacity(args.len());
+    let mut env_next = false;
+    for arg in args {
+        match arg.split_once('=') {
+            Some((key, _)) if env_next => shown.push(format!("{}=***", key)),
+            _ => shown.push(arg.clone()),
+        }
+        env_next = arg == "-e";
+    }
+    shown.join(" ")
 }
 
 /// Apply an MCP config to Claude Code.
-fn apply_to_claude_code(mcp: &crate::global_config::McpConfig) -> Result<bool, Error> {
+fn apply_to_claude_code(mcp: &McpConfig, manifest: &mut Manifest) -> Result<bool, Error> {
     // Check if claude CLI exists
     let which = Command::new("which").arg("claude").output();
     if which.is_err() || !which.unwrap().status.success() {
@@ -63,6 +405,15 @@ fn apply_to_claude_code(mcp: &crate::global_config::McpConfig) -> Result<bool, E
         return Ok(false);
     }
 
+    let launch = match launch(mcp) {
+        Ok(launch) => launch,
+        Err(e) => {
+            warn!(name = %mcp.name, error = %e, "Unresolved MCP placeholders");
+            human!("  ! {} (failed: {})", mcp.name, e);
+            return Ok(false);
+        }
+    };
+
     // Build command args
     let mut args = vec![
         "mcp".to_string(),
@@ -70,28 +421,229 @@ fn apply_to_claude_code(mcp: &crate::global_config::McpConfig) -> Result<bool, E
         mcp.name.clone(),
         "-s".to_string(),
         mcp.scope.clone(),
-        "--".to_string(),
-        mcp.command.clone(),
     ];
-    args.extend(mcp.args.clone());
+    match &launch {
+        Launch::Command { command, env } => {
+            // Visible in `ps` while claude runs; placeholders avoid this (CLI-004)
+            for (key, value) in env {
+                args.push("-e".to_string());
+                args.push(format!("{}={}", key, value));
+            }
+            args.push("--".to_string());
+            args.extend(command.iter().cloned());
+        }
+        Launch::Remote { transport, url } => {
+            args.push("--transport".to_string());
+            args.push(transport.as_str().to_string());
+            args.push(url.clone());
+        }
+    }
 
-    let output = Command::new("claude").args(&args).output()?;
+    let (file, pointer) = claude_entry_location(mcp)?;
+    let mut output = run_claude(&args)?;
+    let mut updated = false;
+    if !output.status.success()
+        && String::from_utf8_lossy(&output.stderr).contains("already exists")
+    {
+        if read_pointer(&file, &pointer).is_some_and(|entry| claude_entry_matches(&entry, &launch))
+        {
+            info!(name = %mcp.name, "MCP already registered with Claude Code");
+            human!("  = {} (already registered)", mcp.name);
+            record_claude_entry(mcp, manifest, &file, &pointer)?;
+            return Ok(true);
+        }
+        // Registered with other settings, e.g. an older env: register it again
+        let remove = ["mcp", "remove", &mcp.name, "-s", &mcp.scope].map(String::from);
+        run_claude(&remove)?;
+        output = run_claude(&args)?;
+        updated = true;
+    }
 
     if output.status.success() {
-        info!(name = %mcp.name, "Registered MCP with Claude Code");
-        println!("  + {} (Claude Code)", mcp.name);
+        info!(name = %mcp.name, updated, "Registered MCP with Claude Code");
+        if updated {
+            human!("  ~ {} (Claude Code, updated)", mcp.name);
+        } else {
+            human!("  + {} (Claude Code)", mcp.name);
+        }
+        record_claude_entry(mcp, manifest, &file, &pointer)?;
         Ok(true)
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        // Check if already exists (not a failure)
-        if stderr.contains("already exists") {
-            info!(name = %mcp.name, "MCP already registered with Claude Code");
-            println!("  = {} (already registered)", mcp.name);
-            Ok(true)
-        } else {
-            warn!(name = %mcp.name, stderr = %stderr, "Failed to register MCP");
-            println!("  ! {} (failed: {})", mcp.name, stderr.trim());
-            Ok(false)
+        warn!(name = %mcp.name, stderr = %stderr, "Failed to register MCP");
+        human!("  ! {} (failed: {})", mcp.name, stderr.trim());
+        Ok(false)
+    }
+}
+
+/// Run `claude` with `args`.
+fn run_claude(args: &[String]) -> Result<std::process::Output, Error> {
+    Command::new("claude")
+        .args(args)
+        .output()
+        .map_err(|e| Error::Command {
+            command: format!("claude {}", redacted(args)),
+            stderr: e.to_string(),
+        })
+}
+
+/// Record Claude Code's entry for `mcp` as managed, for `sqrl unapply` and
+/// drift checks.
+fn record_claude_entry(
+    mcp: &McpConfig,
+    manifest: &mut Manifest,
+    file: &std::path::Path,
+    pointer: &str,
+) -> Result<(), Error> {
+    let project = match mcp.scope.as_str() {
+        "user" => None,
+        _ => Some(std::env::current_dir()?),
+    };
+    manifest.record("claude_code", &mcp.name, file, pointer, project.as_deref());
+    Ok(())
+}
+
+/// Whether Claude Code's `entry` launches or reaches the server as `launch`
+/// says: same command line and environment, or same URL.
+fn claude_entry_matches(entry: &serde_json::Value, launch: &Launch) -> bool {
+    let field = |name: &str| entry.get(name).filter(|value| !value.is_null());
+    match launch {
+        Launch::Command { command, env } => {
+            let args = field("args").cloned().unwrap_or_else(|| json!([]));
+            let registered_env = field("env").cloned().unwrap_or_else(|| json!({}));
+            let env: serde_json::Map<String, serde_json::Value> = env
+                .iter()
+                .map(|(key, value)| (key.clone(), json!(value)))
+                .collect();
+            field("command") == command.first().map(|c| json!(c)).as_ref()
+                && args == json!(command[1..])
+                && registered_env == serde_json::Value::Object(env)
         }
+        Launch::Remote { url, .. } => field("url") == Some(&json!(url)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_env_and_cwd_reach_the_launch() {
+        let mut mcp = McpConfig {
+            name: "db".to_string(),
+            command: "db-mcp".to_string(),
+            args: vec!["--read-only".to_string()],
+            env: [("B", "2"), ("A", "1")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            scope: "project".to_string(),
+            cwd: None,
+            transport: Transport::Stdio,
+            url: None,
+        };
+        assert_eq!(
+            launch(&mcp).unwrap(),
+            Launch::Command {
+                command: vec!["db-mcp".to_string(), "--read-only".to_string()],
+                env: vec![
+                    ("A".to_string(), "1".to_string()),
+                    ("B".to_string(), "2".to_string())
+                ],
+            }
+        );
+
+        // No tool config can set a working directory, so mcp-exec does
+        mcp.cwd = Some("services/db".to_string());
+        assert_eq!(
+            launch(&mcp).unwrap(),
+            Launch::Command {
+                command: ["sqrl", "_internal", "mcp-exec", "db"]
+                    .map(String::from)
+                    .to_vec(),
+                env: Vec::new(),
+            }
+        );
+
+        let remote = McpConfig {
+            name: "docs".to_string(),
+            command: String::new(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            scope: "user".to_string(),
+            cwd: None,
+            transport: Transport::Sse,
+            url: Some("https://mcp.example.com/sse".to_string()),
+        };
+        assert_eq!(
+            launch(&remote).unwrap(),
+            Launch::Remote {
+                transport: Transport::Sse,
+                url: "https://mcp.example.com/sse".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_redacted_hides_env_values() {
+        let args = [
+            "mcp",
+            "add",
+            "db",
+            "-e",
+            "TOKEN=s3cret",
+            "--",
+            "db-mcp",
+            "--x=1",
+        ]
+        .map(String::from);
+        assert_eq!(redacted(&args), "mcp add db -e TOKEN=*** -- db-mcp --x=1");
+    }
+
+    #[test]
+    fn test_claude_entry_matches_changed_env_false() {
+        let launch = Launch::Command {
+            command: vec!["db-mcp".to_string(), "--read-only".to_string()],
+            env: vec![("TOKEN".to_string(), "new".to_string())],
+        };
+        let entry = |token: &str| {
+            json!({
+                "type": "stdio",
+                "command": "db-mcp",
+                "args": ["--read-only"],
+                "env": {"TOKEN": token},
+            })
+        };
+        assert!(claude_entry_matches(&entry("new"), &launch));
+        assert!(!claude_entry_matches(&entry("old"), &launch));
+
+        let remote = Launch::Remote {
+            transport: Transport::Http,
+            url: "https://mcp.example.com/mcp".to_string(),
+        };
+        assert!(claude_entry_matches(
+            &json!({"type": "http", "url": "https://mcp.example.com/mcp"}),
+            &remote
+        ));
+        assert!(!claude_entry_matches(&entry("new"), &remote));
+    }
+
+    #[test]
+    fn test_apply_report_json_names_tools_skips_empty_context() {
+        let report = ApplyReport {
+            claude_code: vec!["db".to_string()],
+            continue_dev: vec!["squirrel".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "profile": null,
+                "claude_code": ["db"],
+                "continue": ["squirrel"],
+                "aider": null,
+            })
+        );
     }
 }
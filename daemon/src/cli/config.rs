@@ -0,0 +1,313 @@
+//! Config inspection and editing commands (CLI-011).
+
+use serde_yaml::Value;
+
+use crate::atomic;
+use crate::cli::output::{self, human};
+use crate::config::format::ConfigFormat;
+use crate::config::migrate::{self, Versioned};
+use crate::config::validate::type_name;
+use crate::config::{project_root, resolve, Config};
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::lock::SqrlLock;
+
+/// Show the effective project configuration.
+/// With `resolved`, print every key with the layer it came from.
+pub fn show(resolved: bool) -> Result<(), Error> {
+    let project_root = project_root()?;
+    let result = resolve::resolve(&project_root)?;
+
+    if resolved {
+        if output::is_json() {
+            let sources: serde_json::Map<String, serde_json::Value> = result
+                .sources
+                .iter()
+                .map(|(key, (value, source))| {
+                    let entry = serde_json::json!({ "value": value, "source": source.to_string() });
+                    (key.clone(), entry)
+                })
+                .collect();
+            return output::emit(&sources);
+        }
+        for (key, (value, source)) in &result.sources {
+            println!("{} = {}  # {}", key, serde_json::to_string(value)?, source);
+        }
+    } else {
+        let mut config = result.config;
+        config.internal = None;
+        if output::is_json() {
+            return output::emit(&config);
+        }
+        let yaml = serde_yaml::to_string(&config).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        print!("{}", yaml);
+    }
+
+    Ok(())
+}
+
+/// Print a config value. Project keys show the effective value.
+pub fn get(key: &str, global: bool) -> Result<(), Error> {
+    let tree = if global {
+        to_value(&GlobalConfig::load()?)?
+    } else {
+        to_value(&resolve::resolve(&project_root()?)?.config)?
+    };
+
+    let value = lookup(&tree, key)
+        .ok_or_else(|| Error::ConfigParse(format!("Unknown config key '{}'", key)))?;
+    if output::is_json() {
+        return output::emit(value);
+    }
+    match value {
+        Value::String(s) => println!("{}", s),
+        Value::Mapping(_) => print!(
+            "{}",
+            serde_yaml::to_string(value).map_err(|e| Error::ConfigParse(e.to_string()))?
+        ),
+        other => println!("{}", serde_json::to_string(other)?),
+    }
+    Ok(())
+}
+
+/// Print JSON Schema for the project or global config.
+pub fn schema(global: bool) -> Result<(), Error> {
+    let schema = if global {
+        schemars::schema_for!(GlobalConfig)
+    } else {
+        schemars::schema_for!(Config)
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+/// Set a config value, validated against the schema.
+/// With `local`, write only this key to the machine-local overlay.
+pub fn set(key: &str, raw: &str, global: bool, local: bool) -> Result<(), Error> {
+    let value = resolve::parse_scalar(raw);
+    if key == "version" {
+        return Err(Error::ConfigParse(
+            "'version' is managed by sqrl; use 'sqrl config migrate'".to_string(),
+        ));
+    }
+
+    let _lock = if global {
+        SqrlLock::global()?
+    } else {
+        let project_root = project_root()?;
+        if !Config::exists(&project_root) {
+            return Err(Error::ConfigNotFound(Config::path(&project_root)));
+        }
+        SqrlLock::project(&project_root)?
+    };
+    if global {
+        GlobalConfig::init()?;
+        let mut tree = to_value(&GlobalConfig::load()?)?;
+        check_type(&to_value(&GlobalConfig::default())?, key, &value)?;
+        resolve::set_path(&mut tree, key, value);
+        let config: GlobalConfig = from_value(tree, key)?;
+        config.save()?;
+    } else if local {
+        let project_root = project_root()?;
+        let mut tree = resolve::local_tree(&project_root)?
+            .unwrap_or_else(|| Value::Mapping(Default::default()));
+        check_type(&to_value(&Config::default())?, key, &value)?;
+        resolve::set_path(&mut tree, key, value);
+        from_value::<Config>(tree.clone(), key)?.check()?;
+        atomic::write(
+            &Config::local_path(&project_root),
+            serde_json::to_string_pretty(&tree)?,
+        )?;
+    } else {
+        let project_root = project_root()?;
+        let mut tree = to_value(&Config::load(&project_root)?)?;
+        check_type(&to_value(&Config::default())?, key, &value)?;
+        resolve::set_path(&mut tree, key, value);
+        let config: Config = from_value(tree, key)?;
+        config.check()?;
+        config.save(&project_root)?;
+    }
+
+    human!("{} = {}", key, raw);
+    Ok(())
+}
+
+/// Convert the project config file to another format.
+pub fn convert(to: ConfigFormat) -> Result<(), Error> {
+    let project_root = project_root()?;
+    let from = ConfigFormat::detect(&project_root)?
+        .ok_or_else(|| Error::ConfigNotFound(Config::path(&project_root)))?;
+
+    if from == to {
+        human!("Config is already {}.", to.file_name());
+        return Ok(());
+    }
+    let _lock = SqrlLock::project(&project_root)?;
+
+    let config = Config::load(&project_root)?;
+    config.save_as(&project_root, to)?;
+    std::fs::remove_file(from.path(&project_root))?;
+    human!(
+        "Converted .sqrl/{} to .sqrl/{}",
+        from.file_name(),
+        to.file_name()
+    );
+    Ok(())
+}
+
+/// Select the active global profile, clear it, or list profiles.
+pub fn use_profile(name: Option<&str>, none: bool) -> Result<(), Error> {
+    GlobalConfig::init()?;
+    let _lock = SqrlLock::global()?;
+    let mut config = GlobalConfig::load()?;
+
+    if none {
+        config.profile = None;
+        config.save()?;
+        human!("No profile active.");
+        return Ok(());
+    }
+
+    let Some(name) = name else {
+        if config.profiles.is_empty() {
+            human!("No profiles defined. Add them under 'profiles:' in ~/.sqrl/config.yaml.");
+        }
+        for profile in config.profiles.keys() {
+            let marker = if config.profile.as_ref() == Some(profile) {
+                "*"
+            } else {
+                " "
+            };
+            human!("{} {}", marker, profile);
+        }
+        return Ok(());
+    };
+
+    if !config.profiles.contains_key(name) {
+        let known: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+        return Err(Error::ConfigParse(format!(
+            "Unknown profile '{}' (defined: {})",
+            name,
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        )));
+    }
+    config.profile = Some(name.to_string());
+    config.save()?;
+    human!(
+        "Using profile '{}'. Run 'sqrl apply' to update tool configs.",
+        name
+    );
+    Ok(())
+}
+
+/// Rewrite a config file in the current layout version.
+pub fn migrate(global: bool) -> Result<(), Error> {
+    let project_root = project_root()?;
+    let (path, format, current) = if global {
+        (
+            GlobalConfig::path()?,
+            ConfigFormat::Yaml,
+            GlobalConfig::VERSION,
+        )
+    } else {
+        let format = ConfigFormat::detect(&project_root)?
+            .ok_or_else(|| Error::ConfigNotFound(Config::path(&project_root)))?;
+        (format.path(&project_root), format, Config::VERSION)
+    };
+    if !path.exists() {
+        return Err(Error::ConfigNotFound(path));
+    }
+    let _lock = if global {
+        SqrlLock::global()?
+    } else {
+        SqrlLock::project(&project_root)?
+    };
+
+    let tree: Value = format.parse(&std::fs::read_to_string(&path)?)?;
+    let from = migrate::version_of(&tree);
+    if from >= current {
+        human!("{} is already at version {}.", path.display(), from);
+        return Ok(());
+    }
+
+    if global {
+        GlobalConfig::load()?.save()?;
+    } else {
+        Config::load(&project_root)?.save(&project_root)?;
+    }
+    human!(
+        "Migrated {} from version {} to {}",
+        path.display(),
+        from,
+        current
+    );
+    Ok(())
+}
+
+/// Check a new value has the same type as the schema default for `key`.
+fn check_type(schema: &Value, key: &str, value: &Value) -> Result<(), Error> {
+    let expected = lookup(schema, key)
+        .filter(|v| !v.is_mapping())
+        .ok_or_else(|| Error::ConfigParse(format!("Unknown config key '{}'", key)))?;
+
+    if type_name(expected) != type_name(value) {
+        return Err(Error::ConfigParse(format!(
+            "'{}' expects {}, got {}",
+            key,
+            type_name(expected),
+            type_name(value)
+        )));
+    }
+    Ok(())
+}
+
+fn lookup<'a>(tree: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(tree, |node, part| node.get(part))
+}
+
+fn to_value<T: serde::Serialize>(config: &T) -> Result<Value, Error> {
+    serde_yaml::to_value(config).map_err(|e| Error::ConfigParse(e.to_string()))
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(tree: Value, key: &str) -> Result<T, Error> {
+    serde_yaml::from_value(tree)
+        .map_err(|e| Error::ConfigParse(format!("Invalid value for '{}': {}", key, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(key: &str, raw: &str) -> Result<(), Error> {
+        let schema = to_value(&Config::default()).unwrap();
+        check_type(&schema, key, &resolve::parse_scalar(raw))
+    }
+
+    #[test]
+    fn test_check_type_bool_key_non_bool_rejected() {
+        assert!(check("hooks.pre_push_block", "true").is_ok());
+        let err = check("hooks.pre_push_block", "sometimes").unwrap_err();
+        assert!(err.to_string().contains("'hooks.pre_push_block' expects"));
+        assert!(check("hooks.pre_push_block", "1").is_err());
+    }
+
+    #[test]
+    fn test_check_type_unknown_key_rejected() {
+        let err = check("hooks.no_such_key", "true").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown config key 'hooks.no_such_key'"));
+        assert!(check("no_such_section.key", "true").is_err());
+    }
+
+    #[test]
+    fn test_check_type_nested_key_accepted_section_rejected() {
+        assert!(check("hooks.snapshots", "false").is_ok());
+        // A section is not a value that can be set
+        assert!(check("hooks", "true").is_err());
+    }
+}
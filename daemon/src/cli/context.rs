@@ -0,0 +1,183 @@
+//! Pinned memories kept in agent context files (`context.section`, CLI-024).
+//!
+//! Squirrel owns only the text between its markers; the rest of each file is
+//! left as written. Tools that never call MCP still read current rules.
+
+use std::fs;
+use std::path::Path;
+
+use crate::atomic;
+use crate::config::Config;
+use crate::error::Error;
+use crate::storage::{self, Memory, Storage, Visibility};
+
+/// Tag that puts a memory in the section (`sqrl memory pin`).
+pub const PIN_TAG: &str = "pinned";
+
+const START: &str = "<!-- START Squirrel Pinned Memories -->";
+const END: &str = "<!-- END Squirrel Pinned Memories -->";
+
+/// Rewrite the section in every `context.files` entry. The caller holds the
+/// project lock. Returns the files that changed.
+pub fn refresh(project_root: &Path, config: &Config) -> Result<Vec<String>, Error> {
+    let db = storage::db_path(project_root);
+    let mut pinned = if db.exists() {
+        Storage::open(&db)?.list_all_memories()?
+    } else {
+        Vec::new()
+    };
+    // The files are committed, so private memories stay out
+    pinned.retain(|m| m.tags.iter().any(|t| t == PIN_TAG) && Visibility::shareable(m));
+    // Oldest first, so pinning one more memory adds one line to the diff
+    pinned.sort_by(|a, b| (&a.created_at, &a.id).cmp(&(&b.created_at, &b.id)));
+
+    let block = render(&pinned);
+    rewrite(project_root, config, Some(&block))
+}
+
+/// Take the section out of every `context.files` entry (goaway). Returns the
+/// files that changed.
+pub fn remove(project_root: &Path, config: &Config) -> Result<Vec<String>, Error> {
+    rewrite(project_root, config, None)
+}
+
+fn rewrite(
+    project_root: &Path,
+    config: &Config,
+    block: Option<&str>,
+) -> Result<Vec<String>, Error> {
+    config.context.check()?;
+    let mut changed = Vec::new();
+    for file in &config.context.files {
+        let path = project_root.join(file);
+        confine(project_root, &path)?;
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && block.is_some() => String::new(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(Error::at(&path)(e)),
+        };
+        let updated = replace_section(&content, block);
+        if updated != content {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            atomic::write(&path, updated)?;
+            changed.push(file.clone());
+        }
+    }
+    Ok(changed)
+}
+
+/// Refuse `path` if it resolves outside the project, e.g. through a
+/// symlinked file or directory. A missing file is judged by its nearest
+/// existing ancestor.
+fn confine(project_root: &Path, path: &Path) -> Result<(), Error> {
+    let root = project_root
+        .canonicalize()
+        .map_err(Error::at(project_root))?;
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(project_root);
+    let target = existing.canonicalize().map_err(Error::at(existing))?;
+    if !target.starts_with(&root) {
+        return Err(Error::ConfigParse(format!(
+            "context.files: '{}' resolves outside the project",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// The marked section listing `memories`.
+fn render(memories: &[Memory]) -> String {
+    let mut block = format!(
+        "{}\n## Pinned Memories\n\nMaintained by Squirrel from memories tagged `{}`; \
+         change them with `sqrl memory pin` / `unpin`, not here.\n\n",
+        START, PIN_TAG
+    );
+    if memories.is_empty() {
+        block.push_str("No memories are pinned.\n");
+    }
+    for memory in memories {
+        let content = memory.content.split_whitespace().collect::<Vec<_>>();
+        block.push_str(&format!("- {}\n", content.join(" ")));
+    }
+    block.push_str(END);
+    block
+}
+
+/// `content` with its section replaced by `block`, appended if it has none,
+/// or removed when `block` is None.
+fn replace_section(content: &str, block: Option<&str>) -> String {
+    let (before, after) = match (content.find(START), content.find(END)) {
+        (Some(start), Some(end)) if start < end => (&content[..start], &content[end + END.len()..]),
+        _ => (content, ""),
+    };
+    let parts: Vec<&str> = [before.trim_end(), block.unwrap_or(""), after.trim()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.is_empty() {
+        return String::new();
+    }
+    format!("{}\n", parts.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory(content: &str) -> Memory {
+        Memory {
+            id: "ae777456".to_string(),
+            memory_type: "project".to_string(),
+            content: content.to_string(),
+            tags: vec![PIN_TAG.to_string()],
+            use_count: 1,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_section_is_replaced_in_place() {
+        let first = render(&[memory("Use pnpm")]);
+        let file = replace_section("# Project\n\nHand-written notes.\n", Some(&first));
+        assert!(file.starts_with("# Project\n\nHand-written notes.\n\n<!-- START"));
+        assert!(file.contains("- Use pnpm\n"));
+
+        let edited = format!("{}\n## Later\n", file);
+        let second = render(&[memory("Use pnpm"), memory("Run cargo nextest")]);
+        let refreshed = replace_section(&edited, Some(&second));
+        assert!(refreshed.contains("- Use pnpm\n- Run cargo nextest\n"));
+        assert!(refreshed.ends_with(&format!("{}\n\n## Later\n", END)));
+        assert_eq!(replace_section(&refreshed, Some(&second)), refreshed);
+
+        assert_eq!(
+            replace_section(&refreshed, None),
+            "# Project\n\nHand-written notes.\n\n## Later\n"
+        );
+    }
+
+    #[test]
+    fn test_files_stay_in_the_project() {
+        let mut config = Config::default();
+        for file in ["../outside.md", "/etc/motd", "docs/../../x.md"] {
+            config.context.files = vec![file.to_string()];
+            assert!(config.context.check().is_err(), "{}", file);
+        }
+        config.context.files = vec!["docs/AGENTS.md".to_string()];
+        assert!(config.context.check().is_ok());
+
+        let project = tempfile::TempDir::new().unwrap();
+        assert!(confine(project.path(), &project.path().join("docs/AGENTS.md")).is_ok());
+        #[cfg(unix)]
+        {
+            let outside = tempfile::TempDir::new().unwrap();
+            std::os::unix::fs::symlink(outside.path(), project.path().join("docs")).unwrap();
+            assert!(confine(project.path(), &project.path().join("docs/AGENTS.md")).is_err());
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Read-only SQL console and metadata listing for memory databases (CLI-028).
+
+use std::path::PathBuf;
+
+use serde_json::{json, Map, Value};
+
+use crate::cli::memory;
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::lock::SqrlLock;
+use crate::storage::{self, query, Storage};
+
+/// Widest cell shown in the table; JSON output is never cut.
+const MAX_CELL_CHARS: usize = 60;
+
+/// Run one SQL statement against the project (or global) memory database.
+pub fn query(sql: &str, global: bool, unsafe_writes: bool) -> Result<(), Error> {
+    let (path, lock): (PathBuf, _) = if global {
+        let lock = unsafe_writes.then(SqrlLock::global).transpose()?;
+        (GlobalConfig::memory_db_path()?, lock)
+    } else {
+        let root = memory::root()?;
+        let lock = unsafe_writes
+            .then(|| SqrlLock::project(&root))
+            .transpose()?;
+        (storage::db_path(&root), lock)
+    };
+    let result = query::query(&path, sql, unsafe_writes)?;
+    drop(lock);
+
+    if result.columns.is_empty() {
+        output::emit(&json!({ "changed": result.changed }))?;
+        human!("{} rows changed.", result.changed);
+        return Ok(());
+    }
+    let objects: Vec<Map<String, Value>> = result
+        .rows
+        .iter()
+        .map(|row| {
+            result
+                .columns
+                .iter()
+                .cloned()
+                .zip(row.iter().cloned())
+                .collect()
+        })
+        .collect();
+    output::emit(&objects)?;
+
+    let cells: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(cell).collect())
+        .collect();
+    let widths: Vec<usize> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([name.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |values: &[String]| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, &width)| format!("{:<width$}", value))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    human!("{}", line(&result.columns));
+    for row in &cells {
+        human!("{}", line(row));
+    }
+    human!(
+        "({} row{})",
+        cells.len(),
+        if cells.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// List subsystem metadata (`meta`), all namespaces unless one is given.
+pub fn meta(namespace: Option<&str>, global: bool) -> Result<(), Error> {
+    let path = if global {
+        GlobalConfig::memory_db_path()?
+    } else {
+        storage::db_path(&memory::root()?)
+    };
+    if !path.exists() {
+        return Err(Error::DatabaseNotFound(path));
+    }
+    let entries = Storage::open_read_only(&path)?.meta_entries(namespace)?;
+    output::emit(&entries)?;
+
+    if entries.is_empty() {
+        human!("No metadata stored.");
+        return Ok(());
+    }
+    for entry in &entries {
+        human!(
+            "{}/{} = {}  ({})",
+            entry.namespace,
+            entry.key,
+            cell(&Value::String(entry.value.clone())),
+            entry.updated_at
+        );
+    }
+    Ok(())
+}
+
+/// A value as one table cell: no newlines, long text cut.
+fn cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "NULL".to_string(),
+        Value::String(s) => s.split_whitespace().collect::<Vec<_>>().join(" "),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_CELL_CHARS {
+        let cut: String = text.chars().take(MAX_CELL_CHARS - 1).collect();
+        format!("{}…", cut)
+    } else {
+        text
+    }
+}
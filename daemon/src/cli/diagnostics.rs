@@ -0,0 +1,242 @@
+//! Diagnostics bundle for bug reports (`sqrl doctor --report`, CLI-012).
+//!
+//! A `.tar.gz` of what a maintainer usually asks for first: the doctor
+//! result, config files as written, the tables of both databases, the hook
+//! scripts, recent warnings and errors from the log, and the platform.
+//! Memories themselves are left out. Every file is sanitized: the project
+//! and home paths become `<project>` and `~`, and the redaction rules
+//! (CONFIG-007) run over the text, the built-in ones always.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::{json, Value};
+
+use crate::cli::git;
+use crate::cli::hooks;
+use crate::config::format::ConfigFormat;
+use crate::config::redact::Redactor;
+use crate::config::{Config, RedactionConfig};
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::storage::{self, query};
+
+/// Warning and error lines kept from the log, newest last.
+const MAX_LOG_LINES: usize = 200;
+
+/// Directory the bundle's files sit under.
+const BUNDLE_DIR: &str = "sqrl-report";
+
+/// Path of the bundle when `--report` is given no file.
+pub fn default_path() -> PathBuf {
+    PathBuf::from(format!(
+        "sqrl-report-{}.tar.gz",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Write the bundle for `project_root` to `out`, with `doctor` as the
+/// doctor result. Returns the names of the files it holds.
+pub fn write_bundle(project_root: &Path, doctor: &Value, out: &Path) -> Result<Vec<String>, Error> {
+    let sanitizer = Sanitizer::new(project_root, dirs::home_dir().as_deref());
+    let mut files = vec![
+        (
+            "doctor.json".to_string(),
+            serde_json::to_string_pretty(doctor)?,
+        ),
+        (
+            "environment.json".to_string(),
+            serde_json::to_string_pretty(&environment(project_root))?,
+        ),
+        (
+            "schema.json".to_string(),
+            serde_json::to_string_pretty(&json!({
+                "project": schema(&storage::db_path(project_root)),
+                "global": schema(&GlobalConfig::memory_db_path()?),
+            }))?,
+        ),
+    ];
+
+    let mut configs = vec![project_root.join(".sqrl").join("config.local.json")];
+    if let Ok(Some(format)) = ConfigFormat::detect(project_root) {
+        configs.insert(0, format.path(project_root));
+    }
+    for path in configs {
+        if let Ok(content) = fs::read_to_string(&path) {
+            files.push((format!("config/{}", file_name(&path)), content));
+        }
+    }
+    if let Ok(content) = fs::read_to_string(GlobalConfig::path()?) {
+        files.push(("config/global.yaml".to_string(), content));
+    }
+    let hooks_dir = project_root.join(".git").join("hooks");
+    for hook in hooks::HOOK_NAMES {
+        if let Ok(content) = fs::read_to_string(hooks_dir.join(hook)) {
+            files.push((format!("hooks/{}", hook), content));
+        }
+    }
+    let log = recent_problems(&GlobalConfig::log_path()?);
+    if !log.is_empty() {
+        files.push(("logs/problems.log".to_string(), log));
+    }
+
+    let file = File::create(out).map_err(Error::at(out))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mtime = chrono::Utc::now().timestamp().max(0) as u64;
+    for (name, content) in &files {
+        let content = sanitizer.clean(content);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        archive.append_data(
+            &mut header,
+            format!("{}/{}", BUNDLE_DIR, name),
+            content.as_bytes(),
+        )?;
+    }
+    archive.into_inner()?.finish()?;
+    Ok(files.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Platform and install details.
+fn environment(project_root: &Path) -> Value {
+    let shell = std::env::var("SHELL").ok().map(|shell| {
+        Path::new(&shell)
+            .file_name()
+            .map_or(shell.clone(), |name| name.to_string_lossy().to_string())
+    });
+    json!({
+        "sqrl_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "executable": std::env::current_exe().ok(),
+        "shell": shell,
+        "ci": std::env::var_os("CI").is_some(),
+        "git": git::Location::of(project_root),
+    })
+}
+
+/// Tables of the database at `path` with their definitions and row counts,
+/// or null when there is no database.
+fn schema(path: &Path) -> Value {
+    let Ok(tables) = query::query(
+        path,
+        "SELECT name, sql FROM sqlite_master WHERE type = 'table' ORDER BY name",
+        false,
+    ) else {
+        return Value::Null;
+    };
+    let tables: Vec<Value> = tables
+        .rows
+        .into_iter()
+        .map(|row| {
+            let name = row[0].as_str().unwrap_or_default().to_string();
+            let count = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+            let rows = query::query(path, &count, false)
+                .ok()
+                .and_then(|result| result.rows.into_iter().next())
+                .and_then(|row| row.into_iter().next());
+            json!({ "table": name, "sql": row[1], "rows": rows })
+        })
+        .collect();
+    json!(tables)
+}
+
+/// The last `MAX_LOG_LINES` warning and error lines of the log at `path`
+/// and its first rotated file.
+fn recent_problems(path: &Path) -> String {
+    let rotated = path.with_file_name(format!("{}.1", file_name(path)));
+    let text = [rotated.as_path(), path]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let problems: Vec<&str> = text
+        .lines()
+        .filter(|line| line.contains(" WARN ") || line.contains(" ERROR "))
+        .collect();
+    let start = problems.len().saturating_sub(MAX_LOG_LINES);
+    problems[start..]
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Strips paths and secrets from text bound for a bug report.
+struct Sanitizer {
+    /// Paths and what replaces them, longest first.
+    paths: Vec<(String, &'static str)>,
+    redactor: Redactor,
+}
+
+impl Sanitizer {
+    fn new(project_root: &Path, home: Option<&Path>) -> Self {
+        let mut paths = vec![(project_root.to_string_lossy().to_string(), "<project>")];
+        if let Some(home) = home {
+            paths.push((home.to_string_lossy().to_string(), "~"));
+        }
+        paths.retain(|(path, _)| path.len() > 1);
+        paths.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+
+        // Project patterns when the config loads; the report may be about one that doesn't
+        let patterns = Config::effective(project_root)
+            .map(|config| config.redaction.patterns)
+            .unwrap_or_default();
+        let redactor = Redactor::new(&RedactionConfig {
+            builtin: true,
+            patterns,
+        })
+        .or_else(|_| Redactor::new(&RedactionConfig::default()))
+        .expect("built-in redaction rules");
+        Self { paths, redactor }
+    }
+
+    fn clean(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (path, replacement) in &self.paths {
+            text = text.replace(path.as_str(), replacement);
+        }
+        self.redactor.redact(&text).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_text_is_sanitized() {
+        let home = TempDir::new().unwrap();
+        let root = home.path().join("work").join("app");
+        let sanitizer = Sanitizer::new(&root, Some(home.path()));
+        let text = format!(
+            "root: {}\nlog: {}/.sqrl/logs/cli.log\ntoken: ghp_{}\n",
+            root.display(),
+            home.path().display(),
+            "a".repeat(36)
+        );
+        assert_eq!(
+            sanitizer.clean(&text),
+            "root: <project>\nlog: ~/.sqrl/logs/cli.log\ntoken: [REDACTED:github-token]\n"
+        );
+
+        let log = home.path().join("cli.log");
+        fs::write(home.path().join("cli.log.1"), "t  WARN sqrl: old\n").unwrap();
+        fs::write(&log, "t  INFO sqrl: fine\nt ERROR sqrl: broke\n").unwrap();
+        assert_eq!(
+            recent_problems(&log),
+            "t  WARN sqrl: old\nt ERROR sqrl: broke\n"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Diagnose Squirrel setup problems (CLI-012).
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cli::diagnostics;
+use crate::cli::git;
+use crate::cli::output::{self, human};
+use crate::cli::security::{self, Finding, Severity};
+use crate::cli::status::{self, CheckStatus};
+use crate::config::validate::{self, Problem};
+use crate::config::{project_root, Config};
+use crate::docs;
+use crate::error::{Error, ExitCode};
+use crate::global_config::GlobalConfig;
+use crate::storage;
+
+/// Runs per measured path; the median is reported.
+const BENCH_RUNS: usize = 5;
+
+/// Latency budget for the pre-push docguard check.
+const DOCGUARD_BUDGET: Duration = Duration::from_millis(50);
+/// Latency budget for assembling the `squirrel_get_memory` response.
+const MEMORY_BUDGET: Duration = Duration::from_millis(100);
+
+/// Timing of one hot path against its budget.
+#[derive(Debug, Serialize)]
+struct Measurement {
+    name: &'static str,
+    median_ms: f64,
+    budget_ms: f64,
+    ok: bool,
+}
+
+/// Run the doctor command, writing a diagnostics bundle to `bundle` if given.
+/// Returns exit code.
+pub fn run(bench: bool, security: bool, bundle: Option<&Path>) -> Result<i32, Error> {
+    let project_root = project_root()?;
+
+    human!("Squirrel Doctor");
+    let project = validate::validate_project(&project_root)?;
+    let global = validate::validate_global()?;
+    let mcps = validate::validate_mcps()?;
+    let mut failed = false;
+    failed |= report("Project config", &project);
+    failed |= report("Global config", &global);
+    failed |= report("MCP configs", &mcps);
+    let hooks = status::check_hooks(&project_root);
+    match hooks.status {
+        CheckStatus::Ok => human!("  Hooks: ok"),
+        CheckStatus::Skip => human!("  Hooks: skipped ({})", hooks.message),
+        CheckStatus::Fail => {
+            human!("  Hooks: {}", hooks.message);
+            failed = true;
+        }
+    }
+
+    let mut result = json!({
+        "ok": !failed,
+        "project_config": project,
+        "global_config": global,
+        "mcp_configs": mcps,
+        "hooks": hooks,
+    });
+    if bench && project.is_empty() {
+        let measurements = run_bench(&project_root)?;
+        failed |= measurements.iter().any(|m| !m.ok);
+        result["ok"] = json!(!failed);
+        result["bench"] = json!(measurements);
+    }
+    if security {
+        let findings = security::scan(&project_root)?;
+        failed |= report_security(&findings);
+        result["ok"] = json!(!failed);
+        result["security"] = json!(findings);
+    }
+    if let Some(path) = bundle {
+        let files = diagnostics::write_bundle(&project_root, &result, path)?;
+        human!();
+        human!(
+            "Wrote {} ({} files); attach it to the issue.",
+            path.display(),
+            files.len()
+        );
+        result["report"] = json!({ "path": path, "files": files });
+    }
+    output::emit(&result)?;
+
+    if failed {
+        human!();
+        human!("Fix the problems above, or run 'sqrl config get <key>' to see a default.");
+        return Ok(ExitCode::Problems.code());
+    }
+    Ok(0)
+}
+
+/// Time the paths that run on every push and every memory lookup.
+fn run_bench(project_root: &Path) -> Result<Vec<Measurement>, Error> {
+    human!("  Hook latency (median of {} runs):", BENCH_RUNS);
+
+    let docguard = measure("docguard check", DOCGUARD_BUDGET, || {
+        let config = Config::effective(project_root)?;
+        let matcher = config.doc_matcher()?;
+        git::push_range(project_root)?;
+        docs::find_doc_files(project_root, &matcher);
+        Ok(())
+    })?;
+
+    let profile = GlobalConfig::active_profile_name();
+    let context_chars = Config::effective(project_root)?.mcp.context_chars;
+    let memory = measure("memory retrieval", MEMORY_BUDGET, || {
+        storage::format_memories_as_markdown(
+            project_root,
+            &[],
+            None,
+            None,
+            Some(context_chars),
+            profile.as_deref(),
+            &[],
+        )?;
+        Ok(())
+    })?;
+
+    Ok(vec![docguard, memory])
+}
+
+fn measure(
+    name: &'static str,
+    budget: Duration,
+    mut f: impl FnMut() -> Result<(), Error>,
+) -> Result<Measurement, Error> {
+    let mut times = Vec::with_capacity(BENCH_RUNS);
+    for _ in 0..BENCH_RUNS {
+        let start = Instant::now();
+        f()?;
+        times.push(start.elapsed());
+    }
+    times.sort();
+    let median = times[BENCH_RUNS / 2];
+
+    let measurement = Measurement {
+        name,
+        median_ms: median.as_secs_f64() * 1000.0,
+        budget_ms: budget.as_secs_f64() * 1000.0,
+        ok: median <= budget,
+    };
+    human!(
+        "    {}: {:.1} ms (budget {} ms){}",
+        name,
+        measurement.median_ms,
+        measurement.budget_ms,
+        if measurement.ok { "" } else { " - over budget" }
+    );
+    Ok(measurement)
+}
+
+/// Print check results. Returns true if there were problems.
+fn report(label: &str, problems: &[Problem]) -> bool {
+    if problems.is_empty() {
+        human!("  {}: ok", label);
+        return false;
+    }
+    human!("  {}: {} problem(s)", label, problems.len());
+    for problem in problems {
+        human!("    {}", problem);
+    }
+    true
+}
+
+/// Print security findings. Returns true if any should fail the doctor.
+fn report_security(findings: &[Finding]) -> bool {
+    if findings.is_empty() {
+        human!("  Security: ok");
+        return false;
+    }
+    human!("  Security: {} finding(s)", findings.len());
+    for finding in findings {
+        let mark = match finding.severity {
+            Severity::Fail => "✗",
+            Severity::Warn => "!",
+        };
+        human!(
+            "    {} {} {}: {}",
+            mark,
+            finding.kind,
+            finding.name,
+            finding.message
+        );
+    }
+    findings.iter().any(|f| f.severity == Severity::Fail)
+}
@@ -0,0 +1,129 @@
+//! Change feed as line-delimited JSON (`sqrl events`, CLI-033).
+//!
+//! Every write to memories, ratings and proposals lands in the `changes`
+//! feed whichever process made it (CLI, hooks, MCP server, web UI), so
+//! tailing the feed is how an editor extension or status bar hears about
+//! them. Lines are always JSON, one event each, whatever `--json` says.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::cli::memory;
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::storage::changes::Change;
+use crate::storage::{self, Storage};
+
+/// How often `--follow` checks the feed.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Print events after `since`, then with `follow` keep printing new ones
+/// until interrupted. Following starts at the current generation unless
+/// `since` is given.
+pub fn run(since: Option<i64>, follow: bool, global: bool) -> Result<(), Error> {
+    let path = if global {
+        GlobalConfig::memory_db_path()?
+    } else {
+        storage::db_path(&memory::root()?)
+    };
+    let storage = Storage::open(&path)?;
+    let mut seq = match (since, follow) {
+        (Some(since), _) => since,
+        (None, true) => storage.generation()?,
+        (None, false) => 0,
+    };
+
+    let mut stdout = io::stdout().lock();
+    loop {
+        match write_events(&storage, seq, &mut stdout)? {
+            Some(last) => seq = last,
+            // A closed pipe (`| head`) ends the stream, not an error
+            None => return Ok(()),
+        }
+        if !follow {
+            return Ok(());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Write the events after `seq` to `out`, one line each. Returns the last
+/// sequence number written (`seq` if none), or `None` once `out` is closed.
+fn write_events(
+    storage: &Storage,
+    mut seq: i64,
+    out: &mut impl Write,
+) -> Result<Option<i64>, Error> {
+    for change in storage.changes_since(seq)? {
+        seq = change.seq;
+        let line = json!({
+            "seq": change.seq,
+            "event": event_name(&change),
+            "id": change.id,
+            "at": change.at,
+        });
+        if writeln!(out, "{}", line).and_then(|_| out.flush()).is_err() {
+            return Ok(None);
+        }
+    }
+    Ok(Some(seq))
+}
+
+/// Stable name for a change: `memory.created`, `rating.updated`, ...
+fn event_name(change: &Change) -> String {
+    let subject = match change.table.as_str() {
+        "memories" => "memory",
+        "ratings" => "rating",
+        "pending" => "proposal",
+        other => other,
+    };
+    let action = match change.op.as_str() {
+        "insert" => "created",
+        "update" => "updated",
+        "delete" => "deleted",
+        other => other,
+    };
+    format!("{}.{}", subject, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_events_after_seq_one_json_line_each() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let id = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let created = storage.generation().unwrap();
+        storage
+            .update_memory(&id, None, Some("Use pnpm 9"), None)
+            .unwrap();
+        storage.delete_memory(&id).unwrap();
+
+        let mut out = Vec::new();
+        let last = write_events(&storage, 0, &mut out).unwrap().unwrap();
+        assert_eq!(last, storage.generation().unwrap());
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let events: Vec<&str> = lines.iter().map(|l| l["event"].as_str().unwrap()).collect();
+        assert_eq!(
+            events,
+            ["memory.created", "memory.updated", "memory.deleted"]
+        );
+        assert!(lines.iter().all(|l| l["id"] == json!(id)));
+
+        let mut out = Vec::new();
+        write_events(&storage, created, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+        let mut out = Vec::new();
+        assert_eq!(write_events(&storage, last, &mut out).unwrap(), Some(last));
+        assert!(out.is_empty());
+    }
+}
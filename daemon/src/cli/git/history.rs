@@ -0,0 +1,220 @@
+//! Walks of the history reachable from HEAD.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use git2::Delta;
+
+use super::discover;
+use crate::error::Error;
+use crate::paths;
+
+/// Non-merge commits reachable from HEAD made at or after `since` (Unix
+/// seconds), and how many of them touched each file, most touched first.
+/// Empty outside a repository or on an unborn branch.
+pub fn edits_since(path: &Path, since: i64) -> Result<(usize, Vec<(String, usize)>), Error> {
+    let Ok(repo) = discover(path) else {
+        return Ok((0, Vec::new()));
+    };
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        return Ok((0, Vec::new()));
+    }
+    walk.set_sorting(git2::Sort::TIME)?;
+
+    let mut commits = 0;
+    let mut counts: std::collections::HashMap<String, usize> = Default::default();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.time().seconds() < since {
+            break;
+        }
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        commits += 1;
+        let parent = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+        let diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), None)?;
+        for delta in diff.deltas() {
+            if let Some(file) = delta.new_file().path().or(delta.old_file().path()) {
+                *counts
+                    .entry(file.to_string_lossy().to_string())
+                    .or_default() += 1;
+            }
+        }
+    }
+    let mut files: Vec<(String, usize)> = counts.into_iter().collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok((commits, files))
+}
+
+/// Where each of `missing` was moved to, following renames (detected like
+/// `git diff -M`) and chains of them through the non-merge commits reachable
+/// from HEAD, newest first. The walk stops once every path has been renamed,
+/// deleted or reached its creation, or after `RENAME_WALK_LIMIT` commits;
+/// paths never renamed are left out. Empty outside a repository.
+pub fn renames(path: &Path, missing: &[String]) -> Result<HashMap<String, String>, Error> {
+    renames_within(path, missing, RENAME_WALK_LIMIT)
+}
+
+/// Commits `renames` looks back through, so a path that was never in the
+/// history doesn't cost a walk of all of it.
+const RENAME_WALK_LIMIT: usize = 1000;
+
+fn renames_within(
+    path: &Path,
+    missing: &[String],
+    limit: usize,
+) -> Result<HashMap<String, String>, Error> {
+    let Ok(repo) = discover(path) else {
+        return Ok(HashMap::new());
+    };
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        return Ok(HashMap::new());
+    }
+    walk.set_sorting(git2::Sort::TIME)?;
+
+    let mut moves: HashMap<String, String> = HashMap::new();
+    let mut pending: HashSet<&str> = missing.iter().map(String::as_str).collect();
+    for oid in walk.take(limit) {
+        if pending.is_empty() {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let parent = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+        let mut diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), None)?;
+        diff.find_similar(None)?;
+        for delta in diff.deltas() {
+            if delta.status() == Delta::Added {
+                // Nothing older can have moved a path created here
+                if let Some(new) = delta.new_file().path().map(paths::from_path) {
+                    pending.remove(new.as_str());
+                }
+                continue;
+            }
+            let Some(old) = delta.old_file().path().map(paths::from_path) else {
+                continue;
+            };
+            match delta.status() {
+                Delta::Renamed => {
+                    if let Some(new) = delta.new_file().path() {
+                        // Newest first, so a later rename of the same path wins
+                        moves.entry(old.clone()).or_insert(paths::from_path(new));
+                    }
+                }
+                Delta::Deleted => {}
+                _ => continue,
+            }
+            pending.remove(old.as_str());
+        }
+    }
+
+    let mut found = HashMap::new();
+    for path in missing {
+        let mut current = path;
+        // A file moved back and forth would otherwise loop
+        for _ in 0..moves.len() {
+            match moves.get(current) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        if current != path {
+            found.insert(path.clone(), current.clone());
+        }
+    }
+    Ok(found)
+}
+
+/// Commit times (Unix seconds) of the non-merge commits reachable from HEAD
+/// made since the newest one touching a doc, newest first, at most `limit`.
+/// `None` outside a repository or on an unborn branch.
+pub fn undocumented_commits(
+    path: &Path,
+    is_doc: impl Fn(&str) -> bool,
+    limit: usize,
+) -> Result<Option<Vec<i64>>, Error> {
+    let Ok(repo) = discover(path) else {
+        return Ok(None);
+    };
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        return Ok(None);
+    }
+    walk.set_sorting(git2::Sort::TIME)?;
+
+    let mut times = Vec::new();
+    for oid in walk {
+        if times.len() >= limit {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let parent = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+        let diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), None)?;
+        let touches_doc = diff.deltas().any(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or(delta.old_file().path())
+                .is_some_and(|file| is_doc(&paths::from_path(file)))
+        });
+        if touches_doc {
+            break;
+        }
+        times.push(commit.time().seconds());
+    }
+    Ok(Some(times))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::git::tests::commit;
+    use crate::cli::git::{push_range, FileStatus};
+    use git2::Repository;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_push_range_detects_renames() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let content = "pub fn login() {}\npub fn logout() {}\n";
+        let base = commit(&repo, "auth.rs", content, "auth");
+        repo.reference("refs/remotes/origin/main", base, true, "test")
+            .unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("auth.rs")).unwrap();
+        index.write().unwrap();
+        fs::create_dir_all(dir.path().join("auth")).unwrap();
+        commit(&repo, "auth/mod.rs", content, "move");
+
+        let range = push_range(dir.path()).unwrap().unwrap();
+        assert_eq!(range.changes.len(), 1);
+        assert_eq!(range.changes[0].path, "auth/mod.rs");
+        assert_eq!(range.changes[0].status, FileStatus::Renamed);
+        assert_eq!(range.changes[0].old_path.as_deref(), Some("auth.rs"));
+
+        // Moved again: memories naming the first path follow the chain
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("auth/mod.rs")).unwrap();
+        index.write().unwrap();
+        fs::create_dir_all(dir.path().join("src/auth")).unwrap();
+        commit(&repo, "src/auth/mod.rs", content, "move again");
+        let missing = ["auth.rs".to_string(), "never.rs".to_string()];
+        let moves = renames(dir.path(), &missing).unwrap();
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves["auth.rs"], "src/auth/mod.rs");
+
+        // The first move is two commits back, past a one-commit window
+        assert!(renames_within(dir.path(), &missing, 1).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+//! Git queries for hooks.
+//!
+//! Uses libgit2, so hooks work without a `git` binary on PATH.
+//!
+//! Git runs hooks with `GIT_DIR` (and, for `--work-tree` setups such as a
+//! bare dotfiles repository, `GIT_WORK_TREE`) set. `discover` honors them as
+//! git does, since the work tree may have no `.git` to discover.
+
+mod history;
+mod push;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::paths;
+
+pub use history::{edits_since, renames, undocumented_commits};
+pub use push::{push_range, FileChange, FileStatus, PushRange};
+
+/// Paths in the index under `path`, relative to it. Empty outside a repository.
+pub fn tracked_files(path: &Path) -> Result<Vec<String>, Error> {
+    let Ok(repo) = discover(path) else {
+        return Ok(Vec::new());
+    };
+    let Some(workdir) = repo.workdir() else {
+        return Ok(Vec::new());
+    };
+    let below = path.canonicalize()?;
+    let prefix = below
+        .strip_prefix(workdir.canonicalize()?)
+        .unwrap_or(Path::new(""));
+    let prefix = paths::from_path(prefix);
+    let prefix = if prefix.is_empty() {
+        prefix
+    } else {
+        format!("{}/", prefix)
+    };
+    let index = repo.index()?;
+    Ok(index
+        .iter()
+        .filter_map(|entry| {
+            let file = String::from_utf8_lossy(&entry.path);
+            file.strip_prefix(&prefix).map(str::to_string)
+        })
+        .collect())
+}
+
+/// Full IDs of `rev` and its first-parent ancestors, newest first, at most `limit`.
+pub fn first_parents(path: &Path, rev: &str, limit: usize) -> Result<Vec<String>, Error> {
+    let repo = discover(path)?;
+    let mut commit = repo.revparse_single(rev)?.peel_to_commit()?;
+    let mut ids = vec![commit.id().to_string()];
+    while ids.len() < limit {
+        let Ok(parent) = commit.parent(0) else {
+            break;
+        };
+        ids.push(parent.id().to_string());
+        commit = parent;
+    }
+    Ok(ids)
+}
+
+/// When `rev` was committed.
+pub fn commit_time(path: &Path, rev: &str) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+    let repo = discover(path)?;
+    let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+    chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+        .ok_or_else(|| Error::InvalidArgument(format!("{} has an invalid commit time", rev)))
+}
+
+/// Those of `commits` that no branch, tag or `HEAD` of the repository at
+/// `path` reaches, such as commits replaced by a rebase. Empty outside a
+/// repository, since nothing can be checked there.
+pub fn unreachable(path: &Path, commits: &[String]) -> Result<Vec<String>, Error> {
+    let Ok(repo) = discover(path) else {
+        return Ok(Vec::new());
+    };
+    let mut pending: HashSet<&str> = commits.iter().map(String::as_str).collect();
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut walk = repo.revwalk()?;
+    walk.push_glob("*")?;
+    // An unborn HEAD has nothing to push
+    let _ = walk.push_head();
+    for oid in walk {
+        pending.remove(oid?.to_string().as_str());
+        if pending.is_empty() {
+            break;
+        }
+    }
+    Ok(commits
+        .iter()
+        .filter(|commit| pending.contains(commit.as_str()))
+        .cloned()
+        .collect())
+}
+
+/// Where a project sits relative to git.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Location {
+    /// The project is the root of a work tree; hooks can be installed.
+    Root,
+    /// Inside a work tree, but below its root; hooks are not installed.
+    Nested,
+    /// Not in a repository (or in a bare one): memories only.
+    None,
+}
+
+/// The repository whose work tree root is `path`, if any.
+pub fn repository_at(path: &Path) -> Option<Repository> {
+    let repo = discover(path).ok()?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    (workdir == path.canonicalize().ok()?).then_some(repo)
+}
+
+impl Location {
+    /// How the project at `path` relates to its repository.
+    pub fn of(path: &Path) -> Location {
+        let Ok(repo) = discover(path) else {
+            return Location::None;
+        };
+        let Some(workdir) = repo.workdir() else {
+            return Location::None;
+        };
+        match (workdir.canonicalize(), path.canonicalize()) {
+            (Ok(workdir), Ok(path)) if workdir == path => Location::Root,
+            _ => Location::Nested,
+        }
+    }
+
+    /// What the project loses here, for status and init.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Location::Root => "repository root",
+            Location::Nested => {
+                "inside a repository but not at its root; hooks, snapshots and the push check are off"
+            }
+            Location::None => {
+                "not a git repository; memories only (no hooks, snapshots or push check)"
+            }
+        }
+    }
+}
+
+/// The repository for `path`: the one `GIT_DIR` names when `path` is in its
+/// work tree, else the one found by searching up from `path`.
+pub fn discover(path: &Path) -> Result<Repository, git2::Error> {
+    let git_dir = std::env::var_os("GIT_DIR").map(PathBuf::from);
+    let work_tree = std::env::var_os("GIT_WORK_TREE").map(PathBuf::from);
+    discover_with(path, git_dir.as_deref(), work_tree.as_deref())
+}
+
+/// Work tree of the repository named by `GIT_DIR` and `GIT_WORK_TREE`, for
+/// hooks started outside it. `None` without `GIT_DIR` or for a bare repository.
+pub fn env_work_tree() -> Option<PathBuf> {
+    let git_dir = PathBuf::from(std::env::var_os("GIT_DIR")?);
+    let work_tree = std::env::var_os("GIT_WORK_TREE").map(PathBuf::from);
+    let repo = open_env(&git_dir, work_tree.as_deref()).ok()?;
+    repo.workdir().map(Path::to_path_buf)
+}
+
+fn discover_with(
+    path: &Path,
+    git_dir: Option<&Path>,
+    work_tree: Option<&Path>,
+) -> Result<Repository, git2::Error> {
+    if let Some(git_dir) = git_dir {
+        if let Ok(repo) = open_env(git_dir, work_tree) {
+            let inside = match (repo.workdir(), path.canonicalize()) {
+                (Some(workdir), Ok(path)) => workdir
+                    .canonicalize()
+                    .is_ok_and(|workdir| path.starts_with(workdir)),
+                _ => false,
+            };
+            if inside {
+                return Ok(repo);
+            }
+        }
+    }
+    Repository::discover(path)
+}
+
+/// Open `git_dir`, with `work_tree` as its work tree if given. Relative
+/// paths are taken from the current directory, as git sets them.
+fn open_env(git_dir: &Path, work_tree: Option<&Path>) -> Result<Repository, git2::Error> {
+    let cwd = std::env::current_dir().map_err(|e| git2::Error::from_str(&e.to_string()))?;
+    let repo = Repository::open(cwd.join(git_dir))?;
+    if let Some(work_tree) = work_tree {
+        repo.set_workdir(&cwd.join(work_tree), false)?;
+    }
+    Ok(repo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Oid;
+    use std::fs;
+    use tempfile::TempDir;
+
+    pub(super) fn commit(repo: &Repository, file: &str, content: &str, message: &str) -> Oid {
+        fs::write(repo.workdir().unwrap().join(file), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let parents: Vec<_> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<_> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unreachable_commits() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let first = commit(&repo, "a.txt", "a\n", "first").to_string();
+        let second = commit(&repo, "a.txt", "b\n", "second");
+        // Amend-like rewrite: move the branch back past the second commit
+        let head = repo.head().unwrap().name().unwrap().to_string();
+        repo.reference(&head, Oid::from_str(&first).unwrap(), true, "test")
+            .unwrap();
+
+        let commits = [first, second.to_string(), "0".repeat(40)];
+        let gone = unreachable(dir.path(), &commits).unwrap();
+        assert_eq!(gone, commits[1..]);
+    }
+
+    #[test]
+    fn test_discover_honors_git_dir_and_work_tree() {
+        let dir = TempDir::new().unwrap();
+        let git_dir = dir.path().join("dotfiles.git");
+        let home = dir.path().join("home");
+        fs::create_dir_all(&home).unwrap();
+        Repository::init_bare(&git_dir).unwrap();
+        assert!(discover_with(&home, None, None).is_err());
+
+        let repo = discover_with(&home, Some(&git_dir), Some(&home)).unwrap();
+        assert_eq!(
+            repo.workdir().unwrap().canonicalize().unwrap(),
+            home.canonicalize().unwrap()
+        );
+
+        // A path outside that work tree is still discovered from the path
+        let other = dir.path().join("other");
+        let own = Repository::init(&other).unwrap();
+        let repo = discover_with(&other, Some(&git_dir), Some(&home)).unwrap();
+        assert_eq!(repo.path(), own.path());
+
+        fs::create_dir_all(other.join("sub")).unwrap();
+        assert_eq!(Location::of(&other), Location::Root);
+        assert_eq!(Location::of(&other.join("sub")), Location::Nested);
+    }
+}
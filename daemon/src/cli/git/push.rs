@@ -0,0 +1,174 @@
+//! What a push would send, for the pre-push hook.
+
+use std::path::Path;
+
+use git2::{BranchType, Delta, Oid, Patch, Repository};
+
+use super::discover;
+use crate::error::Error;
+
+/// How a pushed file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Modified,
+    Deleted,
+    /// Moved, possibly with edits (`old_path` is set).
+    Renamed,
+}
+
+impl FileStatus {
+    /// Letter used by `git diff --name-status`.
+    pub fn letter(self) -> char {
+        match self {
+            FileStatus::Added => 'A',
+            FileStatus::Modified => 'M',
+            FileStatus::Deleted => 'D',
+            FileStatus::Renamed => 'R',
+        }
+    }
+}
+
+/// A file changed by the commits being pushed.
+pub struct FileChange {
+    pub path: String,
+    /// Path before a rename.
+    pub old_path: Option<String>,
+    pub status: FileStatus,
+    /// Added and removed line counts; `None` for binary files.
+    pub lines: Option<(usize, usize)>,
+}
+
+/// What a push would send: commits and changed files between upstream and HEAD.
+pub struct PushRange {
+    /// `<short id> <summary>`, newest first.
+    pub commits: Vec<String>,
+    pub changes: Vec<FileChange>,
+}
+
+/// Compare HEAD with its upstream branch, else origin/main or origin/master.
+/// `None` outside a repository, on an unborn branch, or without anything to compare to.
+pub fn push_range(path: &Path) -> Result<Option<PushRange>, Error> {
+    let Ok(repo) = discover(path) else {
+        return Ok(None);
+    };
+    let Some(upstream) = upstream_oid(&repo) else {
+        return Ok(None);
+    };
+    let Ok(head) = repo.head().and_then(|head| head.peel_to_commit()) else {
+        return Ok(None);
+    };
+
+    let mut walk = repo.revwalk()?;
+    walk.push(head.id())?;
+    walk.hide(upstream)?;
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let short_id = commit.as_object().short_id()?;
+        commits.push(format!(
+            "{} {}",
+            short_id.as_str().unwrap_or_default(),
+            commit.summary().unwrap_or_default()
+        ));
+    }
+
+    let base = repo.find_commit(upstream)?.tree()?;
+    let mut diff = repo.diff_tree_to_tree(Some(&base), Some(&head.tree()?), None)?;
+    // Pair deletes with adds of similar content, like `git diff -M`
+    diff.find_similar(None)?;
+    let mut changes = Vec::new();
+    for idx in 0..diff.deltas().len() {
+        let Some(patch) = Patch::from_diff(&diff, idx)? else {
+            continue;
+        };
+        let delta = patch.delta();
+        let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+            continue;
+        };
+        let lines = if delta.flags().is_binary() {
+            None
+        } else {
+            let (_, added, removed) = patch.line_stats()?;
+            Some((added, removed))
+        };
+        let status = match delta.status() {
+            Delta::Added => FileStatus::Added,
+            Delta::Deleted => FileStatus::Deleted,
+            Delta::Renamed => FileStatus::Renamed,
+            _ => FileStatus::Modified,
+        };
+        let old_path = (status == FileStatus::Renamed)
+            .then(|| delta.old_file().path())
+            .flatten()
+            .map(|old| old.to_string_lossy().to_string());
+        changes.push(FileChange {
+            path: path.to_string_lossy().to_string(),
+            old_path,
+            status,
+            lines,
+        });
+    }
+
+    Ok(Some(PushRange { commits, changes }))
+}
+
+/// Commit the push is compared against.
+fn upstream_oid(repo: &Repository) -> Option<Oid> {
+    let tracked = repo
+        .head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(String::from))
+        .and_then(|name| repo.find_branch(&name, BranchType::Local).ok())
+        .and_then(|branch| branch.upstream().ok())
+        .and_then(|upstream| upstream.get().target());
+
+    tracked.or_else(|| {
+        ["refs/remotes/origin/main", "refs/remotes/origin/master"]
+            .iter()
+            .find_map(|name| repo.refname_to_id(name).ok())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::git::tests::commit;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_push_range_against_origin_main() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        assert!(push_range(dir.path()).unwrap().is_none());
+
+        let base = commit(&repo, "README.md", "hello\n", "docs");
+        repo.reference("refs/remotes/origin/main", base, true, "test")
+            .unwrap();
+        commit(&repo, "main.rs", "fn main() {}\n", "code");
+
+        let range = push_range(dir.path()).unwrap().unwrap();
+        assert_eq!(range.commits.len(), 1);
+        assert!(range.commits[0].ends_with(" code"));
+        assert_eq!(range.changes.len(), 1);
+        assert_eq!(range.changes[0].path, "main.rs");
+        assert_eq!(range.changes[0].status, FileStatus::Added);
+        assert_eq!(range.changes[0].lines, Some((1, 0)));
+
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("README.md")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "drop", &tree, &[&head])
+            .unwrap();
+        let range = push_range(dir.path()).unwrap().unwrap();
+        let deleted = range
+            .changes
+            .iter()
+            .find(|c| c.path == "README.md")
+            .unwrap();
+        assert_eq!(deleted.status, FileStatus::Deleted);
+    }
+}
@@ -1,13 +1,25 @@
 //! Remove Squirrel from a project.
+//!
+//! `.sqrl/` and the skill directory are moved to the trash (TRASH-001).
 
 use std::fs;
 use std::io::{self, Write};
 
 use tracing::warn;
 
+use crate::atomic;
+use crate::cli::context;
 use crate::cli::hooks;
+use crate::cli::output::human;
 use crate::config::Config;
 use crate::error::Error;
+use crate::global_config::projects::Registry;
+use crate::lock::SqrlLock;
+use crate::trash::{self, Entry, Kind};
+
+/// Trash payload names for a goaway entry.
+const SQRL_PAYLOAD: &str = "sqrl";
+const SKILL_PAYLOAD: &str = "skill";
 
 /// Run the goaway command.
 pub fn run(force: bool) -> Result<(), Error> {
@@ -15,13 +27,13 @@ pub fn run(force: bool) -> Result<(), Error> {
     let sqrl_dir = project_root.join(".sqrl");
 
     if !sqrl_dir.exists() {
-        println!("No .sqrl/ directory found in this project.");
+        human!("No .sqrl/ directory found in this project.");
         return Ok(());
     }
 
     // Show what will be removed
-    println!("This will remove:");
-    println!("  .sqrl/ ({})", sqrl_dir.display());
+    human!("This will remove:");
+    human!("  .sqrl/ ({})", sqrl_dir.display());
     print_dir_contents(&sqrl_dir, 4)?;
 
     let skill_dir = project_root
@@ -29,7 +41,7 @@ pub fn run(force: bool) -> Result<(), Error> {
         .join("skills")
         .join("squirrel-session");
     if skill_dir.exists() {
-        println!("  .claude/skills/squirrel-session/");
+        human!("  .claude/skills/squirrel-session/");
     }
 
     // Confirm unless --force
@@ -41,39 +53,108 @@ pub fn run(force: bool) -> Result<(), Error> {
         io::stdin().read_line(&mut input)?;
 
         if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled.");
+            human!("Cancelled.");
             return Ok(());
         }
     }
 
+    let lock = SqrlLock::project(&project_root)?;
+
     // Uninstall git hooks
     if hooks::has_git(&project_root) {
         if let Err(e) = hooks::uninstall_hooks(&project_root) {
             warn!(error = %e, "Failed to uninstall git hooks");
         } else {
-            println!("Git hooks removed.");
+            human!("Git hooks removed.");
         }
     }
 
     // Unregister MCP servers
-    if let Ok(config) = Config::load(&project_root) {
+    if let Ok(config) = Config::effective(&project_root) {
         unregister_mcp_servers(&config);
     }
 
+    // Removed files go to the global trash, since .sqrl/ itself leaves
+    let entry = Entry::create(
+        &trash::global_dir()?,
+        Kind::Goaway,
+        &project_root,
+        format!("goaway in {}", project_root.display()),
+    )?;
+
     // Remove skill directory
     if skill_dir.exists() {
-        fs::remove_dir_all(&skill_dir)?;
-        println!("Skill file removed.");
+        trash::move_path(&skill_dir, &entry.payload(SKILL_PAYLOAD))?;
+        human!("Skill file removed.");
     }
 
-    // Remove memory triggers from CLAUDE.md
+    // Remove memory triggers and the pinned-memory section
     remove_memory_triggers(&project_root);
+    if let Ok(config) = Config::effective(&project_root) {
+        match context::remove(&project_root, &config) {
+            Ok(files) if !files.is_empty() => {
+                human!("Pinned memories removed from {}.", files.join(", "))
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "Failed to remove pinned memories"),
+        }
+    }
 
-    // Remove .sqrl/ directory
-    fs::remove_dir_all(&sqrl_dir)?;
-    println!("Removed .sqrl/");
-    println!("Squirrel has left the building.");
+    // Remove .sqrl/ directory (the lock file goes with it)
+    drop(lock);
+    trash::move_path(&sqrl_dir, &entry.payload(SQRL_PAYLOAD))?;
+    human!("Removed .sqrl/ (run 'sqrl undo' to restore it)");
+
+    let mut registry = Registry::load()?;
+    if registry.remove(&project_root) {
+        registry.save()?;
+    }
+
+    human!("Squirrel has left the building.");
+
+    Ok(())
+}
+
+/// Put back what `run` moved to the trash and reinstall git hooks.
+/// MCP registration and CLAUDE.md triggers are left to `sqrl apply` / `sqrl init --repair`.
+pub fn restore(entry: &Entry) -> Result<(), Error> {
+    let project_root = &entry.project;
+    let sqrl_dir = project_root.join(".sqrl");
+    if sqrl_dir.exists() {
+        return Err(Error::Io(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists; remove it or run 'sqrl goaway' first",
+                sqrl_dir.display()
+            ),
+        )));
+    }
+    trash::move_path(&entry.payload(SQRL_PAYLOAD), &sqrl_dir)?;
+
+    let skill = entry.payload(SKILL_PAYLOAD);
+    if skill.exists() {
+        let skill_dir = project_root
+            .join(".claude")
+            .join("skills")
+            .join("squirrel-session");
+        trash::move_path(&skill, &skill_dir)?;
+    }
+
+    if let Ok(config) = Config::effective(project_root) {
+        if hooks::has_git(project_root) && config.hooks.auto_install {
+            hooks::install_hooks(project_root, config.hooks.pre_push_block)?;
+            if config.wants_post_commit_hook() {
+                hooks::install_post_commit_hook(project_root)?;
+            }
+            if config.hooks.on_checkout {
+                hooks::install_post_checkout_hook(project_root)?;
+            }
+        }
+    }
 
+    let mut registry = Registry::load()?;
+    registry.register(project_root);
+    registry.save()?;
     Ok(())
 }
 
@@ -99,10 +180,10 @@ fn remove_memory_triggers(project_root: &std::path::Path) {
             }
             new_content.push('\n');
 
-            if let Err(e) = fs::write(&claude_md_path, new_content) {
+            if let Err(e) = atomic::write(&claude_md_path, new_content) {
                 warn!(error = %e, "Failed to clean CLAUDE.md");
             } else {
-                println!("Memory triggers removed from CLAUDE.md.");
+                human!("Memory triggers removed from CLAUDE.md.");
             }
         }
     }
@@ -115,10 +196,10 @@ fn print_dir_contents(path: &std::path::Path, indent: usize) -> Result<(), Error
             let name = entry.file_name();
             let metadata = entry.metadata()?;
             if metadata.is_dir() {
-                println!("{}{}/", indent_str, name.to_string_lossy());
+                human!("{}{}/", indent_str, name.to_string_lossy());
             } else {
                 let size = metadata.len();
-                println!(
+                human!(
                     "{}{} ({})",
                     indent_str,
                     name.to_string_lossy(),
@@ -152,7 +233,7 @@ fn unregister_claude_code_mcp() {
 
     match output {
         Ok(o) if o.status.success() => {
-            println!("MCP server unregistered from Claude Code.");
+            human!("MCP server unregistered from Claude Code.");
         }
         _ => {
             warn!("Failed to unregister MCP server from Claude Code");
@@ -0,0 +1,164 @@
+//! Search everything Squirrel knows (CLI-020).
+
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobMatcher};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use crate::cli::output::{self, human};
+use crate::config::{find_project_root, Config};
+use crate::error::{Error, ExitCode};
+use crate::global_config::GlobalConfig;
+use crate::storage::{self, lint, Memory, Storage};
+
+/// A matching memory and the database it came from.
+#[derive(Serialize)]
+struct Hit {
+    source: &'static str,
+    #[serde(flatten)]
+    memory: Memory,
+}
+
+/// Search memories in the project and global databases.
+/// Returns exit code 1 when nothing matches, like grep.
+pub fn run(pattern: &str, path: Option<&str>, ignore_case: bool) -> Result<i32, Error> {
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| Error::InvalidArgument(format!("pattern '{}': {}", pattern, e)))?;
+    let path = path
+        .map(|glob| {
+            Glob::new(glob)
+                .map(|g| g.compile_matcher())
+                .map_err(|e| Error::InvalidArgument(format!("--path '{}': {}", glob, e)))
+        })
+        .transpose()?;
+
+    let cwd = std::env::current_dir()?;
+    let root = find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))?;
+    let dbs = [
+        ("project", storage::db_path(&root)),
+        ("global", GlobalConfig::memory_db_path()?),
+    ];
+    let hits = search(&dbs, &regex, path.as_ref())?;
+    output::emit(&hits)?;
+
+    for Hit { source, memory } in &hits {
+        human!(
+            "{}  {:<7}  {}",
+            storage::short_id(&memory.id),
+            source,
+            highlight(&memory.content, &regex)
+        );
+    }
+    Ok(exit_code(&hits))
+}
+
+/// Matching memories in each database that exists, in order.
+fn search(
+    dbs: &[(&'static str, PathBuf)],
+    regex: &Regex,
+    path: Option<&GlobMatcher>,
+) -> Result<Vec<Hit>, Error> {
+    let mut hits = Vec::new();
+    for (source, db) in dbs {
+        if !db.exists() {
+            continue;
+        }
+        for memory in Storage::open(db)?.list_all_memories()? {
+            if matches(&memory, regex, path) {
+                hits.push(Hit { source, memory });
+            }
+        }
+    }
+    Ok(hits)
+}
+
+/// 1 when nothing matched, like grep (ERROR-002).
+fn exit_code(hits: &[Hit]) -> i32 {
+    if hits.is_empty() {
+        ExitCode::Problems.code()
+    } else {
+        0
+    }
+}
+
+/// Content or a tag matches, and with `--path`, a mentioned file matches too.
+fn matches(memory: &Memory, regex: &Regex, path: Option<&GlobMatcher>) -> bool {
+    let text = regex.is_match(&memory.content) || memory.tags.iter().any(|t| regex.is_match(t));
+    text && path.is_none_or(|glob| {
+        lint::mentioned_paths(&memory.content)
+            .iter()
+            .any(|p| glob.is_match(Path::new(p.trim_end_matches('/'))))
+    })
+}
+
+fn highlight(text: &str, regex: &Regex) -> String {
+    regex
+        .replace_all(text, |caps: &regex::Captures| {
+            output::paint(&caps[0], "1;31")
+        })
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_search_content_tag_and_path_matches_listed() {
+        let dir = TempDir::new().unwrap();
+        let project = dir.path().join("project.db");
+        let storage = Storage::open(&project).unwrap();
+        storage
+            .store_memory("project", "Run migrations in src/db/migrate.rs", &[])
+            .unwrap();
+        storage
+            .store_memory("project", "Use pnpm", &["tooling".to_string()])
+            .unwrap();
+        let dbs = [
+            ("project", project),
+            ("global", dir.path().join("missing.db")),
+        ];
+        let regex = |pattern: &str| RegexBuilder::new(pattern).build().unwrap();
+        let found = |pattern: &str, path: Option<&str>| -> Vec<String> {
+            let glob = path.map(|g| Glob::new(g).unwrap().compile_matcher());
+            search(&dbs, &regex(pattern), glob.as_ref())
+                .unwrap()
+                .into_iter()
+                .map(|hit| format!("{}: {}", hit.source, hit.memory.content))
+                .collect()
+        };
+
+        assert_eq!(
+            found("migrat", None),
+            ["project: Run migrations in src/db/migrate.rs"]
+        );
+        assert_eq!(found("^tool", None), ["project: Use pnpm"]);
+        assert_eq!(found("Run|pnpm", Some("src/db/*")).len(), 1);
+        assert!(found("migrat", Some("tests/**")).is_empty());
+    }
+
+    #[test]
+    fn test_exit_code_no_hits_problems() {
+        assert_eq!(exit_code(&[]), ExitCode::Problems.code());
+        let memory = Memory {
+            id: "a".to_string(),
+            memory_type: "project".to_string(),
+            content: "Use pnpm".to_string(),
+            tags: Vec::new(),
+            use_count: 1,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        assert_eq!(
+            exit_code(&[Hit {
+                source: "project",
+                memory
+            }]),
+            0
+        );
+    }
+}
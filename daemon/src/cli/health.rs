@@ -0,0 +1,234 @@
+//! Project health score (`sqrl status --score`, CONFIG-011).
+//!
+//! Three parts, each scored 0-100 and weighted by `health.weights`: doc
+//! debt (code committed since docs last changed, by count and age), memory
+//! freshness (share of memories stored, edited or reinforced lately) and
+//! hook status (the `hooks` check of `sqrl status`). A part that can't be
+//! measured, such as doc debt outside a repository, is left out rather than
+//! scored 0. The web UI serves the same score as JSON and as an SVG badge.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::cli::git;
+use crate::cli::memory;
+use crate::cli::output::{self, human};
+use crate::cli::status::{self, CheckStatus};
+use crate::config::{Config, HealthConfig};
+use crate::error::Error;
+use crate::storage::{self, Storage};
+
+/// One part of the score.
+#[derive(Debug, Clone, Serialize)]
+pub struct Part {
+    /// `docs`, `memories` or `hooks`.
+    pub name: &'static str,
+    pub weight: u32,
+    /// 0-100, or none if it couldn't be measured.
+    pub score: Option<u8>,
+    pub detail: String,
+}
+
+/// A part's score, if measured, and what it is based on.
+type Reading = (Option<u8>, String);
+
+/// The weighted score and its parts.
+#[derive(Debug, Clone, Serialize)]
+pub struct Health {
+    /// 0-100, or none if no weighted part could be measured.
+    pub score: Option<u8>,
+    pub parts: Vec<Part>,
+}
+
+/// Score a project.
+pub fn assess(project_root: &Path) -> Result<Health, Error> {
+    let config = Config::effective(project_root)?;
+    let settings = &config.health;
+    settings.check()?;
+    let part = |name, weight, (score, detail)| Part {
+        name,
+        weight,
+        score,
+        detail,
+    };
+    let parts = vec![
+        part(
+            "docs",
+            settings.weights.docs,
+            doc_debt(project_root, &config)?,
+        ),
+        part(
+            "memories",
+            settings.weights.memories,
+            freshness(project_root, settings)?,
+        ),
+        part("hooks", settings.weights.hooks, hooks(project_root)),
+    ];
+    Ok(Health {
+        score: weighted(&parts),
+        parts,
+    })
+}
+
+/// Print the score of the current project.
+pub fn run() -> Result<(), Error> {
+    let health = assess(&memory::root()?)?;
+    human!("Squirrel Health: {}", describe(health.score));
+    for part in &health.parts {
+        human!(
+            "  {:<9} {:>3}  weight {:<3} {}",
+            part.name,
+            part.score.map_or("-".to_string(), |s| s.to_string()),
+            part.weight,
+            part.detail
+        );
+    }
+    output::emit(&health)
+}
+
+/// Weighted mean of the measured parts with a weight above 0.
+fn weighted(parts: &[Part]) -> Option<u8> {
+    let (total, weights) = parts
+        .iter()
+        .filter_map(|part| Some((u64::from(part.score?), u64::from(part.weight))))
+        .fold((0, 0), |(total, weights), (score, weight)| {
+            (total + score * weight, weights + weight)
+        });
+    (weights > 0).then(|| ((total + weights / 2) / weights) as u8)
+}
+
+/// `82/100`, or `unknown`.
+fn describe(score: Option<u8>) -> String {
+    score.map_or("unknown".to_string(), |s| format!("{}/100", s))
+}
+
+/// 100 when nothing is left, 0 at `limit` or beyond.
+fn remaining(used: u64, limit: u32) -> u8 {
+    let limit = u64::from(limit.max(1));
+    (100 - used.min(limit) * 100 / limit) as u8
+}
+
+fn doc_debt(project_root: &Path, config: &Config) -> Result<Reading, Error> {
+    let settings = &config.health;
+    let matcher = config.doc_matcher()?;
+    let limit = settings.doc_debt_commits as usize;
+    let Some(times) = git::undocumented_commits(project_root, |p| matcher.is_doc(p), limit)? else {
+        return Ok(unmeasured("not a git repository, or no commits"));
+    };
+    let Some(&oldest) = times.last() else {
+        return Ok(measured(100, "docs changed in the latest commit"));
+    };
+    let age = (chrono::Utc::now().timestamp() - oldest).max(0) as u64;
+    let days = age / 86_400;
+    let score = remaining(times.len() as u64, settings.doc_debt_commits)
+        .min(remaining(days, settings.doc_debt_days));
+    let commits = match times.len() {
+        1 => "1 commit".to_string(),
+        n if n >= limit => format!("{}+ commits", limit),
+        n => format!("{} commits", n),
+    };
+    Ok(measured(
+        score,
+        format!(
+            "{} without a doc change, oldest {}",
+            commits,
+            status::human_age(age)
+        ),
+    ))
+}
+
+fn freshness(project_root: &Path, settings: &HealthConfig) -> Result<Reading, Error> {
+    if let Some(problem) = storage::check_health(project_root) {
+        return Ok(unmeasured(problem.to_string()));
+    }
+    let memories = Storage::open(&storage::db_path(project_root))?.list_all_memories()?;
+    if memories.is_empty() {
+        return Ok(unmeasured("no memories yet"));
+    }
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(settings.stale_days.into());
+    let fresh = memories
+        .iter()
+        .filter(|m| {
+            chrono::DateTime::parse_from_rfc3339(&m.updated_at).is_ok_and(|at| at >= cutoff)
+        })
+        .count();
+    Ok(measured(
+        (fresh * 100 / memories.len()) as u8,
+        format!(
+            "{} of {} memories used or changed in the last {} days",
+            fresh,
+            memories.len(),
+            settings.stale_days
+        ),
+    ))
+}
+
+fn hooks(project_root: &Path) -> Reading {
+    let check = status::check_hooks(project_root);
+    match check.status {
+        CheckStatus::Ok => measured(100, check.message),
+        CheckStatus::Fail => measured(0, check.message),
+        CheckStatus::Skip => unmeasured(check.message),
+    }
+}
+
+fn measured(score: u8, detail: impl Into<String>) -> Reading {
+    (Some(score), detail.into())
+}
+
+fn unmeasured(detail: impl Into<String>) -> Reading {
+    (None, detail.into())
+}
+
+/// Flat SVG badge reading `squirrel | 82%`: green from 80, yellow from 50,
+/// red below, grey when unknown.
+pub fn badge(score: Option<u8>) -> String {
+    let (value, color) = match score {
+        Some(s) if s >= 80 => (format!("{}%", s), "#4c1"),
+        Some(s) if s >= 50 => (format!("{}%", s), "#dfb317"),
+        Some(s) => (format!("{}%", s), "#e05d44"),
+        None => ("unknown".to_string(), "#9f9f9f"),
+    };
+    let label = "squirrel";
+    // Verdana 11px averages about 7px a character
+    let label_width = label.len() * 7 + 10;
+    let value_width = value.len() * 7 + 10;
+    let width = label_width + value_width;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}"><title>{label}: {value}</title><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/><g fill="#fff" text-anchor="middle" font-family="Verdana,DejaVu Sans,sans-serif" font-size="11"><text x="{label_x}" y="14">{label}</text><text x="{value_x}" y="14">{value}</text></g></svg>"##,
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_weighs_measured_parts_only() {
+        let part = |name, weight, score| Part {
+            name,
+            weight,
+            score,
+            detail: String::new(),
+        };
+        let parts = [
+            part("docs", 40, Some(50)),
+            part("memories", 30, Some(100)),
+            part("hooks", 30, None),
+        ];
+        assert_eq!(weighted(&parts), Some(71));
+        assert_eq!(weighted(&parts[2..]), None);
+        assert_eq!(weighted(&[part("docs", 0, Some(10))]), None);
+
+        assert_eq!(remaining(0, 20), 100);
+        assert_eq!(remaining(5, 20), 75);
+        assert_eq!(remaining(40, 20), 0);
+
+        assert!(badge(Some(71)).contains(">71%</text>"));
+        assert!(badge(Some(71)).contains("#dfb317"));
+        assert!(badge(None).contains(">unknown</text>"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Writing, upgrading and removing hooks, keeping other tools' lines.
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use tracing::info;
+
+use super::scripts::{
+    pre_push_script, HOOK_NAMES, HOOK_VERSION, POST_CHECKOUT_HOOK, POST_COMMIT_HOOK,
+};
+use super::section::{append_section, is_empty_script, remove_squirrel_section};
+use super::state::{post_checkout_state, post_commit_state, pre_push_state, HookState};
+use crate::atomic;
+use crate::error::Error;
+
+/// Install Squirrel git hooks.
+pub fn install_hooks(project_root: &Path, pre_push_block: bool) -> Result<(), Error> {
+    let git_dir = project_root.join(".git");
+    if !git_dir.exists() {
+        return Ok(()); // No git, nothing to do
+    }
+
+    let hooks_dir = git_dir.join("hooks");
+    fs::create_dir_all(&hooks_dir)?;
+
+    // Install pre-push hook only
+    let pre_push_path = hooks_dir.join("pre-push");
+    install_hook(&pre_push_path, pre_push_script(pre_push_block))?;
+    info!(blocking = pre_push_block, "Installed pre-push hook");
+
+    Ok(())
+}
+
+/// Install the post-commit hook (snapshots and the pinned-memory section).
+pub fn install_post_commit_hook(project_root: &Path) -> Result<(), Error> {
+    let hooks_dir = project_root.join(".git").join("hooks");
+    fs::create_dir_all(&hooks_dir)?;
+    install_hook(&hooks_dir.join("post-commit"), POST_COMMIT_HOOK)?;
+    info!("Installed post-commit hook");
+    Ok(())
+}
+
+/// Rewrite the Squirrel hooks that are installed but out of date, keeping
+/// the rest of each file. Returns the hooks rewritten.
+pub fn upgrade_hooks(
+    project_root: &Path,
+    pre_push_block: bool,
+) -> Result<Vec<&'static str>, Error> {
+    let hooks_dir = project_root.join(".git").join("hooks");
+    let mut upgraded = Vec::new();
+    if pre_push_state(project_root, pre_push_block) == HookState::Stale {
+        rewrite_hook(&hooks_dir.join("pre-push"), pre_push_script(pre_push_block))?;
+        upgraded.push("pre-push");
+    }
+    if post_commit_state(project_root) == HookState::Stale {
+        rewrite_hook(&hooks_dir.join("post-commit"), POST_COMMIT_HOOK)?;
+        upgraded.push("post-commit");
+    }
+    if post_checkout_state(project_root) == HookState::Stale {
+        rewrite_hook(&hooks_dir.join("post-checkout"), POST_CHECKOUT_HOOK)?;
+        upgraded.push("post-checkout");
+    }
+    for hook in &upgraded {
+        info!(hook, version = HOOK_VERSION, "Upgraded hook");
+    }
+    Ok(upgraded)
+}
+
+/// Install the post-checkout hook (`hooks.on_checkout`).
+pub fn install_post_checkout_hook(project_root: &Path) -> Result<(), Error> {
+    let hooks_dir = project_root.join(".git").join("hooks");
+    fs::create_dir_all(&hooks_dir)?;
+    install_hook(&hooks_dir.join("post-checkout"), POST_CHECKOUT_HOOK)?;
+    info!("Installed post-checkout hook");
+    Ok(())
+}
+
+/// Install a single hook, preserving existing hooks.
+fn install_hook(path: &Path, content: &str) -> Result<(), Error> {
+    let final_content = if path.exists() {
+        let existing = fs::read_to_string(path)?;
+
+        // Already has our hook
+        if existing.contains("Squirrel") {
+            return Ok(());
+        }
+
+        // Append to existing hook
+        append_section(&existing, content)
+    } else {
+        content.to_string()
+    };
+    write_hook(path, &final_content)
+}
+
+/// Replace the Squirrel section of a hook, preserving the rest.
+fn rewrite_hook(path: &Path, content: &str) -> Result<(), Error> {
+    let cleaned = remove_squirrel_section(&fs::read_to_string(path)?);
+    let final_content = if is_empty_script(&cleaned) {
+        content.to_string()
+    } else {
+        append_section(&cleaned, content)
+    };
+    write_hook(path, &final_content)
+}
+
+fn write_hook(path: &Path, content: &str) -> Result<(), Error> {
+    atomic::write(path, content)?;
+
+    // Make executable
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms)?;
+
+    Ok(())
+}
+
+/// Uninstall Squirrel git hooks.
+pub fn uninstall_hooks(project_root: &Path) -> Result<(), Error> {
+    let hooks_dir = project_root.join(".git").join("hooks");
+    if !hooks_dir.exists() {
+        return Ok(());
+    }
+
+    // The post-commit file may hold the old docguard-record hook
+    for hook in HOOK_NAMES {
+        let hook_path = hooks_dir.join(hook);
+        if !hook_path.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(&hook_path)?;
+        if content.contains("Squirrel") {
+            // Remove our section or the entire file
+            let cleaned = remove_squirrel_section(&content);
+            if is_empty_script(&cleaned) {
+                fs::remove_file(&hook_path)?;
+            } else {
+                atomic::write(&hook_path, format!("{}\n", cleaned.trim_end()))?;
+            }
+            info!(hook, "Removed Squirrel hook");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::hooks::scripts::{installed_version, PRE_PUSH_HOOK, PRE_PUSH_HOOK_BLOCKING};
+    use crate::cli::hooks::section::squirrel_section;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_stale_hooks_are_upgraded_in_place() {
+        for script in [
+            PRE_PUSH_HOOK,
+            PRE_PUSH_HOOK_BLOCKING,
+            POST_COMMIT_HOOK,
+            POST_CHECKOUT_HOOK,
+        ] {
+            assert_eq!(installed_version(script), HOOK_VERSION);
+        }
+
+        let dir = TempDir::new().unwrap();
+        let hooks_dir = dir.path().join(".git").join("hooks");
+        fs::create_dir_all(&hooks_dir).unwrap();
+        let old = "#!/bin/sh\nrun-linter\n\n# Squirrel: shows changes for doc review before push\n\
+                   sqrl _internal docguard-check 2>/dev/null || true\n";
+        fs::write(hooks_dir.join("pre-push"), old).unwrap();
+        assert_eq!(installed_version(old), 1);
+        assert_eq!(pre_push_state(dir.path(), false), HookState::Stale);
+        assert_eq!(post_commit_state(dir.path()), HookState::Missing);
+
+        assert_eq!(upgrade_hooks(dir.path(), false).unwrap(), ["pre-push"]);
+        assert_eq!(pre_push_state(dir.path(), false), HookState::Current);
+        let content = fs::read_to_string(hooks_dir.join("pre-push")).unwrap();
+        assert!(content.starts_with("#!/bin/sh\nrun-linter\n"));
+        assert!(upgrade_hooks(dir.path(), false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_only_the_marked_section_is_removed() {
+        let dir = TempDir::new().unwrap();
+        let hooks_dir = dir.path().join(".git").join("hooks");
+        fs::create_dir_all(&hooks_dir).unwrap();
+        let path = hooks_dir.join("pre-push");
+        let theirs = "#!/bin/sh\nmake docs\n./scripts/check-docstrings\n";
+        fs::write(&path, theirs).unwrap();
+
+        install_hook(&path, PRE_PUSH_HOOK).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches("#!/bin/sh").count(), 1);
+        assert_eq!(pre_push_state(dir.path(), false), HookState::Current);
+        assert_eq!(
+            squirrel_section(&content),
+            PRE_PUSH_HOOK.trim_start_matches("#!/bin/sh\n").trim_end()
+        );
+
+        rewrite_hook(&path, PRE_PUSH_HOOK_BLOCKING).unwrap();
+        assert_eq!(pre_push_state(dir.path(), true), HookState::Current);
+        uninstall_hooks(dir.path()).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), theirs);
+    }
+}
@@ -0,0 +1,133 @@
+//! Git hook installation and management.
+
+mod install;
+mod scripts;
+mod section;
+mod state;
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::cli::internal;
+use crate::cli::output::{self, human};
+use crate::config::{find_project_root, Config};
+use crate::error::{Error, ExitCode};
+use crate::lock::SqrlLock;
+
+pub use install::{
+    install_hooks, install_post_checkout_hook, install_post_commit_hook, uninstall_hooks,
+    upgrade_hooks,
+};
+pub use scripts::{installed_version, scripts, HOOK_NAMES, HOOK_VERSION};
+pub use section::{has_markers, squirrel_section, RETIRED_LINES};
+pub use state::{post_checkout_state, post_commit_state, pre_push_state, HookState};
+
+/// Hooks `sqrl hook run` can run by hand (CLI-025).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Hook {
+    PrePush,
+    PostCommit,
+    PostCheckout,
+}
+
+/// Run a hook's handler as git would, saying first whether the hook is
+/// installed. Returns the exit code the hook would have.
+pub fn run(hook: Hook, commit: Option<&str>) -> Result<i32, Error> {
+    let cwd = std::env::current_dir()?;
+    let project_root =
+        find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))?;
+    if commit.is_some() && hook != Hook::PostCommit {
+        return Err(Error::InvalidArgument(
+            "--commit only applies to post-commit".to_string(),
+        ));
+    }
+    let config = Config::effective(&project_root)?;
+
+    match hook {
+        Hook::PrePush => {
+            let state = match pre_push_state(&project_root, config.hooks.pre_push_block) {
+                HookState::Current => "installed",
+                HookState::Stale => "out of date (run 'sqrl hook upgrade')",
+                HookState::Missing => "not installed",
+            };
+            human!("pre-push hook: {}", state);
+            let allowed = internal::docguard_check()?;
+            human!(
+                "pre-push would {}.",
+                if allowed {
+                    "allow the push"
+                } else {
+                    "block the push"
+                }
+            );
+            Ok(if allowed {
+                0
+            } else {
+                ExitCode::PushBlocked.code()
+            })
+        }
+        Hook::PostCommit => {
+            let state = match (
+                post_commit_state(&project_root),
+                config.wants_post_commit_hook(),
+            ) {
+                (HookState::Current, _) => "installed",
+                (HookState::Stale, _) => "out of date (run 'sqrl hook upgrade')",
+                (HookState::Missing, true) => "not installed (run 'sqrl init --repair')",
+                (HookState::Missing, false) => {
+                    "not needed (hooks.snapshots and context.section are off)"
+                }
+            };
+            human!("post-commit hook: {}", state);
+            internal::post_commit(commit)?;
+            Ok(0)
+        }
+        Hook::PostCheckout => {
+            let state = match (post_checkout_state(&project_root), config.hooks.on_checkout) {
+                (HookState::Current, _) => "installed",
+                (HookState::Stale, _) => "out of date (run 'sqrl hook upgrade')",
+                (HookState::Missing, true) => "not installed (run 'sqrl init --repair')",
+                (HookState::Missing, false) => "not needed (hooks.on_checkout is off)",
+            };
+            human!("post-checkout hook: {}", state);
+            internal::post_checkout()?;
+            Ok(0)
+        }
+    }
+}
+
+/// Rewrite out-of-date hooks in the current project (`sqrl hook upgrade`).
+pub fn upgrade() -> Result<(), Error> {
+    let cwd = std::env::current_dir()?;
+    let project_root =
+        find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))?;
+    let config = Config::effective(&project_root)?;
+    let _lock = SqrlLock::project(&project_root)?;
+    let upgraded = upgrade_hooks(&project_root, config.hooks.pre_push_block)?;
+
+    for hook in &upgraded {
+        human!("Upgraded {} hook to version {}.", hook, HOOK_VERSION);
+    }
+    if upgraded.is_empty() {
+        human!("Installed hooks are current (version {}).", HOOK_VERSION);
+    }
+    let pre_push_missing = pre_push_state(&project_root, config.hooks.pre_push_block)
+        == HookState::Missing
+        && config.hooks.auto_install;
+    let post_commit_missing =
+        post_commit_state(&project_root) == HookState::Missing && config.wants_post_commit_hook();
+    let post_checkout_missing =
+        post_checkout_state(&project_root) == HookState::Missing && config.hooks.on_checkout;
+    if pre_push_missing || post_commit_missing || post_checkout_missing {
+        human!("Some hooks are not installed; run 'sqrl init --repair'.");
+    }
+    output::emit(&json!({ "version": HOOK_VERSION, "upgraded": upgraded }))?;
+    Ok(())
+}
+
+/// Check if git is initialized in the project.
+pub fn has_git(project_root: &Path) -> bool {
+    project_root.join(".git").exists()
+}
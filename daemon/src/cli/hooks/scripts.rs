@@ -0,0 +1,125 @@
+//! The scripts Squirrel writes into `.git/hooks`.
+
+/// Version of the scripts below. Bump it whenever one changes, so copies
+/// installed by older binaries show up as out of date.
+pub const HOOK_VERSION: u32 = 4;
+
+/// Hooks Squirrel may install.
+pub const HOOK_NAMES: [&str; 3] = ["pre-push", "post-commit", "post-checkout"];
+
+/// Start of the line carrying the version in each script.
+const VERSION_MARKER: &str = "# Squirrel hook version ";
+
+/// Pre-push hook script content.
+/// Shows diff summary for AI to review before push. Only a blocking plugin
+/// (exit 20) stops the push; any other failure lets it through.
+pub(super) const PRE_PUSH_HOOK: &str = r#"#!/bin/sh
+# START Squirrel hook
+# Squirrel: shows changes for doc review before push
+# AI reads this output and decides if docs need updating
+# Squirrel hook version 4
+
+sqrl _internal docguard-check || [ $? -ne 20 ] || exit 1
+# END Squirrel hook
+"#;
+
+/// Blocking pre-push hook (`hooks.pre_push_block`).
+/// Fails the push when code changed without any doc update.
+pub(super) const PRE_PUSH_HOOK_BLOCKING: &str = r#"#!/bin/sh
+# START Squirrel hook
+# Squirrel: blocks pushes that change code without updating docs
+# Squirrel: bypass with git push --no-verify
+# Squirrel hook version 4
+
+sqrl _internal docguard-check || exit 1
+# END Squirrel hook
+"#;
+
+/// Post-commit hook (`hooks.snapshots`, `context.section`).
+/// Records which memories were active at each commit and refreshes pinned memories.
+pub(super) const POST_COMMIT_HOOK: &str = r#"#!/bin/sh
+# START Squirrel hook
+# Squirrel: records active memories and refreshes pinned memories in CLAUDE.md
+# Squirrel hook version 4
+
+sqrl _internal post-commit 2>/dev/null || true
+# END Squirrel hook
+"#;
+
+/// Post-checkout hook (`hooks.on_checkout`).
+/// Regenerates files derived from memories when the branch changes.
+pub(super) const POST_CHECKOUT_HOOK: &str = r#"#!/bin/sh
+# START Squirrel hook
+# Squirrel: refreshes files generated from memories after switching branches
+# Squirrel hook version 4
+
+[ "$3" = "1" ] && sqrl _internal post-checkout >/dev/null 2>&1 || true
+# END Squirrel hook
+"#;
+
+/// Version of the Squirrel script in a hook file. Scripts from before
+/// versioning have no marker and count as version 1.
+pub fn installed_version(content: &str) -> u32 {
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(VERSION_MARKER)?.parse().ok())
+        .unwrap_or(1)
+}
+
+/// Every script this version may write for `hook` (one of `HOOK_NAMES`).
+pub fn scripts(hook: &str) -> &'static [&'static str] {
+    match hook {
+        "pre-push" => &[PRE_PUSH_HOOK, PRE_PUSH_HOOK_BLOCKING],
+        "post-commit" => &[POST_COMMIT_HOOK],
+        "post-checkout" => &[POST_CHECKOUT_HOOK],
+        _ => &[],
+    }
+}
+
+pub(super) fn pre_push_script(pre_push_block: bool) -> &'static str {
+    if pre_push_block {
+        PRE_PUSH_HOOK_BLOCKING
+    } else {
+        PRE_PUSH_HOOK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ExitCode;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pre_push_hook_blocking_plugin_exit_blocks_push() {
+        let dir = TempDir::new().unwrap();
+        let sqrl = dir.path().join("sqrl");
+        let hook = dir.path().join("pre-push");
+        fs::write(&hook, PRE_PUSH_HOOK).unwrap();
+        let run = |script: &Path, code: i32| {
+            fs::write(
+                &sqrl,
+                format!("#!/bin/sh\necho \"plugin failed\" >&2\nexit {}\n", code),
+            )
+            .unwrap();
+            fs::set_permissions(&sqrl, fs::Permissions::from_mode(0o755)).unwrap();
+            let out = std::process::Command::new("sh")
+                .arg(script)
+                .env("PATH", format!("{}:/bin:/usr/bin", dir.path().display()))
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8_lossy(&out.stderr), "plugin failed\n");
+            out.status.success()
+        };
+
+        assert!(run(&hook, 0));
+        assert!(!run(&hook, ExitCode::PushBlocked.code()));
+        assert!(run(&hook, ExitCode::Failure.code()));
+
+        fs::write(&hook, PRE_PUSH_HOOK_BLOCKING).unwrap();
+        assert!(!run(&hook, ExitCode::Failure.code()));
+    }
+}
@@ -0,0 +1,82 @@
+//! Squirrel's section of a hook file, which may hold other tools' lines too.
+
+/// Lines around Squirrel's section of a hook. Only what lies between them is
+/// Squirrel's to rewrite or remove.
+const START: &str = "# START Squirrel hook";
+const END: &str = "# END Squirrel hook";
+
+/// `existing` followed by the section of `script`, without its shebang.
+pub(super) fn append_section(existing: &str, script: &str) -> String {
+    let section = script.strip_prefix("#!/bin/sh\n").unwrap_or(script);
+    format!("{}\n\n{}", existing.trim(), section)
+}
+
+/// Whether only shebangs and whitespace remain.
+pub(super) fn is_empty_script(content: &str) -> bool {
+    content
+        .lines()
+        .all(|line| line.trim().is_empty() || line.starts_with("#!"))
+}
+
+/// Remove Squirrel section from hook content.
+pub(super) fn remove_squirrel_section(content: &str) -> String {
+    let ours = squirrel_lines(content);
+    content
+        .lines()
+        .zip(ours)
+        .filter(|(_, ours)| !ours)
+        .map(|(line, _)| line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The lines of a hook that Squirrel wrote (what `sqrl hook upgrade` replaces).
+pub fn squirrel_section(content: &str) -> String {
+    content
+        .lines()
+        .zip(squirrel_lines(content))
+        .filter(|(_, ours)| *ours)
+        .map(|(line, _)| line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether `content` has a marked Squirrel section (version 3 and later).
+pub fn has_markers(content: &str) -> bool {
+    content.lines().any(|line| line.trim() == START)
+}
+
+/// Lines marked scripts of earlier versions wrote that the current ones no
+/// longer do, so hooks not yet upgraded aren't taken for tampered ones.
+pub const RETIRED_LINES: [&str; 1] = ["sqrl _internal docguard-check 2>/dev/null || true"];
+
+/// For each line of `content`, whether it is Squirrel's: the lines from
+/// `START` to `END`, or in hooks from before the markers (version 2 and
+/// older) the exact lines those scripts wrote.
+fn squirrel_lines(content: &str) -> Vec<bool> {
+    if !has_markers(content) {
+        return content.lines().map(is_legacy_line).collect();
+    }
+    let mut inside = false;
+    content
+        .lines()
+        .map(|line| match line.trim() {
+            START => {
+                inside = true;
+                true
+            }
+            END if inside => {
+                inside = false;
+                true
+            }
+            _ => inside,
+        })
+        .collect()
+}
+
+/// A line of an unmarked script from version 2 or older.
+fn is_legacy_line(line: &str) -> bool {
+    line.starts_with("# Squirrel")
+        || line.starts_with("# AI reads this output")
+        || line.contains("sqrl _internal ")
+}
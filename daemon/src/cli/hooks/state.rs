@@ -0,0 +1,61 @@
+//! Whether each hook is installed and current.
+
+use std::fs;
+use std::path::Path;
+
+use super::scripts::{pre_push_script, POST_CHECKOUT_HOOK, POST_COMMIT_HOOK};
+
+/// State of a Squirrel hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    Missing,
+    /// Installed, but not the script this version (and blocking mode) would write.
+    Stale,
+    Current,
+}
+
+/// Check the Squirrel pre-push hook against the expected script.
+pub fn pre_push_state(project_root: &Path, pre_push_block: bool) -> HookState {
+    hook_state(
+        &project_root.join(".git").join("hooks").join("pre-push"),
+        pre_push_script(pre_push_block),
+    )
+}
+
+/// Check the Squirrel post-commit hook against the expected script.
+pub fn post_commit_state(project_root: &Path) -> HookState {
+    hook_state(
+        &project_root.join(".git").join("hooks").join("post-commit"),
+        POST_COMMIT_HOOK,
+    )
+}
+
+/// Check the Squirrel post-checkout hook against the expected script.
+pub fn post_checkout_state(project_root: &Path) -> HookState {
+    hook_state(
+        &project_root
+            .join(".git")
+            .join("hooks")
+            .join("post-checkout"),
+        POST_CHECKOUT_HOOK,
+    )
+}
+
+fn hook_state(path: &Path, expected: &str) -> HookState {
+    let Ok(content) = fs::read_to_string(path) else {
+        return HookState::Missing;
+    };
+
+    // Check if it's our hook (contains "Squirrel")
+    if !content.contains("Squirrel") {
+        return HookState::Missing;
+    }
+
+    // Our block may be appended to another hook, so compare without the shebang
+    let body = expected.trim_start_matches("#!/bin/sh").trim();
+    if content.contains(body) {
+        HookState::Current
+    } else {
+        HookState::Stale
+    }
+}
@@ -0,0 +1,488 @@
+//! Bulk import from other tools (`sqrl import`, CLI-029).
+//!
+//! From rule files, each list item becomes a memory, tagged with the slug of
+//! the heading it sits under, so "## Testing" gives `testing`. Items under a
+//! heading that mentions preferences become `preference` memories, the rest
+//! `project`. From SQLite and JSON stores, each row or object becomes a
+//! memory, with `Columns` naming the fields to read. From formatter configs,
+//! each setting becomes a `code-style` rule (styles.rs).
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::cli::memory::{self, IMPORT_TAG};
+use crate::cli::output::{self, human};
+use crate::cli::progress::Progress;
+use crate::cli::styles;
+use crate::error::Error;
+use crate::lock::SqrlLock;
+use crate::storage::{self, NewMemory, Storage};
+
+/// File format `sqrl import --from` reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    /// Cursor's `.cursorrules`: list items, or every line if it has none.
+    Cursorrules,
+    /// A CLAUDE.md or AGENTS.md style file: list items.
+    ClaudeMd,
+    /// A table in an SQLite database, such as an old `memory.db`.
+    Sqlite,
+    /// A JSON array of objects, or an object holding one.
+    Json,
+    /// An `.editorconfig`: one code style rule per setting and section.
+    Editorconfig,
+    /// A `rustfmt.toml`: one code style rule per setting.
+    Rustfmt,
+    /// A Prettier config (`.prettierrc`, JSON or YAML): one code style rule per option.
+    Prettier,
+}
+
+/// Where `--from sqlite|json` finds each field. Unset type and tags columns
+/// are read from `memory_type` and `tags` when those exist.
+#[derive(Debug, Clone)]
+pub struct Columns {
+    /// SQLite table, or the JSON key holding the array.
+    pub table: String,
+    pub content: String,
+    pub memory_type: Option<String>,
+    pub tags: Option<String>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            table: "memories".to_string(),
+            content: "content".to_string(),
+            memory_type: None,
+            tags: None,
+        }
+    }
+}
+
+/// A list item of a markdown file and the heading above it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Item {
+    pub heading: Option<String>,
+    pub text: String,
+}
+
+/// A memory the import would create.
+#[derive(Debug, Serialize)]
+struct Imported {
+    memory_type: &'static str,
+    content: String,
+    tags: Vec<String>,
+    /// Already a memory, so skipped.
+    exists: bool,
+}
+
+/// Import `path` into the current project. With `dry_run`, only list what
+/// would be stored.
+pub fn run(from: ImportSource, path: &Path, columns: &Columns, dry_run: bool) -> Result<(), Error> {
+    let mut imported = match from {
+        ImportSource::Sqlite => from_sqlite(path, columns)?,
+        ImportSource::Json => from_json(path, columns)?,
+        ImportSource::Editorconfig | ImportSource::Rustfmt | ImportSource::Prettier => {
+            let content = fs::read_to_string(path).map_err(Error::at(path))?;
+            let rules = match from {
+                ImportSource::Editorconfig => styles::from_editorconfig(&content),
+                ImportSource::Rustfmt => styles::from_rustfmt(&content)?,
+                _ => styles::from_prettier(&content)?,
+            };
+            rules
+                .into_iter()
+                .map(|content| Imported {
+                    memory_type: "project",
+                    content,
+                    tags: vec![IMPORT_TAG.to_string(), styles::CODE_STYLE_TAG.to_string()],
+                    exists: false,
+                })
+                .collect()
+        }
+        ImportSource::Cursorrules | ImportSource::ClaudeMd => {
+            let content = fs::read_to_string(path).map_err(Error::at(path))?;
+            let mut items = list_items(&content);
+            if items.is_empty() && from == ImportSource::Cursorrules {
+                items = plain_lines(&content);
+            }
+            items
+                .into_iter()
+                .map(|item| {
+                    let (memory_type, tags) = categorize(item.heading.as_deref());
+                    Imported {
+                        memory_type,
+                        content: item.text,
+                        tags,
+                        exists: false,
+                    }
+                })
+                .collect()
+        }
+    };
+
+    let root = memory::root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let mut progress = Progress::new("Importing", imported.len() as u64);
+    let mut seen = HashSet::new();
+    for memory in &mut imported {
+        memory.exists =
+            !seen.insert(memory.content.clone()) || storage.has_content(&memory.content)?;
+        progress.inc(1);
+    }
+    if !dry_run {
+        // One transaction: an interrupted import stores nothing
+        let batch: Vec<NewMemory> = imported
+            .iter()
+            .filter(|m| !m.exists)
+            .map(|m| NewMemory {
+                memory_type: m.memory_type.to_string(),
+                content: m.content.clone(),
+                tags: m.tags.clone(),
+            })
+            .collect();
+        storage.add_memories_batch(&batch)?;
+    }
+    progress.finish();
+    output::emit(&json!({ "dry_run": dry_run, "memories": imported }))?;
+
+    let new = imported.iter().filter(|m| !m.exists).count();
+    for memory in &imported {
+        let mark = if memory.exists { "=" } else { "+" };
+        human!(
+            "{} {:<10}  [{}] {}",
+            mark,
+            memory.memory_type,
+            memory.tags.join(", "),
+            memory.content
+        );
+    }
+    human!();
+    human!(
+        "{} {} new memories from {} ({} already stored).",
+        if dry_run { "Would import" } else { "Imported" },
+        new,
+        path.display(),
+        imported.len() - new
+    );
+    Ok(())
+}
+
+/// Type and tags for items under `heading`.
+fn categorize(heading: Option<&str>) -> (&'static str, Vec<String>) {
+    let mut tags = vec![IMPORT_TAG.to_string()];
+    let Some(heading) = heading else {
+        return ("project", tags);
+    };
+    let slug = heading
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let memory_type = if slug.contains("preference") {
+        "preference"
+    } else {
+        "project"
+    };
+    if !slug.is_empty() {
+        tags.push(slug);
+    }
+    (memory_type, tags)
+}
+
+/// Rows of `columns.table` in the SQLite database at `path`.
+fn from_sqlite(path: &Path, columns: &Columns) -> Result<Vec<Imported>, Error> {
+    if !path.is_file() {
+        return Err(Error::InvalidArgument(format!(
+            "no database at {}",
+            path.display()
+        )));
+    }
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+    let available = stmt
+        .query_map([&columns.table], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    if available.is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "no table '{}' in {}",
+            columns.table,
+            path.display()
+        )));
+    }
+    let (content, memory_type, tags) = columns.resolve(&available)?;
+
+    // Names are checked against the table, so quoting them is enough
+    let select = |column: Option<&str>| column.map_or("NULL".to_string(), quote);
+    let sql = format!(
+        "SELECT {}, {}, {} FROM {}",
+        quote(content),
+        select(memory_type),
+        select(tags),
+        quote(&columns.table)
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, Option<String>>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+    let mut imported = Vec::new();
+    for row in rows {
+        let (content, memory_type, tags) = row?;
+        let tags =
+            tags.map(|tags| serde_json::from_str::<Value>(&tags).unwrap_or(Value::String(tags)));
+        imported.extend(mapped(content, memory_type.as_deref(), tags.as_ref()));
+    }
+    Ok(imported)
+}
+
+/// Objects of the JSON file at `path`: a top-level array, or the array under
+/// `columns.table`.
+fn from_json(path: &Path, columns: &Columns) -> Result<Vec<Imported>, Error> {
+    let text = fs::read_to_string(path).map_err(Error::at(path))?;
+    let document: Value = serde_json::from_str(&text).map_err(|e| {
+        Error::InvalidArgument(format!("{} is not valid JSON: {}", path.display(), e))
+    })?;
+    let objects = match &document {
+        Value::Array(objects) => objects,
+        Value::Object(map) => match map.get(&columns.table) {
+            Some(Value::Array(objects)) => objects,
+            _ => {
+                return Err(Error::InvalidArgument(format!(
+                    "no '{}' array in {}",
+                    columns.table,
+                    path.display()
+                )))
+            }
+        },
+        _ => {
+            return Err(Error::InvalidArgument(format!(
+                "{} holds neither an array nor an object",
+                path.display()
+            )))
+        }
+    };
+    let available: Vec<String> = objects
+        .iter()
+        .filter_map(Value::as_object)
+        .flat_map(|object| object.keys().cloned())
+        .collect();
+    let (content, memory_type, tags) = columns.resolve(&available)?;
+
+    let mut imported = Vec::new();
+    for object in objects.iter().filter_map(Value::as_object) {
+        let text = object.get(content).and_then(Value::as_str);
+        let memory_type = memory_type
+            .and_then(|c| object.get(c))
+            .and_then(Value::as_str);
+        let tags = tags.and_then(|c| object.get(c));
+        imported.extend(mapped(text.map(str::to_string), memory_type, tags));
+    }
+    Ok(imported)
+}
+
+impl Columns {
+    /// Content, type and tags column names, checked against the source's.
+    fn resolve<'a>(
+        &'a self,
+        available: &[String],
+    ) -> Result<(&'a str, Option<&'a str>, Option<&'a str>), Error> {
+        let has = |column: &str| available.iter().any(|c| c == column);
+        let check = |column: &'a str| {
+            if has(column) {
+                Ok(column)
+            } else {
+                let mut names = available.to_vec();
+                names.sort();
+                names.dedup();
+                Err(Error::InvalidArgument(format!(
+                    "no column '{}' in '{}' (columns: {})",
+                    column,
+                    self.table,
+                    names.join(", ")
+                )))
+            }
+        };
+        let optional = |column: &'a Option<String>, default: &'static str| match column {
+            Some(column) => check(column).map(Some),
+            None => Ok(Some(default).filter(|d| has(d))),
+        };
+        Ok((
+            check(&self.content)?,
+            optional(&self.memory_type, "memory_type")?,
+            optional(&self.tags, "tags")?,
+        ))
+    }
+}
+
+/// A memory from a mapped row. Types other than `preference` import as
+/// `project`; tags may be a JSON array or a comma-separated string.
+fn mapped(
+    content: Option<String>,
+    memory_type: Option<&str>,
+    tags: Option<&Value>,
+) -> Option<Imported> {
+    let content = content?.trim().to_string();
+    if content.is_empty() {
+        return None;
+    }
+    let mut all = vec![IMPORT_TAG.to_string()];
+    let source: Vec<String> = match tags {
+        Some(Value::Array(tags)) => tags
+            .iter()
+            .filter_map(|tag| tag.as_str().map(str::to_string))
+            .collect(),
+        Some(Value::String(tags)) => tags.split(',').map(str::to_string).collect(),
+        _ => Vec::new(),
+    };
+    for tag in source {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !all.contains(&tag) {
+            all.push(tag);
+        }
+    }
+    Some(Imported {
+        memory_type: if memory_type == Some("preference") {
+            "preference"
+        } else {
+            "project"
+        },
+        content,
+        tags: all,
+        exists: false,
+    })
+}
+
+/// An SQL identifier in double quotes.
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// List items outside code fences and Squirrel's own blocks, with their headings.
+pub(crate) fn list_items(content: &str) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut heading = None;
+    let (mut in_fence, mut in_squirrel) = (false, false);
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+        } else if line.starts_with("<!-- START Squirrel") {
+            in_squirrel = true;
+        } else if line.starts_with("<!-- END Squirrel") {
+            in_squirrel = false;
+        } else if in_fence || in_squirrel {
+            continue;
+        } else if let Some(title) = line.strip_prefix('#') {
+            heading = Some(title.trim_start_matches('#').trim().to_string());
+        } else {
+            let item = ["- ", "* ", "+ "]
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+                .map(str::trim);
+            if let Some(text) = item.filter(|text| !text.is_empty()) {
+                items.push(Item {
+                    heading: heading.clone(),
+                    text: text.to_string(),
+                });
+            }
+        }
+    }
+    items
+}
+
+/// Every non-empty line outside code fences, for rule files written as prose.
+fn plain_lines(content: &str) -> Vec<Item> {
+    let mut in_fence = false;
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            if line.starts_with("```") {
+                in_fence = !in_fence;
+                return false;
+            }
+            !in_fence && !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|line| Item {
+            heading: None,
+            text: line.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_items_are_categorized_by_heading() {
+        let content = "Intro line\n- Use pnpm\n## Code Style\n* No emojis\n\
+                       ```\n- not a rule\n```\n### My Preferences\n- Answer tersely\n";
+        let items = list_items(content);
+        let texts: Vec<&str> = items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(texts, ["Use pnpm", "No emojis", "Answer tersely"]);
+
+        assert_eq!(
+            categorize(items[0].heading.as_deref()),
+            ("project", vec![IMPORT_TAG.to_string()])
+        );
+        let (memory_type, tags) = categorize(items[1].heading.as_deref());
+        assert_eq!((memory_type, tags[1].as_str()), ("project", "code-style"));
+        assert_eq!(categorize(items[2].heading.as_deref()).0, "preference");
+
+        let prose = plain_lines("# Rules\nAlways write tests.\n\nPrefer small PRs.\n");
+        assert_eq!(prose.len(), 2);
+    }
+
+    #[test]
+    fn test_stores_are_mapped_by_column() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE notes (body TEXT, kind TEXT, labels TEXT);
+             INSERT INTO notes VALUES ('Use pnpm', 'preference', 'js, tooling');
+             INSERT INTO notes VALUES ('Run cargo fmt', 'fact', '[\"rust\"]');
+             INSERT INTO notes VALUES ('  ', NULL, NULL);",
+        )
+        .unwrap();
+        let mut columns = Columns {
+            table: "notes".to_string(),
+            content: "body".to_string(),
+            memory_type: Some("kind".to_string()),
+            tags: Some("labels".to_string()),
+        };
+        let imported = from_sqlite(&path, &columns).unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].memory_type, "preference");
+        assert_eq!(imported[0].tags, [IMPORT_TAG, "js", "tooling"]);
+        assert_eq!(imported[1].memory_type, "project");
+        assert_eq!(imported[1].tags, [IMPORT_TAG, "rust"]);
+
+        columns.content = "text".to_string();
+        let err = from_sqlite(&path, &columns).unwrap_err();
+        assert!(err.to_string().contains("columns: body, kind, labels"));
+
+        let path = dir.path().join("shared.json");
+        fs::write(
+            &path,
+            r#"{"memories": [{"content": "Use pnpm", "tags": ["team"]}, {"id": 1}]}"#,
+        )
+        .unwrap();
+        let imported = from_json(&path, &Columns::default()).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].tags, [IMPORT_TAG, "team"]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Claude Code files: the session skill and the CLAUDE.md memory triggers.
+
+use std::fs;
+use std::path::Path;
+
+use tracing::info;
+
+use crate::atomic;
+use crate::cli::output::human;
+use crate::error::Error;
+
+/// Create the session start skill file.
+pub(super) fn create_skill_file(project_root: &Path) -> Result<(), Error> {
+    let skill_dir = project_root
+        .join(".claude")
+        .join("skills")
+        .join("squirrel-session");
+    fs::create_dir_all(&skill_dir)?;
+
+    let skill_content = r#"---
+name: squirrel-session
+description: Load behavioral corrections from Squirrel memory at session start. Use when starting a new coding session.
+user-invocable: false
+---
+
+At the start of this session, load corrections from Squirrel:
+
+1. Call `squirrel_get_memory` to get all behavioral corrections.
+2. Apply these corrections throughout the session.
+"#;
+
+    let skill_path = skill_dir.join("SKILL.md");
+    atomic::write(&skill_path, skill_content)?;
+    info!(path = %skill_path.display(), "Created skill file");
+    human!("Skill file created.");
+
+    Ok(())
+}
+
+/// Add Squirrel memory triggers to CLAUDE.md.
+pub(super) fn add_memory_triggers(project_root: &Path) -> Result<(), Error> {
+    let claude_dir = project_root.join(".claude");
+    fs::create_dir_all(&claude_dir)?;
+
+    let claude_md_path = claude_dir.join("CLAUDE.md");
+
+    let trigger_block = r#"
+<!-- START Squirrel Memory Protocol -->
+## Squirrel Memory Protocol
+
+You have access to Squirrel memory tools via MCP.
+
+### When to store (squirrel_store_memory):
+- User corrects your behavior → type: "preference" (global, applies everywhere)
+- You learn a project-specific rule → type: "project" (only this project)
+
+### When NOT to store:
+- Research in progress
+- General knowledge
+- Conversation context
+
+### Rules:
+- Store corrections proactively. Don't ask permission.
+- Every memory: "Do X" or "Don't do Y" or "When Z, do W"
+- Keep concise (1-2 sentences)
+- If a loaded memory was wrong or outdated, call squirrel_rate_memory with useful: false
+<!-- END Squirrel Memory Protocol -->
+"#;
+
+    if claude_md_path.exists() {
+        let content = fs::read_to_string(&claude_md_path)?;
+
+        // Check if triggers already exist
+        if content.contains("START Squirrel Memory Protocol") {
+            info!("Memory triggers already in CLAUDE.md");
+            return Ok(());
+        }
+
+        // Append to existing file
+        let new_content = format!("{}\n{}", content.trim_end(), trigger_block);
+        atomic::write(&claude_md_path, new_content)?;
+    } else {
+        atomic::write(&claude_md_path, trigger_block.trim_start())?;
+    }
+
+    info!("Added memory triggers to CLAUDE.md");
+    human!("Memory triggers added to CLAUDE.md.");
+
+    Ok(())
+}
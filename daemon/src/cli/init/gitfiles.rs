@@ -0,0 +1,276 @@
+//! `.gitignore`, `.gitattributes` and the merge driver for `.sqrl/`.
+
+use std::fs;
+use std::path::Path;
+
+use tracing::info;
+
+use crate::atomic;
+use crate::cli::git;
+use crate::error::Error;
+
+/// Entries ignoring `.sqrl/` except the shared config and memory bundle.
+/// The local overlay stays ignored.
+const GITIGNORE_ENTRIES: &[&str] = &[
+    ".sqrl/*",
+    "!.sqrl/config.yaml",
+    "!.sqrl/config.toml",
+    "!.sqrl/shared.json",
+    ".sqrl/config.local.json",
+];
+
+/// Attribute line merging the shared memory bundle (SHARE-001) with
+/// `sqrl _internal merge-memories` instead of line by line.
+const GITATTRIBUTES_ENTRY: &str = ".sqrl/shared.json merge=sqrl-memories";
+
+/// Name of the merge driver in the repository's git config.
+const MERGE_DRIVER: &str = "sqrl-memories";
+
+/// Add .sqrl entries to .gitignore if not already present. Returns true if it changed.
+///
+/// Entries already in `.git/info/exclude` count too. Repos that keep them
+/// there instead of `.gitignore` get any missing ones there as well.
+pub(super) fn add_to_gitignore(project_root: &Path) -> Result<bool, Error> {
+    let gitignore_path = project_root.join(".gitignore");
+    let exclude_path = git_info_path(project_root, "exclude");
+    let gitignore = fs::read_to_string(&gitignore_path).unwrap_or_default();
+    let exclude = exclude_path
+        .as_deref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let lines = || gitignore.lines().chain(exclude.lines()).map(str::trim);
+
+    let mut missing: Vec<&str> = GITIGNORE_ENTRIES
+        .iter()
+        .copied()
+        .filter(|entry| !lines().any(|line| line == *entry))
+        .collect();
+
+    // Older projects ignore all of .sqrl/, hiding the shared config and bundle;
+    // the entries take the place of that line
+    let mut migrated = false;
+    for (path, content) in [
+        (Some(gitignore_path.clone()), &gitignore),
+        (exclude_path.clone(), &exclude),
+    ] {
+        let Some(path) = path.filter(|_| content.lines().any(is_legacy_entry)) else {
+            continue;
+        };
+        atomic::write(&path, replace_legacy_entry(content, &missing))?;
+        info!(path = %path.display(), "Replaced the .sqrl/ ignore entry");
+        missing.clear();
+        migrated = true;
+    }
+    if migrated || missing.is_empty() {
+        return Ok(migrated);
+    }
+
+    let (path, content) = match exclude_path {
+        Some(path) if keeps_entries_local(&gitignore, &exclude) => (path, exclude),
+        _ => (gitignore_path, gitignore),
+    };
+    append_lines(&path, content, &missing)?;
+    info!(path = %path.display(), "Added .sqrl ignore entries");
+
+    Ok(true)
+}
+
+/// A line ignoring all of `.sqrl/`, as older versions wrote.
+fn is_legacy_entry(line: &str) -> bool {
+    matches!(line.trim(), ".sqrl/" | ".sqrl")
+}
+
+/// `content` with its first legacy line replaced by `entries`, and any
+/// other legacy line dropped.
+fn replace_legacy_entry(content: &str, entries: &[&str]) -> String {
+    let mut entries = Some(entries);
+    let mut replaced = String::new();
+    for line in content.lines() {
+        if is_legacy_entry(line) {
+            for entry in entries.take().unwrap_or_default() {
+                replaced.push_str(entry);
+                replaced.push('\n');
+            }
+        } else {
+            replaced.push_str(line);
+            replaced.push('\n');
+        }
+    }
+    replaced
+}
+
+/// Mark `.sqrl/shared.json` for the Squirrel merge driver and define the
+/// driver in the repository's config. Returns true if anything changed.
+///
+/// Only at a repository root. The attribute goes to `.git/info/attributes`
+/// when the ignore entries are kept in `.git/info/exclude`.
+pub(super) fn add_merge_driver(project_root: &Path) -> Result<bool, Error> {
+    let Some(repo) = git::repository_at(project_root) else {
+        return Ok(false);
+    };
+    let mut changed = false;
+
+    let gitattributes_path = project_root.join(".gitattributes");
+    let gitattributes = fs::read_to_string(&gitattributes_path).unwrap_or_default();
+    let info_path = repo.commondir().join("info").join("attributes");
+    let info = fs::read_to_string(&info_path).unwrap_or_default();
+    let present = gitattributes
+        .lines()
+        .chain(info.lines())
+        .any(|line| line.split_whitespace().collect::<Vec<_>>().join(" ") == GITATTRIBUTES_ENTRY);
+    if !present {
+        let exclude =
+            fs::read_to_string(repo.commondir().join("info").join("exclude")).unwrap_or_default();
+        let gitignore = fs::read_to_string(project_root.join(".gitignore")).unwrap_or_default();
+        if keeps_entries_local(&gitignore, &exclude) {
+            append_lines(&info_path, info, &[GITATTRIBUTES_ENTRY])?;
+        } else {
+            append_lines(&gitattributes_path, gitattributes, &[GITATTRIBUTES_ENTRY])?;
+        }
+        changed = true;
+    }
+
+    // The driver itself can't be committed, so each clone defines it
+    let mut config = repo
+        .config()
+        .and_then(|config| config.open_level(git2::ConfigLevel::Local))
+        .map_err(|e| Error::Command {
+            command: "git config".to_string(),
+            stderr: e.to_string(),
+        })?;
+    for (key, value) in [
+        ("name", "Squirrel shared memories"),
+        ("driver", "sqrl _internal merge-memories %O %A %B"),
+    ] {
+        let key = format!("merge.{}.{}", MERGE_DRIVER, key);
+        if config.get_string(&key).ok().as_deref() != Some(value) {
+            config.set_str(&key, value).map_err(|e| Error::Command {
+                command: format!("git config {}", key),
+                stderr: e.to_string(),
+            })?;
+            changed = true;
+        }
+    }
+    if changed {
+        info!("Defined the merge driver for .sqrl/shared.json");
+    }
+    Ok(changed)
+}
+
+/// `.git/info/<name>` of the repository rooted at `project_root`, if any.
+fn git_info_path(project_root: &Path, name: &str) -> Option<std::path::PathBuf> {
+    git::repository_at(project_root).map(|repo| repo.commondir().join("info").join(name))
+}
+
+/// Whether Squirrel's ignore entries are kept in `.git/info/exclude`
+/// rather than `.gitignore`, given both files' content.
+fn keeps_entries_local(gitignore: &str, exclude: &str) -> bool {
+    let has_entry = |content: &str| {
+        content
+            .lines()
+            .any(|line| GITIGNORE_ENTRIES.contains(&line.trim()))
+    };
+    has_entry(exclude) && !has_entry(gitignore)
+}
+
+/// Append `lines` to the file at `path`, whose current content is `content`.
+fn append_lines(path: &Path, mut content: String, lines: &[&str]) -> Result<(), Error> {
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    atomic::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn read(path: std::path::PathBuf) -> String {
+        fs::read_to_string(path).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_add_to_gitignore_new_repo_writes_gitignore_once() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/").unwrap();
+
+        assert!(add_to_gitignore(dir.path()).unwrap());
+        let expected = format!("target/\n{}\n", GITIGNORE_ENTRIES.join("\n"));
+        assert_eq!(read(dir.path().join(".gitignore")), expected);
+        assert!(!add_to_gitignore(dir.path()).unwrap());
+        assert_eq!(read(dir.path().join(".gitignore")), expected);
+    }
+
+    #[test]
+    fn test_add_to_gitignore_entries_in_exclude_stay_there() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        let exclude = dir.path().join(".git/info/exclude");
+        fs::write(&exclude, ".sqrl/*\n!.sqrl/config.yaml\n").unwrap();
+
+        assert!(add_to_gitignore(dir.path()).unwrap());
+        assert!(!dir.path().join(".gitignore").exists());
+        assert_eq!(read(exclude), format!("{}\n", GITIGNORE_ENTRIES.join("\n")));
+    }
+
+    #[test]
+    fn test_add_to_gitignore_legacy_entry_replaced() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(".gitignore"),
+            "target/\n.sqrl/\nnode_modules/\n",
+        )
+        .unwrap();
+
+        assert!(add_to_gitignore(dir.path()).unwrap());
+        assert_eq!(
+            read(dir.path().join(".gitignore")),
+            format!("target/\n{}\nnode_modules/\n", GITIGNORE_ENTRIES.join("\n"))
+        );
+        assert!(!add_to_gitignore(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn test_add_merge_driver_attribute_follows_ignore_entries() {
+        for local in [false, true] {
+            let dir = TempDir::new().unwrap();
+            let repo = Repository::init(dir.path()).unwrap();
+            if local {
+                fs::write(dir.path().join(".git/info/exclude"), ".sqrl/*\n").unwrap();
+            }
+
+            assert!(add_merge_driver(dir.path()).unwrap());
+            let (written, untouched) = (
+                dir.path().join(".git/info/attributes"),
+                dir.path().join(".gitattributes"),
+            );
+            let (written, untouched) = if local {
+                (written, untouched)
+            } else {
+                (untouched, written)
+            };
+            assert_eq!(read(written), format!("{}\n", GITATTRIBUTES_ENTRY));
+            assert!(!untouched.exists());
+            let config = repo.config().unwrap();
+            assert_eq!(
+                config.get_string("merge.sqrl-memories.driver").unwrap(),
+                "sqrl _internal merge-memories %O %A %B"
+            );
+            assert!(!add_merge_driver(dir.path()).unwrap());
+        }
+
+        let plain = TempDir::new().unwrap();
+        assert!(!add_merge_driver(plain.path()).unwrap());
+    }
+}
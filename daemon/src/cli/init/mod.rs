@@ -0,0 +1,190 @@
+//! Initialize Squirrel for a project.
+
+mod claude;
+mod gitfiles;
+mod prompt;
+mod repair;
+mod template;
+
+use std::io::{self, IsTerminal};
+
+use clap::ValueEnum;
+use tracing::{info, warn};
+
+use crate::cli::git;
+use crate::cli::hooks;
+use crate::cli::output::{self, human};
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::projects;
+use crate::lock::SqrlLock;
+use crate::storage;
+use claude::{add_memory_triggers, create_skill_file};
+use gitfiles::{add_merge_driver, add_to_gitignore};
+use prompt::{prompt_memories_only, prompt_preset, prompt_template};
+use repair::repair;
+use template::add_doc_dirs;
+
+pub use template::Template;
+
+/// What `sqrl init` sets up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Preset {
+    /// Only `.sqrl/` and its config: no hooks, skill, CLAUDE.md triggers or MCPs.
+    Minimal,
+    /// Everything, with a pre-push hook that blocks pushes without doc updates.
+    DocsStrict,
+    /// Everything, with an informational pre-push hook.
+    Full,
+}
+
+/// Options for `sqrl init`.
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    /// Never prompt; use the preset (default `full`).
+    pub yes: bool,
+    pub preset: Option<Preset>,
+    /// Skip git hook installation.
+    pub no_hooks: bool,
+    /// Skip MCP registration (`sqrl apply`).
+    pub no_mcp: bool,
+    /// On an initialized project, fix what is missing instead of stopping.
+    pub repair: bool,
+    /// Doc settings for the project's ecosystem; defaults if None.
+    pub template: Option<Template>,
+}
+
+impl Preset {
+    /// Project config written for this preset.
+    fn config(self) -> Config {
+        let mut config = Config::default();
+        match self {
+            Preset::Minimal => config.hooks.auto_install = false,
+            Preset::DocsStrict => config.hooks.pre_push_block = true,
+            Preset::Full => {}
+        }
+        config
+    }
+}
+
+/// Run the init command.
+pub fn run(opts: InitOptions) -> Result<(), Error> {
+    let project_root = std::env::current_dir()?;
+    let sqrl_dir = project_root.join(".sqrl");
+
+    if sqrl_dir.exists() {
+        if opts.repair {
+            let _lock = SqrlLock::project(&project_root)?;
+            return repair(&project_root, &opts);
+        }
+        human!("Squirrel already initialized in this project.");
+        human!("Run 'sqrl init --repair' to fix a partial install,");
+        human!("or 'sqrl goaway' first if you want to reinitialize.");
+        return Ok(());
+    }
+
+    let interactive = !opts.yes && !output::is_json() && io::stdin().is_terminal();
+    let preset = match opts.preset {
+        Some(preset) => preset,
+        None if !interactive => Preset::Full,
+        None => prompt_preset()?,
+    };
+    let template = match opts.template {
+        Some(Template::Auto) => Some(Template::detect(&project_root).unwrap_or(Template::Auto)),
+        Some(template) => Some(template),
+        None if !interactive => None,
+        None => prompt_template(&project_root)?,
+    };
+
+    // Hooks need a repository root; offer to leave them out for good
+    let location = git::Location::of(&project_root);
+    let wants_hooks = preset != Preset::Minimal && !opts.no_hooks;
+    let memories_only =
+        location != git::Location::Root && wants_hooks && interactive && prompt_memories_only()?;
+
+    // Create .sqrl directory, locked so a concurrent init can't interleave
+    let _lock = SqrlLock::project(&project_root)?;
+    if Config::exists(&project_root) {
+        human!("Squirrel already initialized in this project.");
+        return Ok(());
+    }
+    info!(path = %sqrl_dir.display(), "Created .sqrl directory");
+
+    // Create database with its tables
+    storage::ensure_schema(&project_root)?;
+    info!(path = %sqrl_dir.join("memory.db").display(), "Created database");
+
+    // Ignore .sqrl/ except the shared config, and merge the shared memories by ID
+    add_to_gitignore(&project_root)?;
+    if let Err(e) = add_merge_driver(&project_root) {
+        warn!(error = %e, "Failed to set up the merge driver for .sqrl/shared.json");
+    }
+
+    // Create config from the preset, with the template's doc settings
+    let mut initial = preset.config();
+    if let Some(template) = template {
+        initial.docs = template.docs();
+        if template == Template::Auto {
+            human!("No Cargo.toml, package.json, or pyproject.toml found; default doc settings.");
+        } else {
+            human!("Doc settings from the {} template.", template.name());
+        }
+        let found = add_doc_dirs(&project_root, &mut initial.docs);
+        if !found.is_empty() {
+            human!("Docs also listed from {}.", found.join(", "));
+        }
+    }
+    if memories_only {
+        initial.hooks.auto_install = false;
+    }
+    initial.save(&project_root)?;
+    info!("Created config.yaml");
+    let config = Config::effective(&project_root)?;
+
+    projects::touch(&project_root);
+
+    // Install git hooks if git exists
+    if config.hooks.auto_install && !opts.no_hooks && hooks::has_git(&project_root) {
+        let installed =
+            hooks::install_hooks(&project_root, config.hooks.pre_push_block).and_then(|()| {
+                if config.wants_post_commit_hook() {
+                    hooks::install_post_commit_hook(&project_root)?;
+                }
+                if config.hooks.on_checkout {
+                    hooks::install_post_checkout_hook(&project_root)?;
+                }
+                Ok(())
+            });
+        if let Err(e) = installed {
+            warn!(error = %e, "Failed to install git hooks");
+        } else {
+            human!("Git hooks installed.");
+        }
+    } else if location != git::Location::Root && config.hooks.auto_install && !opts.no_hooks {
+        human!("Git: {}.", location.describe());
+        human!("Run 'sqrl init --repair' at a repository root to install hooks later.");
+    }
+
+    if preset != Preset::Minimal {
+        // Create skill file
+        create_skill_file(&project_root)?;
+
+        // Add memory triggers to CLAUDE.md
+        add_memory_triggers(&project_root)?;
+    }
+
+    // Apply global MCP configs
+    if preset != Preset::Minimal && !opts.no_mcp {
+        human!();
+        crate::cli::apply::apply()?;
+    }
+
+    human!();
+    human!("Squirrel initialized.");
+    human!();
+    human!("Next steps:");
+    human!("  Check status: sqrl status");
+    human!("  Configure: sqrl config");
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Questions `sqrl init` asks on a terminal.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use super::template::Template;
+use super::Preset;
+use crate::cli::output::human;
+use crate::error::Error;
+
+/// Ask which preset to use. Empty input picks `full`.
+pub(super) fn prompt_preset() -> Result<Preset, Error> {
+    loop {
+        print!("Preset [minimal/docs-strict/full] (full): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Ok(Preset::Full);
+        }
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(Preset::Full);
+        }
+        match Preset::from_str(input, true) {
+            Ok(preset) => return Ok(preset),
+            Err(_) => human!("Unknown preset '{}'.", input),
+        }
+    }
+}
+
+/// Ask whether a project outside a repository root should go without hooks.
+pub(super) fn prompt_memories_only() -> Result<bool, Error> {
+    human!("This is not the root of a git repository, so hooks can't be installed.");
+    print!("Set up memories only, without hooks? [Y/n] ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+    Ok(input.is_empty() || input == "y" || input == "yes")
+}
+
+/// Offer the template matching the project's manifest. None if there is no
+/// match or the user declines.
+pub(super) fn prompt_template(project_root: &Path) -> Result<Option<Template>, Error> {
+    let Some(template) = Template::detect(project_root) else {
+        return Ok(None);
+    };
+    human!(
+        "This looks like a {} project. The {} template sets {}.",
+        match template {
+            Template::Rust => "Rust",
+            Template::Node => "Node",
+            _ => "Python",
+        },
+        template.name(),
+        template.summary()
+    );
+    print!("Use these doc settings? [Y/n] ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim().to_lowercase();
+    Ok((input.is_empty() || input == "y" || input == "yes").then_some(template))
+}
@@ -0,0 +1,81 @@
+//! `sqrl init --repair`: fix what a partial install is missing.
+
+use std::path::Path;
+
+use serde_json::json;
+
+use super::gitfiles::{add_merge_driver, add_to_gitignore};
+use super::InitOptions;
+use crate::cli::hooks::{self, HookState};
+use crate::cli::output::{self, human};
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::projects;
+use crate::storage;
+
+/// Fix a partial install, reporting each repair performed.
+pub(super) fn repair(project_root: &Path, opts: &InitOptions) -> Result<(), Error> {
+    let mut repairs: Vec<&str> = Vec::new();
+
+    if !Config::exists(project_root) {
+        Config::default().save(project_root)?;
+        repairs.push("wrote missing .sqrl/config.yaml");
+    }
+
+    if !project_root.join(".sqrl").join("memory.db").exists() {
+        repairs.push("created missing .sqrl/memory.db");
+        storage::ensure_schema(project_root)?;
+    } else if storage::ensure_schema(project_root)? {
+        repairs.push("created missing database tables");
+    }
+
+    if add_to_gitignore(project_root)? {
+        repairs.push("added missing .gitignore entries");
+    }
+    if add_merge_driver(project_root)? {
+        repairs.push("set up the merge driver for .sqrl/shared.json");
+    }
+
+    let config = Config::effective(project_root)?;
+    if config.hooks.auto_install && !opts.no_hooks && hooks::has_git(project_root) {
+        let block = config.hooks.pre_push_block;
+        match hooks::pre_push_state(project_root, block) {
+            HookState::Missing => {
+                hooks::install_hooks(project_root, block)?;
+                repairs.push("installed missing pre-push hook");
+            }
+            HookState::Stale | HookState::Current => {}
+        }
+        for hook in hooks::upgrade_hooks(project_root, block)? {
+            repairs.push(match hook {
+                "pre-push" => "upgraded stale pre-push hook",
+                "post-commit" => "upgraded stale post-commit hook",
+                _ => "upgraded stale post-checkout hook",
+            });
+        }
+        if config.wants_post_commit_hook()
+            && hooks::post_commit_state(project_root) == HookState::Missing
+        {
+            hooks::install_post_commit_hook(project_root)?;
+            repairs.push("installed missing post-commit hook");
+        }
+        if config.hooks.on_checkout
+            && hooks::post_checkout_state(project_root) == HookState::Missing
+        {
+            hooks::install_post_checkout_hook(project_root)?;
+            repairs.push("installed missing post-checkout hook");
+        }
+    }
+
+    projects::touch(project_root);
+
+    if repairs.is_empty() {
+        human!("Nothing to repair.");
+    } else {
+        for r in &repairs {
+            human!("Repaired: {}", r);
+        }
+    }
+    output::emit(&json!({ "repairs": repairs }))?;
+    Ok(())
+}
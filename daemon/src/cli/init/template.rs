@@ -0,0 +1,156 @@
+//! Doc settings per ecosystem (`sqrl init --template`).
+
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use crate::config::DocsConfig;
+
+/// Doc settings for an ecosystem (`sqrl init --template`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Template {
+    /// Pick from the files in the project root.
+    Auto,
+    /// Rust crate or workspace: Cargo.toml.
+    Rust,
+    /// Node project or monorepo: package.json.
+    Node,
+    /// Python service: pyproject.toml, setup.py, or requirements.txt.
+    Python,
+}
+
+impl Template {
+    /// The template for the project at `root`, if its manifest is recognized.
+    pub(super) fn detect(root: &Path) -> Option<Template> {
+        let has = |file: &str| root.join(file).is_file();
+        if has("Cargo.toml") {
+            Some(Template::Rust)
+        } else if has("package.json") {
+            Some(Template::Node)
+        } else if ["pyproject.toml", "setup.py", "requirements.txt"]
+            .iter()
+            .any(|file| has(file))
+        {
+            Some(Template::Python)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Template::Auto => "auto",
+            Template::Rust => "rust",
+            Template::Node => "node",
+            Template::Python => "python",
+        }
+    }
+
+    /// One line describing the settings, for the init prompt.
+    pub(super) fn summary(self) -> String {
+        let docs = self.docs();
+        format!(
+            "doc extensions {}; excludes {}",
+            docs.extensions.join(", "),
+            docs.exclude_paths.join(", ")
+        )
+    }
+
+    /// `docs` settings for this ecosystem. `Auto` keeps the defaults.
+    pub(super) fn docs(self) -> DocsConfig {
+        let list = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        match self {
+            Template::Auto => DocsConfig::default(),
+            Template::Rust => DocsConfig {
+                extensions: list(&["md", "mdc", "txt"]),
+                include_paths: list(&[
+                    "docs/",
+                    "specs/",
+                    "book/",
+                    "crates/*/*.md",
+                    ".claude/",
+                    ".cursor/",
+                ]),
+                exclude_paths: list(&["target/", "vendor/", ".git/"]),
+                ..DocsConfig::default()
+            },
+            Template::Node => DocsConfig {
+                extensions: list(&["md", "mdx", "mdc", "txt"]),
+                include_paths: list(&[
+                    "docs/",
+                    "packages/*/*.md",
+                    "apps/*/*.md",
+                    ".claude/",
+                    ".cursor/",
+                ]),
+                exclude_paths: list(&[
+                    "node_modules/",
+                    "dist/",
+                    "build/",
+                    "coverage/",
+                    ".next/",
+                    ".git/",
+                ]),
+                ..DocsConfig::default()
+            },
+            Template::Python => DocsConfig {
+                extensions: list(&["md", "mdc", "rst", "txt"]),
+                include_paths: list(&["docs/", "specs/", ".claude/", ".cursor/"]),
+                exclude_paths: list(&[
+                    ".venv/",
+                    "venv/",
+                    "__pycache__/",
+                    ".tox/",
+                    "build/",
+                    "dist/",
+                    "site/",
+                    ".git/",
+                ]),
+                ..DocsConfig::default()
+            },
+        }
+    }
+}
+
+/// Top-level directories that usually hold docs.
+const DOC_DIRS: &[&str] = &["doc/", "docs/", "documentation/", "book/", "specs/", "adr/"];
+
+/// Add existing doc directories missing from `docs.include_paths`. Returns those added.
+pub(super) fn add_doc_dirs(project_root: &Path, docs: &mut DocsConfig) -> Vec<String> {
+    let mut added = Vec::new();
+    for dir in DOC_DIRS {
+        if project_root.join(dir).is_dir() && !docs.include_paths.iter().any(|p| p == dir) {
+            docs.include_paths.push(dir.to_string());
+            added.push(dir.to_string());
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_template_detection_and_docs() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(Template::detect(dir.path()), None);
+        fs::write(dir.path().join("pyproject.toml"), "").unwrap();
+        assert_eq!(Template::detect(dir.path()), Some(Template::Python));
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(Template::detect(dir.path()), Some(Template::Rust));
+
+        fs::create_dir(dir.path().join("documentation")).unwrap();
+        let mut docs = Template::Node.docs();
+        assert_eq!(add_doc_dirs(dir.path(), &mut docs), ["documentation/"]);
+        assert!(add_doc_dirs(dir.path(), &mut docs).is_empty());
+
+        for template in [Template::Rust, Template::Node, Template::Python] {
+            let matcher = crate::config::matcher::DocMatcher::new(&template.docs()).unwrap();
+            assert!(matcher.is_doc("docs/guide.md"));
+            assert!(!matcher.is_doc("src/main.rs"));
+        }
+    }
+}
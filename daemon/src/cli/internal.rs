@@ -1,204 +1,648 @@
 //! Hidden internal commands for git hooks.
 
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use tracing::debug;
+use serde_json::json;
+use tracing::{debug, info, warn};
 
+use crate::cli::context;
+use crate::cli::git::{self, FileChange, FileStatus, PushRange};
+use crate::cli::notify::{self, Event};
+use crate::cli::output;
+use crate::cli::plugins;
+use crate::cli::targets;
+use crate::config::matcher::DocMatcher;
+use crate::config::messages::{HookMessages, Message};
+use crate::config::{find_project_root, Config};
+use crate::docs::{find_doc_files, mentions};
 use crate::error::Error;
+use crate::global_config::{template, GlobalConfig, PluginEvent};
+use crate::lock::SqrlLock;
+use crate::storage::bundle::Bundle;
+use crate::storage::lint;
+use crate::storage::{self, Memory, Storage};
+
+/// Most memories the pre-push summary lists.
+const MAX_LISTED_MEMORIES: usize = 10;
+
+/// Project a hook runs for: found from the work tree git names in
+/// `GIT_DIR`/`GIT_WORK_TREE`, else from the current directory.
+pub(crate) fn hook_project_root() -> Result<Option<PathBuf>, Error> {
+    let start = match git::env_work_tree() {
+        Some(work_tree) => work_tree,
+        None => std::env::current_dir()?,
+    };
+    Ok(find_project_root(&start))
+}
 
 /// Show diff summary before push (called by pre-push hook).
-/// AI reads this output and decides if docs need updating.
+/// AI reads this output and decides if docs need updating; with `--quiet`
+/// it is one line. Returns false (block) when a plugin with `on_failure:
+/// block` fails, or with `hooks.pre_push_block` and no doc changes.
 pub fn docguard_check() -> Result<bool, Error> {
     // Find project root
-    let project_root = match find_project_root() {
+    let project_root = match hook_project_root()? {
         Some(path) => path,
         None => {
-            // Not a Squirrel project, allow push
+            debug!("Not in a Squirrel project; allowing push");
             return Ok(true);
         }
     };
+    check_push(&project_root, output::is_quiet(), &mut io::stdout().lock())
+}
 
-    // Get commits that will be pushed
-    let commits = get_unpushed_commits();
-    if commits.is_empty() {
-        // Nothing to push
+/// `docguard_check` for the project at `project_root`, printing to `out`.
+fn check_push(project_root: &Path, quiet: bool, out: &mut impl Write) -> Result<bool, Error> {
+    // Get commits and changed files that will be pushed
+    let Some(PushRange { commits, changes }) = git::push_range(project_root)? else {
+        debug!("No upstream, origin/main, or origin/master to compare HEAD with; allowing push");
         return Ok(true);
-    }
-
-    // Get combined diff stats
-    let diff_stats = get_diff_stats_for_push();
-    if diff_stats.is_empty() {
+    };
+    if commits.is_empty() || changes.is_empty() {
+        debug!(commits = commits.len(), "Nothing to push; allowing push");
         return Ok(true);
     }
 
     // Find doc files in the project
-    let doc_files = find_doc_files(&project_root);
-
-    // Print the summary for AI to review
-    println!();
-    println!("═══════════════════════════════════════════════════════════════");
-    println!(" Squirrel: Review changes before push");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
-    println!(" Commits to push: {}", commits.len());
-    println!();
-    println!(" Files changed:");
-    for stat in &diff_stats {
-        println!("   {}", stat);
-    }
-    println!();
+    let config = Config::effective(project_root)?;
+    let matcher = config.doc_matcher()?;
+    let messages = config.hook_messages()?;
+    let doc_files = find_doc_files(project_root, &matcher);
+    for change in &changes {
+        debug!(
+            path = %change.path,
+            doc = matcher.is_doc(&change.path),
+            "Changed file"
+        );
+    }
 
-    if !doc_files.is_empty() {
-        println!(" Doc files in repo:");
-        for doc in &doc_files {
-            println!("   {}", doc);
+    let stale = stale_references(project_root, &matcher, &doc_files, &changes);
+    let (memories, moved) = match memories_for_changes(project_root, &changes) {
+        Ok(found) => found,
+        Err(e) => {
+            warn!(error = %e, "Could not read memories for changed files");
+            (Vec::new(), 0)
         }
-        println!();
+    };
+    if !quiet {
+        print_summary(
+            out,
+            &messages,
+            commits.len(),
+            &changes,
+            &doc_files,
+            &stale,
+            (&memories, moved),
+        )?;
+    }
+
+    // Informational unless hooks.pre_push_block is set
+    let docs_touched = changes.iter().any(|change| matcher.is_doc(&change.path));
+    info!(
+        commits = commits.len(),
+        files = changes.len(),
+        docs_touched,
+        "docguard check"
+    );
+    let files: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+    let deleted: Vec<&str> = changes
+        .iter()
+        .filter(|c| c.status == FileStatus::Deleted)
+        .map(|c| c.path.as_str())
+        .collect();
+    let renamed: Vec<_> = changes
+        .iter()
+        .filter_map(|c| Some(json!({ "from": c.old_path.as_deref()?, "to": c.path })))
+        .collect();
+    let plugins_ok = plugins::run(
+        PluginEvent::PrePushCheck,
+        project_root,
+        json!({
+            "commits": commits,
+            "files": files,
+            "deleted": deleted,
+            "renamed": renamed,
+            "docs_touched": docs_touched,
+        }),
+    );
+    let say = |message| messages.render(message, commits.len(), changes.len());
+    if !plugins_ok {
+        if quiet {
+            writeln!(out, "{}", say(Message::PluginBlockedQuiet))?;
+            return Ok(false);
+        }
+        print_verdict(
+            out,
+            " ✗ ",
+            &say(Message::PluginBlocked),
+            &say(Message::PluginBlockedHint),
+        )?;
+        return Ok(false);
+    }
+    debug!(
+        pre_push_block = config.hooks.pre_push_block,
+        docs_touched, "Deciding whether to block"
+    );
+    if config.hooks.pre_push_block && !docs_touched {
+        notify::notify(Event::PushBlocked, &say(Message::NotifyPushBlocked));
+        if quiet {
+            writeln!(out, "{}", say(Message::PushBlockedQuiet))?;
+            return Ok(false);
+        }
+        print_verdict(
+            out,
+            " ✗ ",
+            &say(Message::PushBlocked),
+            &say(Message::PushBlockedHint),
+        )?;
+        return Ok(false);
     }
 
-    println!(" → Review if any docs need updating based on these changes.");
-    println!("═══════════════════════════════════════════════════════════════");
-    println!();
+    if quiet {
+        let mut stale_docs: Vec<&str> = stale.iter().map(|(doc, _)| doc.as_str()).collect();
+        stale_docs.dedup();
+        let summary = match (docs_touched, stale_docs.len()) {
+            (true, 0) => say(Message::QuietDocsUpdated),
+            (false, 0) => say(Message::QuietNoDocs),
+            (_, n) => {
+                messages.render_count(Message::QuietStaleDocs, commits.len(), changes.len(), n)
+            }
+        };
+        writeln!(out, "{}", summary)?;
+        return Ok(true);
+    }
+    print_verdict(out, " → ", &say(Message::Review), "")?;
 
-    // Always allow push - this is informational only
     Ok(true)
 }
 
-/// Find project root by walking up directories looking for .sqrl.
-fn find_project_root() -> Option<PathBuf> {
-    let cwd = std::env::current_dir().ok()?;
-    let mut current = cwd.as_path();
+/// Closing lines of the pre-push summary: `text` after `mark`, then `hint`,
+/// each further line indented to match.
+fn print_verdict(out: &mut impl Write, mark: &str, text: &str, hint: &str) -> Result<(), Error> {
+    for (i, line) in text.lines().chain(hint.lines()).enumerate() {
+        writeln!(out, "{}{}", if i == 0 { mark } else { "   " }, line)?;
+    }
+    writeln!(
+        out,
+        "═══════════════════════════════════════════════════════════════"
+    )?;
+    writeln!(out)?;
+    Ok(())
+}
 
-    loop {
-        if current.join(".sqrl").exists() {
-            return Some(current.to_path_buf());
+/// The full pre-push summary, up to the verdict. `moved` counts the
+/// `memories` naming an old path.
+fn print_summary(
+    out: &mut impl Write,
+    messages: &HookMessages,
+    commits: usize,
+    changes: &[FileChange],
+    doc_files: &[String],
+    stale: &[(String, &FileChange)],
+    (memories, moved): (&[Memory], usize),
+) -> Result<(), Error> {
+    writeln!(out)?;
+    let files = changes.len();
+    let say = |message| messages.render(message, commits, files);
+    writeln!(
+        out,
+        "═══════════════════════════════════════════════════════════════"
+    )?;
+    for line in say(Message::Title).lines() {
+        writeln!(out, " {}", line)?;
+    }
+    writeln!(
+        out,
+        "═══════════════════════════════════════════════════════════════"
+    )?;
+    writeln!(out)?;
+    writeln!(out, " {}", say(Message::CommitsToPush))?;
+    writeln!(out)?;
+    writeln!(out, " {}", say(Message::FilesChanged))?;
+    let paths: Vec<String> = changes
+        .iter()
+        .map(|change| match &change.old_path {
+            Some(old) => format!("{} → {}", old, change.path),
+            None => change.path.clone(),
+        })
+        .collect();
+    let width = paths.iter().map(|p| p.chars().count()).max().unwrap_or(0);
+    for (change, path) in changes.iter().zip(&paths) {
+        match change.lines {
+            Some((added, removed)) => writeln!(
+                out,
+                "   {} {:<width$} | +{} -{}",
+                change.status.letter(),
+                path,
+                added,
+                removed
+            )?,
+            None => writeln!(
+                out,
+                "   {} {:<width$} | binary",
+                change.status.letter(),
+                path
+            )?,
         }
-        current = current.parent()?;
     }
-}
+    writeln!(out)?;
+
+    if !doc_files.is_empty() {
+        writeln!(out, " {}", say(Message::DocFiles))?;
+        for doc in doc_files {
+            writeln!(out, "   {}", doc)?;
+        }
+        writeln!(out)?;
+    }
 
-/// Get list of commits that will be pushed (not yet on remote).
-fn get_unpushed_commits() -> Vec<String> {
-    // Get the upstream branch
-    let upstream = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "@{upstream}"])
-        .output();
-
-    let upstream_ref = match upstream {
-        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).trim().to_string(),
-        _ => {
-            // No upstream, compare against origin/main or origin/master
-            let main_exists = Command::new("git")
-                .args(["rev-parse", "--verify", "origin/main"])
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false);
-
-            if main_exists {
-                "origin/main".to_string()
-            } else {
-                "origin/master".to_string()
+    if !stale.is_empty() {
+        writeln!(out, " {}", say(Message::StaleDocs))?;
+        for (doc, change) in stale {
+            match &change.old_path {
+                Some(old) => writeln!(out, "   {} → {} (now {})", doc, old, change.path)?,
+                None => writeln!(out, "   {} → {}", doc, change.path)?,
             }
         }
-    };
+        writeln!(out)?;
+    }
 
-    // Get commits between upstream and HEAD
-    let output = Command::new("git")
-        .args(["log", "--oneline", &format!("{}..HEAD", upstream_ref)])
-        .output();
+    if !memories.is_empty() {
+        writeln!(out, " {}", say(Message::MemoriesHeading))?;
+        for memory in memories.iter().take(MAX_LISTED_MEMORIES) {
+            writeln!(
+                out,
+                "   {} {}",
+                storage::short_id(&memory.id),
+                memory.content
+            )?;
+        }
+        if memories.len() > MAX_LISTED_MEMORIES {
+            let more = memories.len() - MAX_LISTED_MEMORIES;
+            writeln!(
+                out,
+                "   {}",
+                messages.render_count(Message::MoreMemories, commits, files, more)
+            )?;
+        }
+        writeln!(out)?;
+    }
 
-    match output {
-        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
-            .lines()
-            .map(String::from)
-            .collect(),
-        _ => vec![],
+    if moved > 0 {
+        writeln!(
+            out,
+            " {}",
+            messages.render_count(Message::MovedMemories, commits, files, moved)
+        )?;
+        writeln!(out)?;
     }
+    Ok(())
 }
 
-/// Get diff stats for changes being pushed.
-fn get_diff_stats_for_push() -> Vec<String> {
-    // Get the upstream branch
-    let upstream = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "@{upstream}"])
-        .output();
-
-    let upstream_ref = match upstream {
-        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).trim().to_string(),
-        _ => {
-            let main_exists = Command::new("git")
-                .args(["rev-parse", "--verify", "origin/main"])
-                .output()
-                .map(|o| o.status.success())
-                .unwrap_or(false);
-
-            if main_exists {
-                "origin/main".to_string()
-            } else {
-                "origin/master".to_string()
-            }
+/// Post-commit hook: record the active memories for `commit` (default HEAD,
+/// `hooks.snapshots`) and refresh the pinned-memory section (`context.section`).
+pub fn post_commit(commit: Option<&str>) -> Result<(), Error> {
+    let Some(project_root) = hook_project_root()? else {
+        debug!("Not in a Squirrel project; nothing to do");
+        return Ok(());
+    };
+    let config = Config::effective(&project_root)?;
+    if !config.wants_post_commit_hook() {
+        debug!("hooks.snapshots and context.section are off; nothing to do");
+        return Ok(());
+    }
+    let _lock = SqrlLock::project(&project_root)?;
+    if config.hooks.snapshots {
+        let rev = commit.unwrap_or("HEAD");
+        if let Some(id) = git::first_parents(&project_root, rev, 1)?.pop() {
+            Storage::open(&storage::db_path(&project_root))?.record_snapshot(&id)?;
+            debug!(commit = %id, "Recorded memory snapshot");
         }
+    } else {
+        debug!("hooks.snapshots is off; no snapshot recorded");
+    }
+    if config.context.section {
+        let files = context::refresh(&project_root, &config)?;
+        if files.is_empty() {
+            debug!("Pinned-memory section already current");
+        }
+        for file in files {
+            info!(file = %file, "Refreshed pinned memories");
+        }
+    } else {
+        debug!("context.section is off; CLAUDE.md left alone");
+    }
+    Ok(())
+}
+
+/// Regenerate files derived from memories after a branch switch (called by
+/// the post-checkout hook with `hooks.on_checkout`): the pinned-memory
+/// section and, when it exists, Aider's conventions file.
+pub fn post_checkout() -> Result<(), Error> {
+    let Some(project_root) = hook_project_root()? else {
+        debug!("Not in a Squirrel project; nothing to do");
+        return Ok(());
     };
+    refresh_after_checkout(&project_root)
+}
 
-    // Get diff stat
-    let output = Command::new("git")
-        .args([
-            "diff",
-            "--stat",
-            "--stat-width=60",
-            &format!("{}..HEAD", upstream_ref),
-        ])
-        .output();
-
-    match output {
-        Ok(out) if out.status.success() => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            stdout
-                .lines()
-                .filter(|line| !line.trim().is_empty())
-                .filter(|line| !line.contains("files changed"))
-                .filter(|line| !line.contains("file changed"))
-                .map(|line| line.trim().to_string())
-                .collect()
-        }
-        _ => vec![],
+/// `post_checkout` for the project at `project_root`.
+fn refresh_after_checkout(project_root: &Path) -> Result<(), Error> {
+    let config = Config::effective(project_root)?;
+    if !config.hooks.on_checkout {
+        debug!("hooks.on_checkout is off; nothing to do");
+        return Ok(());
+    }
+    let _lock = SqrlLock::project(project_root)?;
+    if config.context.section {
+        for file in context::refresh(project_root, &config)? {
+            info!(file = %file, "Refreshed pinned memories");
+        }
+    } else {
+        debug!("context.section is off; CLAUDE.md left alone");
     }
+    if project_root.join(targets::AIDER_CONVENTIONS).exists() {
+        targets::apply_to_aider(project_root)?;
+        info!(
+            file = targets::AIDER_CONVENTIONS,
+            "Regenerated Aider conventions"
+        );
+    }
+    Ok(())
 }
 
-/// Find documentation files in the project.
-fn find_doc_files(project_root: &PathBuf) -> Vec<String> {
-    let mut docs = Vec::new();
-
-    // Common doc locations
-    let doc_patterns = [
-        "README.md",
-        "CHANGELOG.md",
-        "docs/*.md",
-        "specs/*.md",
-        ".claude/*.md",
-        "*.md",
-    ];
-
-    for pattern in &doc_patterns {
-        if let Ok(entries) = glob::glob(&project_root.join(pattern).to_string_lossy()) {
-            for entry in entries.flatten() {
-                if let Ok(relative) = entry.strip_prefix(project_root) {
-                    let path_str = relative.to_string_lossy().to_string();
-                    // Skip node_modules, target, etc.
-                    if !path_str.contains("node_modules")
-                        && !path_str.contains("target/")
-                        && !path_str.contains(".git/")
-                        && !docs.contains(&path_str)
-                    {
-                        docs.push(path_str);
-                    }
-                }
-            }
+/// Merge two edits of the shared memory bundle into `ours` (the
+/// `sqrl-memories` merge driver `sqrl init` defines). An unreadable version
+/// fails, so git falls back to a conflict.
+pub fn merge_memories(base: &Path, ours: &Path, theirs: &Path) -> Result<(), Error> {
+    let merged = Bundle::merge_edits(
+        &Bundle::load(base)?,
+        &Bundle::load(ours)?,
+        &Bundle::load(theirs)?,
+    );
+    merged.save(ours)?;
+    info!(memories = merged.memories.len(), "Merged shared memories");
+    Ok(())
+}
+
+/// Launch an MCP server with placeholders resolved (called by tool configs).
+/// Returns the server's exit code.
+pub fn mcp_exec(name: &str) -> Result<i32, Error> {
+    let mcp = template::resolve_mcp(&GlobalConfig::get_mcp(name)?)?;
+    if let Some(url) = &mcp.url {
+        return Err(Error::InvalidArgument(format!(
+            "{} is a remote server at {}; tools connect to it directly",
+            name, url
+        )));
+    }
+
+    let mut command = Command::new(&mcp.command);
+    command.args(&mcp.args).envs(&mcp.env);
+    if let Some(cwd) = &mcp.cwd {
+        command.current_dir(cwd);
+    }
+    let status = command.status().map_err(|e| Error::Command {
+        command: mcp.command.clone(),
+        stderr: e.to_string(),
+    })?;
+
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Doc files whose text still contains the old path of a deleted or renamed
+/// non-doc file, with that change.
+fn stale_references<'a>(
+    project_root: &Path,
+    matcher: &DocMatcher,
+    doc_files: &[String],
+    changes: &'a [FileChange],
+) -> Vec<(String, &'a FileChange)> {
+    let gone: Vec<(&str, &FileChange)> = changes
+        .iter()
+        .filter_map(|change| match change.status {
+            FileStatus::Deleted => Some((change.path.as_str(), change)),
+            FileStatus::Renamed => Some((change.old_path.as_deref()?, change)),
+            _ => None,
+        })
+        .filter(|(path, _)| !matcher.is_doc(path))
+        .collect();
+    if gone.is_empty() {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    for doc in doc_files {
+        let Ok(content) = std::fs::read_to_string(project_root.join(doc)) else {
+            continue;
+        };
+        for (_, change) in gone.iter().filter(|(path, _)| mentions(&content, path)) {
+            found.push((doc.clone(), *change));
         }
     }
+    found
+}
 
-    docs.sort();
-    debug!(count = docs.len(), "Found doc files");
-    docs
+/// Project memories mentioning a changed file, or a renamed file's old
+/// path, and how many of them name an old path. One query reads them all
+/// (`project_memories_mentioning`); nothing is written, so the push never
+/// waits on the project lock. `sqrl memory lint --fix` follows the moves.
+fn memories_for_changes(
+    project_root: &Path,
+    changes: &[FileChange],
+) -> Result<(Vec<Memory>, usize), Error> {
+    let renames: Vec<(&str, &str)> = changes
+        .iter()
+        .filter_map(|change| Some((change.old_path.as_deref()?, change.path.as_str())))
+        .collect();
+    let paths: Vec<&str> = changes
+        .iter()
+        .map(|change| change.path.as_str())
+        .chain(renames.iter().map(|(old, _)| *old))
+        .collect();
+    let db = storage::db_path(project_root);
+    if !db.exists() {
+        return Ok((Vec::new(), 0));
+    }
+    let storage = Storage::open_read_only(&db)?;
+
+    // The query matches substrings; keep memories naming one of the paths
+    let memories: Vec<Memory> = storage
+        .project_memories_mentioning(&paths)?
+        .into_iter()
+        .filter(|memory| {
+            lint::mentioned_paths(&memory.content)
+                .iter()
+                .any(|p| paths.contains(&crate::paths::normalize(p).as_str()))
+        })
+        .collect();
+    let moved = memories
+        .iter()
+        .filter(|memory| {
+            renames
+                .iter()
+                .any(|(old, new)| lint::rename_path(memory, old, new).is_some())
+        })
+        .count();
+    Ok((memories, moved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::bundle::SharedMemory;
+    use tempfile::TempDir;
+
+    fn change(status: FileStatus, path: &str, old_path: Option<&str>) -> FileChange {
+        FileChange {
+            path: path.to_string(),
+            old_path: old_path.map(str::to_string),
+            status,
+            lines: Some((1, 1)),
+        }
+    }
+
+    #[test]
+    fn test_stale_references_moved_or_deleted_code_listed_renamed_doc_not() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(
+            root.join("README.md"),
+            "See src/old.rs and docs/old-guide.md.\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("docs/guide.md"), "Don't call src/gone.rs.\n").unwrap();
+        let changes = [
+            change(FileStatus::Renamed, "src/new.rs", Some("src/old.rs")),
+            change(FileStatus::Deleted, "src/gone.rs", None),
+            change(
+                FileStatus::Renamed,
+                "docs/guide.md",
+                Some("docs/old-guide.md"),
+            ),
+            change(FileStatus::Modified, "src/main.rs", None),
+        ];
+        let doc_files = ["README.md".to_string(), "docs/guide.md".to_string()];
+        let matcher = Config::default().doc_matcher().unwrap();
+
+        let stale: Vec<String> = stale_references(root, &matcher, &doc_files, &changes)
+            .into_iter()
+            .map(|(doc, change)| format!("{} -> {}", doc, change.path))
+            .collect();
+        assert_eq!(
+            stale,
+            ["README.md -> src/new.rs", "docs/guide.md -> src/gone.rs"]
+        );
+    }
+
+    #[test]
+    fn test_refresh_after_checkout_regenerates_conventions_only_when_on() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".sqrl")).unwrap();
+        let mut config = Config::default();
+        config.save(root).unwrap();
+        storage::store_memory(root, "project", "Use pnpm", &[]).unwrap();
+        let conventions = root.join(targets::AIDER_CONVENTIONS);
+        std::fs::write(&conventions, "from the other branch\n").unwrap();
+
+        refresh_after_checkout(root).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&conventions).unwrap(),
+            "from the other branch\n"
+        );
+
+        config.hooks.on_checkout = true;
+        config.save(root).unwrap();
+        refresh_after_checkout(root).unwrap();
+        let content = std::fs::read_to_string(&conventions).unwrap();
+        assert!(content.starts_with("# Project Conventions"));
+        assert!(content.contains("Use pnpm"));
+    }
+
+    #[test]
+    fn test_check_push_quiet_one_line_same_exit() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let repo = git2::Repository::init(root).unwrap();
+        std::fs::create_dir_all(root.join(".sqrl")).unwrap();
+        let mut config = Config::default();
+        config.save(root).unwrap();
+        let sig = git2::Signature::now("t", "t@example.com").unwrap();
+        let commit = |content: &str| {
+            std::fs::write(root.join("main.rs"), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("main.rs")).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<git2::Commit> = repo
+                .head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .into_iter()
+                .collect();
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, content, &tree, &parents)
+                .unwrap()
+        };
+        let base = commit("fn main() {}\n");
+        repo.reference("refs/remotes/origin/main", base, true, "")
+            .unwrap();
+        commit("fn main() { run() }\n");
+
+        let check = |quiet| {
+            let mut out = Vec::new();
+            let allowed = check_push(root, quiet, &mut out).unwrap();
+            (allowed, String::from_utf8(out).unwrap())
+        };
+        let messages = config.hook_messages().unwrap();
+        for (block, summary) in [
+            (false, Message::QuietNoDocs),
+            (true, Message::PushBlockedQuiet),
+        ] {
+            config.hooks.pre_push_block = block;
+            config.save(root).unwrap();
+            let (allowed, full) = check(false);
+            let (quiet_allowed, quiet) = check(true);
+            assert_eq!(allowed, !block);
+            assert_eq!(quiet_allowed, allowed);
+            assert!(full.lines().count() > 5);
+            assert_eq!(quiet, format!("{}\n", messages.render(summary, 1, 1)));
+        }
+    }
+
+    #[test]
+    fn test_merge_memories_files_round_trip_keeps_both_sides() {
+        let dir = TempDir::new().unwrap();
+        let path = |name: &str| dir.path().join(name);
+        let memory = |id: &str, content: &str| SharedMemory {
+            id: id.to_string(),
+            memory_type: "project".to_string(),
+            content: content.to_string(),
+            tags: Vec::new(),
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+            updated_at: "2026-01-01T00:00:00+00:00".to_string(),
+        };
+        let write = |name: &str, memories: Vec<SharedMemory>| {
+            let mut bundle = Bundle::load(&path(name)).unwrap();
+            bundle.memories = memories;
+            bundle.save(&path(name)).unwrap();
+        };
+        write("base", vec![memory("a", "Use pnpm")]);
+        write(
+            "ours",
+            vec![memory("a", "Use pnpm"), memory("b", "Run clippy")],
+        );
+        write("theirs", vec![memory("c", "Tag releases")]);
+
+        merge_memories(&path("base"), &path("ours"), &path("theirs")).unwrap();
+        let merged = Bundle::load(&path("ours")).unwrap();
+        let contents: Vec<&str> = merged.memories.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["Run clippy", "Tag releases"]);
+
+        // An unreadable side fails and leaves ours as it was
+        std::fs::write(path("theirs"), "<<<<<<< not json").unwrap();
+        let before = std::fs::read_to_string(path("ours")).unwrap();
+        assert!(merge_memories(&path("base"), &path("ours"), &path("theirs")).is_err());
+        assert_eq!(std::fs::read_to_string(path("ours")).unwrap(), before);
+    }
 }
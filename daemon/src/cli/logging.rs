@@ -0,0 +1,121 @@
+//! Logging setup: stderr, plus an optional rotating file (CLI-017).
+//!
+//! Hooks run without a visible terminal, so `~/.sqrl/logs/cli.log` is where
+//! their failures can be diagnosed later.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::Arc;
+
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+use crate::cli::output;
+use crate::global_config::GlobalConfig;
+
+/// Install the tracing subscriber. `RUST_LOG`, when set, wins over -q/-v on stderr.
+pub fn init(quiet: bool, verbose: u8, log_file_flag: bool) {
+    let level = match (quiet, verbose) {
+        (true, _) => "error",
+        (false, 0) => "warn",
+        (false, 1) => "info",
+        (false, 2) => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(format!("sqrl={}", level)));
+    let stderr_layer = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(output::use_color(std::io::stderr().is_terminal()))
+        .with_filter(filter);
+
+    let file_layer = open_log_file(log_file_flag).map(|(file, level)| {
+        fmt::layer()
+            .with_writer(Arc::new(file))
+            .with_ansi(false)
+            .with_filter(EnvFilter::new(format!("sqrl={}", level)))
+    });
+
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(file_layer)
+        .init();
+}
+
+/// Open the log file if enabled by flag or `log.file`, rotating it first.
+/// Returns the file and its level. Failures disable file logging silently.
+fn open_log_file(flag: bool) -> Option<(File, String)> {
+    let config = if GlobalConfig::exists() {
+        GlobalConfig::load().ok()?.log
+    } else {
+        Default::default()
+    };
+    if !flag && !config.file {
+        return None;
+    }
+
+    let path = GlobalConfig::log_path().ok()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    rotate(&path, config.max_size_kb * 1024, config.keep);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .ok()?;
+    Some((file, config.level))
+}
+
+/// Shift `cli.log` → `cli.log.1` → … → `cli.log.<keep>` once it exceeds `max_bytes`.
+fn rotate(path: &Path, max_bytes: u64, keep: u32) {
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size <= max_bytes {
+        return;
+    }
+    let numbered = |n: u32| path.with_file_name(format!("cli.log.{}", n));
+    if keep == 0 {
+        let _ = fs::remove_file(path);
+        return;
+    }
+    let _ = fs::remove_file(numbered(keep));
+    for n in (1..keep).rev() {
+        let _ = fs::rename(numbered(n), numbered(n + 1));
+    }
+    let _ = fs::rename(path, numbered(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rotate_shifts_and_drops_oldest() {
+        let dir = TempDir::new().unwrap();
+        let log = dir.path().join("cli.log");
+        fs::write(&log, "current").unwrap();
+        fs::write(dir.path().join("cli.log.1"), "older").unwrap();
+        fs::write(dir.path().join("cli.log.2"), "oldest").unwrap();
+
+        rotate(&log, 1, 2);
+
+        assert!(!log.exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("cli.log.1")).unwrap(),
+            "current"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("cli.log.2")).unwrap(),
+            "older"
+        );
+        assert!(!dir.path().join("cli.log.3").exists());
+    }
+
+    #[test]
+    fn test_rotate_leaves_small_file() {
+        let dir = TempDir::new().unwrap();
+        let log = dir.path().join("cli.log");
+        fs::write(&log, "small").unwrap();
+        rotate(&log, 1024, 3);
+        assert!(log.exists());
+    }
+}
@@ -0,0 +1,163 @@
+//! `sqrl mcp test`: the initialize handshake with a stored server.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use super::remote::{http_initialize, sse_initialize};
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::global_config::{template, GlobalConfig, McpConfig, Transport};
+use crate::mcp::PROTOCOL_VERSION;
+
+/// How long `sqrl mcp test` waits for the initialize response.
+const TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Launch or connect to a stored MCP server and complete the initialize
+/// handshake.
+pub fn test(name: &str) -> Result<(), Error> {
+    let mcp = template::resolve_mcp(&GlobalConfig::get_mcp(name)?)?;
+    let started = Instant::now();
+    let result = match &mcp.url {
+        Some(url) => remote_handshake(mcp.transport, url),
+        None => handshake(&mcp),
+    };
+    let elapsed_ms = started.elapsed().as_millis();
+    let server = result?;
+
+    let server_name = server["serverInfo"]["name"].as_str().unwrap_or("unknown");
+    let version = server["serverInfo"]["version"].as_str().unwrap_or("");
+    human!(
+        "{} works: {} {} answered initialize in {}ms (protocol {}).",
+        name,
+        server_name,
+        version,
+        elapsed_ms,
+        server["protocolVersion"].as_str().unwrap_or("unknown")
+    );
+    output::emit(&json!({
+        "name": name,
+        "ok": true,
+        "elapsed_ms": elapsed_ms,
+        "server": server,
+    }))
+}
+
+/// The `initialize` request `sqrl mcp test` sends.
+pub(super) fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "sqrl", "version": env!("CARGO_PKG_VERSION") }
+        }
+    })
+}
+
+/// The result of an `initialize` response, or the error it carries.
+fn initialize_result(response: Value) -> Result<Value, String> {
+    match response.get("result") {
+        Some(result) => Ok(result.clone()),
+        None => Err(format!(
+            "initialize returned an error: {}",
+            response["error"]
+        )),
+    }
+}
+
+/// Send `initialize` over stdio and return the server's result.
+fn handshake(mcp: &McpConfig) -> Result<Value, Error> {
+    let command_line = format!("{} {}", mcp.command, mcp.args.join(" "));
+    let failed = |stderr: String| Error::Command {
+        command: command_line.trim_end().to_string(),
+        stderr,
+    };
+
+    let mut command = Command::new(&mcp.command);
+    if let Some(cwd) = &mcp.cwd {
+        command.current_dir(cwd);
+    }
+    let mut child = command
+        .args(&mcp.args)
+        .envs(&mcp.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| failed(e.to_string()))?;
+
+    let request = initialize_request();
+    if let Some(mut stdin) = child.stdin.take() {
+        // A server that exits at once closes stdin; its stderr explains why
+        let _ = writeln!(stdin, "{}", request);
+    }
+
+    // Drain stderr meanwhile so a chatty server can't block on a full pipe
+    let mut stderr_pipe = child.stderr.take().expect("stderr is piped");
+    let stderr_reader = thread::spawn(move || {
+        let mut stderr = String::new();
+        let _ = stderr_pipe.read_to_string(&mut stderr);
+        stderr
+    });
+
+    // Servers may log to stdout before answering, so skip non-responses
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let response = BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+            .find(|message| message["id"] == 1);
+        let _ = tx.send(response);
+    });
+    let response = rx.recv_timeout(TEST_TIMEOUT);
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let stderr = stderr.trim();
+
+    match response {
+        Ok(Some(response)) => initialize_result(response).map_err(failed),
+        Ok(None) if !stderr.is_empty() => Err(failed(stderr.to_string())),
+        Ok(None) => Err(failed("exited without answering initialize".to_string())),
+        Err(_) => Err(failed(format!(
+            "no initialize response within {}s",
+            TEST_TIMEOUT.as_secs()
+        ))),
+    }
+}
+
+/// Send `initialize` to a remote server and return its result.
+fn remote_handshake(transport: Transport, url: &str) -> Result<Value, Error> {
+    let failed = |message: String| Error::Command {
+        command: format!("{} {}", transport.as_str(), url),
+        stderr: message,
+    };
+
+    // A stream that never sends the response would block, so time it out
+    let (tx, rx) = mpsc::channel();
+    let target = url.to_string();
+    thread::spawn(move || {
+        let response = match transport {
+            Transport::Sse => sse_initialize(&target),
+            _ => http_initialize(&target),
+        };
+        let _ = tx.send(response);
+    });
+    match rx.recv_timeout(TEST_TIMEOUT) {
+        Ok(response) => response.and_then(initialize_result).map_err(failed),
+        Err(_) => Err(failed(format!(
+            "no initialize response within {}s",
+            TEST_TIMEOUT.as_secs()
+        ))),
+    }
+}
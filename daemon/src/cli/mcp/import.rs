@@ -0,0 +1,153 @@
+//! `sqrl mcp import`: MCP servers from another tool's configuration.
+
+use std::fs;
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use serde_json::json;
+use tracing::info;
+
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::global_config::{GlobalConfig, McpConfig};
+use crate::lock::SqrlLock;
+
+/// Tool whose MCP configuration can be imported.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ImportSource {
+    /// Claude Code (~/.claude.json and project .mcp.json)
+    Claude,
+    /// Cursor (~/.cursor/mcp.json and project .cursor/mcp.json)
+    Cursor,
+}
+
+/// Import MCP servers from an existing tool configuration.
+pub fn import(source: ImportSource, overwrite: bool) -> Result<(), Error> {
+    GlobalConfig::init()?;
+    let _lock = SqrlLock::global()?;
+
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+    for (path, scope) in source_files(source)? {
+        if !path.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(&path)?;
+        let json: serde_json::Value = serde_json::from_str(&content)?;
+        let (mcps, unsupported) = GlobalConfig::parse_mcp_servers(&json["mcpServers"], scope);
+        info!(path = %path.display(), count = mcps.len(), "Read MCP servers");
+        candidates.extend(mcps);
+        skipped.extend(unsupported);
+    }
+
+    if candidates.is_empty() && skipped.is_empty() {
+        human!("No MCP servers found in {:?} config.", source);
+        return output::emit(
+            &json!({ "imported": [], "unchanged": [], "conflicts": [], "skipped": [] }),
+        );
+    }
+
+    let mut imported = Vec::new();
+    let mut unchanged = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut invalid = Vec::new();
+    for mcp in candidates {
+        if let Err(e) = mcp.check() {
+            human!("  - {} (skipped: {})", mcp.name, e);
+            invalid.push(mcp.name);
+            continue;
+        }
+        match GlobalConfig::get_mcp(&mcp.name) {
+            Ok(existing) if existing == mcp => {
+                human!("  = {} (unchanged)", mcp.name);
+                unchanged.push(mcp.name);
+            }
+            Ok(existing) if !overwrite => {
+                human!("  ! {} (conflict)", mcp.name);
+                print_conflict(&existing, &mcp);
+                conflicts.push(mcp.name);
+            }
+            Err(Error::ConfigParse(_)) if !overwrite => {
+                human!("  ! {} (conflict: stored config is invalid)", mcp.name);
+                conflicts.push(mcp.name);
+            }
+            _ => {
+                GlobalConfig::save_mcp(&mcp)?;
+                human!("  + {}", mcp.name);
+                imported.push(mcp.name);
+            }
+        }
+    }
+
+    for name in &skipped {
+        human!("  - {} (skipped: no command or supported url)", name);
+    }
+    skipped.extend(invalid);
+
+    human!();
+    human!(
+        "Imported {}, conflicts {}, skipped {}.",
+        imported.len(),
+        conflicts.len(),
+        skipped.len()
+    );
+    if !conflicts.is_empty() {
+        human!("Re-run with --overwrite to replace conflicting configs.");
+    }
+
+    output::emit(&json!({
+        "imported": imported,
+        "unchanged": unchanged,
+        "conflicts": conflicts,
+        "skipped": skipped,
+    }))
+}
+
+/// Config files to read for a source, with the scope to assign.
+fn source_files(source: ImportSource) -> Result<Vec<(PathBuf, &'static str)>, Error> {
+    let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+    let cwd = std::env::current_dir()?;
+
+    Ok(match source {
+        ImportSource::Claude => vec![
+            (home.join(".claude.json"), "user"),
+            (cwd.join(".mcp.json"), "project"),
+        ],
+        ImportSource::Cursor => vec![
+            (home.join(".cursor").join("mcp.json"), "user"),
+            (cwd.join(".cursor").join("mcp.json"), "project"),
+        ],
+    })
+}
+
+/// Print how an imported config differs from the stored one.
+fn print_conflict(existing: &McpConfig, incoming: &McpConfig) {
+    if existing.command != incoming.command || existing.args != incoming.args {
+        human!(
+            "      stored:   {} {}",
+            existing.command,
+            existing.args.join(" ")
+        );
+        human!(
+            "      imported: {} {}",
+            incoming.command,
+            incoming.args.join(" ")
+        );
+    }
+    if existing.url != incoming.url {
+        human!(
+            "      url: {} -> {}",
+            existing.url.as_deref().unwrap_or("(none)"),
+            incoming.url.as_deref().unwrap_or("(none)")
+        );
+    }
+    if existing.env != incoming.env {
+        human!("      env differs");
+    }
+    if existing.cwd != incoming.cwd {
+        human!("      cwd differs");
+    }
+    if existing.scope != incoming.scope {
+        human!("      scope: {} -> {}", existing.scope, incoming.scope);
+    }
+}
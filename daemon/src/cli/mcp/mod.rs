@@ -0,0 +1,204 @@
+//! MCP config management commands (CLI-010).
+
+mod handshake;
+mod import;
+mod remote;
+
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+
+use serde_json::json;
+
+use crate::atomic;
+use crate::cli::output::{self, human};
+use crate::config::validate;
+use crate::error::{Error, ExitCode};
+use crate::global_config::{GlobalConfig, McpConfig, Transport};
+use crate::lock::SqrlLock;
+
+pub use handshake::test;
+pub use import::{import, ImportSource};
+
+/// List stored MCP configs.
+pub fn list() -> Result<(), Error> {
+    let mut mcps = GlobalConfig::list_mcps()?;
+    mcps.sort_by(|a, b| a.name.cmp(&b.name));
+    if mcps.is_empty() {
+        human!("No MCP configs. Add one with 'sqrl mcp add <name> -- <command>'.");
+    }
+    for mcp in &mcps {
+        let launch = match &mcp.url {
+            Some(url) => format!("{} {}", mcp.transport.as_str(), url),
+            None => format!("{} {}", mcp.command, mcp.args.join(" ")),
+        };
+        human!("  {} ({}): {}", mcp.name, mcp.scope, launch.trim_end());
+    }
+    output::emit(&mcps)
+}
+
+/// Store a new MCP config from a command line, or from the transport and
+/// URL of a remote server.
+pub fn add(
+    name: &str,
+    command: &[String],
+    remote: Option<(Transport, String)>,
+    scope: &str,
+    env: &[String],
+    cwd: Option<&str>,
+    overwrite: bool,
+) -> Result<(), Error> {
+    let (transport, url) = match remote {
+        Some((transport, url)) => (transport, Some(url)),
+        None => (Transport::Stdio, None),
+    };
+    let (program, args) = match command.split_first() {
+        Some((program, args)) => (program.clone(), args.to_vec()),
+        None if url.is_some() => (String::new(), Vec::new()),
+        None => {
+            return Err(Error::InvalidArgument(
+                "missing command after '--'".to_string(),
+            ))
+        }
+    };
+    let mcp = McpConfig {
+        name: name.to_string(),
+        command: program,
+        args,
+        env: parse_env(env)?,
+        scope: scope.to_string(),
+        cwd: cwd.map(String::from),
+        transport,
+        url,
+    };
+    mcp.check()?;
+    GlobalConfig::init()?;
+    let _lock = SqrlLock::global()?;
+    if !overwrite && GlobalConfig::mcp_path(name)?.exists() {
+        return Err(Error::InvalidArgument(format!(
+            "MCP '{}' already exists; pass --overwrite to replace it",
+            name
+        )));
+    }
+    GlobalConfig::save_mcp(&mcp)?;
+    human!("Added {}. Check it with 'sqrl mcp test {}'.", name, name);
+    output::emit(&mcp)
+}
+
+/// Delete a stored MCP config. Tool configs it was applied to are untouched.
+pub fn remove(name: &str) -> Result<(), Error> {
+    let _lock = SqrlLock::global()?;
+    // Invalid configs can be removed too
+    if !GlobalConfig::mcp_path(name)?.exists() {
+        return Err(Error::McpNotFound(name.to_string()));
+    }
+    GlobalConfig::delete_mcp(name)?;
+    human!(
+        "Removed {}. Run 'sqrl unapply {}' to take it out of tool configs.",
+        name,
+        name
+    );
+    output::emit(&json!({ "removed": name }))
+}
+
+/// Open a stored MCP config in `$VISUAL` or `$EDITOR`, keeping the edit
+/// only if it is still a valid config with the same name.
+pub fn edit(name: &str) -> Result<(), Error> {
+    let _lock = SqrlLock::global()?;
+    let existing = GlobalConfig::get_mcp(name)?;
+    let draft = std::env::temp_dir().join(format!("sqrl-mcp-{}-{}.json", name, std::process::id()));
+    atomic::write(&draft, serde_json::to_string_pretty(&existing)?)?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // `EDITOR="code --wait"` style values carry their own arguments
+    let mut words = editor.split_whitespace();
+    let status = Command::new(words.next().unwrap_or("vi"))
+        .args(words)
+        .arg(&draft)
+        .status();
+    let content = fs::read_to_string(&draft);
+    let _ = fs::remove_file(&draft);
+    let status = status.map_err(|e| Error::Command {
+        command: editor.clone(),
+        stderr: e.to_string(),
+    })?;
+    if !status.success() {
+        return Err(Error::Command {
+            command: editor,
+            stderr: format!("exited with {}; config left unchanged", status),
+        });
+    }
+
+    // Checked as the stored file, so a renamed config is reported as such
+    let edited = validate::parse_mcp(&GlobalConfig::mcp_path(name)?, &content?)?;
+    if edited == existing {
+        human!("{} unchanged.", name);
+    } else {
+        GlobalConfig::save_mcp(&edited)?;
+        human!(
+            "Saved {}. Re-run 'sqrl apply' to update tool configs.",
+            name
+        );
+    }
+    output::emit(&edited)
+}
+
+/// Check stored MCP configs (one, or all) against MCP-CONFIG-001.
+/// Returns exit code.
+pub fn validate(name: Option<&str>) -> Result<i32, Error> {
+    let problems = match name {
+        Some(name) => {
+            let path = GlobalConfig::mcp_path(name)?;
+            if !path.exists() {
+                return Err(Error::McpNotFound(name.to_string()));
+            }
+            let content = fs::read_to_string(&path).map_err(Error::at(&path))?;
+            validate::validate_mcp(&path, &content)
+        }
+        None => validate::validate_mcps()?,
+    };
+    if problems.is_empty() {
+        human!("MCP configs: ok");
+    } else {
+        human!("MCP configs: {} problem(s)", problems.len());
+        for problem in &problems {
+            human!("  {}", problem);
+        }
+    }
+    output::emit(&json!({ "ok": problems.is_empty(), "problems": problems }))?;
+    Ok(if problems.is_empty() {
+        0
+    } else {
+        ExitCode::Problems.code()
+    })
+}
+
+/// `KEY=VALUE` pairs from `--env`.
+fn parse_env(pairs: &[String]) -> Result<HashMap<String, String>, Error> {
+    pairs
+        .iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(Error::InvalidArgument(format!(
+                "invalid --env '{}': expected KEY=VALUE",
+                pair
+            ))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env() {
+        let env = parse_env(&["TOKEN=a=b".to_string(), "EMPTY=".to_string()]).unwrap();
+        assert_eq!(env["TOKEN"], "a=b");
+        assert_eq!(env["EMPTY"], "");
+        assert!(parse_env(&["=value".to_string()]).is_err());
+        assert!(parse_env(&["TOKEN".to_string()]).is_err());
+    }
+}
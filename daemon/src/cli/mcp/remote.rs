@@ -0,0 +1,133 @@
+//! `initialize` over streamable HTTP and SSE, for remote servers.
+
+use std::io::{BufRead, BufReader};
+
+use serde_json::Value;
+
+use super::handshake::initialize_request;
+
+/// `initialize` over streamable HTTP: one POST, answered with JSON or an
+/// event stream.
+pub(super) fn http_initialize(url: &str) -> Result<Value, String> {
+    let response = ureq::post(url)
+        .set("Accept", "application/json, text/event-stream")
+        .send_json(initialize_request())
+        .map_err(|e| e.to_string())?;
+    if response.content_type() == "text/event-stream" {
+        let mut lines = BufReader::new(response.into_reader())
+            .lines()
+            .map_while(Result::ok);
+        return find_response(&mut lines);
+    }
+    response.into_json().map_err(|e| e.to_string())
+}
+
+/// `initialize` over SSE: open the stream, POST to the endpoint it names,
+/// then read the answer from the stream.
+pub(super) fn sse_initialize(url: &str) -> Result<Value, String> {
+    let stream = ureq::get(url)
+        .set("Accept", "text/event-stream")
+        .call()
+        .map_err(|e| e.to_string())?;
+    let mut lines = BufReader::new(stream.into_reader())
+        .lines()
+        .map_while(Result::ok);
+    let endpoint = loop {
+        match next_event(&mut lines) {
+            Some((event, data)) if event == "endpoint" => break resolve_endpoint(url, &data),
+            Some(_) => continue,
+            None => return Err("stream closed before naming an endpoint".to_string()),
+        }
+    };
+    ureq::post(&endpoint)
+        .send_json(initialize_request())
+        .map_err(|e| e.to_string())?;
+    find_response(&mut lines)
+}
+
+/// The `initialize` response among the events on a stream.
+fn find_response(lines: &mut impl Iterator<Item = String>) -> Result<Value, String> {
+    while let Some((_, data)) = next_event(lines) {
+        if let Ok(message) = serde_json::from_str::<Value>(&data) {
+            if message["id"] == 1 {
+                return Ok(message);
+            }
+        }
+    }
+    Err("stream closed without answering initialize".to_string())
+}
+
+/// The next Server-Sent Event on a stream: (event name, data).
+fn next_event(lines: &mut impl Iterator<Item = String>) -> Option<(String, String)> {
+    let mut event = String::from("message");
+    let mut data: Vec<String> = Vec::new();
+    let mut any = false;
+    for line in lines {
+        if line.is_empty() {
+            if any {
+                return Some((event, data.join("\n")));
+            }
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((&line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event = value.to_string(),
+            "data" => data.push(value.to_string()),
+            _ => continue,
+        }
+        any = true;
+    }
+    any.then(|| (event, data.join("\n")))
+}
+
+/// An SSE `endpoint` event's URL, which may be relative to the stream's.
+fn resolve_endpoint(url: &str, endpoint: &str) -> String {
+    if endpoint.contains("://") {
+        return endpoint.to_string();
+    }
+    let path_start = url
+        .find("://")
+        .and_then(|scheme| url[scheme + 3..].find('/').map(|i| scheme + 3 + i));
+    match path_start {
+        None => format!("{}/{}", url, endpoint.trim_start_matches('/')),
+        Some(path) if endpoint.starts_with('/') => format!("{}{}", &url[..path], endpoint),
+        Some(_) => {
+            let dir = url.rfind('/').map_or(url.len(), |i| i + 1);
+            format!("{}{}", &url[..dir], endpoint)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sse_events() {
+        let stream = "event: endpoint\ndata: /messages?session=1\n\n: ping\n\n\
+                      data: {\"jsonrpc\":\"2.0\",\"id\":1,\n\
+                      data: \"result\":{}}\n\n";
+        let mut lines = stream.lines().map(String::from);
+        assert_eq!(
+            next_event(&mut lines),
+            Some(("endpoint".to_string(), "/messages?session=1".to_string()))
+        );
+        assert_eq!(find_response(&mut lines).unwrap()["result"], json!({}));
+
+        let url = "https://mcp.example.com/v1/sse";
+        assert_eq!(
+            resolve_endpoint(url, "/messages?session=1"),
+            "https://mcp.example.com/messages?session=1"
+        );
+        assert_eq!(
+            resolve_endpoint(url, "messages"),
+            "https://mcp.example.com/v1/messages"
+        );
+        assert_eq!(
+            resolve_endpoint(url, "https://other.example.com/m"),
+            "https://other.example.com/m"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! `sqrl memory at`: the memories snapshotted at a commit.
+
+use serde_json::json;
+
+use crate::cli::git;
+use crate::cli::output::{self, human};
+use crate::error::{Error, ExitCode};
+use crate::storage::snapshot::Snapshot;
+use crate::storage::{self, Storage};
+
+use super::root;
+
+/// Commits searched back from the requested one for a snapshot.
+pub(super) const SNAPSHOT_SEARCH_DEPTH: usize = 1000;
+
+/// Show the memories recorded at a commit, or its nearest snapshotted ancestor.
+/// Returns exit code 1 if none is found.
+pub fn at(rev: &str) -> Result<i32, Error> {
+    let root = root()?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let commits = git::first_parents(&root, rev, SNAPSHOT_SEARCH_DEPTH)?;
+    let Some((behind, snapshot)) = nearest_snapshot(&storage, &commits)? else {
+        human!("No memory snapshot at or before {}.", rev);
+        human!("Enable them with 'sqrl config set hooks.snapshots true' and 'sqrl init --repair'.");
+        output::emit(&json!({ "commit": commits[0], "snapshot": null }))?;
+        return Ok(ExitCode::Problems.code());
+    };
+    output::emit(&json!({ "commit": commits[0], "snapshot": snapshot }))?;
+
+    human!(
+        "Memories at {} (recorded {}):",
+        short_commit(&snapshot.commit),
+        snapshot.taken_at
+    );
+    if behind > 0 {
+        human!(
+            "  nearest snapshot, {} commit(s) before {}",
+            behind,
+            short_commit(&commits[0])
+        );
+    }
+    if snapshot.memories.is_empty() {
+        human!("  (none)");
+    }
+    for memory in &snapshot.memories {
+        human!(
+            "{}  {:<10}  {}",
+            storage::short_id(&memory.id),
+            memory.memory_type,
+            memory.content
+        );
+    }
+    Ok(0)
+}
+
+pub(super) fn short_commit(id: &str) -> &str {
+    &id[..id.len().min(8)]
+}
+
+/// The snapshot of the first of `commits` that has one, with how many
+/// commits it is behind the first.
+pub(super) fn nearest_snapshot(
+    storage: &Storage,
+    commits: &[String],
+) -> Result<Option<(usize, Snapshot)>, Error> {
+    commits
+        .iter()
+        .enumerate()
+        .find_map(|(behind, commit)| match storage.snapshot(commit) {
+            Ok(snapshot) => snapshot.map(|s| Ok((behind, s))),
+            Err(e) => Some(Err(e)),
+        })
+        .transpose()
+}
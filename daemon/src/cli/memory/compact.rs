@@ -0,0 +1,61 @@
+//! `sqrl memory compact`: apply retention and shrink the database.
+
+use std::fs;
+
+use serde_json::json;
+
+use crate::cli::git;
+use crate::cli::output::{self, human};
+use crate::cli::progress::Progress;
+use crate::config::Config;
+use crate::error::Error;
+use crate::lock::SqrlLock;
+use crate::storage::{self, Storage};
+use crate::trash;
+
+use super::root;
+
+/// Drop MCP activity and snapshots older than `retention` allows, snapshots of
+/// commits the repository no longer has, and orphaned ratings (CONFIG-008),
+/// then shrink the database file.
+pub fn compact() -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let retention = Config::effective(&root)?.retention;
+    let cutoff = |days: u32| {
+        (days > 0).then(|| (chrono::Utc::now() - chrono::Duration::days(days.into())).to_rfc3339())
+    };
+    let path = storage::db_path(&root);
+    let size = || fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+    let before = size();
+    let progress = Progress::spinner("Compacting memory.db");
+    let storage = Storage::open(&path)?;
+    let unreachable = git::unreachable(&root, &storage.snapshot_commits()?)?;
+    let report = storage.compact(
+        cutoff(retention.activity_days).as_deref(),
+        cutoff(retention.snapshot_days).as_deref(),
+        &unreachable,
+        &trash::restorable_since().to_rfc3339(),
+    )?;
+    progress.finish();
+    let after = size();
+    output::emit(&json!({ "removed": report, "bytes_before": before, "bytes_after": after }))?;
+
+    human!(
+        "Removed {} MCP activity rows, {} usage rows, {} change feed rows and {} snapshots.",
+        report.activity,
+        report.usage,
+        report.changes,
+        report.snapshots
+    );
+    if report.unreachable_snapshots > 0 || report.ratings > 0 {
+        human!(
+            "Removed {} snapshots of unreachable commits and {} ratings of deleted memories.",
+            report.unreachable_snapshots,
+            report.ratings
+        );
+    }
+    human!("memory.db: {} KB -> {} KB.", before / 1024, after / 1024);
+    Ok(())
+}
@@ -0,0 +1,185 @@
+//! `sqrl memory import`: offer bullets from hand-written context files.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cli::import;
+use crate::cli::output::{self, human};
+use crate::cli::plugins;
+use crate::error::Error;
+use crate::global_config::PluginEvent;
+use crate::lock::SqrlLock;
+use crate::storage::meta;
+use crate::storage::{self, Storage};
+
+use super::{root, IMPORT_TAG};
+
+/// Hand-written AI context files scanned by `import`, relative to the project root.
+const CONTEXT_FILES: &[&str] = &["CLAUDE.md", ".claude/CLAUDE.md", "AGENTS.md"];
+
+/// A bullet in a context file that is not yet a memory.
+#[derive(Serialize)]
+struct Candidate {
+    file: &'static str,
+    content: String,
+}
+
+/// Offer bullets from CLAUDE.md/AGENTS.md that aren't memories yet, one at a time.
+/// Declined bullets are recorded locally (`meta`) so they aren't offered again;
+/// a tombstone would make sync delete a teammate's memory with the same text.
+pub fn import(yes: bool) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let candidates = import_candidates(&root, &storage)?;
+
+    if candidates.is_empty() {
+        human!("Context files and memories are in sync.");
+        return output::emit(&json!({ "imported": [], "pending": [] }));
+    }
+    let interactive = !output::is_json() && io::stdin().is_terminal();
+    if !yes && !interactive {
+        for c in &candidates {
+            human!("{}: {}", c.file, c.content);
+        }
+        human!("Run 'sqrl memory import --yes' to import them.");
+        return output::emit(&json!({ "imported": [], "pending": candidates }));
+    }
+
+    let mut imported = Vec::new();
+    let mut pending = Vec::new();
+    for candidate in candidates {
+        let answer = if yes {
+            "y".to_string()
+        } else {
+            print!(
+                "{}: {}\n  Import as a project memory? [y]es / [n]ever / [Enter] later ",
+                candidate.file, candidate.content
+            );
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            input.trim().to_lowercase()
+        };
+        match answer.as_str() {
+            "y" | "yes" => {
+                let tags = [IMPORT_TAG.to_string()];
+                let stored = storage.store_memory("project", &candidate.content, &tags)?;
+                plugins::run(
+                    PluginEvent::MemoryCreated,
+                    &root,
+                    json!({
+                        "id": stored.id,
+                        "memory_type": "project",
+                        "content": candidate.content,
+                        "tags": tags,
+                    }),
+                );
+                imported.push(stored.id);
+            }
+            "n" | "never" => {
+                storage.meta_set(
+                    meta::DECLINED_NAMESPACE,
+                    &storage::content_id("project", &candidate.content),
+                    &candidate.content,
+                )?;
+            }
+            _ => pending.push(candidate),
+        }
+    }
+
+    human!(
+        "Imported {} memories; {} left for later.",
+        imported.len(),
+        pending.len()
+    );
+    output::emit(&json!({ "imported": imported, "pending": pending }))
+}
+
+/// Bullets in the context files not yet stored, and not declined before.
+fn import_candidates(root: &Path, storage: &Storage) -> Result<Vec<Candidate>, Error> {
+    let stored: HashSet<String> = storage
+        .list_all_memories()?
+        .into_iter()
+        .map(|m| m.content)
+        .collect();
+    let declined: HashSet<String> = storage
+        .meta_entries(Some(meta::DECLINED_NAMESPACE))?
+        .into_iter()
+        .map(|entry| entry.key)
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    for file in CONTEXT_FILES {
+        let Ok(content) = fs::read_to_string(root.join(file)) else {
+            continue;
+        };
+        for bullet in context_bullets(&content) {
+            let known = stored.contains(&bullet)
+                || declined.contains(&storage::content_id("project", &bullet));
+            if !known && seen.insert(bullet.clone()) {
+                candidates.push(Candidate {
+                    file,
+                    content: bullet,
+                });
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+/// List items of a markdown file, outside code fences and Squirrel's own block.
+fn context_bullets(content: &str) -> Vec<String> {
+    import::list_items(content)
+        .into_iter()
+        .map(|item| item.text)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_bullets_skip_code_and_squirrel_block() {
+        let content =
+            "# Rules\n- Use pnpm\n  * Run clippy before pushing\n```\n- not a rule\n```\n\
+            <!-- START Squirrel Memory Protocol -->\n- Store corrections\n\
+            <!-- END Squirrel Memory Protocol -->\n-\n";
+        assert_eq!(
+            context_bullets(content),
+            ["Use pnpm", "Run clippy before pushing"]
+        );
+    }
+
+    #[test]
+    fn test_declined_imports_stay_local() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("CLAUDE.md"),
+            "- Use pnpm
+- Run clippy
+",
+        )
+        .unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        storage
+            .meta_set(
+                meta::DECLINED_NAMESPACE,
+                &storage::content_id("project", "Use pnpm"),
+                "Use pnpm",
+            )
+            .unwrap();
+
+        let candidates = import_candidates(dir.path(), &storage).unwrap();
+        let offered: Vec<&str> = candidates.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(offered, ["Run clippy"]);
+        assert!(storage.tombstones().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+//! `sqrl memory lint`: report problems and repair the safe ones.
+
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::cli::git;
+use crate::cli::output::{self, human};
+use crate::error::{Error, ExitCode};
+use crate::lock::SqrlLock;
+use crate::storage::lint::{self, Issue};
+use crate::storage::{self, Memory, Storage};
+
+use super::root;
+
+/// Report problems in stored memories; with `fix`, repair the safe ones.
+/// `untagged` also flags memories without tags. Returns exit code 1 if
+/// problems remain.
+pub fn lint(fix: bool, untagged: bool) -> Result<i32, Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let mut memories = storage.list_all_memories()?;
+
+    let mut fixed = Vec::new();
+    if fix {
+        let moves = moved_paths(&root, &memories)?;
+        storage.atomically(|| {
+            for memory in &memories {
+                let tidied = lint::tidy(memory);
+                let moved = moves.iter().fold(tidied, |changed, (old, new)| {
+                    lint::rename_path(changed.as_ref().unwrap_or(memory), old, new).or(changed)
+                });
+                let Some(memory) = moved else {
+                    continue;
+                };
+                storage.update_memory(
+                    &memory.id,
+                    None,
+                    Some(&memory.content),
+                    Some(&memory.tags),
+                )?;
+                fixed.push(memory.id);
+            }
+            Ok(())
+        })?;
+        if !fixed.is_empty() {
+            memories = storage.list_all_memories()?;
+        }
+    }
+    let issues: Vec<Issue> = lint::lint(&memories, &root, untagged);
+    output::emit(&json!({ "issues": issues, "fixed": fixed }))?;
+
+    if !fixed.is_empty() {
+        human!("Fixed {} memories.", fixed.len());
+    }
+    if issues.is_empty() {
+        human!("No problems found.");
+        return Ok(0);
+    }
+    for issue in &issues {
+        human!(
+            "{}  {:<14} {}",
+            storage::short_id(&issue.id),
+            serde_json::to_value(issue.kind)?
+                .as_str()
+                .unwrap_or_default(),
+            issue.message
+        );
+    }
+    if !fix && issues.iter().any(|issue| issue.kind.fixable()) {
+        human!("Run 'sqrl memory lint --fix' to repair untidy entries and follow moved files.");
+    }
+    Ok(ExitCode::Problems.code())
+}
+
+/// Missing paths that project memories name, mapped to where git history
+/// says they were moved, if that file exists.
+fn moved_paths(root: &Path, memories: &[Memory]) -> Result<Vec<(String, String)>, Error> {
+    let mut missing: Vec<String> = memories
+        .iter()
+        .filter(|memory| memory.memory_type == "project")
+        .flat_map(|memory| lint::mentioned_paths(&memory.content))
+        .filter(|path| !root.join(path).exists())
+        .collect();
+    missing.sort();
+    missing.dedup();
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut moves: Vec<(String, String)> = git::renames(root, &missing)?
+        .into_iter()
+        .filter(|(_, new)| root.join(new).exists())
+        .collect();
+    moves.sort();
+    Ok(moves)
+}
@@ -0,0 +1,181 @@
+//! `sqrl memory list`: by rank, by rating, or as of a date or commit.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cli::git;
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::storage::snapshot::SnapshotMemory;
+use crate::storage::{self, Memory, Rating, Storage, Visibility};
+
+use super::at::{nearest_snapshot, short_commit, SNAPSHOT_SEARCH_DEPTH};
+use super::{open, root};
+
+/// A memory with its MCP ratings, for `list --unhelpful`.
+#[derive(Serialize)]
+struct Rated<'a> {
+    #[serde(flatten)]
+    memory: &'a Memory,
+    #[serde(flatten)]
+    rating: Rating,
+}
+
+/// List memories, most used first. With `unhelpful`, only memories rated
+/// unhelpful more often than useful, most downvoted first. With `as_of`,
+/// the memories at that date or git revision instead.
+pub fn list(
+    memory_type: Option<&str>,
+    visibility: Option<Visibility>,
+    unhelpful: bool,
+    as_of: Option<&str>,
+) -> Result<(), Error> {
+    let storage = open()?;
+    if let Some(as_of) = as_of {
+        return list_as_of(&storage, as_of, memory_type, visibility);
+    }
+    let mut memories = storage.list_all_memories()?;
+    if let Some(memory_type) = memory_type {
+        memories.retain(|m| m.memory_type == memory_type);
+    }
+    if let Some(visibility) = visibility {
+        memories.retain(|m| Visibility::of(m) == visibility);
+    }
+    if unhelpful {
+        return list_unhelpful(memories, &storage.ratings()?);
+    }
+    output::emit(&memories)?;
+
+    if memories.is_empty() {
+        human!("No memories stored.");
+        return Ok(());
+    }
+    for memory in &memories {
+        human!(
+            "{}  {:<10}  [used {}x] {}",
+            storage::short_id(&memory.id),
+            memory.memory_type,
+            memory.use_count,
+            memory.content
+        );
+    }
+    Ok(())
+}
+
+/// The memories at `as_of`: from the latest snapshot (`hooks.snapshots`) at
+/// or before it, else approximated from the memories created by then.
+fn list_as_of(
+    storage: &Storage,
+    as_of: &str,
+    memory_type: Option<&str>,
+    visibility: Option<Visibility>,
+) -> Result<(), Error> {
+    let root = root()?;
+    let (snapshot, cutoff) = match parse_as_of(as_of) {
+        Some(cutoff) => (storage.snapshot_before(&cutoff.to_rfc3339())?, cutoff),
+        None => {
+            let commits = git::first_parents(&root, as_of, SNAPSHOT_SEARCH_DEPTH)?;
+            let snapshot = nearest_snapshot(storage, &commits)?.map(|(_, s)| s);
+            (snapshot, git::commit_time(&root, &commits[0])?)
+        }
+    };
+
+    let mut memories = match &snapshot {
+        Some(snapshot) => snapshot.memories.clone(),
+        // Without a snapshot, deleted memories and earlier wording are lost
+        None => storage
+            .list_all_memories()?
+            .into_iter()
+            .filter(|m| {
+                chrono::DateTime::parse_from_rfc3339(&m.created_at).is_ok_and(|at| at <= cutoff)
+            })
+            .map(|m| SnapshotMemory {
+                id: m.id,
+                memory_type: m.memory_type,
+                content: m.content,
+                tags: m.tags,
+            })
+            .collect(),
+    };
+    if let Some(memory_type) = memory_type {
+        memories.retain(|m| m.memory_type == memory_type);
+    }
+    if let Some(visibility) = visibility {
+        memories.retain(|m| Visibility::from_tags(&m.tags) == visibility);
+    }
+    output::emit(&json!({
+        "as_of": cutoff.to_rfc3339(),
+        "snapshot": snapshot.as_ref().map(|s| json!({ "commit": s.commit, "taken_at": s.taken_at })),
+        "memories": memories,
+    }))?;
+
+    match &snapshot {
+        Some(snapshot) => human!(
+            "Memories as of {} (snapshot of {}, recorded {}):",
+            as_of,
+            short_commit(&snapshot.commit),
+            snapshot.taken_at
+        ),
+        None => {
+            human!(
+                "Memories as of {} (approximate, no snapshot by then):",
+                as_of
+            );
+            human!("  memories created by then that still exist, as they read now");
+        }
+    }
+    if memories.is_empty() {
+        human!("  (none)");
+    }
+    for memory in &memories {
+        human!(
+            "{}  {:<10}  {}",
+            storage::short_id(&memory.id),
+            memory.memory_type,
+            memory.content
+        );
+    }
+    Ok(())
+}
+
+/// `--as-of` as a time, if it is a date (the end of that day, UTC) or an
+/// RFC 3339 time rather than a git revision.
+fn parse_as_of(as_of: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(as_of, "%Y-%m-%d") {
+        return date.and_hms_opt(23, 59, 59).map(|at| at.and_utc());
+    }
+    chrono::DateTime::parse_from_rfc3339(as_of)
+        .ok()
+        .map(|at| at.to_utc())
+}
+
+fn list_unhelpful(memories: Vec<Memory>, ratings: &HashMap<String, Rating>) -> Result<(), Error> {
+    let mut rated: Vec<Rated> = memories
+        .iter()
+        .filter_map(|memory| {
+            let rating = *ratings.get(&memory.id)?;
+            (rating.unhelpful > rating.useful).then_some(Rated { memory, rating })
+        })
+        .collect();
+    rated.sort_by_key(|r| std::cmp::Reverse(r.rating.unhelpful - r.rating.useful));
+    output::emit(&rated)?;
+
+    if rated.is_empty() {
+        human!("No memories rated unhelpful.");
+        return Ok(());
+    }
+    for Rated { memory, rating } in &rated {
+        human!(
+            "{}  {:<10}  [{} unhelpful, {} useful] {}",
+            storage::short_id(&memory.id),
+            memory.memory_type,
+            rating.unhelpful,
+            rating.useful,
+            memory.content
+        );
+    }
+    human!("Run 'sqrl memory delete <id>' to prune.");
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Inspect, import, lint, and delete project memories (CLI-019).
+
+mod at;
+mod compact;
+mod import;
+mod lint;
+mod list;
+mod pending;
+mod secrets;
+mod show;
+mod tags;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::atomic;
+use crate::cli::output::{self, human};
+use crate::config::{find_project_root, Config};
+use crate::error::Error;
+use crate::lock::SqrlLock;
+use crate::storage::{self, Memory, Storage};
+use crate::trash::{self, Entry, Kind};
+
+pub use at::at;
+pub use compact::compact;
+pub use import::import;
+pub use lint::lint;
+pub use list::list;
+pub use pending::{approve, pending, reject};
+pub use secrets::scan_secrets;
+pub use show::show;
+pub use tags::{derive_namespaces, pin, set_namespace, set_visibility};
+
+/// Trash payload for a deleted memory.
+const MEMORY_PAYLOAD: &str = "memory.json";
+
+/// Tag on memories created by `import` and `sqrl import`.
+pub(crate) const IMPORT_TAG: &str = "imported";
+
+/// Delete a memory, keeping it in the trash for `sqrl undo`.
+pub fn delete(id: &str) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let id = storage.resolve_id(id)?;
+    let memory = storage
+        .get_memory(&id)?
+        .ok_or_else(|| Error::MemoryNotFound(id.clone()))?;
+    trash_memory(&root, &storage, &memory)?;
+
+    human!(
+        "Deleted {}: {}",
+        storage::short_id(&memory.id),
+        memory.content
+    );
+    human!("Run 'sqrl undo' to restore it.");
+    output::emit(&json!({ "deleted": memory.id }))
+}
+
+/// Delete a memory from the project database, saving it to the trash first.
+/// The caller holds the project lock.
+pub fn trash_memory(root: &Path, storage: &Storage, memory: &Memory) -> Result<(), Error> {
+    let entry = Entry::create(
+        &trash::project_dir(root),
+        Kind::MemoryDelete,
+        root,
+        format!("delete memory {}", storage::short_id(&memory.id)),
+    )?;
+    atomic::write(
+        &entry.payload(MEMORY_PAYLOAD),
+        serde_json::to_string_pretty(memory)?,
+    )?;
+    storage.delete_memory(&memory.id)
+}
+
+/// Put back the memory saved by `delete`.
+pub fn restore(entry: &Entry) -> Result<String, Error> {
+    let content = fs::read_to_string(entry.payload(MEMORY_PAYLOAD))?;
+    let mut memory: Memory = serde_json::from_str(&content)?;
+    // Newer than the deletion, so sync keeps the restored copy
+    memory.updated_at = chrono::Utc::now().to_rfc3339();
+    let _lock = SqrlLock::project(&entry.project)?;
+    Storage::open(&storage::db_path(&entry.project))?.restore_memory(&memory)?;
+    Ok(memory.id)
+}
+
+/// Root of the current project.
+pub(crate) fn root() -> Result<PathBuf, Error> {
+    let cwd = std::env::current_dir()?;
+    find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))
+}
+
+/// Open the current project's memory database.
+pub(super) fn open() -> Result<Storage, Error> {
+    Storage::open(&storage::db_path(&root()?))
+}
@@ -0,0 +1,103 @@
+//! `sqrl memory pending`, `approve` and `reject`: the MCP approval queue.
+
+use serde_json::json;
+
+use crate::cli::output::{self, human};
+use crate::cli::plugins;
+use crate::error::Error;
+use crate::global_config::PluginEvent;
+use crate::lock::SqrlLock;
+use crate::storage::pending::PendingMemory;
+use crate::storage::{self, NewMemory, Storage};
+
+use super::{open, root};
+
+/// List memories MCP clients proposed while `mcp.require_approval` is on.
+pub fn pending() -> Result<(), Error> {
+    let pending = open()?.pending_memories()?;
+    output::emit(&pending)?;
+
+    if pending.is_empty() {
+        human!("No memories awaiting approval.");
+        return Ok(());
+    }
+    for p in &pending {
+        let repeats = match p.proposals {
+            1 => String::new(),
+            n => format!(", {}x", n),
+        };
+        human!(
+            "{}  {:<10}  [{}{}] {}",
+            storage::short_id(&p.id),
+            p.memory_type,
+            p.client,
+            repeats,
+            p.content
+        );
+    }
+    human!("Run 'sqrl memory approve <id>' or 'sqrl memory reject <id>' (or --all).");
+    Ok(())
+}
+
+/// Move pending memories into the store: one by ID or prefix, or all of them.
+pub fn approve(id: Option<&str>) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    // Taking from the queue and storing commit together, so none are lost
+    let (taken, stored) = storage.atomically(|| {
+        let taken = take_pending(&storage, id)?;
+        let batch: Vec<NewMemory> = taken
+            .iter()
+            .map(|p| NewMemory {
+                memory_type: p.memory_type.clone(),
+                content: p.content.clone(),
+                tags: p.tags.clone(),
+            })
+            .collect();
+        let stored = storage.add_memories_batch(&batch)?;
+        Ok((taken, stored))
+    })?;
+
+    let mut approved = Vec::new();
+    for (p, stored) in taken.iter().zip(stored) {
+        if !stored.deduplicated {
+            plugins::run(
+                PluginEvent::MemoryCreated,
+                &root,
+                json!({ "id": stored.id, "memory_type": p.memory_type, "content": p.content, "tags": p.tags }),
+            );
+        }
+        human!("Approved {}: {}", storage::short_id(&stored.id), p.content);
+        approved.push(stored.id);
+    }
+    output::emit(&json!({ "approved": approved }))
+}
+
+/// Drop pending memories: one by ID or prefix, or all of them.
+pub fn reject(id: Option<&str>) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let taken = take_pending(&Storage::open(&storage::db_path(&root))?, id)?;
+    for p in &taken {
+        human!("Rejected {}: {}", storage::short_id(&p.id), p.content);
+    }
+    output::emit(&json!({ "rejected": taken.iter().map(|p| &p.id).collect::<Vec<_>>() }))
+}
+
+/// Remove the named pending memory, or every one when `id` is None.
+fn take_pending(storage: &Storage, id: Option<&str>) -> Result<Vec<PendingMemory>, Error> {
+    match id {
+        Some(id) => Ok(vec![storage.take_pending(id)?]),
+        None => {
+            let all = storage.pending_memories()?;
+            if all.is_empty() {
+                human!("No memories awaiting approval.");
+            }
+            for p in &all {
+                storage.take_pending(&p.id)?;
+            }
+            Ok(all)
+        }
+    }
+}
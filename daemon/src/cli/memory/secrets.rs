@@ -0,0 +1,70 @@
+//! `sqrl memory scan-secrets`: audit memories against the redaction rules.
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::cli::output::{self, human};
+use crate::cli::progress::Progress;
+use crate::config::Config;
+use crate::error::{Error, ExitCode};
+use crate::lock::SqrlLock;
+use crate::storage::{self, Storage};
+
+use super::root;
+
+/// A memory the redaction rules match (CONFIG-007).
+#[derive(Serialize)]
+struct Secret {
+    id: String,
+    rules: Vec<&'static str>,
+    redacted: String,
+}
+
+/// Audit stored memories against the redaction rules. Matches are shown
+/// redacted; `fix` rewrites them so. Returns exit code 1 if any remain.
+pub fn scan_secrets(fix: bool) -> Result<i32, Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let redactor = Config::effective(&root)?.redactor()?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+
+    let memories = storage.list_all_memories()?;
+    let mut progress = Progress::new("Scanning memories", memories.len() as u64);
+    let mut secrets = Vec::new();
+    for memory in memories {
+        let (redacted, rules) = redactor.redact(&memory.content);
+        if !rules.is_empty() {
+            if fix {
+                storage.update_memory(&memory.id, None, Some(&redacted), None)?;
+            }
+            secrets.push(Secret {
+                id: memory.id,
+                rules,
+                redacted,
+            });
+        }
+        progress.inc(1);
+    }
+    progress.finish();
+    output::emit(&json!({ "secrets": secrets, "fixed": fix }))?;
+
+    if secrets.is_empty() {
+        human!("No secrets found.");
+        return Ok(0);
+    }
+    for secret in &secrets {
+        human!(
+            "{}  {:<24} {}",
+            storage::short_id(&secret.id),
+            secret.rules.join(","),
+            secret.redacted
+        );
+    }
+    if fix {
+        human!("Redacted {} memories.", secrets.len());
+        human!("Copies in commit snapshots and the trash are not rewritten.");
+        return Ok(0);
+    }
+    human!("Run 'sqrl memory scan-secrets --fix' to redact them.");
+    Ok(ExitCode::Problems.code())
+}
@@ -0,0 +1,169 @@
+//! `sqrl memory show`: one memory, with its MCP trace.
+
+use serde::Serialize;
+
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::storage::activity::MemoryEvent;
+use crate::storage::{Memory, Visibility};
+
+use super::open;
+
+/// A memory with the MCP client that stored it, for `show`.
+#[derive(Serialize)]
+struct Shown<'a> {
+    #[serde(flatten)]
+    memory: &'a Memory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_by: Option<&'a str>,
+    /// MCP calls that stored, reinforced, or rated it (`--trace`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace: Option<Vec<MemoryEvent>>,
+}
+
+/// Show one memory by ID or unambiguous prefix. With `trace`, also the MCP
+/// calls that stored, reinforced, and rated it.
+pub fn show(id: &str, trace: bool) -> Result<(), Error> {
+    let storage = open()?;
+    let id = storage.resolve_id(id)?;
+    let memory = storage
+        .get_memory(&id)?
+        .ok_or_else(|| Error::MemoryNotFound(id.clone()))?;
+    let created_by = storage.created_by(&id)?;
+    let events = if trace {
+        Some(storage.memory_events(&id)?)
+    } else {
+        None
+    };
+    output::emit(&Shown {
+        memory: &memory,
+        created_by: created_by.as_deref(),
+        trace: events.clone(),
+    })?;
+
+    human!("id:       {}", memory.id);
+    human!("type:     {}", memory.memory_type);
+    human!("tags:     {}", memory.tags.join(", "));
+    human!("visible:  {}", Visibility::of(&memory).as_str());
+    human!("used:     {}x", memory.use_count);
+    human!("created:  {}", memory.created_at);
+    human!("updated:  {}", memory.updated_at);
+    if let Some(client) = &created_by {
+        human!("by:       {}", client);
+    }
+    human!();
+    human!("{}", memory.content);
+
+    let Some(events) = events else {
+        return Ok(());
+    };
+    human!();
+    if events.is_empty() {
+        human!("Trace: no MCP calls recorded (stored from the CLI, web UI, sync, or share).");
+        return Ok(());
+    }
+    human!("Trace:");
+    for line in trace_lines(&memory, &events) {
+        human!("  {}", line);
+    }
+    Ok(())
+}
+
+/// One line per traced call: time, what it did, and the client.
+fn trace_lines(memory: &Memory, events: &[MemoryEvent]) -> Vec<String> {
+    // A store logged when the memory was created made it; later ones reinforced it
+    let created = chrono::DateTime::parse_from_rfc3339(&memory.created_at).ok();
+    let made_it = |at: &str| {
+        let at = chrono::DateTime::parse_from_rfc3339(at).ok();
+        matches!((created, at), (Some(c), Some(a)) if (a - c).num_seconds().abs() < 2)
+    };
+    events
+        .iter()
+        .map(|event| {
+            let action = match event.action.as_str() {
+                "store" if made_it(&event.at) => "stored",
+                "store" => "reinforced",
+                "rate" => "rated",
+                other => other,
+            };
+            format!(
+                "{}  {:<10}  {}",
+                event.at.get(..19).unwrap_or(&event.at).replace('T', " "),
+                action,
+                format!("{} {}", event.client, event.version).trim()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::activity::Action;
+    use crate::storage::Storage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_trace_lines_store_at_creation_stored_later_reinforced() {
+        let memory = Memory {
+            id: "a".to_string(),
+            memory_type: "project".to_string(),
+            content: "Use pnpm".to_string(),
+            tags: Vec::new(),
+            use_count: 2,
+            created_at: "2026-03-01T10:00:00+00:00".to_string(),
+            updated_at: "2026-03-01T11:00:00+00:00".to_string(),
+        };
+        let event = |client: &str, version: &str, action: &str, at: &str| MemoryEvent {
+            client: client.to_string(),
+            version: version.to_string(),
+            action: action.to_string(),
+            at: at.to_string(),
+        };
+        let events = [
+            event("cursor", "1.2", "store", "2026-03-01T10:00:01+00:00"),
+            event("claude-code", "", "store", "2026-03-01T11:00:00+00:00"),
+            event("cursor", "1.2", "rate", "2026-03-01T12:30:00+00:00"),
+        ];
+        assert_eq!(
+            trace_lines(&memory, &events),
+            [
+                "2026-03-01 10:00:01  stored      cursor 1.2",
+                "2026-03-01 11:00:00  reinforced  claude-code",
+                "2026-03-01 12:30:00  rated       cursor 1.2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shown_json_trace_only_when_asked() {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::open(&dir.path().join("memory.db")).unwrap();
+        let id = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        let memory = storage.get_memory(&id).unwrap().unwrap();
+        storage
+            .record_activity("cursor", "1.2", Action::Store, Some(&memory.id))
+            .unwrap();
+        storage
+            .record_activity("cursor", "1.2", Action::Get, None)
+            .unwrap();
+
+        let shown = |trace| {
+            serde_json::to_value(Shown {
+                memory: &memory,
+                created_by: Some("cursor"),
+                trace,
+            })
+            .unwrap()
+        };
+        let traced = shown(Some(storage.memory_events(&memory.id).unwrap()));
+        let trace = traced["trace"].as_array().unwrap();
+        assert_eq!(trace.len(), 1);
+        assert_eq!(trace[0]["client"], "cursor");
+        assert_eq!(trace[0]["version"], "1.2");
+        assert_eq!(trace[0]["action"], "store");
+        assert!(trace[0]["at"].is_string());
+        assert_eq!(traced["created_by"], "cursor");
+        assert!(shown(None).get("trace").is_none());
+    }
+}
@@ -0,0 +1,164 @@
+//! `sqrl memory pin`, `visibility` and `namespace`: settings kept as tags.
+
+use serde_json::json;
+
+use crate::cli::context;
+use crate::cli::output::{self, human};
+use crate::config::Config;
+use crate::error::Error;
+use crate::lock::SqrlLock;
+use crate::storage::{self, Memory, Storage, Visibility};
+
+use super::root;
+
+/// Add or remove the pinned tag, then refresh the context section if the
+/// project keeps one (CLI-024).
+pub fn pin(id: &str, pinned: bool) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let id = storage.resolve_id(id)?;
+    let memory = storage
+        .get_memory(&id)?
+        .ok_or_else(|| Error::MemoryNotFound(id.clone()))?;
+
+    let mut tags: Vec<String> = memory
+        .tags
+        .iter()
+        .filter(|t| *t != context::PIN_TAG)
+        .cloned()
+        .collect();
+    if pinned {
+        tags.push(context::PIN_TAG.to_string());
+    }
+    if tags != memory.tags {
+        storage.update_memory(&id, None, None, Some(&tags))?;
+    }
+    human!(
+        "{} {}: {}",
+        if pinned { "Pinned" } else { "Unpinned" },
+        storage::short_id(&id),
+        memory.content
+    );
+
+    let config = Config::effective(&root)?;
+    let refreshed = if config.context.section {
+        context::refresh(&root, &config)?
+    } else {
+        if pinned {
+            human!("Turn on the CLAUDE.md section with 'sqrl config set context.section true'.");
+        }
+        Vec::new()
+    };
+    for file in &refreshed {
+        human!("Pinned memories refreshed in {}.", file);
+    }
+    output::emit(&json!({ "id": id, "pinned": pinned, "refreshed": refreshed }))
+}
+
+/// Set how far a memory may be shared (SCHEMA-001), then refresh the context
+/// section, which only lists shareable memories.
+pub fn set_visibility(id: &str, visibility: Visibility) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let id = storage.resolve_id(id)?;
+    let memory = storage
+        .get_memory(&id)?
+        .ok_or_else(|| Error::MemoryNotFound(id.clone()))?;
+
+    let tags = visibility.apply_to(&memory.tags);
+    if tags != memory.tags {
+        storage.update_memory(&id, None, None, Some(&tags))?;
+    }
+    human!(
+        "{} is now {}: {}",
+        storage::short_id(&id),
+        visibility.as_str(),
+        memory.content
+    );
+
+    let config = Config::effective(&root)?;
+    if config.context.section && tags != memory.tags {
+        for file in context::refresh(&root, &config)? {
+            human!("Pinned memories refreshed in {}.", file);
+        }
+    }
+    output::emit(&json!({ "id": id, "visibility": visibility }))
+}
+
+/// Scope a memory to a subdirectory, or with None clear its scope (CONFIG-009).
+pub fn set_namespace(id: &str, namespace: Option<&str>) -> Result<(), Error> {
+    let root = root()?;
+    if let Some(namespace) = namespace {
+        if !root.join(namespace).is_dir() {
+            return Err(Error::InvalidArgument(format!(
+                "no directory '{}' in {}",
+                namespace,
+                root.display()
+            )));
+        }
+    }
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let id = storage.resolve_id(id)?;
+    let memory = storage
+        .get_memory(&id)?
+        .ok_or_else(|| Error::MemoryNotFound(id.clone()))?;
+
+    let tags = storage::with_namespace(&memory.tags, namespace);
+    if tags != memory.tags {
+        storage.update_memory(&id, None, None, Some(&tags))?;
+    }
+    let updated = Memory { tags, ..memory };
+    let namespace = storage::namespace(&updated);
+    match namespace {
+        Some(namespace) => human!(
+            "{} is scoped to {}: {}",
+            storage::short_id(&id),
+            namespace,
+            updated.content
+        ),
+        None => human!(
+            "{} applies to the whole project: {}",
+            storage::short_id(&id),
+            updated.content
+        ),
+    }
+    output::emit(&json!({ "id": id, "namespace": namespace }))
+}
+
+/// Scope unscoped project memories by the paths they mention, using the
+/// configured `namespaces`.
+pub fn derive_namespaces() -> Result<(), Error> {
+    let root = root()?;
+    let namespaces = Config::effective(&root)?.namespaces;
+    if namespaces.is_empty() {
+        return Err(Error::InvalidArgument(
+            "no namespaces configured; add them with 'sqrl config set namespaces ...'".to_string(),
+        ));
+    }
+    let _lock = SqrlLock::project(&root)?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+
+    let mut scoped = Vec::new();
+    for memory in storage.list_all_memories()? {
+        if memory.memory_type != "project" || storage::namespace(&memory).is_some() {
+            continue;
+        }
+        let Some(namespace) = storage::derive_namespace(&memory.content, &namespaces) else {
+            continue;
+        };
+        let tags = storage::with_namespace(&memory.tags, Some(&namespace));
+        storage.update_memory(&memory.id, None, None, Some(&tags))?;
+        human!(
+            "{}  {:<24} {}",
+            storage::short_id(&memory.id),
+            namespace,
+            memory.content
+        );
+        scoped.push(json!({ "id": memory.id, "namespace": namespace }));
+    }
+    human!("Scoped {} memories.", scoped.len());
+    output::emit(&json!({ "scoped": scoped }))
+}
@@ -1,8 +1,39 @@
 //! CLI commands.
 
 pub mod apply;
+pub mod config;
+pub mod context;
+pub mod db;
+pub mod diagnostics;
+pub mod doctor;
+pub mod events;
+pub mod git;
 pub mod goaway;
+pub mod grep;
+pub mod health;
 pub mod hooks;
+pub mod import;
 pub mod init;
 pub mod internal;
+pub mod logging;
+pub mod mcp;
+pub mod memory;
+pub mod notify;
+pub mod output;
+pub mod plugins;
+pub mod progress;
+pub mod projects;
+pub mod report;
+pub mod rules;
+pub mod security;
+pub mod setup;
+pub mod share;
+pub mod stats;
 pub mod status;
+pub mod styles;
+pub mod sync;
+pub mod targets;
+pub mod ui;
+pub mod unapply;
+pub mod undo;
+pub mod upgrade;
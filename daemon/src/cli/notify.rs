@@ -0,0 +1,90 @@
+//! Desktop notifications (CLI-018).
+//!
+//! Best effort through the platform's own tool: `notify-send` on Linux,
+//! `osascript` on macOS. Failures are logged, never surfaced.
+
+use std::process::Command;
+
+use tracing::debug;
+
+use crate::global_config::{GlobalConfig, NotificationsConfig};
+
+/// Events that can raise a notification.
+#[derive(Debug, Clone, Copy)]
+pub enum Event {
+    PushBlocked,
+}
+
+impl Event {
+    fn enabled(self, config: &NotificationsConfig) -> bool {
+        match self {
+            Event::PushBlocked => config.push_blocked,
+        }
+    }
+}
+
+/// Show a notification if the event is enabled in the global config.
+pub fn notify(event: Event, message: &str) {
+    let config = GlobalConfig::load()
+        .map(|config| config.notifications)
+        .unwrap_or_default();
+    let Some(mut command) = command(event, message, &config) else {
+        return;
+    };
+    if let Err(e) = command.status() {
+        debug!(?event, error = %e, "Failed to show notification");
+    }
+}
+
+/// The command showing `message`, or `None` when the event is off or the
+/// platform has no notification tool.
+fn command(event: Event, message: &str, config: &NotificationsConfig) -> Option<Command> {
+    if !event.enabled(config) {
+        return None;
+    }
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title \"Squirrel\"",
+            applescript_string(message)
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        Some(command)
+    } else if cfg!(target_os = "linux") {
+        let mut command = Command::new("notify-send");
+        command.args(["Squirrel", message]);
+        Some(command)
+    } else {
+        debug!(?event, "Notifications not supported on this platform");
+        None
+    }
+}
+
+/// Quote a string for AppleScript.
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_push_blocked_fires_only_when_enabled() {
+        let mut config = NotificationsConfig::default();
+        assert!(command(Event::PushBlocked, "Push blocked", &config).is_none());
+
+        config.push_blocked = true;
+        let command = command(Event::PushBlocked, "Push \"blocked\"", &config);
+        if cfg!(target_os = "linux") {
+            let command = command.unwrap();
+            assert_eq!(command.get_program(), "notify-send");
+            let args: Vec<_> = command.get_args().collect();
+            assert_eq!(args, ["Squirrel", "Push \"blocked\""]);
+        } else if cfg!(target_os = "macos") {
+            let command = command.unwrap();
+            let script = command.get_args().nth(1).unwrap().to_str().unwrap();
+            assert!(script.starts_with("display notification \"Push \\\"blocked\\\"\""));
+        }
+    }
+}
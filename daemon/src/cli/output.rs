@@ -0,0 +1,147 @@
+//! Output mode for CLI commands (CLI-014).
+//!
+//! With `--json` or `SQRL_OUTPUT=json`, commands print one JSON document to
+//! stdout and their human-readable text to stderr. `--quiet` drops the
+//! human-readable text; `--color` decides whether it is colored.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use tracing::info;
+
+use crate::error::Error;
+
+/// Environment variable selecting the output mode (`json` or `text`).
+pub const ENV_VAR: &str = "SQRL_OUTPUT";
+
+/// When to color output (`--color`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and `NO_COLOR` is unset.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+static COLOR: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// Set the output mode from the global flags and `SQRL_OUTPUT`.
+pub fn init(json_flag: bool, quiet: bool, color: ColorChoice) {
+    let json = json_mode(json_flag, std::env::var(ENV_VAR).ok().as_deref());
+    JSON.store(json, Ordering::Relaxed);
+    QUIET.store(quiet, Ordering::Relaxed);
+    COLOR.store(color as u8, Ordering::Relaxed);
+}
+
+/// Whether `--json` or the value of `SQRL_OUTPUT` asks for JSON.
+fn json_mode(json_flag: bool, env: Option<&str>) -> bool {
+    json_flag || env.is_some_and(|v| v.eq_ignore_ascii_case("json"))
+}
+
+/// Whether commands emit JSON.
+pub fn is_json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// Whether human-readable text is suppressed.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Whether to color a stream, given whether it is a terminal.
+pub fn use_color(is_terminal: bool) -> bool {
+    match COLOR.load(Ordering::Relaxed) {
+        c if c == ColorChoice::Always as u8 => true,
+        c if c == ColorChoice::Never as u8 => false,
+        _ => is_terminal && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+    }
+}
+
+/// Wrap text in an ANSI color (e.g. `"32"` for green) if human output is colored.
+pub fn paint(text: &str, code: &str) -> String {
+    let terminal = if is_json() {
+        std::io::stderr().is_terminal()
+    } else {
+        std::io::stdout().is_terminal()
+    };
+    if use_color(terminal) {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Print a command's structured result. Does nothing in text mode.
+pub fn emit<T: Serialize>(value: &T) -> Result<(), Error> {
+    if is_json() {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    }
+    Ok(())
+}
+
+/// Report a command's error: a JSON document on stdout in JSON mode,
+/// else `error[code]: message` and a hint on stderr.
+pub fn report_error(err: &Error) {
+    info!(code = err.code(), error = %err, "Command failed");
+    if is_json() {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&err.to_json()).unwrap_or_default()
+        );
+        return;
+    }
+    let label = format!("error[{}]:", err.code());
+    if use_color(std::io::stderr().is_terminal()) {
+        eprintln!("\x1b[31m{}\x1b[0m {}", label, err);
+    } else {
+        eprintln!("{} {}", label, err);
+    }
+    if let Some(hint) = err.hint() {
+        eprintln!("  hint: {}", hint);
+    }
+}
+
+/// Print human-readable text: stdout in text mode, stderr in JSON mode, nowhere with `--quiet`.
+macro_rules! human {
+    () => {
+        if !$crate::cli::output::is_quiet() {
+            if $crate::cli::output::is_json() {
+                eprintln!()
+            } else {
+                println!()
+            }
+        }
+    };
+    ($($arg:tt)*) => {
+        if !$crate::cli::output::is_quiet() {
+            if $crate::cli::output::is_json() {
+                eprintln!($($arg)*)
+            } else {
+                println!($($arg)*)
+            }
+        }
+    };
+}
+
+pub(crate) use human;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_mode_flag_or_env_json_enables() {
+        assert!(json_mode(true, None));
+        assert!(json_mode(true, Some("text")));
+        assert!(json_mode(false, Some("json")));
+        assert!(json_mode(false, Some("JSON")));
+        assert!(!json_mode(false, Some("text")));
+        assert!(!json_mode(false, Some("")));
+        assert!(!json_mode(false, None));
+    }
+}
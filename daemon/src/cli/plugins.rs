@@ -0,0 +1,116 @@
+//! External command plugins (PLUGIN-001).
+//!
+//! Each plugin listed in the global config for an event runs with a JSON
+//! document on stdin. Its stdout is discarded (it would corrupt `mcp-serve`),
+//! stderr passes through. Failures follow the plugin's `on_failure`.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use crate::global_config::{FailurePolicy, GlobalConfig, Plugin, PluginEvent};
+
+/// How often a running plugin is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Run every plugin for `event`. Returns false if a `block` plugin failed.
+pub fn run(event: PluginEvent, project_root: &Path, data: Value) -> bool {
+    let Ok(config) = GlobalConfig::load() else {
+        return true;
+    };
+    let input = json!({ "event": event, "project": project_root, "data": data }).to_string();
+
+    let mut ok = true;
+    for plugin in config.plugins.iter().filter(|p| p.events.contains(&event)) {
+        let Err(reason) = run_one(plugin, &input) else {
+            debug!(plugin = plugin.name, ?event, "Plugin succeeded");
+            continue;
+        };
+        match plugin.on_failure {
+            FailurePolicy::Ignore => {
+                debug!(plugin = plugin.name, ?event, reason, "Plugin failed")
+            }
+            policy => {
+                info!(plugin = plugin.name, ?event, reason, "Plugin failed");
+                eprintln!("warning: plugin '{}' failed: {}", plugin.name, reason);
+                ok &= policy != FailurePolicy::Block;
+            }
+        }
+    }
+    ok
+}
+
+/// Run one plugin to completion or timeout.
+fn run_one(plugin: &Plugin, input: &str) -> Result<(), String> {
+    let deadline = Instant::now() + Duration::from_secs(plugin.timeout_secs);
+    let (program, args) = plugin
+        .command
+        .split_first()
+        .ok_or_else(|| "empty command".to_string())?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| format!("cannot run '{}': {}", program, e))?;
+
+    // Written from another thread: a plugin that never reads a document
+    // bigger than the pipe buffer must not hold us past the deadline. One
+    // that exits without reading it is fine.
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+
+    loop {
+        match child.try_wait().map_err(|e| e.to_string())? {
+            Some(status) if status.success() => return Ok(()),
+            Some(status) => return Err(format!("exited with {}", status)),
+            None if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}s", plugin.timeout_secs));
+            }
+            None => std::thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn plugin(script: &str, timeout_secs: u64) -> Plugin {
+        Plugin {
+            name: "test".to_string(),
+            events: vec![PluginEvent::PrePushCheck],
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            timeout_secs,
+            on_failure: FailurePolicy::Block,
+        }
+    }
+
+    #[test]
+    fn test_run_one_reports_exit_and_timeout() {
+        let input = r#"{"event":"pre_push_check"}"#;
+        assert!(run_one(&plugin("grep -q pre_push_check", 5), input).is_ok());
+        let failed = run_one(&plugin("exit 3", 5), input).unwrap_err();
+        assert!(failed.contains("exited"));
+        let slow = run_one(&plugin("sleep 5", 0), input).unwrap_err();
+        assert!(slow.contains("timed out"));
+
+        // More than a pipe holds, to a plugin that never reads it
+        let large = "x".repeat(1 << 20);
+        let start = Instant::now();
+        let stuck = run_one(&plugin("sleep 5", 0), &large).unwrap_err();
+        assert!(stuck.contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+}
@@ -0,0 +1,134 @@
+//! Progress of long-running commands (CLI-014).
+//!
+//! On a terminal, a bar (or spinner, when the total is unknown) on stderr,
+//! cleared when the work is done. Otherwise, as in CI logs or with `--json`
+//! or `--quiet`, an info log line every few seconds, so a slow run still
+//! shows it is moving.
+
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use tracing::info;
+
+use crate::cli::output;
+
+/// Time between log lines when no bar is drawn.
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Progress of one operation.
+pub struct Progress {
+    label: &'static str,
+    total: Option<u64>,
+    done: u64,
+    bar: Option<ProgressBar>,
+    started: Instant,
+    logged: Instant,
+}
+
+impl Progress {
+    /// Track `total` items.
+    pub fn new(label: &'static str, total: u64) -> Self {
+        Self::start(label, Some(total))
+    }
+
+    /// Track work with no item count, such as a single long statement.
+    pub fn spinner(label: &'static str) -> Self {
+        Self::start(label, None)
+    }
+
+    fn start(label: &'static str, total: Option<u64>) -> Self {
+        let visible = std::io::stderr().is_terminal() && !output::is_json() && !output::is_quiet();
+        let bar = visible.then(|| {
+            let bar = match total {
+                Some(total) => {
+                    ProgressBar::with_draw_target(Some(total), ProgressDrawTarget::stderr())
+                        .with_style(
+                            ProgressStyle::with_template("{msg} [{bar:30}] {pos}/{len} ({eta})")
+                                .expect("progress template")
+                                .progress_chars("=> "),
+                        )
+                }
+                None => {
+                    let bar = ProgressBar::new_spinner();
+                    bar.enable_steady_tick(Duration::from_millis(120));
+                    bar
+                }
+            };
+            bar.set_message(label);
+            bar
+        });
+        let now = Instant::now();
+        Self {
+            label,
+            total,
+            done: 0,
+            bar,
+            started: now,
+            logged: now,
+        }
+    }
+
+    /// Count `n` more items done.
+    pub fn inc(&mut self, n: u64) {
+        self.done += n;
+        if let Some(bar) = &self.bar {
+            bar.inc(n);
+        } else if self.log_due(Instant::now()) {
+            match self.total {
+                Some(total) => info!(done = self.done, total, "{}", self.label),
+                None => info!(done = self.done, "{}", self.label),
+            }
+        }
+    }
+
+    /// Whether a log line is due at `now`; if so, the next one is
+    /// `LOG_INTERVAL` later.
+    fn log_due(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.logged) < LOG_INTERVAL {
+            return false;
+        }
+        self.logged = now;
+        true
+    }
+
+    /// Clear the bar and log how long the operation took.
+    pub fn finish(self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        info!(
+            done = self.done,
+            elapsed_ms = self.started.elapsed().as_millis() as u64,
+            "{} finished",
+            self.label
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_due_every_interval_not_before() {
+        let start = Instant::now();
+        let mut progress = Progress {
+            label: "Importing",
+            total: Some(10),
+            done: 0,
+            bar: None,
+            started: start,
+            logged: start,
+        };
+        progress.inc(3);
+        progress.inc(2);
+        assert_eq!(progress.done, 5);
+
+        let second = Duration::from_secs(1);
+        assert!(!progress.log_due(start + LOG_INTERVAL - second));
+        assert!(progress.log_due(start + LOG_INTERVAL));
+        assert!(!progress.log_due(start + LOG_INTERVAL + second));
+        assert!(progress.log_due(start + LOG_INTERVAL * 2));
+    }
+}
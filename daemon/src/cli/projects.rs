@@ -0,0 +1,230 @@
+//! List and prune registered projects (CLI-013).
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::cli::output::{self, human};
+use crate::cli::status::{self, human_age, CheckStatus};
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::projects::{ProjectEntry, Registry};
+use crate::storage::{self, Storage};
+
+/// A registered project with what needs attention in it.
+#[derive(Serialize)]
+struct Health<'a> {
+    #[serde(flatten)]
+    entry: &'a ProjectEntry,
+    /// `ok`, `attention`, or `missing` (path or `.sqrl/` gone).
+    state: &'static str,
+    memories: Option<i64>,
+    pending: Option<i64>,
+    /// Latest MCP call recorded in the project.
+    last_mcp: Option<String>,
+    issues: Vec<String>,
+}
+
+/// List registered projects with memory counts and problems.
+pub fn list() -> Result<(), Error> {
+    let registry = Registry::load()?;
+    let projects: Vec<Health> = registry.projects.iter().map(health).collect();
+    output::emit(&projects)?;
+    if projects.is_empty() {
+        human!("No projects registered. Run 'sqrl init' in a project.");
+        return Ok(());
+    }
+
+    human!("Projects ({}):", projects.len());
+    for project in &projects {
+        let mark = match project.state {
+            "ok" => output::paint("✓", "32"),
+            "attention" => output::paint("!", "33"),
+            _ => output::paint("✗", "31"),
+        };
+        let mut details = Vec::new();
+        if let Some(memories) = project.memories {
+            details.push(format!("{} memories", memories));
+        }
+        if let Some(age) = age(&project.entry.last_activity) {
+            details.push(format!("last activity {}", age));
+        }
+        match project.last_mcp.as_deref().and_then(age) {
+            Some(age) => details.push(format!("last MCP call {}", age)),
+            None if project.memories.is_some() => details.push("no MCP calls".to_string()),
+            None => {}
+        }
+        human!(
+            "  {} {}  ({})",
+            mark,
+            project.entry.path.display(),
+            details.join(", ")
+        );
+        for issue in &project.issues {
+            human!("      {}", issue);
+        }
+    }
+    if projects.iter().any(|p| p.state == "missing") {
+        human!();
+        human!("Run 'sqrl projects prune' to forget missing projects.");
+    }
+    Ok(())
+}
+
+fn health(entry: &ProjectEntry) -> Health<'_> {
+    let mut health = Health {
+        entry,
+        state: "missing",
+        memories: None,
+        pending: None,
+        last_mcp: None,
+        issues: Vec::new(),
+    };
+    let root = &entry.path;
+    if !root.exists() {
+        health.issues.push("path no longer exists".to_string());
+        return health;
+    }
+    if !root.join(".sqrl").exists() {
+        health.issues.push(".sqrl/ removed".to_string());
+        return health;
+    }
+
+    if let Err(e) = Config::effective(root) {
+        health.issues.push(e.to_string());
+    }
+    for check in [status::check_database(root), status::check_hooks(root)] {
+        if check.status == CheckStatus::Fail {
+            health.issues.push(check.message);
+        }
+    }
+    if storage::check_health(root).is_none() {
+        fill_counts(root, &mut health);
+    }
+    if health.pending.is_some_and(|n| n > 0) {
+        health.issues.push(format!(
+            "{} memories awaiting approval ('sqrl memory pending')",
+            health.pending.unwrap_or_default()
+        ));
+    }
+    health.state = if health.issues.is_empty() {
+        "ok"
+    } else {
+        "attention"
+    };
+    health
+}
+
+/// Memory and pending counts and the last MCP call. Best effort.
+fn fill_counts(root: &Path, health: &mut Health) {
+    health.memories = storage::get_memory_counts(root)
+        .ok()
+        .map(|counts| counts.values().sum());
+    let Ok(storage) = Storage::open(&storage::db_path(root)) else {
+        return;
+    };
+    health.pending = storage.pending_count().ok();
+    health.last_mcp = storage
+        .client_stats()
+        .ok()
+        .and_then(|stats| stats.into_iter().map(|c| c.last_seen).max());
+}
+
+/// "2 hours ago" for an RFC 3339 time.
+fn age(at: &str) -> Option<String> {
+    let at = chrono::DateTime::parse_from_rfc3339(at).ok()?;
+    let secs = (chrono::Utc::now() - at.to_utc()).num_seconds().max(0);
+    Some(human_age(secs as u64))
+}
+
+/// Remove projects whose `.sqrl/` no longer exists.
+pub fn prune() -> Result<(), Error> {
+    let mut registry = Registry::load()?;
+    let removed = registry.prune();
+    registry.save()?;
+    output::emit(&serde_json::json!({ "removed": removed }))?;
+
+    if removed.is_empty() {
+        human!("No missing projects.");
+    }
+    for path in &removed {
+        human!("Removed {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn entry(path: PathBuf) -> ProjectEntry {
+        ProjectEntry {
+            path,
+            added_at: "2026-01-01T00:00:00+00:00".to_string(),
+            last_activity: "2026-01-01T00:00:00+00:00".to_string(),
+        }
+    }
+
+    /// An initialized project (no git) with one memory and `pending` proposals.
+    fn project(root: &Path, pending: usize) {
+        std::fs::create_dir_all(root.join(".sqrl")).unwrap();
+        Config::default().save(root).unwrap();
+        let storage = Storage::open(&storage::db_path(root)).unwrap();
+        storage.store_memory("project", "Use pnpm", &[]).unwrap();
+        for i in 0..pending {
+            storage
+                .propose_memory("project", &format!("Idea {}", i), &[], "cursor")
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_health_missing_paths_are_what_prune_removes() {
+        let dir = TempDir::new().unwrap();
+        let ok = dir.path().join("ok");
+        let busy = dir.path().join("busy");
+        let removed = dir.path().join("removed");
+        let gone = dir.path().join("gone");
+        project(&ok, 0);
+        project(&busy, 2);
+        std::fs::create_dir_all(&removed).unwrap();
+        let mut registry = Registry {
+            projects: [&ok, &busy, &removed, &gone]
+                .map(|path| entry(path.clone()))
+                .to_vec(),
+        };
+
+        let healths: Vec<Health> = registry.projects.iter().map(health).collect();
+        let states: Vec<(&str, Option<i64>, Vec<&str>)> = healths
+            .iter()
+            .map(|h| {
+                let issues = h.issues.iter().map(String::as_str).collect();
+                (h.state, h.memories, issues)
+            })
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("ok", Some(1), vec![]),
+                (
+                    "attention",
+                    Some(1),
+                    vec!["2 memories awaiting approval ('sqrl memory pending')"]
+                ),
+                ("missing", None, vec![".sqrl/ removed"]),
+                ("missing", None, vec!["path no longer exists"]),
+            ]
+        );
+
+        let missing: Vec<PathBuf> = healths
+            .iter()
+            .filter(|h| h.state == "missing")
+            .map(|h| h.entry.path.clone())
+            .collect();
+        drop(healths);
+        assert_eq!(registry.prune(), missing);
+        assert_eq!(registry.projects.len(), 2);
+    }
+}
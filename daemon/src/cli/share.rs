@@ -0,0 +1,82 @@
+//! Share tagged memories with the team through the repo (SHARE-001).
+
+use serde_json::json;
+
+use crate::cli::output::{self, human};
+use crate::config::{find_project_root, Config};
+use crate::error::Error;
+use crate::lock::SqrlLock;
+use crate::storage::bundle::{self, Bundle};
+use crate::storage::{self, Storage, Visibility};
+
+/// Tag selecting memories for `push` when none is given.
+pub const DEFAULT_TAG: &str = "team";
+
+/// Write project memories carrying any of `tags` into `.sqrl/shared.json`.
+/// Private memories are never written, whatever their tags.
+pub fn push(tags: &[String]) -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let tags = if tags.is_empty() {
+        vec![DEFAULT_TAG.to_string()]
+    } else {
+        tags.to_vec()
+    };
+
+    let mut memories = Storage::open(&storage::db_path(&root))?.list_all_memories()?;
+    memories.retain(|m| m.memory_type == "project" && m.tags.iter().any(|t| tags.contains(t)));
+    let selected = memories.len();
+    memories.retain(Visibility::shareable);
+    let private = selected - memories.len();
+
+    let path = bundle::path(&root);
+    let mut shared = Bundle::load(&path)?;
+    let changed = shared.merge(&memories);
+    shared.save(&path)?;
+
+    human!(
+        "{} of {} memories tagged {} written to {}.",
+        changed,
+        memories.len(),
+        tags.join(", "),
+        path.display()
+    );
+    if private > 0 {
+        human!("{} private memories left out.", private);
+    }
+    if changed > 0 {
+        human!("Commit it to share them.");
+    }
+    output::emit(&json!({
+        "path": path,
+        "selected": memories.len(),
+        "private": private,
+        "changed": changed
+    }))
+}
+
+/// Merge `.sqrl/shared.json` into this project's memories.
+pub fn pull() -> Result<(), Error> {
+    let root = root()?;
+    let _lock = SqrlLock::project(&root)?;
+    let path = bundle::path(&root);
+    if !path.exists() {
+        human!("No shared memories ({} not found).", path.display());
+        return output::emit(&json!({ "path": path, "changed": 0 }));
+    }
+
+    let shared = Bundle::load(&path)?;
+    let changed = shared.apply(&Storage::open(&storage::db_path(&root))?)?;
+
+    human!(
+        "{} of {} shared memories added or updated.",
+        changed,
+        shared.memories.len()
+    );
+    output::emit(&json!({ "path": path, "changed": changed }))
+}
+
+fn root() -> Result<std::path::PathBuf, Error> {
+    let cwd = std::env::current_dir()?;
+    find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))
+}
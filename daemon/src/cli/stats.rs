@@ -0,0 +1,135 @@
+//! Usage numbers for the current project (CLI-023).
+
+use serde_json::json;
+
+use crate::cli::memory;
+use crate::cli::output::{self, human};
+use crate::cli::status::human_age;
+use crate::error::Error;
+use crate::storage::activity::ClientStats;
+use crate::storage::usage::{self, UsageStats};
+use crate::storage::{self, Storage};
+
+/// Show memory counts and MCP tool calls; with `by_client`, one row per client.
+pub fn run(by_client: bool) -> Result<(), Error> {
+    let root = memory::root()?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let clients = storage.client_stats()?;
+
+    if by_client {
+        output::emit(&clients)?;
+        return print_clients(&clients);
+    }
+
+    let counts = storage::get_memory_counts(&root)?;
+    let total = |f: fn(&ClientStats) -> i64| clients.iter().map(f).sum::<i64>();
+    let (stored, retrievals, rated) = (
+        total(|c| c.stored),
+        total(|c| c.retrievals),
+        total(|c| c.rated),
+    );
+    output::emit(&json!({
+        "memories": counts,
+        "stored": stored,
+        "retrievals": retrievals,
+        "rated": rated,
+        "clients": clients.len(),
+    }))?;
+
+    let mut parts: Vec<String> = counts.iter().map(|(k, v)| format!("{} {}", v, k)).collect();
+    parts.sort();
+    human!(
+        "Memories:   {} ({})",
+        counts.values().sum::<i64>(),
+        parts.join(", ")
+    );
+    human!("Stored:     {} via MCP", stored);
+    human!("Retrievals: {}", retrievals);
+    human!("Ratings:    {}", rated);
+    human!("Clients:    {}", clients.len());
+    if clients.len() > 1 {
+        human!("Run 'sqrl stats --by-client' for a breakdown.");
+    }
+    Ok(())
+}
+
+/// Show recorded commands, hook handlers and MCP tool calls with their timings.
+pub fn usage() -> Result<(), Error> {
+    let root = memory::root()?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let stats = storage.usage_stats()?;
+    output::emit(&stats)?;
+
+    if !usage::enabled() {
+        human!(
+            "Usage analytics are off. Turn them on with 'sqrl config set analytics.enabled true --global'."
+        );
+    }
+    if stats.is_empty() {
+        human!("No usage recorded yet.");
+        return Ok(());
+    }
+    print_usage(&stats);
+    Ok(())
+}
+
+fn print_usage(stats: &[UsageStats]) {
+    let width = stats.iter().map(|s| s.name.len()).max().unwrap_or(0).max(4);
+    human!(
+        "{:<7}  {:<width$}  {:>5}  {:>7}  {:>7}  LAST USED",
+        "KIND",
+        "NAME",
+        "COUNT",
+        "AVG MS",
+        "MAX MS"
+    );
+    for s in stats {
+        human!(
+            "{:<7}  {:<width$}  {:>5}  {:>7}  {:>7}  {}",
+            s.kind,
+            s.name,
+            s.count,
+            s.avg_ms,
+            s.max_ms,
+            age(&s.last_used)
+        );
+    }
+}
+
+/// How long ago an RFC 3339 time was, or the time itself if unparsable.
+fn age(at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(at)
+        .ok()
+        .and_then(|at| (chrono::Utc::now() - at.to_utc()).to_std().ok())
+        .map_or_else(|| at.to_string(), |age| human_age(age.as_secs()))
+}
+
+fn print_clients(clients: &[ClientStats]) -> Result<(), Error> {
+    if clients.is_empty() {
+        human!("No MCP activity recorded yet.");
+        return Ok(());
+    }
+    let names: Vec<String> = clients
+        .iter()
+        .map(|c| format!("{} {}", c.client, c.version).trim().to_string())
+        .collect();
+    let width = names.iter().map(String::len).max().unwrap_or(0).max(6);
+    human!(
+        "{:<width$}  {:>6}  {:>10}  {:>5}  LAST SEEN",
+        "CLIENT",
+        "STORED",
+        "RETRIEVALS",
+        "RATED"
+    );
+    for (client, name) in clients.iter().zip(&names) {
+        human!(
+            "{:<width$}  {:>6}  {:>10}  {:>5}  {}",
+            name,
+            client.stored,
+            client.retrievals,
+            client.rated,
+            age(&client.last_seen)
+        );
+    }
+    Ok(())
+}
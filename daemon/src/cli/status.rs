@@ -1,74 +1,619 @@
-//! Show Squirrel status.
+//! Show Squirrel status (CLI-006).
+//!
+//! Status runs a fixed list of component checks. Each failure class has its
+//! own exit code; the first failing check decides it.
 
+use std::fs;
 use std::path::Path;
 
-use crate::error::Error;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::cli::apply::{claude_entry_location, launch, selected_mcps};
+use crate::cli::git;
+use crate::cli::hooks::{self, HookState};
+use crate::cli::output::{self, human};
+use crate::cli::targets;
+use crate::config::{project_root, Config};
+use crate::error::{Error, ExitCode};
+use crate::global_config::managed::{read_pointer, DriftKind, Manifest};
+use crate::global_config::projects::Registry;
 use crate::global_config::GlobalConfig;
-use crate::storage;
+use crate::storage::{self, DbProblem};
+
+/// `.sqrl/` is missing.
+pub const EXIT_NOT_INITIALIZED: i32 = ExitCode::NotInitialized.code();
+/// `memory.db` is missing, corrupt, or lacks tables.
+pub const EXIT_DATABASE: i32 = ExitCode::Storage.code();
+/// Pre-push hook is missing or out of date.
+pub const EXIT_HOOKS: i32 = ExitCode::HooksOutdated.code();
+/// An enabled MCP is not registered in an enabled tool.
+pub const EXIT_MCP: i32 = ExitCode::McpUnregistered.code();
+
+/// Memories listed under "Recent" in watch mode.
+const WATCH_RECENT: usize = 5;
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Fail => "fail",
+            CheckStatus::Skip => "skip",
+        }
+    }
+}
+
+/// Result of one component check.
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    /// Exit code when failed.
+    #[serde(skip)]
+    pub exit_code: i32,
+}
+
+impl Check {
+    fn ok(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            message: message.into(),
+            exit_code: 0,
+        }
+    }
+
+    fn fail(name: &'static str, exit_code: i32, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            message: message.into(),
+            exit_code,
+        }
+    }
+
+    fn skip(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            message: message.into(),
+            exit_code: 0,
+        }
+    }
+}
 
 /// Run the status command. Returns exit code.
 pub fn run() -> Result<i32, Error> {
-    let project_root = std::env::current_dir()?;
+    let project_root = project_root()?;
     let sqrl_dir = project_root.join(".sqrl");
+    let initialized = sqrl_dir.exists();
+    let global = if GlobalConfig::exists() {
+        GlobalConfig::load().ok()
+    } else {
+        None
+    };
+
+    let mut checks = project_checks(&project_root);
+    if initialized {
+        checks.push(check_mcps(global.as_ref()));
+    }
+    let exit_code = first_failure(&checks);
 
-    println!("Squirrel Status");
-    println!("  Project: {}", project_root.display());
+    // Structured copy of what's printed, for --json
+    let mut report = Map::new();
+    report.insert("project".into(), json!(project_root));
+    report.insert("initialized".into(), json!(initialized));
+    report.insert("ok".into(), json!(exit_code == 0));
+    report.insert("exit_code".into(), json!(exit_code));
+    report.insert("checks".into(), json!(checks));
 
-    if !sqrl_dir.exists() {
-        println!("  Initialized: no");
-        println!();
-        println!("Run 'sqrl init' to initialize Squirrel for this project.");
-        return Ok(1);
+    human!("Squirrel Status");
+    human!("  Project: {}", project_root.display());
+    human!();
+    human!("Checks:");
+    for check in &checks {
+        let mark = match check.status {
+            CheckStatus::Ok => output::paint("✓", "32"),
+            CheckStatus::Fail => output::paint("✗", "31"),
+            CheckStatus::Skip => output::paint("-", "2"),
+        };
+        human!("  {} {}: {}", mark, check.name, check.message);
     }
 
-    println!("  Initialized: yes");
+    if !initialized {
+        output::emit(&report)?;
+        return Ok(exit_code);
+    }
+
+    let location = git::Location::of(&project_root);
+    if location != git::Location::Root {
+        human!();
+        human!("Git: {}", location.describe());
+    }
+    report.insert("git".into(), json!(location));
 
     // Memory counts
-    let counts = storage::get_memory_counts(&project_root)?;
-    let total: i64 = counts.values().sum();
-    if total > 0 {
-        let mut parts: Vec<String> = counts.iter().map(|(k, v)| format!("{} {}", v, k)).collect();
-        parts.sort();
-        println!("  Memories: {} total ({})", total, parts.join(", "));
-    } else {
-        println!("  Memories: 0");
+    human!();
+    if let Ok(counts) = storage::get_memory_counts(&project_root) {
+        let total: i64 = counts.values().sum();
+        if total > 0 {
+            let mut parts: Vec<String> =
+                counts.iter().map(|(k, v)| format!("{} {}", v, k)).collect();
+            parts.sort();
+            human!("Memories: {} total ({})", total, parts.join(", "));
+        } else {
+            human!("Memories: 0");
+        }
+        report.insert("memories".into(), json!(counts));
+    }
+    let db = storage::db_path(&project_root);
+    if db.exists() {
+        if let Ok(pending) = storage::Storage::open(&db).and_then(|s| s.pending_count()) {
+            if pending > 0 {
+                human!(
+                    "Pending: {} awaiting approval ('sqrl memory pending')",
+                    pending
+                );
+            }
+            report.insert("pending".into(), json!(pending));
+        }
     }
 
     // Last activity
     if let Some(last_activity) = get_last_activity(&sqrl_dir) {
-        println!("  Last activity: {}", last_activity);
+        human!("Last activity: {}", last_activity);
+        report.insert("last_activity".into(), json!(last_activity));
     }
 
     // Global config info
-    println!();
+    human!();
     if let Ok(global_dir) = GlobalConfig::dir() {
-        println!("Global Config: {}", global_dir.display());
-        if GlobalConfig::exists() {
-            if let Ok(config) = GlobalConfig::load() {
-                let mut enabled = Vec::new();
-                if config.tools.claude_code {
-                    enabled.push("Claude Code");
-                }
-                if config.tools.git {
-                    enabled.push("Git");
-                }
-                if config.tools.cursor {
-                    enabled.push("Cursor");
-                }
-                if config.tools.codex {
-                    enabled.push("Codex");
-                }
-                println!("  Enabled tools: {}", enabled.join(", "));
+        let mut global_report = Map::new();
+        global_report.insert("dir".into(), json!(global_dir));
+        global_report.insert("configured".into(), json!(GlobalConfig::exists()));
+        human!("Global Config: {}", global_dir.display());
+        if let Some(config) = &global {
+            if let Some((name, _)) = config.active_profile() {
+                human!("  Profile: {}", name);
+                global_report.insert("profile".into(), json!(name));
             }
+            let enabled = enabled_tools(&config.clone().with_profile());
+            human!("  Enabled tools: {}", enabled.join(", "));
+            global_report.insert("tools".into(), json!(enabled));
             if let Ok(mcps) = GlobalConfig::list_mcps() {
-                println!("  MCP configs: {}", mcps.len());
+                human!("  MCP configs: {}", mcps.len());
+                global_report.insert("mcp_configs".into(), json!(mcps.len()));
+            }
+            if let Ok(manifest) = Manifest::load() {
+                print_drift(&manifest, &mut global_report);
             }
         } else {
-            println!("  Not configured. Run 'sqrl config' to set up.");
+            human!("  Not configured. Run 'sqrl config' to set up.");
+        }
+        report.insert("global".into(), Value::Object(global_report));
+    }
+
+    output::emit(&report)?;
+    Ok(exit_code)
+}
+
+/// Print every exit code (ERROR-002) as JSON, whatever the output mode:
+/// the mapping is for scripts.
+pub fn explain_exit_codes() -> Result<(), Error> {
+    let codes: Vec<Value> = ExitCode::ALL
+        .iter()
+        .map(|exit| {
+            json!({
+                "code": exit.code(),
+                "name": exit.name(),
+                "meaning": exit.meaning(),
+                "errors": exit.error_codes().collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&codes)?);
+    Ok(())
+}
+
+/// Status of every registered project (REGISTRY-001) as one table.
+/// Returns the exit code of the first failing check in any of them.
+pub fn run_all() -> Result<i32, Error> {
+    let registry = Registry::load()?;
+    let mut projects = Vec::new();
+    for entry in &registry.projects {
+        let checks = project_checks(&entry.path);
+        let memories = storage::get_memory_counts(&entry.path)
+            .ok()
+            .filter(|_| storage::check_health(&entry.path).is_none())
+            .map(|counts| counts.values().sum::<i64>());
+        projects.push((entry.path.clone(), checks, memories));
+    }
+    let exit_code = projects
+        .iter()
+        .map(|(_, checks, _)| first_failure(checks))
+        .find(|&code| code != 0)
+        .unwrap_or(0);
+
+    if projects.is_empty() {
+        human!("No projects registered. Run 'sqrl init' in a project.");
+    } else {
+        let width = projects
+            .iter()
+            .map(|(path, _, _)| path.display().to_string().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("PROJECT".len());
+        human!("Squirrel Status ({} projects)", projects.len());
+        human!();
+        human!(
+            "    {:<width$}  {:<11}  {:<8}  {:<5}  MEMORIES",
+            "PROJECT",
+            "INITIALIZED",
+            "DATABASE",
+            "HOOKS"
+        );
+        for (path, checks, memories) in &projects {
+            let status = |name: &str| {
+                checks
+                    .iter()
+                    .find(|c| c.name == name)
+                    .map_or("-", |c| c.status.as_str())
+            };
+            let mark = match first_failure(checks) {
+                0 => output::paint("✓", "32"),
+                _ => output::paint("✗", "31"),
+            };
+            human!(
+                "  {} {:<width$}  {:<11}  {:<8}  {:<5}  {}",
+                mark,
+                path.display(),
+                status("initialized"),
+                status("database"),
+                status("hooks"),
+                memories.map_or_else(|| "-".to_string(), |n| n.to_string())
+            );
+            for check in checks.iter().filter(|c| c.status == CheckStatus::Fail) {
+                human!("      {}: {}", check.name, check.message);
+            }
+        }
+        let failing = projects
+            .iter()
+            .filter(|(_, checks, _)| first_failure(checks) != 0)
+            .count();
+        human!();
+        match failing {
+            0 => human!("All projects healthy."),
+            n => human!("{} of {} projects need attention.", n, projects.len()),
+        }
+    }
+
+    let report: Vec<Value> = projects
+        .iter()
+        .map(|(path, checks, memories)| {
+            json!({
+                "project": path,
+                "ok": first_failure(checks) == 0,
+                "exit_code": first_failure(checks),
+                "checks": checks,
+                "memories": memories,
+            })
+        })
+        .collect();
+    output::emit(&json!({
+        "ok": exit_code == 0,
+        "exit_code": exit_code,
+        "projects": report,
+    }))?;
+    Ok(exit_code)
+}
+
+/// Checks that only need the project: `initialized`, then `database` and
+/// `hooks` if it is. The `mcp` check depends on the current directory.
+fn project_checks(project_root: &Path) -> Vec<Check> {
+    if !project_root.join(".sqrl").exists() {
+        return vec![Check::fail(
+            "initialized",
+            EXIT_NOT_INITIALIZED,
+            "not initialized; run 'sqrl init'",
+        )];
+    }
+    vec![
+        Check::ok("initialized", ".sqrl/ present"),
+        check_database(project_root),
+        check_hooks(project_root),
+    ]
+}
+
+/// Exit code of the first failing check, 0 if none fail.
+fn first_failure(checks: &[Check]) -> i32 {
+    checks
+        .iter()
+        .find(|c| c.status == CheckStatus::Fail)
+        .map_or(0, |c| c.exit_code)
+}
+
+/// Redraw status every `secs` seconds, with the latest memory changes, until interrupted.
+pub fn watch(secs: u64) -> Result<(), Error> {
+    if output::is_json() {
+        return Err(Error::InvalidArgument(
+            "--watch prints for a terminal; drop --json".to_string(),
+        ));
+    }
+    let interval = std::time::Duration::from_secs(secs.max(1));
+    loop {
+        // Home and clear, so the dashboard redraws in place
+        print!("\x1b[H\x1b[2J");
+        run()?;
+        print_recent(&project_root()?);
+        human!();
+        human!(
+            "{}",
+            output::paint(
+                &format!(
+                    "Refreshing every {}s at {}. Ctrl-C to exit.",
+                    interval.as_secs(),
+                    chrono::Local::now().format("%H:%M:%S")
+                ),
+                "2"
+            )
+        );
+        std::thread::sleep(interval);
+    }
+}
+
+/// Most recently stored or changed project memories.
+fn print_recent(project_root: &Path) {
+    let path = storage::db_path(project_root);
+    if !path.exists() {
+        return;
+    }
+    let Ok(storage) = storage::Storage::open(&path) else {
+        return;
+    };
+    let Ok(mut memories) = storage.list_all_memories() else {
+        return;
+    };
+    if memories.is_empty() {
+        return;
+    }
+    memories.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    human!();
+    human!("Recent:");
+    for memory in memories.iter().take(WATCH_RECENT) {
+        let age = chrono::DateTime::parse_from_rfc3339(&memory.updated_at)
+            .ok()
+            .and_then(|at| (chrono::Utc::now() - at.to_utc()).to_std().ok())
+            .map_or_else(String::new, |age| human_age(age.as_secs()));
+        human!(
+            "  {}  {:<14} {}",
+            storage::short_id(&memory.id),
+            age,
+            memory.content
+        );
+    }
+}
+
+/// Check that the project database opens and has its tables.
+pub(crate) fn check_database(project_root: &Path) -> Check {
+    match storage::check_health(project_root) {
+        None => Check::ok("database", "healthy"),
+        Some(problem) => {
+            // A newer schema needs a newer sqrl; repairing would not help
+            let (exit_code, fix) = match problem {
+                DbProblem::TooNew(_) => (ExitCode::SchemaTooNew.code(), "sqrl upgrade"),
+                _ => (EXIT_DATABASE, "sqrl init --repair"),
+            };
+            Check::fail("database", exit_code, format!("{}; run '{}'", problem, fix))
+        }
+    }
+}
+
+/// Check that the pre-push (and snapshot) hooks are installed and current.
+pub(crate) fn check_hooks(project_root: &Path) -> Check {
+    let config = match Config::effective(project_root) {
+        Ok(config) => config,
+        Err(e) => return Check::skip("hooks", format!("config unreadable: {}", e)),
+    };
+    if !config.hooks.auto_install {
+        return Check::skip("hooks", "hooks.auto_install is off");
+    }
+    if !hooks::has_git(project_root) {
+        return Check::skip(
+            "hooks",
+            match git::Location::of(project_root) {
+                git::Location::Nested => "not the repository root",
+                _ => "not a git repository",
+            },
+        );
+    }
+    match hooks::post_commit_state(project_root) {
+        HookState::Missing if config.wants_post_commit_hook() => {
+            return Check::fail(
+                "hooks",
+                EXIT_HOOKS,
+                "post-commit hook not installed; run 'sqrl init --repair'",
+            );
+        }
+        HookState::Stale => {
+            return Check::fail(
+                "hooks",
+                EXIT_HOOKS,
+                format!(
+                    "post-commit hook is out of date (version {}); run 'sqrl hook upgrade'",
+                    installed_version(project_root, "post-commit")
+                ),
+            );
+        }
+        _ => {}
+    }
+    match hooks::post_checkout_state(project_root) {
+        HookState::Missing if config.hooks.on_checkout => {
+            return Check::fail(
+                "hooks",
+                EXIT_HOOKS,
+                "post-checkout hook not installed; run 'sqrl init --repair'",
+            );
+        }
+        HookState::Stale => {
+            return Check::fail(
+                "hooks",
+                EXIT_HOOKS,
+                format!(
+                    "post-checkout hook is out of date (version {}); run 'sqrl hook upgrade'",
+                    installed_version(project_root, "post-checkout")
+                ),
+            );
+        }
+        _ => {}
+    }
+    let block = config.hooks.pre_push_block;
+    match hooks::pre_push_state(project_root, block) {
+        HookState::Current if block => Check::ok("hooks", "pre-push hook installed (blocking)"),
+        HookState::Current => Check::ok("hooks", "pre-push hook installed"),
+        HookState::Stale => Check::fail(
+            "hooks",
+            EXIT_HOOKS,
+            format!(
+                "pre-push hook is out of date (version {}); run 'sqrl hook upgrade'",
+                installed_version(project_root, "pre-push")
+            ),
+        ),
+        HookState::Missing => Check::fail(
+            "hooks",
+            EXIT_HOOKS,
+            "pre-push hook not installed; run 'sqrl init --repair'",
+        ),
+    }
+}
+
+/// Version of the Squirrel script in `.git/hooks/<hook>`.
+fn installed_version(project_root: &Path, hook: &str) -> u32 {
+    let path = project_root.join(".git").join("hooks").join(hook);
+    hooks::installed_version(&fs::read_to_string(path).unwrap_or_default())
+}
+
+/// Check that every selected MCP is registered in every enabled MCP-capable tool.
+fn check_mcps(global: Option<&GlobalConfig>) -> Check {
+    let Some(global) = global else {
+        return Check::skip("mcp", "global config not set up");
+    };
+    let mcps = match selected_mcps(global) {
+        Ok(mcps) if !mcps.is_empty() => mcps,
+        Ok(_) => return Check::skip("mcp", "no MCP configs"),
+        Err(e) => return Check::skip("mcp", format!("MCP configs unreadable: {}", e)),
+    };
+    let tools = global.clone().with_profile().tools;
+    if !tools.claude_code && !tools.continue_dev {
+        return Check::skip("mcp", "no MCP-capable tools enabled");
+    }
+
+    let mut missing = Vec::new();
+    for mcp in &mcps {
+        if tools.claude_code {
+            let registered = claude_entry_location(mcp)
+                .map(|(file, pointer)| read_pointer(&file, &pointer).is_some())
+                .unwrap_or(false);
+            if !registered {
+                missing.push(format!("{} (claude_code)", mcp.name));
+            }
+        }
+        if tools.continue_dev
+            && !launch(mcp).is_ok_and(|launch| targets::continue_registered(&launch))
+        {
+            missing.push(format!("{} (continue)", mcp.name));
         }
     }
 
-    Ok(0)
+    if missing.is_empty() {
+        Check::ok("mcp", format!("{} MCP(s) registered", mcps.len()))
+    } else {
+        Check::fail(
+            "mcp",
+            EXIT_MCP,
+            format!("not registered: {}; run 'sqrl apply'", missing.join(", ")),
+        )
+    }
+}
+
+/// Display names of the enabled tools.
+fn enabled_tools(config: &GlobalConfig) -> Vec<&'static str> {
+    let mut enabled = Vec::new();
+    if config.tools.claude_code {
+        enabled.push("Claude Code");
+    }
+    if config.tools.git {
+        enabled.push("Git");
+    }
+    if config.tools.cursor {
+        enabled.push("Cursor");
+    }
+    if config.tools.codex {
+        enabled.push("Codex");
+    }
+    if config.tools.aider {
+        enabled.push("Aider");
+    }
+    if config.tools.continue_dev {
+        enabled.push("Continue");
+    }
+    enabled
+}
+
+/// Print managed entries that were hand-edited or deleted.
+fn print_drift(manifest: &Manifest, report: &mut Map<String, Value>) {
+    if manifest.entries.is_empty() {
+        return;
+    }
+    let drift = manifest.check();
+    report.insert("managed_entries".into(), json!(manifest.entries.len()));
+    report.insert(
+        "drift".into(),
+        drift
+            .iter()
+            .map(|d| {
+                json!({
+                    "name": d.entry.name,
+                    "tool": d.entry.tool,
+                    "file": d.entry.file,
+                    "kind": match d.kind {
+                        DriftKind::Modified => "modified",
+                        DriftKind::Removed => "removed",
+                    },
+                })
+            })
+            .collect(),
+    );
+    human!(
+        "  Managed entries: {} ({} drifted)",
+        manifest.entries.len(),
+        drift.len()
+    );
+    for d in &drift {
+        let what = match d.kind {
+            DriftKind::Modified => "edited by hand",
+            DriftKind::Removed => "removed",
+        };
+        human!(
+            "    ! {} ({}): {} in {}",
+            d.entry.name,
+            d.entry.tool,
+            what,
+            d.entry.file.display()
+        );
+    }
+    if !drift.is_empty() {
+        human!("    Review these entries; 'sqrl apply' re-adds removed ones.");
+    }
 }
 
 /// Get last activity time as human-readable string.
@@ -81,9 +626,12 @@ fn get_last_activity(sqrl_dir: &Path) -> Option<String> {
 
     let duration = std::time::SystemTime::now().duration_since(modified).ok()?;
 
-    let secs = duration.as_secs();
+    Some(human_age(duration.as_secs()))
+}
 
-    let human = if secs < 60 {
+/// Format an age in seconds as "5 minutes ago".
+pub fn human_age(secs: u64) -> String {
+    if secs < 60 {
         "just now".to_string()
     } else if secs < 3600 {
         let mins = secs / 60;
@@ -94,7 +642,36 @@ fn get_last_activity(sqrl_dir: &Path) -> Option<String> {
     } else {
         let days = secs / 86400;
         format!("{} day{} ago", days, if days == 1 { "" } else { "s" })
-    };
+    }
+}
 
-    Some(human)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_project_checks_uninitialized_fails_not_initialized() {
+        let dir = TempDir::new().unwrap();
+        let checks = project_checks(dir.path());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].name, "initialized");
+        assert_eq!(first_failure(&checks), EXIT_NOT_INITIALIZED);
+    }
+
+    #[test]
+    fn test_project_checks_broken_database_fails_database() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join(".sqrl")).unwrap();
+        storage::ensure_schema(dir.path()).unwrap();
+        let checks = project_checks(dir.path());
+        let names: Vec<&str> = checks.iter().map(|c| c.name).collect();
+        assert_eq!(names, ["initialized", "database", "hooks"]);
+        assert_eq!(first_failure(&checks), 0);
+
+        fs::write(storage::db_path(dir.path()), "not a database").unwrap();
+        let checks = project_checks(dir.path());
+        assert_eq!(checks[1].status, CheckStatus::Fail);
+        assert_eq!(first_failure(&checks), EXIT_DATABASE);
+    }
 }
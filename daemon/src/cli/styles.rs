@@ -0,0 +1,206 @@
+//! Code style rules from formatter configs (`sqrl import --from
+//! editorconfig|rustfmt|prettier`, CLI-029).
+//!
+//! Each setting becomes one instruction, so an assistant writing code by hand
+//! follows the same layout the formatter would produce. Common settings get
+//! a sentence; anything else is quoted as `key = value` for its tool.
+
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Tag on memories imported from formatter configs, as for `## Code Style`.
+pub const CODE_STYLE_TAG: &str = "code-style";
+
+/// Rules from an `.editorconfig`, scoped by section glob (`*` is every file).
+pub fn from_editorconfig(content: &str) -> Vec<String> {
+    // Properties before the first section only apply to the file itself (`root`)
+    let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push((glob.trim().to_string(), Vec::new()));
+        } else if let (Some((_, properties)), Some((key, value))) =
+            (sections.last_mut(), line.split_once('='))
+        {
+            properties.push((key.trim().to_lowercase(), value.trim().to_lowercase()));
+        }
+    }
+
+    let mut rules = Vec::new();
+    for (glob, properties) in &sections {
+        let get = |key: &str| {
+            properties
+                .iter()
+                .rev()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        let indent = indent_rule(get("indent_style"), get("indent_size").or(get("tab_width")));
+        let others =
+            properties
+                .iter()
+                .filter_map(|(key, value)| match (key.as_str(), value.as_str()) {
+                    ("end_of_line", eol) => {
+                        Some(format!("Use {} line endings", eol.to_uppercase()))
+                    }
+                    ("charset", charset) => Some(format!("Save files as {}", charset)),
+                    ("trim_trailing_whitespace", "true") => {
+                        Some("Trim trailing whitespace".to_string())
+                    }
+                    ("insert_final_newline", "true") => {
+                        Some("End files with a newline".to_string())
+                    }
+                    ("insert_final_newline", "false") => {
+                        Some("Don't end files with a newline".to_string())
+                    }
+                    ("max_line_length", "off") => None,
+                    ("max_line_length", length) => {
+                        Some(format!("Keep lines within {} characters", length))
+                    }
+                    _ => None,
+                });
+        for rule in indent.into_iter().chain(others) {
+            rules.push(scoped(glob, &rule));
+        }
+    }
+    rules
+}
+
+/// Rules from a `rustfmt.toml` or `.rustfmt.toml`.
+pub fn from_rustfmt(content: &str) -> Result<Vec<String>, Error> {
+    let table: toml::Table = toml::from_str(content)
+        .map_err(|e| Error::InvalidArgument(format!("not a rustfmt.toml: {}", e)))?;
+    let mut rules = Vec::new();
+    for (key, value) in &table {
+        let rule = match (key.as_str(), value) {
+            ("max_width", toml::Value::Integer(n)) => {
+                format!("Keep Rust lines within {} characters", n)
+            }
+            ("hard_tabs", toml::Value::Boolean(true)) => "Indent Rust with tabs".to_string(),
+            ("hard_tabs", toml::Value::Boolean(false)) => continue,
+            ("tab_spaces", toml::Value::Integer(n)) => {
+                format!("Indent Rust with {} spaces", n)
+            }
+            ("edition", toml::Value::String(edition)) => {
+                format!("Write Rust for the {} edition", edition)
+            }
+            ("newline_style", toml::Value::String(style)) => {
+                format!("Use {} line endings in Rust files", style)
+            }
+            _ => format!("Format Rust as rustfmt's `{} = {}` does", key, value),
+        };
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+/// Rules from a Prettier config (`.prettierrc` as JSON or YAML).
+/// `overrides` are skipped.
+pub fn from_prettier(content: &str) -> Result<Vec<String>, Error> {
+    let config: Value = serde_yaml::from_str(content)
+        .map_err(|e| Error::InvalidArgument(format!("not a Prettier config: {}", e)))?;
+    let Some(options) = config.as_object() else {
+        return Err(Error::InvalidArgument(
+            "not a Prettier config: expected an object of options".to_string(),
+        ));
+    };
+    let mut rules = Vec::new();
+    for (key, value) in options {
+        let rule = match (key.as_str(), value) {
+            ("overrides" | "$schema" | "plugins", _) => continue,
+            ("printWidth", Value::Number(n)) => format!("Keep lines within {} characters", n),
+            ("tabWidth", Value::Number(n)) => format!("Indent with {} spaces", n),
+            ("useTabs", Value::Bool(true)) => "Indent with tabs".to_string(),
+            ("useTabs", Value::Bool(false)) => continue,
+            ("semi", Value::Bool(true)) => "End statements with semicolons".to_string(),
+            ("semi", Value::Bool(false)) => "Don't end statements with semicolons".to_string(),
+            ("singleQuote", Value::Bool(true)) => "Use single quotes for strings".to_string(),
+            ("singleQuote", Value::Bool(false)) => "Use double quotes for strings".to_string(),
+            ("trailingComma", Value::String(mode)) => match mode.as_str() {
+                "none" => "Don't use trailing commas".to_string(),
+                "es5" => "Use trailing commas where ES5 allows them".to_string(),
+                _ => "Use trailing commas wherever possible".to_string(),
+            },
+            ("bracketSpacing", Value::Bool(false)) => {
+                "Don't put spaces inside object braces".to_string()
+            }
+            ("arrowParens", Value::String(mode)) if mode == "avoid" => {
+                "Omit parentheses around a single arrow function parameter".to_string()
+            }
+            ("endOfLine", Value::String(eol)) if eol != "auto" => {
+                format!("Use {} line endings", eol.to_uppercase())
+            }
+            _ => format!("Format as Prettier's `{}: {}` does", key, value),
+        };
+        rules.push(rule);
+    }
+    Ok(rules)
+}
+
+/// `indent_style` and `indent_size` as one rule.
+fn indent_rule(style: Option<&str>, size: Option<&str>) -> Option<String> {
+    match (style, size) {
+        (Some("tab"), _) => Some("Indent with tabs".to_string()),
+        (Some("space"), Some(size)) if size != "tab" => {
+            Some(format!("Indent with {} spaces", size))
+        }
+        (Some("space"), _) => Some("Indent with spaces".to_string()),
+        _ => None,
+    }
+}
+
+/// `rule` limited to files matching `glob`, unless that is every file.
+fn scoped(glob: &str, rule: &str) -> String {
+    match glob {
+        "*" | "**" => rule.to_string(),
+        glob => format!("In {} files: {}", glob, lowercase_first(rule)),
+    }
+}
+
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_lowercase().chain(chars).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatter_configs_become_rules() {
+        let editorconfig = "root = true\n\n[*]\nindent_style = space\nindent_size = 4\n\
+                            insert_final_newline = true\n\n[Makefile]\nindent_style = tab\n";
+        assert_eq!(
+            from_editorconfig(editorconfig),
+            [
+                "Indent with 4 spaces",
+                "End files with a newline",
+                "In Makefile files: indent with tabs"
+            ]
+        );
+
+        let rustfmt = from_rustfmt("max_width = 80\nimports_granularity = \"Crate\"\n").unwrap();
+        assert_eq!(
+            rustfmt,
+            [
+                "Format Rust as rustfmt's `imports_granularity = \"Crate\"` does",
+                "Keep Rust lines within 80 characters"
+            ]
+        );
+
+        let prettier = from_prettier("semi: false\nsingleQuote: true\noverrides: []\n").unwrap();
+        assert_eq!(
+            prettier,
+            [
+                "Don't end statements with semicolons",
+                "Use single quotes for strings"
+            ]
+        );
+        assert!(from_prettier("- not\n- options\n").is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Merge memories with another machine's copy (SYNC-001).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cli::output::{self, human};
+use crate::config::{find_project_root, Config};
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::lock::SqrlLock;
+use crate::storage::sync::{self, SyncReport};
+use crate::storage::{self, meta, Storage};
+
+/// Run the sync command.
+pub fn run(peer: &Path, global: bool) -> Result<(), Error> {
+    let local_db = local_db_path(global)?;
+    let text = peer.to_string_lossy();
+    if let Some(remote) = Remote::parse(&text)? {
+        return sync_remote(&local_db, &remote, global);
+    }
+
+    let peer_db = peer_db_path(peer, global)?;
+    if same_file(&local_db, &peer_db) {
+        return Err(Error::Sync("peer is this store".to_string()));
+    }
+    let _locks = lock_both(&local_db, &peer_db)?;
+    let key = peer_db.canonicalize()?.to_string_lossy().into_owned();
+    merge(&local_db, &peer_db, &key, &peer_db.display().to_string())?;
+    Ok(())
+}
+
+fn local_db_path(global: bool) -> Result<PathBuf, Error> {
+    if global {
+        return GlobalConfig::memory_db_path();
+    }
+    let cwd = std::env::current_dir()?;
+    let root = find_project_root(&cwd).ok_or_else(|| Error::ConfigNotFound(Config::path(&cwd)))?;
+    Ok(storage::db_path(&root))
+}
+
+/// Copy the peer's database here over scp, merge into the copy, and copy it
+/// back only if the merge changed it.
+fn sync_remote(local_db: &Path, remote: &Remote, global: bool) -> Result<(), Error> {
+    let _lock = SqrlLock::store(local_db)?;
+    let copy = local_db.with_file_name(PEER_COPY);
+    let remote_db = remote.db_path(global);
+    remote.fetch(&remote_db, &copy)?;
+    let result = merge(
+        local_db,
+        &copy,
+        &remote.spec(&remote_db),
+        &remote.spec(&remote_db),
+    )
+    .and_then(|report| {
+        if report.pushed + report.deleted_peer > 0 {
+            remote.put(&copy, &remote_db)?;
+        }
+        Ok(())
+    });
+    let _ = fs::remove_file(&copy);
+    result
+}
+
+/// Merge `peer_db` into `local_db` and back; the caller holds the locks.
+/// `key` names the peer in the local sync cursor, `name` in messages.
+fn merge(local_db: &Path, peer_db: &Path, key: &str, name: &str) -> Result<SyncReport, Error> {
+    let local = Storage::open(local_db)?;
+    let peer = Storage::open(peer_db)?;
+    // Generations of both sides after the last sync; unchanged means nothing to merge
+    let cursor = |local: &Storage, peer: &Storage| -> Result<String, Error> {
+        Ok(format!("{}:{}", local.generation()?, peer.generation()?))
+    };
+    if local.meta_get(meta::SYNC_NAMESPACE, key)? == Some(cursor(&local, &peer)?) {
+        let report = SyncReport::default();
+        output::emit(&report)?;
+        human!(
+            "Already in sync with {}: nothing changed since the last sync.",
+            name
+        );
+        return Ok(report);
+    }
+    let report = sync::sync(&local, &peer)?;
+    local.meta_set(meta::SYNC_NAMESPACE, key, &cursor(&local, &peer)?)?;
+    output::emit(&report)?;
+
+    human!(
+        "Synced with {}: {} pulled, {} pushed, {} deleted here, {} deleted there.",
+        name,
+        report.pulled,
+        report.pushed,
+        report.deleted_local,
+        report.deleted_peer
+    );
+    if report.private > 0 {
+        human!("{} private memories left out.", report.private);
+    }
+    Ok(report)
+}
+
+/// Lock both stores, in path order so two syncs of the same pair in opposite
+/// directions can't each hold one lock and wait for the other.
+fn lock_both(local_db: &Path, peer_db: &Path) -> Result<[SqrlLock; 2], Error> {
+    let dir = |db: &Path| -> Result<PathBuf, Error> {
+        let dir = db.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(dir).map_err(Error::at(dir))?;
+        Ok(dir.canonicalize()?)
+    };
+    let (first, second) = if dir(local_db)? <= dir(peer_db)? {
+        (local_db, peer_db)
+    } else {
+        (peer_db, local_db)
+    };
+    Ok([SqrlLock::store(first)?, SqrlLock::store(second)?])
+}
+
+/// Database file for a local `--peer`: a `memory.db` path, a project, or a `~/.sqrl`-style dir.
+fn peer_db_path(peer: &Path, global: bool) -> Result<PathBuf, Error> {
+    if peer.is_file() {
+        return Ok(peer.to_path_buf());
+    }
+    let candidates = if global {
+        vec![peer.join("memory.db"), peer.join(".sqrl").join("memory.db")]
+    } else {
+        vec![storage::db_path(peer), peer.join("memory.db")]
+    };
+    candidates
+        .into_iter()
+        .find(|p| p.is_file())
+        .ok_or_else(|| Error::Sync(format!("no memory.db found at '{}'", peer.display())))
+}
+
+/// Where the peer's database is copied while merging, next to the local one.
+const PEER_COPY: &str = "memory.db.peer";
+
+/// A peer on another machine: `ssh://[user@]host[:port]/path` or scp's `host:path`.
+#[derive(Debug, PartialEq)]
+struct Remote {
+    host: String,
+    port: Option<u16>,
+    path: String,
+}
+
+impl Remote {
+    /// `None` for a local path.
+    fn parse(text: &str) -> Result<Option<Self>, Error> {
+        if let Some(rest) = text.strip_prefix("ssh://") {
+            let (authority, path) = match rest.find('/') {
+                Some(i) => rest.split_at(i),
+                None => (rest, ""),
+            };
+            let (host, port) = match authority.rsplit_once(':') {
+                Some((host, port)) => {
+                    let port = port
+                        .parse()
+                        .map_err(|_| Error::Sync(format!("bad port in '{}'", text)))?;
+                    (host, Some(port))
+                }
+                None => (authority, None),
+            };
+            if host.is_empty() || path.is_empty() {
+                return Err(Error::Sync(format!(
+                    "'{}' needs a host and a path, e.g. ssh://desktop/home/me/project",
+                    text
+                )));
+            }
+            return Ok(Some(Self {
+                host: host.to_string(),
+                port,
+                path: path.to_string(),
+            }));
+        }
+        if text.contains("://") {
+            return Err(Error::Sync(format!(
+                "'{}' is not a path, ssh:// URL or host:path",
+                text
+            )));
+        }
+        if !is_scp_style(text) {
+            return Ok(None);
+        }
+        let (host, path) = text.split_once(':').unwrap_or_default();
+        Ok(Some(Self {
+            host: host.to_string(),
+            port: None,
+            path: if path.is_empty() { "." } else { path }.to_string(),
+        }))
+    }
+
+    /// The peer's database: the path itself if it names a `.db` file, else
+    /// the project's (or, with `--global`, the `~/.sqrl`-style dir's) `memory.db`.
+    fn db_path(&self, global: bool) -> String {
+        let dir = self.path.trim_end_matches('/');
+        if dir.ends_with(".db") {
+            dir.to_string()
+        } else if global {
+            format!("{}/memory.db", dir)
+        } else {
+            format!("{}/.sqrl/memory.db", dir)
+        }
+    }
+
+    /// `host:path`, as scp takes it.
+    fn spec(&self, path: &str) -> String {
+        format!("{}:{}", self.host, path)
+    }
+
+    fn fetch(&self, remote_db: &str, to: &Path) -> Result<(), Error> {
+        let mut scp = self.scp();
+        scp.arg(self.spec(remote_db)).arg(to);
+        run_transport(scp, &format!("copy {}", self.spec(remote_db)))
+    }
+
+    /// Upload beside the peer's database, then rename over it, so a reader on
+    /// the peer never sees a half-copied file.
+    fn put(&self, from: &Path, remote_db: &str) -> Result<(), Error> {
+        let staged = format!("{}.sync", remote_db);
+        let mut scp = self.scp();
+        scp.arg(from).arg(self.spec(&staged));
+        run_transport(scp, &format!("copy to {}", self.spec(&staged)))?;
+        let mut ssh = Command::new("ssh");
+        if let Some(port) = self.port {
+            ssh.arg("-p").arg(port.to_string());
+        }
+        ssh.arg(&self.host).arg(format!(
+            "mv -f -- {} {}",
+            shell_quote(&staged),
+            shell_quote(remote_db)
+        ));
+        run_transport(ssh, &format!("replace {}", self.spec(remote_db)))
+    }
+
+    fn scp(&self) -> Command {
+        let mut scp = Command::new("scp");
+        scp.arg("-q");
+        if let Some(port) = self.port {
+            scp.arg("-P").arg(port.to_string());
+        }
+        scp
+    }
+}
+
+fn run_transport(mut command: Command, what: &str) -> Result<(), Error> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command
+        .status()
+        .map_err(|e| Error::Sync(format!("could not run {} to {}: {}", program, what, e)))?;
+    if !status.success() {
+        return Err(Error::Sync(format!("{} could not {}", program, what)));
+    }
+    Ok(())
+}
+
+/// Quote for the remote POSIX shell ssh runs the command in.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// `host:path` as accepted by scp and ssh, but not a Windows drive letter.
+fn is_scp_style(text: &str) -> bool {
+    match text.split_once(':') {
+        Some((host, _)) => host.len() > 1 && !host.contains('/'),
+        None => false,
+    }
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(host: &str, port: Option<u16>, path: &str) -> Option<Remote> {
+        Some(Remote {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_remote_parse_ssh_url_and_scp_style() {
+        assert_eq!(
+            Remote::parse("ssh://me@desktop:2222/home/me/proj").unwrap(),
+            remote("me@desktop", Some(2222), "/home/me/proj")
+        );
+        assert_eq!(
+            Remote::parse("desktop:code/proj").unwrap(),
+            remote("desktop", None, "code/proj")
+        );
+        assert_eq!(
+            Remote::parse("desktop:").unwrap(),
+            remote("desktop", None, ".")
+        );
+    }
+
+    #[test]
+    fn test_remote_parse_local_paths_are_none() {
+        assert_eq!(Remote::parse("/mnt/desktop/proj").unwrap(), None);
+        assert_eq!(Remote::parse("C:\\code\\proj").unwrap(), None);
+        assert_eq!(Remote::parse("./a:b").unwrap(), None);
+    }
+
+    #[test]
+    fn test_remote_parse_bad_address_fails() {
+        assert!(Remote::parse("ssh://desktop").is_err());
+        assert!(Remote::parse("ssh://desktop:port/x").is_err());
+        assert!(Remote::parse("https://desktop/x").is_err());
+    }
+
+    #[test]
+    fn test_remote_db_path_project_global_and_file() {
+        let r = remote("h", None, "code/proj/").unwrap();
+        assert_eq!(r.db_path(false), "code/proj/.sqrl/memory.db");
+        assert_eq!(r.db_path(true), "code/proj/memory.db");
+        let r = remote("h", None, "/x/memory.db").unwrap();
+        assert_eq!(r.db_path(false), "/x/memory.db");
+    }
+
+    #[test]
+    fn test_shell_quote_single_quote_escaped() {
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
@@ -0,0 +1,376 @@
+//! Apply targets for tools configured by file (CLI-004).
+//!
+//! Continue reads MCP servers from `~/.continue/config.json`; Squirrel's own
+//! server is registered there alongside the configured ones.
+//! Aider has no MCP support, so it gets a conventions file generated from memories.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::atomic;
+use crate::cli::apply::Launch;
+use crate::cli::output::human;
+use crate::error::Error;
+use crate::global_config::managed::{self, read_pointer, Manifest};
+use crate::global_config::{GlobalConfig, McpConfig, Transport};
+use crate::storage;
+
+/// Conventions file generated for Aider, relative to the project root.
+pub const AIDER_CONVENTIONS: &str = ".sqrl/CONVENTIONS.md";
+
+/// Continue's server list in its config file.
+const CONTINUE_SERVERS: &str = "/experimental/modelContextProtocolServers";
+
+/// The MCPs Continue gets: Squirrel's own server (CLI-007), then `mcps`.
+/// Continue has no per-project MCP config, so Squirrel's tool calls pass
+/// `project_root` to pick the store.
+pub fn continue_mcps(mcps: &[McpConfig]) -> Vec<McpConfig> {
+    let squirrel = McpConfig {
+        name: "squirrel".to_string(),
+        command: "sqrl".to_string(),
+        args: vec!["mcp-serve".to_string()],
+        env: Default::default(),
+        scope: "user".to_string(),
+        cwd: None,
+        transport: Transport::Stdio,
+        url: None,
+    };
+    std::iter::once(squirrel)
+        .chain(mcps.iter().cloned())
+        .collect()
+}
+
+/// Get the Continue config file path.
+pub fn continue_config_path() -> Result<PathBuf, Error> {
+    dirs::home_dir()
+        .map(|h| h.join(".continue").join("config.json"))
+        .ok_or(Error::HomeDirNotFound)
+}
+
+/// Continue server entry for `launch`.
+fn continue_entry(launch: &Launch) -> Value {
+    let transport = match launch {
+        Launch::Command { command, env } => {
+            let mut transport = json!({
+                "type": "stdio",
+                "command": command[0],
+                "args": command[1..],
+            });
+            if !env.is_empty() {
+                transport["env"] = env
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into();
+            }
+            transport
+        }
+        Launch::Remote { transport, url } => {
+            json!({ "type": continue_transport(*transport), "url": url })
+        }
+    };
+    json!({ "transport": transport })
+}
+
+/// Continue's name for a transport.
+fn continue_transport(transport: Transport) -> &'static str {
+    match transport {
+        Transport::Http => "streamable-http",
+        other => other.as_str(),
+    }
+}
+
+/// Fields identifying the Continue entry for `launch`: the command line,
+/// whatever its env, or the URL. The manifest finds entries by it.
+fn continue_key(launch: &Launch) -> Value {
+    match launch {
+        Launch::Command { command, .. } => {
+            json!({ "transport": { "command": command[0], "args": command[1..] } })
+        }
+        Launch::Remote { url, .. } => json!({ "transport": { "url": url } }),
+    }
+}
+
+/// Whether a Continue server entry reaches the same server as `launch`.
+fn launches(server: &Value, launch: &Launch) -> bool {
+    managed::matches(server, &continue_key(launch))
+}
+
+/// Whether Continue already has a server reached as `launch`.
+pub fn continue_registered(launch: &Launch) -> bool {
+    let Ok(path) = continue_config_path() else {
+        return false;
+    };
+    let Some(servers) = read_pointer(&path, CONTINUE_SERVERS) else {
+        return false;
+    };
+    servers
+        .as_array()
+        .is_some_and(|servers| servers.iter().any(|s| launches(s, launch)))
+}
+
+/// Register MCPs in Continue's `experimental.modelContextProtocolServers`.
+/// An entry already launching the same command gets its `env` brought in
+/// line with the config, leaving its other fields alone. Returns the names
+/// of MCPs added or already present.
+pub fn apply_to_continue(
+    mcps: &[(McpConfig, Launch)],
+    manifest: &mut Manifest,
+) -> Result<Vec<String>, Error> {
+    apply_to_continue_at(&continue_config_path()?, mcps, manifest)
+}
+
+/// `apply_to_continue` on the Continue config at `path`.
+fn apply_to_continue_at(
+    path: &Path,
+    mcps: &[(McpConfig, Launch)],
+    manifest: &mut Manifest,
+) -> Result<Vec<String>, Error> {
+    let mut config: Value = if path.exists() {
+        serde_json::from_str(&fs::read_to_string(path)?)?
+    } else {
+        json!({})
+    };
+    let original = config.clone();
+
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| Error::ConfigParse(format!("{} is not an object", path.display())))?;
+    let servers = root
+        .entry("experimental")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| Error::ConfigParse("'experimental' is not an object".to_string()))?
+        .entry("modelContextProtocolServers")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+        .ok_or_else(|| {
+            Error::ConfigParse("'modelContextProtocolServers' is not an array".to_string())
+        })?;
+
+    let mut applied = Vec::new();
+    let mut added = Vec::new();
+    for (mcp, launch) in mcps {
+        let entry = continue_entry(launch);
+        let key = continue_key(launch);
+        match servers.iter_mut().find(|s| launches(s, launch)) {
+            Some(server) if server["transport"]["env"] == entry["transport"]["env"] => {
+                human!("  = {} (already registered with Continue)", mcp.name);
+            }
+            Some(server) => {
+                if let Some(transport) = server["transport"].as_object_mut() {
+                    match entry["transport"].get("env") {
+                        Some(env) => transport.insert("env".to_string(), env.clone()),
+                        None => transport.remove("env"),
+                    };
+                }
+                // Hand-added entries get the new env but stay unmanaged
+                let managed = manifest
+                    .entries
+                    .iter()
+                    .any(|e| e.tool == "continue" && e.key.as_ref() == Some(&key));
+                if managed {
+                    added.push((mcp.name.clone(), key));
+                }
+                human!("  ~ {} (Continue, env updated)", mcp.name);
+            }
+            None => {
+                servers.push(entry);
+                added.push((mcp.name.clone(), key));
+                human!("  + {} (Continue)", mcp.name);
+            }
+        }
+        applied.push(mcp.name.clone());
+    }
+
+    if config != original {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        atomic::write(path, serde_json::to_string_pretty(&config)?)?;
+        info!(path = %path.display(), "Updated Continue config");
+
+        for (name, key) in added {
+            manifest.record_item("continue", &name, path, CONTINUE_SERVERS, key);
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Generate the Aider conventions file and point `.aider.conf.yml` at it.
+pub fn apply_to_aider(project_root: &Path) -> Result<(), Error> {
+    let conventions_path = project_root.join(AIDER_CONVENTIONS);
+    if let Some(parent) = conventions_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let memories = storage::format_memories_as_markdown(
+        project_root,
+        &[],
+        None,
+        None,
+        None,
+        GlobalConfig::active_profile_name().as_deref(),
+        &[],
+    )?;
+    let content = format!(
+        "# Project Conventions\n\nGenerated by Squirrel from stored memories. Do not edit.\n\n{}\n",
+        memories
+    );
+    atomic::write(&conventions_path, content)?;
+
+    let aider_conf = project_root.join(".aider.conf.yml");
+    let existing = if aider_conf.exists() {
+        fs::read_to_string(&aider_conf)?
+    } else {
+        String::new()
+    };
+
+    if existing.contains(AIDER_CONVENTIONS) {
+        human!("  = conventions (already read by Aider)");
+        return Ok(());
+    }
+
+    let new_content = if existing.lines().any(|l| l.starts_with("read:")) {
+        // Existing read list: add our file to it
+        let mut conf: serde_yaml::Value =
+            serde_yaml::from_str(&existing).map_err(|e| Error::ConfigParse(e.to_string()))?;
+        let read = &mut conf["read"];
+        let mut files: Vec<serde_yaml::Value> = match std::mem::take(read) {
+            serde_yaml::Value::Sequence(seq) => seq,
+            serde_yaml::Value::Null => vec![],
+            other => vec![other],
+        };
+        files.push(AIDER_CONVENTIONS.into());
+        *read = serde_yaml::Value::Sequence(files);
+        serde_yaml::to_string(&conf).map_err(|e| Error::ConfigParse(e.to_string()))?
+    } else {
+        let separator = if existing.is_empty() || existing.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        format!("{}{}read: [{}]\n", existing, separator, AIDER_CONVENTIONS)
+    };
+
+    atomic::write(&aider_conf, new_content)?;
+    info!("Pointed .aider.conf.yml at conventions file");
+    human!("  + conventions (Aider)");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn mcp(name: &str, env: &[(&str, &str)]) -> (McpConfig, Launch) {
+        let env: Vec<(String, String)> = env
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mcp = McpConfig {
+            name: name.to_string(),
+            command: format!("{}-mcp", name),
+            args: vec!["--stdio".to_string()],
+            env: env.iter().cloned().collect(),
+            scope: "user".to_string(),
+            cwd: None,
+            transport: Transport::Stdio,
+            url: None,
+        };
+        let launch = Launch::Command {
+            command: vec![mcp.command.clone(), "--stdio".to_string()],
+            env,
+        };
+        (mcp, launch)
+    }
+
+    #[test]
+    fn test_apply_to_continue_reapply_changes_nothing() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".continue").join("config.json");
+        let mut manifest = Manifest::default();
+        let mcps = [mcp("db", &[("TOKEN", "a")])];
+
+        assert_eq!(
+            apply_to_continue_at(&path, &mcps, &mut manifest).unwrap(),
+            ["db"]
+        );
+        let written = fs::read_to_string(&path).unwrap();
+        let config: Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(
+            config["experimental"]["modelContextProtocolServers"][0]["transport"],
+            json!({"type": "stdio", "command": "db-mcp", "args": ["--stdio"], "env": {"TOKEN": "a"}})
+        );
+        assert_eq!(manifest.entries.len(), 1);
+
+        apply_to_continue_at(&path, &mcps, &mut manifest).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), written);
+        assert_eq!(manifest.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_to_continue_changed_env_updates_entry_in_place() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{"models": [], "experimental": {"modelContextProtocolServers": [
+                {"name": "mine", "transport": {"type": "stdio", "command": "db-mcp",
+                 "args": ["--stdio"], "env": {"TOKEN": "old"}}}
+            ]}}"#,
+        )
+        .unwrap();
+        let mut manifest = Manifest::default();
+
+        apply_to_continue_at(&path, &[mcp("db", &[("TOKEN", "new")])], &mut manifest).unwrap();
+        let config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let servers = &config["experimental"]["modelContextProtocolServers"];
+        assert_eq!(servers.as_array().unwrap().len(), 1);
+        assert_eq!(servers[0]["name"], "mine");
+        assert_eq!(servers[0]["transport"]["env"], json!({"TOKEN": "new"}));
+        assert_eq!(config["models"], json!([]));
+        // Hand-added entries stay unmanaged
+        assert!(manifest.entries.is_empty());
+    }
+
+    #[test]
+    fn test_apply_to_aider_reapply_keeps_read_list() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let conf = root.join(".aider.conf.yml");
+        fs::write(&conf, "model: gpt-4o\nread:\n  - NOTES.md\n").unwrap();
+
+        apply_to_aider(root).unwrap();
+        let conventions = fs::read_to_string(root.join(AIDER_CONVENTIONS)).unwrap();
+        assert!(conventions.starts_with("# Project Conventions"));
+        let written = fs::read_to_string(&conf).unwrap();
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&written).unwrap();
+        assert_eq!(
+            yaml["read"],
+            serde_yaml::from_str::<serde_yaml::Value>("[NOTES.md, .sqrl/CONVENTIONS.md]").unwrap()
+        );
+        assert_eq!(yaml["model"], "gpt-4o");
+
+        apply_to_aider(root).unwrap();
+        assert_eq!(fs::read_to_string(&conf).unwrap(), written);
+    }
+
+    #[test]
+    fn test_apply_to_aider_no_read_key_appends_one() {
+        let dir = TempDir::new().unwrap();
+        let conf = dir.path().join(".aider.conf.yml");
+        fs::write(&conf, "model: gpt-4o").unwrap();
+
+        apply_to_aider(dir.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(&conf).unwrap(),
+            "model: gpt-4o\nread: [.sqrl/CONVENTIONS.md]\n"
+        );
+    }
+}
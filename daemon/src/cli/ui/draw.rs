@@ -0,0 +1,79 @@
+//! Rendering: the list, the selected memory, and a footer for keys and input.
+
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::{App, Mode};
+use crate::storage;
+
+/// Key help shown in the footer.
+const KEYS: &str = "↑↓ move  / search  e edit  t tags  d delete  q quit";
+
+impl App {
+    pub(super) fn draw(&mut self, frame: &mut Frame) {
+        let [list_area, detail_area, footer_area] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|&i| {
+                let m = &self.memories[i];
+                ListItem::new(format!(
+                    "{}  {:<10}  [used {}x] {}",
+                    storage::short_id(&m.id),
+                    m.memory_type,
+                    m.use_count,
+                    m.content
+                ))
+            })
+            .collect();
+        let title = match self.query.as_str() {
+            "" => format!(" Memories ({}) ", self.memories.len()),
+            query => format!(
+                " Memories ({} of {}) matching '{}' ",
+                self.visible.len(),
+                self.memories.len(),
+                query
+            ),
+        };
+        let list = List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list);
+
+        let detail = match self.selected() {
+            Some(m) => vec![
+                Line::from(m.content.clone()),
+                Line::from(""),
+                Line::from(format!("id:      {}", m.id)).dim(),
+                Line::from(format!("tags:    {}", m.tags.join(", "))).dim(),
+                Line::from(format!("updated: {}", m.updated_at)).dim(),
+            ],
+            None => vec![Line::from("No memories.").dim()],
+        };
+        frame.render_widget(
+            Paragraph::new(detail)
+                .block(Block::bordered().title(" Detail "))
+                .wrap(Wrap { trim: false }),
+            detail_area,
+        );
+
+        let footer = match self.mode {
+            Mode::Browse if self.message.is_empty() => Line::from(KEYS).dim(),
+            Mode::Browse => Line::from(self.message.as_str()),
+            Mode::Search => Line::from(format!("/{}", self.input)),
+            Mode::EditContent => Line::from(format!("content: {}", self.input)),
+            Mode::EditTags => Line::from(format!("tags (comma-separated): {}", self.input)),
+            Mode::ConfirmDelete => Line::from("Delete this memory? [y/N]").bold(),
+        };
+        frame.render_widget(Paragraph::new(footer), footer_area);
+    }
+}
@@ -0,0 +1,128 @@
+//! Key handling and the writes it triggers.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::{parse_tags, App, Mode};
+use crate::cli::memory;
+use crate::error::Error;
+use crate::lock::SqrlLock;
+use crate::storage;
+
+impl App {
+    /// Handle one key press. Returns false to quit.
+    pub(super) fn handle_key(&mut self, key: KeyEvent) -> Result<bool, Error> {
+        match self.mode {
+            Mode::Browse => return self.browse_key(key.code),
+            Mode::ConfirmDelete => {
+                if matches!(key.code, KeyCode::Char('y')) {
+                    self.delete_selected()?;
+                } else {
+                    self.message = "Kept.".to_string();
+                }
+                self.mode = Mode::Browse;
+            }
+            Mode::Search | Mode::EditContent | Mode::EditTags => match key.code {
+                KeyCode::Esc => {
+                    if self.mode == Mode::Search {
+                        self.query.clear();
+                        self.refilter();
+                    }
+                    self.mode = Mode::Browse;
+                }
+                KeyCode::Enter => {
+                    self.submit()?;
+                    self.mode = Mode::Browse;
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                    self.live_search();
+                }
+                KeyCode::Char(c) => {
+                    self.input.push(c);
+                    self.live_search();
+                }
+                _ => {}
+            },
+        }
+        Ok(true)
+    }
+
+    fn browse_key(&mut self, code: KeyCode) -> Result<bool, Error> {
+        self.message.clear();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Down | KeyCode::Char('j') => self.list.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.list.select_previous(),
+            KeyCode::Home | KeyCode::Char('g') => self.list.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.list.select_last(),
+            KeyCode::Char('/') => {
+                self.input = self.query.clone();
+                self.mode = Mode::Search;
+            }
+            KeyCode::Char('e') => {
+                if let Some(memory) = self.selected() {
+                    self.input = memory.content.clone();
+                    self.mode = Mode::EditContent;
+                }
+            }
+            KeyCode::Char('t') => {
+                if let Some(memory) = self.selected() {
+                    self.input = memory.tags.join(", ");
+                    self.mode = Mode::EditTags;
+                }
+            }
+            KeyCode::Char('d') if self.selected().is_some() => self.mode = Mode::ConfirmDelete,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn live_search(&mut self) {
+        if self.mode == Mode::Search {
+            self.query = self.input.clone();
+            self.refilter();
+        }
+    }
+
+    /// Apply the input line for the current edit mode.
+    fn submit(&mut self) -> Result<(), Error> {
+        let Some(id) = self.selected().map(|m| m.id.clone()) else {
+            return Ok(());
+        };
+        match self.mode {
+            Mode::EditContent => {
+                let content = self.input.trim();
+                if content.is_empty() {
+                    self.message = "Content can't be empty; use d to delete.".to_string();
+                    return Ok(());
+                }
+                let _lock = SqrlLock::project(&self.root)?;
+                self.storage.update_memory(&id, None, Some(content), None)?;
+                self.message = format!("Updated {}.", storage::short_id(&id));
+            }
+            Mode::EditTags => {
+                let tags = parse_tags(&self.input);
+                let _lock = SqrlLock::project(&self.root)?;
+                self.storage.update_memory(&id, None, None, Some(&tags))?;
+                self.message = format!("Tagged {}.", storage::short_id(&id));
+            }
+            _ => return Ok(()),
+        }
+        self.reload()
+    }
+
+    fn delete_selected(&mut self) -> Result<(), Error> {
+        let Some(memory) = self.selected().cloned() else {
+            return Ok(());
+        };
+        {
+            let _lock = SqrlLock::project(&self.root)?;
+            memory::trash_memory(&self.root, &self.storage, &memory)?;
+        }
+        self.message = format!(
+            "Deleted {}. 'sqrl undo' restores it.",
+            storage::short_id(&memory.id)
+        );
+        self.reload()
+    }
+}
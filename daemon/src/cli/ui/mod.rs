@@ -0,0 +1,175 @@
+//! Terminal UI for project memories (CLI-022).
+//!
+//! Browse, search, edit, tag, and delete without leaving the terminal. Every
+//! write takes the project lock for just that write, so hooks and MCP servers
+//! running alongside are not held up while the UI is open.
+
+mod draw;
+mod keys;
+
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::widgets::ListState;
+use ratatui::DefaultTerminal;
+
+use crate::cli::memory;
+use crate::cli::output;
+use crate::error::Error;
+use crate::storage::{self, Memory, Storage};
+
+/// What keystrokes currently do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Browse,
+    Search,
+    EditContent,
+    EditTags,
+    ConfirmDelete,
+}
+
+struct App {
+    root: PathBuf,
+    storage: Storage,
+    memories: Vec<Memory>,
+    query: String,
+    /// Indexes into `memories` matching `query`.
+    visible: Vec<usize>,
+    list: ListState,
+    mode: Mode,
+    input: String,
+    message: String,
+}
+
+/// Open the memory UI for the current project.
+pub fn run() -> Result<(), Error> {
+    if output::is_json() || !io::stdout().is_terminal() {
+        return Err(Error::InvalidArgument(
+            "sqrl ui needs an interactive terminal".to_string(),
+        ));
+    }
+    let root = memory::root()?;
+    let storage = Storage::open(&storage::db_path(&root))?;
+    let mut app = App::new(root, storage)?;
+
+    let mut terminal = ratatui::try_init()?;
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl App {
+    fn new(root: PathBuf, storage: Storage) -> Result<Self, Error> {
+        let mut app = Self {
+            root,
+            storage,
+            memories: Vec::new(),
+            query: String::new(),
+            visible: Vec::new(),
+            list: ListState::default(),
+            mode: Mode::Browse,
+            input: String::new(),
+            message: String::new(),
+        };
+        app.reload()?;
+        Ok(app)
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), Error> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            // A failed write (e.g. lock held by a hook) is shown, not fatal
+            match self.handle_key(key) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(e) => {
+                    self.mode = Mode::Browse;
+                    self.message = e.to_string();
+                }
+            }
+        }
+    }
+
+    /// Re-read memories, keeping the selection on the same memory if it still exists.
+    fn reload(&mut self) -> Result<(), Error> {
+        let selected = self.selected().map(|m| m.id.clone());
+        self.memories = self.storage.list_all_memories()?;
+        self.refilter();
+        if let Some(id) = selected {
+            if let Some(pos) = self.visible.iter().position(|&i| self.memories[i].id == id) {
+                self.list.select(Some(pos));
+            }
+        }
+        Ok(())
+    }
+
+    fn refilter(&mut self) {
+        self.visible = (0..self.memories.len())
+            .filter(|&i| matches_query(&self.memories[i], &self.query))
+            .collect();
+        let last = self.visible.len().checked_sub(1);
+        let selected = self.list.selected().unwrap_or(0);
+        self.list.select(last.map(|last| selected.min(last)));
+    }
+
+    fn selected(&self) -> Option<&Memory> {
+        let index = *self.visible.get(self.list.selected()?)?;
+        self.memories.get(index)
+    }
+}
+
+/// Case-insensitive match of every query word against content, type, and tags.
+fn matches_query(memory: &Memory, query: &str) -> bool {
+    let haystack = format!(
+        "{} {} {}",
+        memory.content,
+        memory.memory_type,
+        memory.tags.join(" ")
+    )
+    .to_lowercase();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|word| haystack.contains(word))
+}
+
+/// Tags from a comma-separated line, trimmed, without blanks or repeats.
+fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_and_tag_parsing() {
+        let memory = Memory {
+            id: "ae777456".to_string(),
+            memory_type: "project".to_string(),
+            content: "Run tests with cargo nextest".to_string(),
+            tags: vec!["ci".to_string()],
+            use_count: 1,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        assert!(matches_query(&memory, ""));
+        assert!(matches_query(&memory, "NEXTEST ci"));
+        assert!(!matches_query(&memory, "nextest pnpm"));
+
+        assert_eq!(parse_tags(" ci, build,,ci "), ["ci", "build"]);
+    }
+}
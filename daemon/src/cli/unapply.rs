@@ -0,0 +1,68 @@
+//! Take applied MCP entries back out of tool configs (CLI-031).
+//!
+//! Only entries recorded in the managed manifest (MANAGED-001) are touched,
+//! so servers a user registered by hand are never removed.
+
+use serde_json::json;
+
+use crate::cli::output::{self, human};
+use crate::error::Error;
+use crate::global_config::managed::{DriftKind, Manifest};
+use crate::global_config::GlobalConfig;
+use crate::lock::SqrlLock;
+
+/// Run the unapply command for `names` (all if empty).
+///
+/// Entries applied from the current directory are removed; `global` adds
+/// user-level ones. Entries edited by hand are kept unless `force`.
+pub fn run(names: &[String], global: bool, force: bool) -> Result<(), Error> {
+    if !GlobalConfig::exists() {
+        human!("Global config not found. Nothing was applied.");
+        return output::emit(&json!({ "removed": [], "kept": [] }));
+    }
+    let _lock = SqrlLock::global()?;
+    let mut manifest = Manifest::load()?;
+    let cwd = std::env::current_dir()?;
+
+    let mut removed = Vec::new();
+    let mut kept = Vec::new();
+    let mut index = 0;
+    while index < manifest.entries.len() {
+        let entry = &manifest.entries[index];
+        let here = match &entry.project {
+            Some(project) => *project == cwd,
+            None => global,
+        };
+        if !here || !(names.is_empty() || names.contains(&entry.name)) {
+            index += 1;
+            continue;
+        }
+        let summary = json!({ "name": entry.name, "tool": entry.tool, "file": entry.file });
+        if entry.drift() == Some(DriftKind::Modified) && !force {
+            human!(
+                "  ! {} ({}): edited by hand in {}, kept",
+                entry.name,
+                entry.tool,
+                entry.file.display()
+            );
+            kept.push(summary);
+            index += 1;
+            continue;
+        }
+        human!("  - {} ({})", entry.name, entry.tool);
+        manifest.unapply(index)?;
+        removed.push(summary);
+    }
+    manifest.save()?;
+
+    human!();
+    if removed.is_empty() && kept.is_empty() {
+        human!("Nothing applied here. Pass --global for user-level entries.");
+    } else {
+        human!("Removed {}, kept {}.", removed.len(), kept.len());
+        if !kept.is_empty() {
+            human!("Re-run with --force to remove entries edited by hand.");
+        }
+    }
+    output::emit(&json!({ "removed": removed, "kept": kept }))
+}
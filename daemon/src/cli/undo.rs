@@ -0,0 +1,40 @@
+//! Restore the last destructive operation (TRASH-001).
+
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use crate::cli::output::{self, human};
+use crate::cli::{goaway, memory};
+use crate::config::find_project_root;
+use crate::error::{Error, ExitCode};
+use crate::trash::{self, Entry, Kind};
+
+/// Run the undo command. Returns exit code.
+pub fn run() -> Result<i32, Error> {
+    let cwd = std::env::current_dir()?;
+    let project_root = find_project_root(&cwd).unwrap_or(cwd);
+
+    let trash_dirs: Vec<PathBuf> = vec![trash::project_dir(&project_root), trash::global_dir()?];
+    let Some(entry) = Entry::latest(&trash_dirs, &project_root)? else {
+        human!("Nothing to undo.");
+        output::emit(&json!({ "restored": null }))?;
+        return Ok(ExitCode::Problems.code());
+    };
+
+    match entry.kind {
+        Kind::MemoryDelete => {
+            let id = memory::restore(&entry)?;
+            human!("Restored memory {}.", crate::storage::short_id(&id));
+        }
+        Kind::Goaway => {
+            goaway::restore(&entry)?;
+            human!("Restored .sqrl/ in {}.", project_root.display());
+            human!("Run 'sqrl apply' to re-register MCP servers.");
+        }
+    }
+
+    output::emit(&json!({ "restored": entry.summary, "kind": entry.kind }))?;
+    entry.remove()?;
+    Ok(0)
+}
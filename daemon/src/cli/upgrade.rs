@@ -0,0 +1,215 @@
+//! Self-update from GitHub releases (CLI-015).
+//!
+//! Release assets are `sqrl-<target>.tar.gz` (containing the `sqrl` binary)
+//! with a `sqrl-<target>.tar.gz.sha256` checksum next to it.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use semver::Version;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::cli::output::{self, human};
+use crate::error::Error;
+
+/// Release feed. `SQRL_UPDATE_URL` overrides it (mirrors, testing).
+const RELEASES_URL: &str = "https://api.github.com/repos/anthropics/squirrel/releases/latest";
+
+/// Longest wait to connect, and then between reads, so a stalled server
+/// fails the upgrade instead of hanging it.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Run the upgrade command. With `check`, only report whether an update exists.
+pub fn run(check: bool) -> Result<(), Error> {
+    let current =
+        Version::parse(env!("CARGO_PKG_VERSION")).map_err(|e| Error::Upgrade(e.to_string()))?;
+    let release = fetch_release()?;
+    let latest = parse_tag(&release.tag_name)?;
+    let update_available = latest > current;
+
+    output::emit(&json!({
+        "current": current.to_string(),
+        "latest": latest.to_string(),
+        "update_available": update_available,
+    }))?;
+    human!("Current version: {}", current);
+    human!("Latest version:  {}", latest);
+
+    if !update_available {
+        human!("sqrl is up to date.");
+        return Ok(());
+    }
+    if check {
+        human!("Run 'sqrl upgrade' to install {}.", latest);
+        return Ok(());
+    }
+
+    let archive_name = format!("sqrl-{}.tar.gz", target()?);
+    let archive_url = asset_url(&release, &archive_name)?;
+    let checksum_url = asset_url(&release, &format!("{}.sha256", archive_name))?;
+
+    human!("Downloading {}...", archive_name);
+    let archive = download(archive_url)?;
+    let expected = String::from_utf8_lossy(&download(checksum_url)?).to_string();
+    verify_checksum(&archive, &expected)?;
+
+    let exe = std::env::current_exe()?;
+    let staged = exe.with_extension("new");
+    extract_binary(&archive, &staged)?;
+    replace_exe(&staged, &exe)?;
+
+    human!("Upgraded sqrl {} -> {}", current, latest);
+    Ok(())
+}
+
+fn fetch_release() -> Result<Release, Error> {
+    let url = std::env::var("SQRL_UPDATE_URL").unwrap_or_else(|_| RELEASES_URL.to_string());
+    agent()
+        .get(&url)
+        .call()
+        .map_err(|e| Error::Upgrade(format!("Failed to check {}: {}", url, e)))?
+        .into_json()
+        .map_err(|e| Error::Upgrade(format!("Invalid release feed: {}", e)))
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout_connect(CONNECT_TIMEOUT)
+        .timeout_read(READ_TIMEOUT)
+        .user_agent(concat!("sqrl/", env!("CARGO_PKG_VERSION")))
+        .build()
+}
+
+fn parse_tag(tag: &str) -> Result<Version, Error> {
+    Version::parse(tag.trim_start_matches('v'))
+        .map_err(|e| Error::Upgrade(format!("Invalid release tag '{}': {}", tag, e)))
+}
+
+fn asset_url<'a>(release: &'a Release, name: &str) -> Result<&'a str, Error> {
+    release
+        .assets
+        .iter()
+        .find(|a| a.name == name)
+        .map(|a| a.browser_download_url.as_str())
+        .ok_or_else(|| {
+            Error::Upgrade(format!(
+                "Release {} has no asset '{}'",
+                release.tag_name, name
+            ))
+        })
+}
+
+fn download(url: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    agent()
+        .get(url)
+        .call()
+        .map_err(|e| Error::Upgrade(format!("Download failed: {}", e)))?
+        .into_reader()
+        .read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Rust target triple of release assets for this platform.
+fn target() -> Result<&'static str, Error> {
+    use std::env::consts::{ARCH, OS};
+    Ok(match (ARCH, OS) {
+        ("x86_64", "linux") => "x86_64-unknown-linux-gnu",
+        ("aarch64", "linux") => "aarch64-unknown-linux-gnu",
+        ("x86_64", "macos") => "x86_64-apple-darwin",
+        ("aarch64", "macos") => "aarch64-apple-darwin",
+        ("x86_64", "windows") => "x86_64-pc-windows-msvc",
+        _ => {
+            return Err(Error::Upgrade(format!(
+                "No release builds for {}-{}",
+                ARCH, OS
+            )))
+        }
+    })
+}
+
+/// Check data against a `sha256sum`-style line ("<hex>  <file>").
+fn verify_checksum(data: &[u8], expected: &str) -> Result<(), Error> {
+    let expected = expected
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let actual: String = Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    if actual != expected {
+        return Err(Error::Upgrade(format!(
+            "Checksum mismatch (expected {}, got {})",
+            expected, actual
+        )));
+    }
+    Ok(())
+}
+
+/// Extract the `sqrl` binary from a release archive to `dest`.
+fn extract_binary(archive: &[u8], dest: &Path) -> Result<(), Error> {
+    let name = if cfg!(windows) { "sqrl.exe" } else { "sqrl" };
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.file_name().and_then(|n| n.to_str()) == Some(name) {
+            entry.unpack(dest)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(dest, fs::Permissions::from_mode(0o755))?;
+            }
+            return Ok(());
+        }
+    }
+    Err(Error::Upgrade(format!("Archive does not contain {}", name)))
+}
+
+/// Swap the running executable for `staged`.
+/// Windows can't overwrite a running exe, so the old one is moved aside first.
+fn replace_exe(staged: &Path, exe: &Path) -> Result<(), Error> {
+    if cfg!(windows) {
+        let old: PathBuf = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old)?;
+    }
+    fs::rename(staged, exe)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_checksum_accepts_sha256sum_format() {
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_checksum(b"hello", &format!("{}  sqrl.tar.gz\n", digest)).is_ok());
+        assert!(verify_checksum(b"hullo", digest).is_err());
+    }
+
+    #[test]
+    fn test_parse_tag_strips_v_prefix() {
+        assert_eq!(parse_tag("v1.2.3").unwrap(), Version::new(1, 2, 3));
+        assert!(parse_tag("latest").is_err());
+    }
+}
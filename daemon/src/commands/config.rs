@@ -0,0 +1,104 @@
+//! `sqrl config`: the web UI, or a subcommand to read and write settings.
+
+use clap::{Args, Subcommand};
+
+use crate::config::format::ConfigFormat;
+use crate::{cli, web, Error};
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    /// Don't open browser automatically
+    #[arg(long)]
+    no_open: bool,
+
+    #[command(subcommand)]
+    cmd: Option<ConfigCommands>,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Show the effective project configuration
+    Show {
+        /// Show where each value came from
+        #[arg(long)]
+        resolved: bool,
+    },
+
+    /// Print a config value (e.g. docs.extensions)
+    Get {
+        key: String,
+
+        /// Read ~/.sqrl/config.yaml instead of the project config
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// Set a config value (e.g. hooks.auto_install false)
+    Set {
+        key: String,
+        value: String,
+
+        /// Write ~/.sqrl/config.yaml instead of the project config
+        #[arg(long)]
+        global: bool,
+
+        /// Write the uncommitted .sqrl/config.local.json overlay
+        #[arg(long, conflicts_with = "global")]
+        local: bool,
+    },
+
+    /// Print JSON Schema for the project config (for editor validation)
+    Schema {
+        /// Schema for ~/.sqrl/config.yaml instead
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// Switch the active global profile (lists profiles without a name)
+    Use {
+        /// Profile name from ~/.sqrl/config.yaml
+        name: Option<String>,
+
+        /// Deactivate the current profile
+        #[arg(long, conflicts_with = "name")]
+        none: bool,
+    },
+
+    /// Upgrade a config file to the current layout version
+    Migrate {
+        /// Migrate ~/.sqrl/config.yaml instead of the project config
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// Convert the project config file to another format
+    Convert {
+        /// Target format
+        #[arg(long, value_enum)]
+        to: ConfigFormat,
+    },
+}
+
+impl ConfigArgs {
+    pub async fn run(self) -> Result<i32, Error> {
+        let Some(cmd) = self.cmd else {
+            web::serve(!self.no_open).await?;
+            return Ok(0);
+        };
+        match cmd {
+            ConfigCommands::Show { resolved } => cli::config::show(resolved)?,
+            ConfigCommands::Get { key, global } => cli::config::get(&key, global)?,
+            ConfigCommands::Set {
+                key,
+                value,
+                global,
+                local,
+            } => cli::config::set(&key, &value, global, local)?,
+            ConfigCommands::Schema { global } => cli::config::schema(global)?,
+            ConfigCommands::Use { name, none } => cli::config::use_profile(name.as_deref(), none)?,
+            ConfigCommands::Migrate { global } => cli::config::migrate(global)?,
+            ConfigCommands::Convert { to } => cli::config::convert(to)?,
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,110 @@
+//! Moving memories and reading the store: `db`, `sync`, `share` and
+//! `projects`.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use crate::{cli, Error};
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Run one SQL statement, read-only unless --unsafe-writes
+    Query {
+        /// SQL statement, e.g. "SELECT id, content FROM memories"
+        sql: String,
+
+        /// Query the global store (~/.sqrl/memory.db) instead
+        #[arg(long)]
+        global: bool,
+
+        /// Allow statements that write (takes the lock; no undo)
+        #[arg(long)]
+        unsafe_writes: bool,
+    },
+
+    /// List state kept by subsystems (sync cursors and the like)
+    Meta {
+        /// Only this namespace, e.g. sync
+        #[arg(long)]
+        namespace: Option<String>,
+
+        /// List the global store's (~/.sqrl/memory.db) instead
+        #[arg(long)]
+        global: bool,
+    },
+}
+
+impl DbCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        match self {
+            DbCommands::Query {
+                sql,
+                global,
+                unsafe_writes,
+            } => cli::db::query(&sql, global, unsafe_writes)?,
+            DbCommands::Meta { namespace, global } => cli::db::meta(namespace.as_deref(), global)?,
+        }
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct SyncArgs {
+    /// Peer project directory or memory.db path, local or over ssh (host:path, ssh://host/path)
+    #[arg(long)]
+    peer: PathBuf,
+
+    /// Sync the global memory store (~/.sqrl/memory.db) instead
+    #[arg(long)]
+    global: bool,
+}
+
+impl SyncArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::sync::run(&self.peer, self.global)?;
+        Ok(0)
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ShareCommands {
+    /// Write tagged project memories into .sqrl/shared.json
+    Push {
+        /// Share memories with this tag (repeatable, default: team)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+
+    /// Merge .sqrl/shared.json into this project's memories
+    Pull,
+}
+
+impl ShareCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        match self {
+            ShareCommands::Push { tags } => cli::share::push(&tags)?,
+            ShareCommands::Pull => cli::share::pull()?,
+        }
+        Ok(0)
+    }
+}
+
+#[derive(Subcommand)]
+pub enum ProjectsCommands {
+    /// List registered projects
+    List,
+
+    /// Forget projects that no longer exist on disk
+    Prune,
+}
+
+impl ProjectsCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        match self {
+            ProjectsCommands::List => cli::projects::list()?,
+            ProjectsCommands::Prune => cli::projects::prune()?,
+        }
+        Ok(0)
+    }
+}
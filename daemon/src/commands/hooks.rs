@@ -0,0 +1,126 @@
+//! Git hooks and doc rules: `hook`, `rules`, and the hidden `_internal`
+//! commands the installed hooks call.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+
+use crate::cli::hooks::Hook;
+use crate::error::ExitCode;
+use crate::{cli, Error};
+
+#[derive(Subcommand)]
+pub enum HookCommands {
+    /// Run a hook's handler now, with debug logging
+    Run {
+        /// Hook to run
+        #[arg(value_enum)]
+        hook: Hook,
+
+        /// Commit to snapshot instead of HEAD (post-commit)
+        #[arg(long)]
+        commit: Option<String>,
+    },
+
+    /// Rewrite installed hooks written by an older version
+    Upgrade,
+}
+
+impl HookCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        match self {
+            HookCommands::Run { hook, commit } => cli::hooks::run(hook, commit.as_deref()),
+            HookCommands::Upgrade => cli::hooks::upgrade().map(|()| 0),
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum RulesCommands {
+    /// Classify example paths as doc or not, and say which setting decided
+    Test {
+        /// Config file to test (default: this project's effective config)
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// YAML or JSON list of {path, doc} expectations; fails on a mismatch
+        #[arg(long)]
+        fixtures: Option<PathBuf>,
+
+        /// Project-relative paths to classify
+        paths: Vec<String>,
+    },
+}
+
+impl RulesCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        let RulesCommands::Test {
+            config,
+            fixtures,
+            paths,
+        } = self;
+        cli::rules::test(config.as_deref(), fixtures.as_deref(), &paths)
+    }
+}
+
+#[derive(Subcommand)]
+pub enum InternalCommands {
+    /// Show diff summary before push (pre-push hook)
+    #[command(name = "docguard-check")]
+    DocguardCheck,
+
+    /// Record memory snapshots and refresh pinned memories (post-commit hook)
+    #[command(
+        name = "post-commit",
+        visible_alias = "docguard-record",
+        alias = "snapshot"
+    )]
+    PostCommit {
+        /// Record against this commit instead of HEAD (rebases, CI replays)
+        #[arg(long, value_name = "SHA")]
+        commit: Option<String>,
+    },
+
+    /// Regenerate files derived from memories after a branch switch (post-checkout hook)
+    #[command(name = "post-checkout")]
+    PostCheckout,
+
+    /// Merge two edits of .sqrl/shared.json by ID (git merge driver)
+    #[command(name = "merge-memories")]
+    MergeMemories {
+        /// Common ancestor (%O)
+        base: PathBuf,
+        /// Current branch's version, overwritten with the result (%A)
+        ours: PathBuf,
+        /// Other branch's version (%B)
+        theirs: PathBuf,
+    },
+
+    /// Launch an MCP server with placeholders resolved (used by tool configs)
+    #[command(name = "mcp-exec")]
+    McpExec {
+        /// MCP config name in ~/.sqrl/mcps/
+        name: String,
+    },
+}
+
+impl InternalCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        match self {
+            InternalCommands::MergeMemories { base, ours, theirs } => {
+                cli::internal::merge_memories(&base, &ours, &theirs)?;
+            }
+            InternalCommands::DocguardCheck => {
+                if !cli::internal::docguard_check()? {
+                    return Ok(ExitCode::PushBlocked.code());
+                }
+            }
+            InternalCommands::PostCommit { commit } => {
+                cli::internal::post_commit(commit.as_deref())?;
+            }
+            InternalCommands::PostCheckout => cli::internal::post_checkout()?,
+            InternalCommands::McpExec { name } => return cli::internal::mcp_exec(&name),
+        }
+        Ok(0)
+    }
+}
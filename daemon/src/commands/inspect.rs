@@ -0,0 +1,131 @@
+//! Looking at a project: `status`, `events`, `stats`, `doctor` and `grep`.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::{cli, Error};
+
+#[derive(Args)]
+pub struct StatusArgs {
+    /// Redraw every SECS seconds (default 2) until interrupted
+    #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "2")]
+    watch: Option<u64>,
+
+    /// One table for every registered project; exit nonzero if any is unhealthy
+    #[arg(long, conflicts_with = "watch")]
+    all: bool,
+
+    /// Print every exit code sqrl uses, with its meaning, as JSON
+    #[arg(long, conflicts_with_all = ["watch", "all"])]
+    explain_exit_codes: bool,
+
+    /// Print the project health score and its parts
+    #[arg(long, conflicts_with_all = ["watch", "all", "explain_exit_codes"])]
+    score: bool,
+}
+
+impl StatusArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        if self.explain_exit_codes {
+            cli::status::explain_exit_codes()?;
+        } else if self.score {
+            cli::health::run()?;
+        } else if self.all {
+            return cli::status::run_all();
+        } else if let Some(secs) = self.watch {
+            cli::status::watch(secs)?;
+        } else {
+            return cli::status::run();
+        }
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct EventsArgs {
+    /// Keep printing new events until interrupted
+    #[arg(long, short)]
+    follow: bool,
+
+    /// Start after this sequence number (default: all, or now with --follow)
+    #[arg(long, value_name = "SEQ")]
+    since: Option<i64>,
+
+    /// Read the global store (~/.sqrl/memory.db) instead
+    #[arg(long)]
+    global: bool,
+}
+
+impl EventsArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::events::run(self.since, self.follow, self.global)?;
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// One row per MCP client (name and version from its handshake)
+    #[arg(long)]
+    by_client: bool,
+
+    /// Commands, hook latencies and MCP tool calls (analytics.enabled)
+    #[arg(long, conflicts_with = "by_client")]
+    usage: bool,
+}
+
+impl StatsArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        if self.usage {
+            cli::stats::usage()?;
+        } else {
+            cli::stats::run(self.by_client)?;
+        }
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Also measure hook latency in this repo against its budgets
+    #[arg(long)]
+    bench: bool,
+
+    /// Also check hooks and managed MCP entries for tampering
+    #[arg(long)]
+    security: bool,
+
+    /// Write a sanitized diagnostics bundle to attach to a bug report
+    #[arg(long, value_name = "FILE")]
+    report: Option<Option<PathBuf>>,
+}
+
+impl DoctorArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        let report = self
+            .report
+            .map(|path| path.unwrap_or_else(cli::diagnostics::default_path));
+        cli::doctor::run(self.bench, self.security, report.as_deref())
+    }
+}
+
+#[derive(Args)]
+pub struct GrepArgs {
+    /// Regular expression matched against content and tags
+    pattern: String,
+
+    /// Only memories mentioning a file that matches this glob (e.g. 'src/auth/**')
+    #[arg(long)]
+    path: Option<String>,
+
+    /// Match case-insensitively
+    #[arg(short, long)]
+    ignore_case: bool,
+}
+
+impl GrepArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::grep::run(&self.pattern, self.path.as_deref(), self.ignore_case)
+    }
+}
@@ -0,0 +1,117 @@
+//! `sqrl mcp`: the MCP configs stored in `~/.sqrl/mcps/`.
+
+use clap::Subcommand;
+
+use crate::cli::mcp::ImportSource;
+use crate::global_config::Transport;
+use crate::{cli, Error};
+
+#[derive(Subcommand)]
+pub enum McpCommands {
+    /// List stored MCP configs
+    List,
+
+    /// Store an MCP server: sqrl mcp add <name> -- <command> [args...]
+    Add {
+        /// Config name (file name in ~/.sqrl/mcps/)
+        name: String,
+
+        /// Command and arguments that launch the server
+        #[arg(last = true, required_unless_present = "url", value_name = "COMMAND")]
+        command: Vec<String>,
+
+        /// URL of a remote server, instead of a command
+        #[arg(long, conflicts_with = "command")]
+        url: Option<String>,
+
+        /// Transport of the remote server at --url
+        #[arg(long, value_enum, requires = "url", default_value = "http")]
+        transport: Transport,
+
+        /// Where tools register it: project, user or local
+        #[arg(long, default_value = "project")]
+        scope: String,
+
+        /// Environment variable for the server (KEY=VALUE, repeatable)
+        #[arg(long, value_name = "KEY=VALUE")]
+        env: Vec<String>,
+
+        /// Directory to start the server in
+        #[arg(long, value_name = "DIR")]
+        cwd: Option<String>,
+
+        /// Replace an existing config with the same name
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Delete a stored MCP config
+    Remove {
+        /// Config name
+        name: String,
+    },
+
+    /// Edit a stored MCP config in $VISUAL or $EDITOR
+    Edit {
+        /// Config name
+        name: String,
+    },
+
+    /// Check stored MCP configs for missing fields, wrong types and bad scopes
+    Validate {
+        /// Config name (default: all)
+        name: Option<String>,
+    },
+
+    /// Launch an MCP server and check it answers the initialize handshake
+    Test {
+        /// Config name
+        name: String,
+    },
+
+    /// Import MCP servers from an existing tool configuration
+    Import {
+        /// Tool to import from
+        #[arg(long, value_enum)]
+        from: ImportSource,
+
+        /// Replace stored configs that differ from the imported ones
+        #[arg(long)]
+        overwrite: bool,
+    },
+}
+
+impl McpCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        match self {
+            McpCommands::List => cli::mcp::list()?,
+            McpCommands::Add {
+                name,
+                command,
+                url,
+                transport,
+                scope,
+                env,
+                cwd,
+                overwrite,
+            } => {
+                let remote = url.map(|url| (transport, url));
+                cli::mcp::add(
+                    &name,
+                    &command,
+                    remote,
+                    &scope,
+                    &env,
+                    cwd.as_deref(),
+                    overwrite,
+                )?;
+            }
+            McpCommands::Remove { name } => cli::mcp::remove(&name)?,
+            McpCommands::Edit { name } => cli::mcp::edit(&name)?,
+            McpCommands::Validate { name } => return cli::mcp::validate(name.as_deref()),
+            McpCommands::Test { name } => cli::mcp::test(&name)?,
+            McpCommands::Import { from, overwrite } => cli::mcp::import(from, overwrite)?,
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,179 @@
+//! `sqrl memory`: inspect and edit the memories stored in this project.
+
+use clap::Subcommand;
+
+use crate::storage::Visibility;
+use crate::{cli, Error};
+
+#[derive(Subcommand)]
+pub enum MemoryCommands {
+    /// List memories with their short IDs
+    List {
+        /// Only memories of this type (preference, project)
+        #[arg(long = "type")]
+        memory_type: Option<String>,
+
+        /// Only memories with this visibility
+        #[arg(long, value_enum)]
+        visibility: Option<Visibility>,
+
+        /// Only memories MCP clients rated unhelpful more often than useful
+        #[arg(long)]
+        unhelpful: bool,
+
+        /// The memories at a date (YYYY-MM-DD or RFC 3339) or git revision instead
+        #[arg(long, value_name = "DATE|REV", conflicts_with = "unhelpful")]
+        as_of: Option<String>,
+    },
+
+    /// Show a memory by ID or unambiguous prefix
+    Show {
+        /// Memory ID or prefix (at least 4 characters)
+        id: String,
+
+        /// Also list the MCP calls that stored, reinforced, or rated it
+        #[arg(long)]
+        trace: bool,
+    },
+
+    /// Show the memories that were active at a commit (needs hooks.snapshots)
+    At {
+        /// Commit, branch, or tag (e.g. HEAD~3)
+        rev: String,
+    },
+
+    /// Offer bullets from CLAUDE.md and AGENTS.md as memories
+    Import {
+        /// Import every new bullet without asking
+        #[arg(long)]
+        yes: bool,
+    },
+
+    /// Check memories for missing files, contradictions, and other problems
+    Lint {
+        /// Repair problems that don't change meaning (whitespace, repeated tags)
+        #[arg(long)]
+        fix: bool,
+        /// Also flag memories without tags
+        #[arg(long)]
+        untagged: bool,
+    },
+
+    /// Drop old MCP activity and snapshots (retention settings) and shrink memory.db
+    Compact,
+
+    /// Find memories containing secrets the redaction rules would remove
+    ScanSecrets {
+        /// Redact them in place
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Delete a memory (restorable with 'sqrl undo')
+    Delete {
+        /// Memory ID or prefix (at least 4 characters)
+        id: String,
+    },
+
+    /// Keep a memory in the pinned section of CLAUDE.md (context.section)
+    Pin {
+        /// Memory ID or prefix (at least 4 characters)
+        id: String,
+    },
+
+    /// Take a memory out of the pinned section
+    Unpin {
+        /// Memory ID or prefix (at least 4 characters)
+        id: String,
+    },
+
+    /// Set whether a memory stays on this machine (private) or may be synced and shared
+    Visibility {
+        /// Memory ID or prefix (at least 4 characters)
+        id: String,
+
+        #[arg(value_enum)]
+        level: Visibility,
+    },
+
+    /// Scope a memory to a subdirectory of a monorepo, for MCP retrieval
+    Namespace {
+        /// Memory ID or prefix (at least 4 characters)
+        #[arg(required_unless_present = "derive")]
+        id: Option<String>,
+
+        /// Subdirectory, e.g. services/billing
+        #[arg(required_unless_present_any = ["clear", "derive"], conflicts_with = "clear")]
+        path: Option<String>,
+
+        /// Make the memory apply to the whole project again
+        #[arg(long)]
+        clear: bool,
+
+        /// Scope every unscoped project memory by the paths it mentions (`namespaces`)
+        #[arg(long, conflicts_with_all = ["id", "clear"])]
+        derive: bool,
+    },
+
+    /// List memories from MCP clients awaiting approval (mcp.require_approval)
+    Pending,
+
+    /// Store a pending memory
+    Approve {
+        /// Pending memory ID or prefix (at least 4 characters)
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        id: Option<String>,
+
+        /// Approve every pending memory
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Discard a pending memory
+    Reject {
+        /// Pending memory ID or prefix (at least 4 characters)
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        id: Option<String>,
+
+        /// Reject every pending memory
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+impl MemoryCommands {
+    pub fn run(self) -> Result<i32, Error> {
+        use cli::memory;
+        match self {
+            MemoryCommands::List {
+                memory_type,
+                visibility,
+                unhelpful,
+                as_of,
+            } => memory::list(
+                memory_type.as_deref(),
+                visibility,
+                unhelpful,
+                as_of.as_deref(),
+            )?,
+            MemoryCommands::Show { id, trace } => memory::show(&id, trace)?,
+            MemoryCommands::At { rev } => return memory::at(&rev),
+            MemoryCommands::Import { yes } => memory::import(yes)?,
+            MemoryCommands::Lint { fix, untagged } => return memory::lint(fix, untagged),
+            MemoryCommands::Compact => memory::compact()?,
+            MemoryCommands::ScanSecrets { fix } => return memory::scan_secrets(fix),
+            MemoryCommands::Delete { id } => memory::delete(&id)?,
+            MemoryCommands::Pin { id } => memory::pin(&id, true)?,
+            MemoryCommands::Unpin { id } => memory::pin(&id, false)?,
+            MemoryCommands::Visibility { id, level } => memory::set_visibility(&id, level)?,
+            MemoryCommands::Namespace {
+                id: Some(id), path, ..
+            } => memory::set_namespace(&id, path.as_deref())?,
+            MemoryCommands::Namespace { .. } => memory::derive_namespaces()?,
+            MemoryCommands::Pending => memory::pending()?,
+            MemoryCommands::Approve { id, .. } => memory::approve(id.as_deref())?,
+            MemoryCommands::Reject { id, .. } => memory::reject(id.as_deref())?,
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,174 @@
+//! Command-line syntax and dispatch. Each subcommand's arguments live with
+//! the group they belong to and hand off to the matching `cli` module.
+
+mod config;
+mod data;
+mod hooks;
+mod inspect;
+mod mcp;
+mod memory;
+mod project;
+
+use clap::{CommandFactory, Subcommand};
+
+use crate::{cli, web, Cli, Error};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Open web UI for global configuration, or inspect config
+    Config(config::ConfigArgs),
+
+    /// Guided first-run setup: detect tools, write global config, init this repo
+    Setup(project::SetupArgs),
+
+    /// Initialize Squirrel for this project
+    Init(project::InitArgs),
+
+    /// Import memories from another tool's rule file or memory store
+    Import(project::ImportArgs),
+
+    /// Apply global MCP configs to current project
+    Apply(project::ApplyArgs),
+
+    /// Remove MCP entries sqrl apply wrote into tool configs
+    Unapply(project::UnapplyArgs),
+
+    /// Remove all Squirrel data from this project
+    Goaway(project::GoawayArgs),
+
+    /// Show Squirrel status
+    Status(inspect::StatusArgs),
+
+    /// Serve the web UI and MCP over HTTP from one process until interrupted
+    Serve {
+        /// Port to listen on (default: ui.port from the global config)
+        #[arg(long)]
+        port: Option<u16>,
+    },
+
+    /// Print memory, rating and proposal changes as JSON lines
+    Events(inspect::EventsArgs),
+
+    /// Show memory counts and MCP usage
+    Stats(inspect::StatsArgs),
+
+    /// Summarize memory, MCP, and git activity over a period, as Markdown
+    Report {
+        /// Start of the period: 7d, 2w, 12h, or a date (2026-01-31)
+        #[arg(long, default_value = "7d")]
+        since: String,
+    },
+
+    /// Query the memory database or list its metadata
+    Db {
+        #[command(subcommand)]
+        cmd: data::DbCommands,
+    },
+
+    /// Check configuration for problems
+    Doctor(inspect::DoctorArgs),
+
+    /// Run git hook handlers by hand, to see why they did what they did
+    Hook {
+        #[command(subcommand)]
+        cmd: hooks::HookCommands,
+    },
+
+    /// Check which paths the doc settings treat as docs
+    Rules {
+        #[command(subcommand)]
+        cmd: hooks::RulesCommands,
+    },
+
+    /// Manage MCP configs in ~/.sqrl/mcps/
+    Mcp {
+        #[command(subcommand)]
+        cmd: mcp::McpCommands,
+    },
+
+    /// Restore what the last destructive command removed
+    Undo,
+
+    /// Browse, search, and edit memories in a terminal UI
+    Ui,
+
+    /// Search memories by regex
+    Grep(inspect::GrepArgs),
+
+    /// Merge memories with another copy of this project
+    Sync(data::SyncArgs),
+
+    /// Share tagged memories with the team via .sqrl/shared.json
+    Share {
+        #[command(subcommand)]
+        cmd: data::ShareCommands,
+    },
+
+    /// Inspect memories stored in this project
+    Memory {
+        #[command(subcommand)]
+        cmd: memory::MemoryCommands,
+    },
+
+    /// List projects initialized with Squirrel
+    Projects {
+        #[command(subcommand)]
+        cmd: data::ProjectsCommands,
+    },
+
+    /// Update sqrl to the latest release
+    Upgrade {
+        /// Only report whether an update is available
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Start MCP server (called by AI tool config, not user)
+    #[command(name = "mcp-serve")]
+    McpServe,
+
+    /// Internal commands (used by git hooks)
+    #[command(hide = true, name = "_internal")]
+    Internal {
+        #[command(subcommand)]
+        cmd: hooks::InternalCommands,
+    },
+}
+
+/// Dispatch a command on the shared runtime. Returns the process exit code.
+pub async fn run(command: Option<Commands>) -> Result<i32, Error> {
+    let Some(command) = command else {
+        Cli::command().print_help().unwrap();
+        println!();
+        return Ok(0);
+    };
+    match command {
+        Commands::Config(args) => args.run().await,
+        Commands::Serve { port } => web::serve_all(port).await.map(|()| 0),
+        Commands::Setup(args) => args.run(),
+        Commands::Init(args) => args.run(),
+        Commands::Import(args) => args.run(),
+        Commands::Apply(args) => args.run(),
+        Commands::Unapply(args) => args.run(),
+        Commands::Goaway(args) => args.run(),
+        Commands::Events(args) => args.run(),
+        Commands::Status(args) => args.run(),
+        Commands::Stats(args) => args.run(),
+        Commands::Report { since } => cli::report::run(&since).map(|()| 0),
+        Commands::Db { cmd } => cmd.run(),
+        Commands::Doctor(args) => args.run(),
+        Commands::Hook { cmd } => cmd.run(),
+        Commands::Rules { cmd } => cmd.run(),
+        Commands::Mcp { cmd } => cmd.run(),
+        Commands::Undo => cli::undo::run(),
+        Commands::Share { cmd } => cmd.run(),
+        Commands::Ui => cli::ui::run().map(|()| 0),
+        Commands::Grep(args) => args.run(),
+        Commands::Sync(args) => args.run(),
+        Commands::Memory { cmd } => cmd.run(),
+        Commands::Projects { cmd } => cmd.run(),
+        Commands::Upgrade { check } => cli::upgrade::run(check).map(|()| 0),
+        Commands::McpServe => crate::mcp::run().map(|()| 0),
+        Commands::Internal { cmd } => cmd.run(),
+    }
+}
@@ -0,0 +1,164 @@
+//! Setting a project up and taking it down: `setup`, `init`, `import`,
+//! `apply`, `unapply` and `goaway`.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::cli::import::{Columns, ImportSource};
+use crate::cli::init::{InitOptions, Preset, Template};
+use crate::{cli, Error};
+
+#[derive(Args)]
+pub struct SetupArgs {
+    /// Don't prompt; enable detected tools and initialize this repo
+    #[arg(long, short)]
+    yes: bool,
+
+    /// Don't offer to initialize the current repo
+    #[arg(long)]
+    no_init: bool,
+}
+
+impl SetupArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::setup::run(self.yes, self.no_init)?;
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// Don't prompt; use --preset or the full preset
+    #[arg(long, short)]
+    yes: bool,
+
+    /// What to set up
+    #[arg(long, value_enum)]
+    preset: Option<Preset>,
+
+    /// Skip git hook installation
+    #[arg(long)]
+    no_hooks: bool,
+
+    /// Skip MCP registration
+    #[arg(long)]
+    no_mcp: bool,
+
+    /// Fix a partial install (missing hooks, tables, gitignore entries)
+    #[arg(long)]
+    repair: bool,
+
+    /// Doc settings for the project's ecosystem (auto: detect from its manifest)
+    #[arg(long, value_enum)]
+    template: Option<Template>,
+}
+
+impl InitArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::init::run(InitOptions {
+            yes: self.yes,
+            preset: self.preset,
+            no_hooks: self.no_hooks,
+            no_mcp: self.no_mcp,
+            repair: self.repair,
+            template: self.template,
+        })?;
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Format of the file
+    #[arg(long, value_enum)]
+    from: ImportSource,
+
+    /// File to import, e.g. .cursorrules, CLAUDE.md, .editorconfig, or an old memory.db
+    path: PathBuf,
+
+    /// SQLite table, or JSON key, holding the memories (sqlite and json)
+    #[arg(long, default_value = "memories")]
+    table: String,
+
+    /// Column holding the memory text (sqlite and json)
+    #[arg(long, value_name = "COLUMN", default_value = "content")]
+    content_column: String,
+
+    /// Column holding the memory type (sqlite and json)
+    #[arg(long, value_name = "COLUMN")]
+    type_column: Option<String>,
+
+    /// Column holding tags, as a JSON array or comma-separated (sqlite and json)
+    #[arg(long, value_name = "COLUMN")]
+    tags_column: Option<String>,
+
+    /// Show what would be imported without storing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl ImportArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        let columns = Columns {
+            table: self.table,
+            content: self.content_column,
+            memory_type: self.type_column,
+            tags: self.tags_column,
+        };
+        cli::import::run(self.from, &self.path, &columns, self.dry_run)?;
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct ApplyArgs {
+    /// Only report what is applied, drifted or missing; exit 1 if out of date
+    #[arg(long)]
+    check: bool,
+}
+
+impl ApplyArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        if self.check {
+            return cli::apply::check();
+        }
+        cli::apply::run()?;
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct UnapplyArgs {
+    /// MCP names (default: all)
+    names: Vec<String>,
+
+    /// Also remove user-level entries, not only those applied here
+    #[arg(long)]
+    global: bool,
+
+    /// Remove entries even if they were edited by hand
+    #[arg(long, short)]
+    force: bool,
+}
+
+impl UnapplyArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::unapply::run(&self.names, self.global, self.force)?;
+        Ok(0)
+    }
+}
+
+#[derive(Args)]
+pub struct GoawayArgs {
+    /// Skip confirmation prompt
+    #[arg(long, short)]
+    force: bool,
+}
+
+impl GoawayArgs {
+    pub fn run(self) -> Result<i32, Error> {
+        cli::goaway::run(self.force)?;
+        Ok(0)
+    }
+}
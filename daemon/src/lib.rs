@@ -4,13 +4,12 @@
 //! Single binary. No daemon. No AI. Just storage + git hooks.
 
 pub mod cli;
-pub mod config;
-pub mod error;
-pub mod global_config;
 pub mod mcp;
-pub mod storage;
+pub mod trash;
 pub mod web;
 
+pub use sqrl_core::{atomic, config, docs, error, global_config, lock, paths, storage, walk};
+
 pub use config::Config;
 pub use error::Error;
 pub use global_config::GlobalConfig;
@@ -2,15 +2,20 @@
 //!
 //! Single binary. No daemon. No AI. Just storage + git hooks.
 
-use clap::{Parser, Subcommand};
-use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+use std::time::Instant;
+
+use clap::{CommandFactory, FromArgMatches, Parser};
+use sqrl_core::{atomic, config, docs, error, global_config, lock, paths, storage, walk};
+use tracing::info;
+
+use cli::output::ColorChoice;
+use commands::Commands;
+use storage::usage::UsageKind;
 
 mod cli;
-mod config;
-mod error;
-mod global_config;
+mod commands;
 mod mcp;
-mod storage;
+mod trash;
 mod web;
 
 pub use error::Error;
@@ -21,104 +26,85 @@ pub use global_config::GlobalConfig;
 #[command(about = "Squirrel - local-first memory system for AI coding tools")]
 #[command(version)]
 struct Cli {
+    /// Override a config value for this run (repeatable)
+    #[arg(
+        short = 'c',
+        long = "override",
+        global = true,
+        value_name = "KEY=VALUE"
+    )]
+    overrides: Vec<String>,
+
+    /// Print structured JSON on stdout (human text goes to stderr)
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// When to use colors (honors NO_COLOR in auto)
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Only print errors and command results
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Show more log output (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Also write logs to ~/.sqrl/logs/cli.log (always on with log.file)
+    #[arg(long, global = true)]
+    log_file: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
-#[derive(Subcommand)]
-enum Commands {
-    /// Open web UI for global configuration
-    Config {
-        /// Don't open browser automatically
-        #[arg(long)]
-        no_open: bool,
-    },
-
-    /// Initialize Squirrel for this project
-    Init,
-
-    /// Apply global MCP configs to current project
-    Apply,
-
-    /// Remove all Squirrel data from this project
-    Goaway {
-        /// Skip confirmation prompt
-        #[arg(long, short)]
-        force: bool,
-    },
-
-    /// Show Squirrel status
-    Status,
-
-    /// Start MCP server (called by AI tool config, not user)
-    #[command(name = "mcp-serve")]
-    McpServe,
-
-    /// Internal commands (used by git hooks)
-    #[command(hide = true, name = "_internal")]
-    Internal {
-        #[command(subcommand)]
-        cmd: InternalCommands,
-    },
-}
-
-#[derive(Subcommand)]
-enum InternalCommands {
-    /// Show diff summary before push (pre-push hook)
-    #[command(name = "docguard-check")]
-    DocguardCheck,
-}
-
-fn main() -> Result<(), Error> {
-    // Initialize logging
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(EnvFilter::from_default_env().add_directive("sqrl=info".parse().unwrap()))
-        .init();
-
-    let cli = Cli::parse();
-
-    match cli.command {
-        None => {
-            use clap::CommandFactory;
-            Cli::command().print_help().unwrap();
-            println!();
+#[tokio::main]
+async fn main() {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    cli::output::init(cli.json, cli.quiet, cli.color);
+
+    // Hook runs exist to show the handlers' reasoning, so log at debug
+    let verbose = match cli.command {
+        Some(Commands::Hook { .. }) => cli.verbose.max(2),
+        _ => cli.verbose,
+    };
+    cli::logging::init(cli.quiet, verbose, cli.log_file);
+    info!(args = ?std::env::args().skip(1).collect::<Vec<_>>(), "sqrl invoked");
+
+    config::resolve::set_flag_overrides(cli.overrides);
+
+    let started = Instant::now();
+    let exit_code = match commands::run(cli.command).await {
+        Ok(code) => code,
+        Err(e) => {
+            cli::output::report_error(&e);
+            e.exit_code().code()
         }
-        Some(Commands::Config { no_open }) => {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                if let Err(e) = web::serve(!no_open).await {
-                    eprintln!("Error: {}", e);
-                    std::process::exit(1);
-                }
-            });
+    };
+    if let Some((kind, name)) = usage_of(&matches) {
+        if let Ok(root) = config::project_root() {
+            storage::usage::record(&root, kind, &name, started.elapsed());
         }
-        Some(Commands::Init) => {
-            cli::init::run()?;
-        }
-        Some(Commands::Apply) => {
-            cli::apply::run()?;
-        }
-        Some(Commands::Goaway { force }) => {
-            cli::goaway::run(force)?;
-        }
-        Some(Commands::Status) => {
-            let exit_code = cli::status::run()?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-        }
-        Some(Commands::McpServe) => {
-            mcp::run()?;
-        }
-        Some(Commands::Internal { cmd }) => match cmd {
-            InternalCommands::DocguardCheck => {
-                if !cli::internal::docguard_check()? {
-                    std::process::exit(1);
-                }
-            }
-        },
     }
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+}
 
-    Ok(())
+/// How a run is logged by usage analytics: hook handlers by hook, anything
+/// else by its subcommand path. None for servers, which run for a session.
+fn usage_of(matches: &clap::ArgMatches) -> Option<(UsageKind, String)> {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    match names.as_slice() {
+        [] | ["mcp-serve"] | ["serve"] | ["config"] | ["_internal", "mcp-exec"] => None,
+        ["_internal", hook] => Some((UsageKind::Hook, hook.to_string())),
+        _ => Some((UsageKind::Command, names.join(" "))),
+    }
 }
@@ -0,0 +1,82 @@
+//! Reuse of `squirrel_get_memory` responses (MCP-002).
+//!
+//! Clients that fetch context before every prompt ask the same question over
+//! and over. A response is kept with the generation of the project database's
+//! change feed (`storage::changes`), and served again until any write, from
+//! this server or any other process, logs a change.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use tracing::debug;
+
+use crate::error::Error;
+use crate::storage;
+
+/// Responses kept; the cache starts over when full.
+const MAX_ENTRIES: usize = 64;
+
+/// Responses by request key, with the generation each was built at.
+static ENTRIES: Mutex<BTreeMap<String, (i64, String)>> = Mutex::new(BTreeMap::new());
+
+/// The response for `key` in `project_root`, from the cache while the store
+/// is unchanged, else from `build`.
+pub(super) fn context(
+    project_root: &Path,
+    key: String,
+    build: impl FnOnce() -> Result<String, Error>,
+) -> Result<String, Error> {
+    let Some(generation) = storage::changes::generation(project_root)? else {
+        return build();
+    };
+    let lock = || ENTRIES.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((built_at, text)) = lock().get(&key) {
+        if *built_at == generation {
+            debug!(generation, "Context served from cache");
+            return Ok(text.clone());
+        }
+    }
+    // Built after the generation was read, so a write in between only costs a rebuild
+    let text = build()?;
+    let mut entries = lock();
+    if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+        entries.clear();
+    }
+    entries.insert(key, (generation, text.clone()));
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::activity::Action;
+    use crate::storage::Storage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_context_is_rebuilt_after_writes() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".sqrl")).unwrap();
+        let storage = Storage::open(&storage::db_path(dir.path())).unwrap();
+        let builds = std::cell::Cell::new(0);
+        let get = || {
+            context(dir.path(), "all".to_string(), || {
+                builds.set(builds.get() + 1);
+                Ok(builds.get().to_string())
+            })
+            .unwrap()
+        };
+        assert_eq!(get(), "1");
+        assert_eq!(get(), "1");
+
+        let id = storage.store_memory("project", "Use pnpm", &[]).unwrap().id;
+        assert_eq!(get(), "2");
+        storage.rate_memory(&id, false).unwrap();
+        assert_eq!(get(), "3");
+        storage
+            .record_activity("t", "1", Action::Get, None)
+            .unwrap();
+        assert_eq!(get(), "3");
+    }
+}
@@ -0,0 +1,249 @@
+//! Concurrent tool calls (CLI-007).
+//!
+//! The stdin loop answers `initialize` and `tools/list` itself and hands each
+//! `tools/call` to its own thread, so a slow retrieval doesn't hold up the
+//! next message. At most `MAX_CONCURRENT_CALLS` handlers run at once; later
+//! calls wait for a slot, and the wait counts against their timeout. A call
+//! that outlasts its tool's timeout is answered with an error, and one the client cancels (`notifications/cancelled`) is
+//! not answered at all, as MCP asks. Handlers can't be interrupted, so either
+//! way the work finishes in the background and its result is dropped.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::{get_project_root, handle_request, JsonRpcRequest, JsonRpcResponse, Session};
+use crate::error::Error;
+use crate::storage::usage::{self, UsageKind};
+
+/// Tool handlers running at once.
+const MAX_CONCURRENT_CALLS: usize = 4;
+
+/// How long a tool may take before the client gets an error instead.
+fn timeout(tool: &str) -> Duration {
+    match tool {
+        // Runs `memory_created` plugins, which have their own timeouts
+        "squirrel_store_memory" => Duration::from_secs(30),
+        "squirrel_get_memory" => Duration::from_secs(10),
+        // Reads every listed doc to find mentions
+        "squirrel_get_doc_for_code" => Duration::from_secs(10),
+        _ => Duration::from_secs(5),
+    }
+}
+
+/// Lock a mutex, carrying on if a handler panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Shared by the stdin loop and the call threads of one server.
+pub(super) struct Dispatcher {
+    session: Mutex<Session>,
+    /// Requests still owed a response, by serialized ID.
+    pending: Mutex<HashSet<String>>,
+    /// Handlers running now.
+    running: Mutex<usize>,
+    freed: Condvar,
+    calls: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Dispatcher {
+    pub(super) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            session: Mutex::new(Session::default()),
+            pending: Mutex::new(HashSet::new()),
+            running: Mutex::new(0),
+            freed: Condvar::new(),
+            calls: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Answer `request`: tool calls on their own thread, anything else now.
+    pub(super) fn dispatch(self: &Arc<Self>, request: JsonRpcRequest) -> Result<(), Error> {
+        if request.method != "tools/call" {
+            let response = handle_request(&request, &self.session);
+            return self.write(&response);
+        }
+        let id = request.id.clone().unwrap_or(Value::Null);
+        lock(&self.pending).insert(id.to_string());
+        let dispatcher = Arc::clone(self);
+        let call = thread::spawn(move || {
+            let response = dispatcher.call(request);
+            if lock(&dispatcher.pending).remove(&id.to_string()) {
+                if let Err(e) = dispatcher.write(&response) {
+                    warn!(error = %e, "Could not write MCP response");
+                }
+            }
+        });
+        let mut calls = lock(&self.calls);
+        calls.retain(|call| !call.is_finished());
+        calls.push(call);
+        Ok(())
+    }
+
+    /// Answer `request` on this thread, for transports with a response per
+    /// request (HTTP). Tool calls still take a slot and time out.
+    pub(super) fn answer(self: &Arc<Self>, request: JsonRpcRequest) -> JsonRpcResponse {
+        if request.method == "tools/call" {
+            self.call(request)
+        } else {
+            handle_request(&request, &self.session)
+        }
+    }
+
+    /// Drop the response owed for `params.requestId`, if any.
+    pub(super) fn cancel(&self, params: &Value) {
+        let Some(id) = params.get("requestId") else {
+            return;
+        };
+        if lock(&self.pending).remove(&id.to_string()) {
+            let reason = params.get("reason").and_then(Value::as_str);
+            debug!(request = %id, reason, "MCP call cancelled");
+        }
+    }
+
+    /// Wait for calls still running, so their responses go out before exit.
+    pub(super) fn finish(&self) {
+        let calls = std::mem::take(&mut *lock(&self.calls));
+        for call in calls {
+            let _ = call.join();
+        }
+    }
+
+    /// Run one tool call in a slot, giving up after the tool's timeout.
+    fn call(self: &Arc<Self>, request: JsonRpcRequest) -> JsonRpcResponse {
+        let tool = request
+            .params
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let limit = timeout(&tool);
+        self.call_within(request, tool, limit)
+    }
+
+    /// Run one tool call in a slot, giving up `limit` after it was made,
+    /// however much of that went to waiting for the slot.
+    fn call_within(
+        self: &Arc<Self>,
+        request: JsonRpcRequest,
+        tool: String,
+        limit: Duration,
+    ) -> JsonRpcResponse {
+        let id = request.id.clone().unwrap_or(Value::Null);
+        let deadline = Instant::now() + limit;
+        let timed_out = |id: Value| {
+            warn!(tool, seconds = limit.as_secs(), "MCP call timed out");
+            JsonRpcResponse::error(
+                id,
+                -32000,
+                format!("{} timed out after {}s", tool, limit.as_secs()),
+            )
+        };
+
+        let Some(slot) = self.acquire(deadline) else {
+            return timed_out(id);
+        };
+        let (tx, rx) = mpsc::channel();
+        let name = tool.clone();
+        thread::spawn(move || {
+            let started = Instant::now();
+            let response = handle_request(&request, &slot.0.session);
+            drop(slot);
+            let elapsed = started.elapsed();
+            let _ = tx.send(response);
+            if let Ok(root) = get_project_root(&request.params) {
+                usage::record(&root, UsageKind::Tool, &name, elapsed);
+            }
+        });
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(response) => response,
+            Err(RecvTimeoutError::Timeout) => timed_out(id),
+            // The handler panicked before answering
+            Err(RecvTimeoutError::Disconnected) => {
+                JsonRpcResponse::error(id, -32603, format!("{} failed", tool))
+            }
+        }
+    }
+
+    /// Wait for a free slot until `deadline` and take it. `None` when every
+    /// slot is still taken then, e.g. by handlers that hang.
+    fn acquire(self: &Arc<Self>, deadline: Instant) -> Option<Slot> {
+        let mut running = lock(&self.running);
+        while *running >= MAX_CONCURRENT_CALLS {
+            let left = deadline.checked_duration_since(Instant::now())?;
+            running = self
+                .freed
+                .wait_timeout(running, left)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        *running += 1;
+        Some(Slot(Arc::clone(self)))
+    }
+
+    /// Write one response line. Lines from different threads never interleave.
+    pub(super) fn write(&self, response: &JsonRpcResponse) -> Result<(), Error> {
+        let line = serde_json::to_string(response)?;
+        debug!(response = %line, "MCP response");
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", line)?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+/// A running handler's place among `MAX_CONCURRENT_CALLS`, given back when
+/// dropped, even by a panicking handler.
+struct Slot(Arc<Dispatcher>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        *lock(&self.0.running) -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_behind_hung_handlers_times_out() {
+        let dispatcher = Dispatcher::new();
+        // Slots held as by handlers that never finish
+        let far = Instant::now() + Duration::from_secs(60);
+        let hung: Vec<Slot> = (0..MAX_CONCURRENT_CALLS)
+            .map(|_| dispatcher.acquire(far).unwrap())
+            .collect();
+        let request: JsonRpcRequest = serde_json::from_value(serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "tools/call",
+            "params": {"name": "squirrel_get_memory", "arguments": {}},
+            "id": 7,
+        }))
+        .unwrap();
+
+        let started = Instant::now();
+        let response = dispatcher.call_within(
+            request,
+            "squirrel_get_memory".to_string(),
+            Duration::from_millis(100),
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(response.id, serde_json::json!(7));
+        let error = response.error.unwrap();
+        assert_eq!(error.code, -32000);
+        assert!(error.message.contains("timed out"));
+
+        // Freed slots are taken again
+        drop(hung);
+        assert!(dispatcher.acquire(Instant::now()).is_some());
+    }
+}
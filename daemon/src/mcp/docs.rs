@@ -0,0 +1,172 @@
+//! Docs for a code path (`squirrel_get_doc_for_code`, MCP-004).
+//!
+//! There are no code-to-doc mappings, so docs are found two ways: doc files
+//! in the path's directory and each directory above it, nearest first, then
+//! listed docs (CONFIG-006) whose text mentions the path. Ignored files
+//! (WALK-001) are left out of both. Project memories scoped to a directory
+//! holding the path, or mentioning it, come with them.
+
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::config::matcher::DocMatcher;
+use crate::config::Config;
+use crate::docs::{find_doc_files, mentions};
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::paths;
+use crate::storage::{self, lint, Memory};
+use crate::walk;
+
+/// Most docs listed per response.
+const MAX_DOCS: usize = 20;
+
+/// Why a doc was listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reason {
+    SameDirectory,
+    ParentDirectory,
+    Mentions,
+}
+
+impl Reason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Reason::SameDirectory => "same directory",
+            Reason::ParentDirectory => "parent directory",
+            Reason::Mentions => "mentions it",
+        }
+    }
+}
+
+/// Handle squirrel_get_doc_for_code.
+pub(super) fn handle(params: &Value) -> Result<Value, Error> {
+    let args = params.get("arguments").unwrap_or(params);
+    let path = args
+        .get("path")
+        .and_then(|p| p.as_str())
+        .map(|p| paths::normalize(p).trim_end_matches('/').to_string())
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| Error::Mcp("Missing 'path' parameter".to_string()))?;
+
+    let project_root = super::get_project_root(params)?;
+    let config = if Config::exists(&project_root) {
+        Config::effective(&project_root)?
+    } else {
+        Config::default()
+    };
+    let matcher = config.doc_matcher()?;
+    let docs = docs_for(&project_root, &matcher, &path);
+    let memories = memories_for(&project_root, &path)?;
+
+    let mut text = String::new();
+    if docs.is_empty() {
+        text.push_str(&format!("No docs found for {}.\n", path));
+    } else {
+        text.push_str(&format!("## Docs for {}\n", path));
+        for (doc, reason) in &docs {
+            text.push_str(&format!("- {} ({})\n", doc, reason.as_str()));
+        }
+    }
+    if !memories.is_empty() {
+        text.push_str(&format!("\n## Memories about {}\n", path));
+        for memory in &memories {
+            text.push_str(&format!(
+                "- {} {}\n",
+                storage::short_id(&memory.id),
+                memory.content
+            ));
+        }
+    }
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": text.trim_end()
+        }]
+    }))
+}
+
+/// Docs near `path`, nearest directory first, then listed docs mentioning it.
+fn docs_for(project_root: &Path, matcher: &DocMatcher, path: &str) -> Vec<(String, Reason)> {
+    let mut found: Vec<(String, Reason)> = Vec::new();
+    let mut dir = Path::new(path).parent();
+    let mut reason = Reason::SameDirectory;
+    while let Some(current) = dir {
+        let here: Vec<String> = walk::files(project_root, &current.to_string_lossy(), Some(1))
+            .into_iter()
+            .filter(|doc| doc != path && matcher.is_doc(doc))
+            .collect();
+        found.extend(here.into_iter().map(|doc| (doc, reason)));
+        reason = Reason::ParentDirectory;
+        dir = current.parent();
+    }
+
+    for doc in find_doc_files(project_root, matcher) {
+        if doc == path || found.iter().any(|(d, _)| *d == doc) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(project_root.join(&doc)) else {
+            continue;
+        };
+        if mentions(&content, path) {
+            found.push((doc, Reason::Mentions));
+        }
+    }
+    found.truncate(MAX_DOCS);
+    found
+}
+
+/// Project memories scoped to a directory holding `path` or mentioning it,
+/// best ranked first.
+fn memories_for(project_root: &Path, path: &str) -> Result<Vec<Memory>, Error> {
+    let profile = GlobalConfig::active_profile_name();
+    let mut memories = Vec::new();
+    storage::visit_memories(project_root, Some("project"), |memory| {
+        let scoped = storage::namespace(&memory).is_some_and(|ns| storage::in_namespace(path, ns));
+        let mentioned = lint::mentioned_paths(&memory.content)
+            .iter()
+            .any(|p| paths::normalize(p) == path);
+        if (scoped || mentioned) && storage::visible_in_profile(&memory, profile.as_deref()) {
+            memories.push(memory);
+        }
+        ControlFlow::Continue(())
+    })?;
+    Ok(memories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DocsConfig;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_docs_for_nearest_first() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/cli")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("README.md"), "Squirrel").unwrap();
+        fs::write(root.join("src/cli/NOTES.md"), "CLI notes").unwrap();
+        fs::write(root.join("src/cli/stats.rs"), "").unwrap();
+        fs::write(root.join("docs/stats.md"), "See src/cli/stats.rs.").unwrap();
+        fs::write(root.join("docs/other.md"), "Nothing here").unwrap();
+        fs::write(root.join("src/cli/DRAFT.md"), "See stats.rs").unwrap();
+        fs::write(root.join(".sqrlignore"), "DRAFT.md\n").unwrap();
+
+        let matcher = DocMatcher::new(&DocsConfig::default()).unwrap();
+        let docs = docs_for(root, &matcher, "src/cli/stats.rs");
+        assert_eq!(
+            docs,
+            [
+                ("src/cli/NOTES.md".to_string(), Reason::SameDirectory),
+                ("README.md".to_string(), Reason::ParentDirectory),
+                ("docs/stats.md".to_string(), Reason::Mentions),
+            ]
+        );
+    }
+}
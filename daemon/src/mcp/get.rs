@@ -0,0 +1,93 @@
+//! MCP-002: squirrel_get_memory.
+
+use serde_json::{json, Value};
+
+use super::cache;
+use super::session::Session;
+use super::tools::get_project_root;
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::storage;
+use crate::storage::activity::Action;
+
+/// Handle squirrel_get_memory, within the client's retrieval profile if any.
+pub(super) fn handle(params: &Value, session: &Session, config: &Config) -> Result<Value, Error> {
+    let args = params.get("arguments").unwrap_or(params);
+    let retrieval = session.retrieval.clone().unwrap_or_default();
+    let max_chars = retrieval.max_chars.unwrap_or(config.mcp.context_chars);
+
+    let allowed_types = retrieval.memory_types.unwrap_or_default();
+    let memory_types: Vec<&str> = match args.get("memory_type").and_then(|t| t.as_str()) {
+        Some(t) if !allowed_types.is_empty() && !allowed_types.iter().any(|a| a == t) => {
+            return Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": "No memories found."
+                }]
+            }));
+        }
+        Some(t) => vec![t],
+        None => allowed_types.iter().map(String::as_str).collect(),
+    };
+
+    let tags: Option<Vec<String>> = args
+        .get("tags")
+        .and_then(|t| t.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .or(retrieval.tags);
+
+    let limit = match (
+        args.get("limit").and_then(|l| l.as_i64()),
+        retrieval.max_items,
+    ) {
+        (Some(asked), Some(max)) => Some(asked.min(max)),
+        (asked, max) => asked.or(max),
+    };
+
+    let files: Vec<String> = args
+        .get("files")
+        .and_then(|f| f.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let project_root = get_project_root(params)?;
+    let profile = GlobalConfig::active_profile_name();
+    let key = json!([
+        project_root,
+        memory_types,
+        tags,
+        limit,
+        max_chars,
+        profile,
+        files
+    ])
+    .to_string();
+    let markdown = cache::context(&project_root, key, || {
+        storage::format_memories_as_markdown(
+            &project_root,
+            &memory_types,
+            tags.as_deref(),
+            limit,
+            Some(max_chars),
+            profile.as_deref(),
+            &files,
+        )
+    })?;
+    session.record(&project_root, Action::Get, None);
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": markdown
+        }]
+    }))
+}
@@ -0,0 +1,197 @@
+//! MCP over HTTP for `sqrl serve` (CLI-034).
+//!
+//! Each POST to `/mcp` carries one JSON-RPC message and gets its response
+//! back as JSON: the streamable HTTP transport, without server-sent events.
+//! `initialize` starts a session and returns its `Mcp-Session-Id`; clients
+//! send it with every later request, so write limits and client attribution
+//! stay per client. Calls go through the same dispatcher as stdio, with the
+//! same slots, timeouts and store lock. Only local requests get here; the
+//! web server checks `Origin` and `Host` for every route.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::dispatch::Dispatcher;
+use super::{JsonRpcRequest, JsonRpcResponse};
+
+/// Header carrying the session ID.
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Most sessions open at once; starting another ends the least recently used.
+const MAX_SESSIONS: usize = 64;
+
+/// Sessions unused this long end, as clients often exit without a DELETE.
+const SESSION_IDLE: Duration = Duration::from_secs(30 * 60);
+
+/// A session's dispatcher and when it was last used.
+struct OpenSession {
+    dispatcher: Arc<Dispatcher>,
+    used: Instant,
+}
+
+/// Open sessions by ID.
+#[derive(Default)]
+struct Sessions(Mutex<HashMap<String, OpenSession>>);
+
+impl Sessions {
+    /// The session's dispatcher, marking it used; none if unknown or idle too long.
+    fn get(&self, id: &str) -> Option<Arc<Dispatcher>> {
+        let mut sessions = lock(&self.0);
+        let session = sessions.get_mut(id)?;
+        if session.used.elapsed() > SESSION_IDLE {
+            sessions.remove(id);
+            return None;
+        }
+        session.used = Instant::now();
+        Some(Arc::clone(&session.dispatcher))
+    }
+
+    /// Start a session, first ending idle ones and, at the cap, the least
+    /// recently used.
+    fn start(&self, id: String, dispatcher: Arc<Dispatcher>) {
+        let mut sessions = lock(&self.0);
+        sessions.retain(|_, session| session.used.elapsed() <= SESSION_IDLE);
+        if sessions.len() >= MAX_SESSIONS {
+            let oldest = sessions
+                .iter()
+                .min_by_key(|(_, session)| session.used)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                sessions.remove(&oldest);
+            }
+        }
+        sessions.insert(
+            id,
+            OpenSession {
+                dispatcher,
+                used: Instant::now(),
+            },
+        );
+    }
+
+    fn end(&self, id: &str) -> bool {
+        lock(&self.0).remove(id).is_some()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Routes for `/mcp`: POST a message, DELETE to end the session.
+pub fn router() -> Router {
+    Router::new()
+        .route("/mcp", post(handle).delete(end_session))
+        .with_state(Arc::new(Sessions::default()))
+}
+
+async fn handle(
+    State(sessions): State<Arc<Sessions>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let request: JsonRpcRequest = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            let response =
+                JsonRpcResponse::error(Value::Null, -32700, format!("Parse error: {}", e));
+            return (StatusCode::BAD_REQUEST, Json(response)).into_response();
+        }
+    };
+    debug!(method = request.method, "MCP HTTP request");
+
+    let (id, dispatcher) = if request.method == "initialize" {
+        let id = match new_session_id() {
+            Ok(id) => id,
+            Err(e) => {
+                warn!(error = %e, "No randomness for a session ID");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+        let dispatcher = Dispatcher::new();
+        sessions.start(id.clone(), Arc::clone(&dispatcher));
+        (id, dispatcher)
+    } else {
+        let Some(id) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+            return (StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id").into_response();
+        };
+        let Some(dispatcher) = sessions.get(id) else {
+            return (StatusCode::NOT_FOUND, "Unknown or ended session").into_response();
+        };
+        (id.to_string(), dispatcher)
+    };
+
+    if request.id.is_none() {
+        if request.method == "notifications/cancelled" {
+            dispatcher.cancel(&request.params);
+        }
+        return StatusCode::ACCEPTED.into_response();
+    }
+
+    // Handlers are blocking (SQLite, plugins), so keep them off the runtime
+    let response = match tokio::task::spawn_blocking(move || dispatcher.answer(request)).await {
+        Ok(response) => response,
+        Err(e) => {
+            warn!(error = %e, "MCP HTTP handler failed");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let mut response = Json(response).into_response();
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+async fn end_session(State(sessions): State<Arc<Sessions>>, headers: HeaderMap) -> StatusCode {
+    let removed = headers
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|id| sessions.end(id));
+    if removed {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// 128 bits from the operating system's random source, in hex.
+fn new_session_id() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_are_capped_and_expire() {
+        let sessions = Sessions::default();
+        for i in 0..MAX_SESSIONS + 1 {
+            sessions.start(i.to_string(), Dispatcher::new());
+        }
+        assert_eq!(lock(&sessions.0).len(), MAX_SESSIONS);
+        assert!(sessions.get("0").is_none());
+        assert!(sessions.get("1").is_some());
+
+        lock(&sessions.0).get_mut("1").unwrap().used -= SESSION_IDLE * 2;
+        assert!(sessions.get("1").is_none());
+        assert!(sessions.end("2"));
+        assert!(!sessions.end("2"));
+
+        let id = new_session_id().unwrap();
+        assert_eq!(id.len(), 32);
+        assert_ne!(id, new_session_id().unwrap());
+    }
+}
@@ -2,18 +2,30 @@
 //!
 //! MCP-001: squirrel_store_memory
 //! MCP-002: squirrel_get_memory
+//! MCP-003: squirrel_rate_memory
 
-use std::io::{BufRead, Write};
-use std::path::Path;
+mod cache;
+mod dispatch;
+mod docs;
+mod get;
+pub mod http;
+mod rate;
+mod session;
+mod store;
+mod tools;
+
+use std::io::BufRead;
+use std::sync::Mutex;
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::{debug, error, info};
 
 use crate::error::Error;
-use crate::storage;
+use session::Session;
+use tools::{get_project_root, get_tools, project_settings};
 
-const PROTOCOL_VERSION: &str = "2024-11-05";
+pub(crate) const PROTOCOL_VERSION: &str = "2024-11-05";
 const SERVER_NAME: &str = "squirrel";
 const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -63,155 +75,18 @@ impl JsonRpcResponse {
     }
 }
 
-/// MCP tool definitions.
-fn get_tools() -> Value {
-    json!({
-        "tools": [
-            {
-                "name": "squirrel_store_memory",
-                "description": "Store a behavioral correction. Use when the user corrects you or you learn a project rule.",
-                "inputSchema": {
-                    "type": "object",
-                    "properties": {
-                        "content": {
-                            "type": "string",
-                            "description": "An actionable instruction: 'Do X', 'Don't do Y', or 'When Z, do W' (1-2 sentences)"
-                        },
-                        "memory_type": {
-                            "type": "string",
-                            "enum": ["preference", "project"],
-                            "description": "Type: preference (global user preference), project (project-specific rule)"
-                        },
-                        "tags": {
-                            "type": "array",
-                            "items": { "type": "string" },
-                            "description": "Tags for organization"
-                        }
-                    },
-                    "required": ["content", "memory_type"]
-                }
-            },
-            {
-                "name": "squirrel_get_memory",
-                "description": "Get behavioral corrections from Squirrel. Call at session start or before making choices.",
-                "inputSchema": {
-                    "type": "object",
-                    "properties": {
-                        "memory_type": {
-                            "type": "string",
-                            "enum": ["preference", "project"],
-                            "description": "Filter by type. Omit to get all."
-                        },
-                        "tags": {
-                            "type": "array",
-                            "items": { "type": "string" },
-                            "description": "Filter by tags. Omit to get all."
-                        },
-                        "limit": {
-                            "type": "integer",
-                            "description": "Max memories to return. Default 50."
-                        }
-                    },
-                    "required": []
-                }
-            }
-        ]
-    })
-}
-
-/// Get project root from MCP params, falling back to cwd.
-fn get_project_root(params: &Value) -> Result<std::path::PathBuf, Error> {
-    // Try to get from arguments
-    if let Some(root) = params
-        .get("arguments")
-        .and_then(|a| a.get("project_root"))
-        .and_then(|p| p.as_str())
-    {
-        let path = Path::new(root);
-        if path.exists() {
-            return Ok(path.to_path_buf());
-        }
-    }
-
-    // Fall back to current working directory
-    std::env::current_dir().map_err(Error::Io)
-}
-
-/// Handle squirrel_store_memory.
-fn handle_store_memory(params: &Value) -> Result<Value, Error> {
-    let args = params.get("arguments").unwrap_or(params);
-
-    let content = args
-        .get("content")
-        .and_then(|c| c.as_str())
-        .ok_or_else(|| Error::Mcp("Missing 'content' parameter".to_string()))?;
-
-    let memory_type = args
-        .get("memory_type")
-        .and_then(|t| t.as_str())
-        .ok_or_else(|| Error::Mcp("Missing 'memory_type' parameter".to_string()))?;
-
-    let tags: Vec<String> = args
-        .get("tags")
-        .and_then(|t| t.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let project_root = get_project_root(params)?;
-    let (_id, deduplicated, use_count) =
-        storage::store_memory(&project_root, memory_type, content, &tags)?;
-
-    let msg = if deduplicated {
-        format!("Memory reinforced (use_count: {}): {}", use_count, content)
-    } else {
-        format!("Memory stored: {}", content)
-    };
-
-    Ok(json!({
-        "content": [{
-            "type": "text",
-            "text": msg
-        }]
-    }))
-}
-
-/// Handle squirrel_get_memory.
-fn handle_get_memory(params: &Value) -> Result<Value, Error> {
-    let args = params.get("arguments").unwrap_or(params);
-
-    let memory_type = args.get("memory_type").and_then(|t| t.as_str());
-
-    let tags: Option<Vec<String>> = args.get("tags").and_then(|t| t.as_array()).map(|arr| {
-        arr.iter()
-            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-            .collect()
-    });
-
-    let limit = args.get("limit").and_then(|l| l.as_i64());
-
-    let project_root = get_project_root(params)?;
-    let markdown =
-        storage::format_memories_as_markdown(&project_root, memory_type, tags.as_deref(), limit)?;
-
-    Ok(json!({
-        "content": [{
-            "type": "text",
-            "text": markdown
-        }]
-    }))
-}
+/// Stores run one at a time, so two calls storing the same content can't
+/// both find it new. The web API takes it too when served alongside.
+pub(crate) static STORES: Mutex<()> = Mutex::new(());
 
 /// Handle incoming MCP request.
-fn handle_request(request: &JsonRpcRequest) -> JsonRpcResponse {
+fn handle_request(request: &JsonRpcRequest, session: &Mutex<Session>) -> JsonRpcResponse {
     let id = request.id.clone().unwrap_or(Value::Null);
 
     match request.method.as_str() {
         "initialize" => {
             info!("MCP initialize");
+            *session.lock().unwrap_or_else(|e| e.into_inner()) = Session::start(&request.params);
             JsonRpcResponse::success(
                 id,
                 json!({
@@ -246,12 +121,39 @@ fn handle_request(request: &JsonRpcRequest) -> JsonRpcResponse {
 
             debug!(tool = tool_name, "MCP tools/call");
 
+            let settings = match project_settings(Some(&request.params)) {
+                Ok(settings) => settings,
+                Err(e) => return JsonRpcResponse::error(id, -32000, e.to_string()),
+            };
+            if settings.mcp.tools.is_disabled(tool_name) {
+                return JsonRpcResponse::error(
+                    id,
+                    -32000,
+                    format!(
+                        "{} is disabled for this project (mcp.tools.disabled)",
+                        tool_name
+                    ),
+                );
+            }
+
+            let session = session.lock().unwrap_or_else(|e| e.into_inner()).clone();
             match tool_name {
-                "squirrel_store_memory" => match handle_store_memory(&request.params) {
+                "squirrel_store_memory" => {
+                    let _store = STORES.lock().unwrap_or_else(|e| e.into_inner());
+                    match store::handle(&request.params, &session, &settings) {
+                        Ok(result) => JsonRpcResponse::success(id, result),
+                        Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
+                    }
+                }
+                "squirrel_get_memory" => match get::handle(&request.params, &session, &settings) {
+                    Ok(result) => JsonRpcResponse::success(id, result),
+                    Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
+                },
+                "squirrel_rate_memory" => match rate::handle(&request.params, &session) {
                     Ok(result) => JsonRpcResponse::success(id, result),
                     Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
                 },
-                "squirrel_get_memory" => match handle_get_memory(&request.params) {
+                "squirrel_get_doc_for_code" => match docs::handle(&request.params) {
                     Ok(result) => JsonRpcResponse::success(id, result),
                     Err(e) => JsonRpcResponse::error(id, -32000, e.to_string()),
                 },
@@ -271,7 +173,7 @@ pub fn run() -> Result<(), Error> {
     info!("Starting MCP server");
 
     let stdin = std::io::stdin();
-    let mut stdout = std::io::stdout();
+    let dispatcher = dispatch::Dispatcher::new();
 
     for line in stdin.lock().lines() {
         let line = line?;
@@ -287,28 +189,25 @@ pub fn run() -> Result<(), Error> {
                 error!(error = %e, "Failed to parse MCP request");
                 let response =
                     JsonRpcResponse::error(Value::Null, -32700, format!("Parse error: {}", e));
-                let response_str = serde_json::to_string(&response)?;
-                writeln!(stdout, "{}", response_str)?;
-                stdout.flush()?;
+                dispatcher.write(&response)?;
                 continue;
             }
         };
 
+        if request.method == "notifications/cancelled" {
+            dispatcher.cancel(&request.params);
+            continue;
+        }
         // Skip notifications (no id)
         if request.id.is_none() && request.method.starts_with("notifications/") {
             debug!(method = request.method, "Skipping notification");
             continue;
         }
 
-        let response = handle_request(&request);
-        let response_str = serde_json::to_string(&response)?;
-
-        debug!(response = %response_str, "MCP response");
-
-        writeln!(stdout, "{}", response_str)?;
-        stdout.flush()?;
+        dispatcher.dispatch(request)?;
     }
 
+    dispatcher.finish();
     info!("MCP server stopped");
     Ok(())
 }
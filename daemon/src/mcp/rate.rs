@@ -0,0 +1,43 @@
+//! MCP-003: squirrel_rate_memory.
+
+use serde_json::{json, Value};
+
+use super::session::Session;
+use super::tools::get_project_root;
+use crate::error::Error;
+use crate::storage;
+use crate::storage::activity::Action;
+
+/// Handle squirrel_rate_memory.
+pub(super) fn handle(params: &Value, session: &Session) -> Result<Value, Error> {
+    let args = params.get("arguments").unwrap_or(params);
+
+    let id = args
+        .get("id")
+        .and_then(|i| i.as_str())
+        .ok_or_else(|| Error::Mcp("Missing 'id' parameter".to_string()))?;
+
+    let useful = args
+        .get("useful")
+        .and_then(|u| u.as_bool())
+        .ok_or_else(|| Error::Mcp("Missing 'useful' parameter".to_string()))?;
+
+    let project_root = get_project_root(params)?;
+    let storage = storage::Storage::open(&storage::db_path(&project_root))?;
+    let id = storage.resolve_id(id)?;
+    let rating = storage.rate_memory(&id, useful)?;
+    session.record(&project_root, Action::Rate, Some(&id));
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": format!(
+                "Rated [{}] {} (useful: {}, unhelpful: {})",
+                storage::short_id(&id),
+                if useful { "useful" } else { "unhelpful" },
+                rating.useful,
+                rating.unhelpful
+            )
+        }]
+    }))
+}
@@ -0,0 +1,179 @@
+//! Per-client session state: retrieval profile, write limits, activity log.
+
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::config::{Config, McpLimits};
+use crate::error::Error;
+use crate::global_config::{GlobalConfig, RetrievalProfile};
+use crate::storage;
+use crate::storage::activity::{Action, UNKNOWN_CLIENT};
+
+/// Window for `mcp.limits.writes_per_minute`.
+const WRITE_WINDOW: Duration = Duration::from_secs(60);
+
+/// State for one connected client. Tool calls run on copies; the write
+/// counts are shared between them.
+#[derive(Debug, Clone)]
+pub(super) struct Session {
+    /// Name and version from `clientInfo`, logged with each tool call.
+    pub(super) client: String,
+    version: String,
+    /// Limits for this client from `retrieval` in the global config.
+    pub(super) retrieval: Option<RetrievalProfile>,
+    writes: Arc<Mutex<Writes>>,
+    /// Projects whose expired activity this session already deleted.
+    expired: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+/// Stores accepted so far in a session.
+#[derive(Debug, Default)]
+struct Writes {
+    /// When recent stores were accepted, for `mcp.limits.writes_per_minute`.
+    recent: VecDeque<Instant>,
+    /// New memories stored, for `mcp.limits.new_per_session`.
+    created: u32,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            client: UNKNOWN_CLIENT.to_string(),
+            version: String::new(),
+            retrieval: None,
+            writes: Arc::default(),
+            expired: Arc::default(),
+        }
+    }
+}
+
+impl Session {
+    /// Pick the retrieval profile for the client named in `initialize`.
+    pub(super) fn start(params: &Value) -> Self {
+        let client = params.get("clientInfo");
+        let field = |key| {
+            client
+                .and_then(|c| c.get(key))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+        };
+        let (name, version) = (field("name"), field("version"));
+        let retrieval = GlobalConfig::load()
+            .ok()
+            .and_then(|config| config.retrieval_profile(name, version).cloned());
+        info!(
+            client = name,
+            version,
+            profile = retrieval.is_some(),
+            "MCP client"
+        );
+        Self {
+            client: if name.is_empty() {
+                UNKNOWN_CLIENT
+            } else {
+                name
+            }
+            .to_string(),
+            version: version.to_string(),
+            retrieval,
+            ..Self::default()
+        }
+    }
+
+    /// Count a store against the project's limits, or explain which one it hits.
+    pub(super) fn admit_write(&self, limits: &McpLimits, is_new: bool) -> Result<(), Error> {
+        let mut writes = self.writes.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        while writes
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= WRITE_WINDOW)
+        {
+            writes.recent.pop_front();
+        }
+        let per_minute = limits.writes_per_minute as usize;
+        if per_minute > 0 && writes.recent.len() >= per_minute {
+            let wait = writes
+                .recent
+                .front()
+                .map_or(WRITE_WINDOW, |at| WRITE_WINDOW - now.duration_since(*at));
+            return Err(Error::Mcp(format!(
+                "Rate limit: at most {} stores per minute (mcp.limits.writes_per_minute). \
+                 Retry in {}s, and only store corrections worth keeping.",
+                per_minute,
+                wait.as_secs() + 1
+            )));
+        }
+        if is_new && limits.new_per_session > 0 && writes.created >= limits.new_per_session {
+            return Err(Error::Mcp(format!(
+                "Quota reached: {} new memories this session (mcp.limits.new_per_session). \
+                 Existing memories can still be reinforced.",
+                limits.new_per_session
+            )));
+        }
+        writes.recent.push_back(now);
+        if is_new {
+            writes.created += 1;
+        }
+        Ok(())
+    }
+
+    /// Log a tool call in the project's database, first deleting activity and
+    /// changes past `retention.activity_days` once a session (CONFIG-008).
+    /// Failures are logged, not returned.
+    pub(super) fn record(&self, project_root: &Path, action: Action, memory_id: Option<&str>) {
+        let recorded = storage::Storage::open(&storage::db_path(project_root)).and_then(|s| {
+            self.expire(project_root, &s)?;
+            s.record_activity(&self.client, &self.version, action, memory_id)
+        });
+        if let Err(e) = recorded {
+            warn!(error = %e, "Could not record MCP activity");
+        }
+    }
+
+    fn expire(&self, project_root: &Path, storage: &storage::Storage) -> Result<(), Error> {
+        let mut expired = self.expired.lock().unwrap_or_else(|e| e.into_inner());
+        if !expired.insert(project_root.to_path_buf()) {
+            return Ok(());
+        }
+        let days = Config::effective(project_root)?.retention.activity_days;
+        if days > 0 {
+            let before = chrono::Utc::now() - chrono::Duration::days(days.into());
+            storage.expire_activity(&before.to_rfc3339())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_limits() {
+        let session = Session::default();
+        let limits = McpLimits {
+            writes_per_minute: 3,
+            new_per_session: 1,
+        };
+        assert!(session.admit_write(&limits, true).is_ok());
+        // Quota spent: new memories are refused, reinforcements are not
+        assert!(session.admit_write(&limits, true).is_err());
+        assert!(session.admit_write(&limits, false).is_ok());
+        assert!(session.admit_write(&limits, false).is_ok());
+        // Three accepted writes fill the window
+        let err = session.admit_write(&limits, false).unwrap_err();
+        assert!(err.to_string().contains("writes_per_minute"));
+
+        let unlimited = McpLimits {
+            writes_per_minute: 0,
+            new_per_session: 0,
+        };
+        assert!(session.admit_write(&unlimited, true).is_ok());
+    }
+}
@@ -0,0 +1,135 @@
+//! MCP-001: squirrel_store_memory.
+
+use serde_json::{json, Value};
+
+use super::session::Session;
+use super::tools::get_project_root;
+use crate::cli::plugins;
+use crate::config::Config;
+use crate::error::Error;
+use crate::global_config::{projects, GlobalConfig, PluginEvent};
+use crate::storage;
+use crate::storage::activity::Action;
+
+/// Handle squirrel_store_memory.
+pub(super) fn handle(params: &Value, session: &Session, config: &Config) -> Result<Value, Error> {
+    let settings = &config.mcp;
+    let args = params.get("arguments").unwrap_or(params);
+
+    let content = args
+        .get("content")
+        .and_then(|c| c.as_str())
+        .ok_or_else(|| Error::Mcp("Missing 'content' parameter".to_string()))?;
+
+    let memory_type = args
+        .get("memory_type")
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| Error::Mcp("Missing 'memory_type' parameter".to_string()))?;
+
+    // Secrets never reach the database (CONFIG-007)
+    let (content, redacted) = config.redactor()?.redact(content);
+    let content = content.as_str();
+    let note = if redacted.is_empty() {
+        String::new()
+    } else {
+        format!(" (redacted: {})", redacted.join(", "))
+    };
+
+    let mut tags: Vec<String> = args
+        .get("tags")
+        .and_then(|t| t.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Project memories about one namespace's files are scoped to it (CONFIG-009)
+    if memory_type == "project"
+        && !tags
+            .iter()
+            .any(|t| t.starts_with(storage::NAMESPACE_TAG_PREFIX))
+    {
+        if let Some(namespace) = storage::derive_namespace(content, &config.namespaces) {
+            tags = storage::with_namespace(&tags, Some(&namespace));
+        }
+    }
+
+    // Preferences belong to the active profile, if any
+    if memory_type == "preference" {
+        if let Some(profile) = GlobalConfig::active_profile_name() {
+            tags.push(format!("{}{}", storage::PROFILE_TAG_PREFIX, profile));
+        }
+    }
+
+    let project_root = get_project_root(params)?;
+    let db = storage::Storage::open(&storage::db_path(&project_root))?;
+    let is_new = !db.has_content(content)?;
+    session.admit_write(&settings.limits, is_new)?;
+
+    // Reinforcing an approved memory adds nothing new, so only new content waits
+    if is_new && settings.require_approval {
+        let (id, queued) = db.propose_memory(memory_type, content, &tags, &session.client)?;
+        if !queued {
+            return Ok(json!({
+                "content": [{
+                    "type": "text",
+                    "text": format!(
+                        "Memory already stored [{}]: {}{}",
+                        storage::short_id(&id),
+                        content,
+                        note
+                    )
+                }]
+            }));
+        }
+        session.record(&project_root, Action::Store, Some(&id));
+        return Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": format!(
+                    "Memory stored pending approval [{}]: {}{} (the user reviews it with 'sqrl memory pending')",
+                    storage::short_id(&id),
+                    content,
+                    note
+                )
+            }]
+        }));
+    }
+    let (id, deduplicated, use_count) =
+        storage::store_memory(&project_root, memory_type, content, &tags)?;
+    projects::touch(&project_root);
+    session.record(&project_root, Action::Store, Some(&id));
+    if !deduplicated {
+        plugins::run(
+            PluginEvent::MemoryCreated,
+            &project_root,
+            json!({ "id": id, "memory_type": memory_type, "content": content, "tags": tags }),
+        );
+    }
+
+    let msg = if deduplicated {
+        format!(
+            "Memory reinforced [{}] (use_count: {}): {}{}",
+            storage::short_id(&id),
+            use_count,
+            content,
+            note
+        )
+    } else {
+        format!(
+            "Memory stored [{}]: {}{}",
+            storage::short_id(&id),
+            content,
+            note
+        )
+    };
+
+    Ok(json!({
+        "content": [{
+            "type": "text",
+            "text": msg
+        }]
+    }))
+}
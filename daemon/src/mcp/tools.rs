@@ -0,0 +1,142 @@
+//! Tool definitions and the project a call targets.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::error::Error;
+
+/// MCP tool definitions, without those the current project disables.
+pub(super) fn get_tools() -> Value {
+    let mut tools = all_tools();
+    // An unreadable config hides nothing here; each call then fails with its error
+    let disabled = project_settings(None).unwrap_or_default().mcp.tools;
+    if let Some(list) = tools["tools"].as_array_mut() {
+        list.retain(|tool| !disabled.is_disabled(tool["name"].as_str().unwrap_or_default()));
+    }
+    tools
+}
+
+/// Config of the project a call targets (`project_root` argument or cwd).
+/// Defaults outside a project.
+pub(super) fn project_settings(params: Option<&Value>) -> Result<Config, Error> {
+    let root = match params {
+        Some(params) => get_project_root(params)?,
+        None => crate::config::project_root()?,
+    };
+    if !Config::exists(&root) {
+        return Ok(Config::default());
+    }
+    Config::effective(&root)
+}
+
+fn all_tools() -> Value {
+    json!({
+        "tools": [
+            {
+                "name": "squirrel_store_memory",
+                "description": "Store a behavioral correction. Use when the user corrects you or you learn a project rule.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "content": {
+                            "type": "string",
+                            "description": "An actionable instruction: 'Do X', 'Don't do Y', or 'When Z, do W' (1-2 sentences)"
+                        },
+                        "memory_type": {
+                            "type": "string",
+                            "enum": ["preference", "project"],
+                            "description": "Type: preference (global user preference), project (project-specific rule)"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Tags for organization"
+                        }
+                    },
+                    "required": ["content", "memory_type"]
+                }
+            },
+            {
+                "name": "squirrel_get_memory",
+                "description": "Get behavioral corrections from Squirrel. Call at session start or before making choices.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_type": {
+                            "type": "string",
+                            "enum": ["preference", "project"],
+                            "description": "Filter by type. Omit to get all."
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Filter by tags. Omit to get all."
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Max memories to return. Default 50."
+                        },
+                        "files": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Project-relative paths you are working on. Memories scoped to their directories come first."
+                        }
+                    },
+                    "required": []
+                }
+            },
+            {
+                "name": "squirrel_rate_memory",
+                "description": "Rate a memory from squirrel_get_memory. Rate useful=false when it was wrong, outdated, or irrelevant to the task.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Memory ID as shown by squirrel_get_memory (e.g. ae777456)"
+                        },
+                        "useful": {
+                            "type": "boolean",
+                            "description": "Whether the memory helped"
+                        }
+                    },
+                    "required": ["id", "useful"]
+                }
+            },
+            {
+                "name": "squirrel_get_doc_for_code",
+                "description": "Find the docs for a code file before editing it: docs in its directory and above, docs that mention it, and project memories about it.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "path": {
+                            "type": "string",
+                            "description": "Project-relative path of the file or directory (e.g. src/cli/stats.rs)"
+                        }
+                    },
+                    "required": ["path"]
+                }
+            }
+        ]
+    })
+}
+
+/// Get project root from MCP params, falling back to cwd.
+pub(super) fn get_project_root(params: &Value) -> Result<PathBuf, Error> {
+    // Try to get from arguments
+    if let Some(root) = params
+        .get("arguments")
+        .and_then(|a| a.get("project_root"))
+        .and_then(|p| p.as_str())
+    {
+        let path = Path::new(root);
+        if path.exists() {
+            return Ok(path.to_path_buf());
+        }
+    }
+
+    // Fall back to the project containing the working directory
+    crate::config::project_root()
+}
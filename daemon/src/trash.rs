@@ -0,0 +1,218 @@
+//! Holding area for destructive operations (TRASH-001).
+//!
+//! Instead of deleting, commands move what they remove into a trash entry:
+//! `.sqrl/trash/` for data inside a project, `~/.sqrl/trash/` when the
+//! project's `.sqrl/` itself goes away. `sqrl undo` restores the newest
+//! entry. Entries older than `trash.retention_days` are purged whenever a
+//! new one is written.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::atomic;
+use crate::error::Error;
+use crate::global_config::{GlobalConfig, TrashConfig};
+
+/// Metadata file inside each entry directory.
+const ENTRY_FILE: &str = "entry.json";
+
+/// What produced a trash entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// `sqrl memory delete`: payload is `memory.json`.
+    MemoryDelete,
+    /// `sqrl goaway`: payload is the removed `sqrl/` dir and `skill/` if any.
+    Goaway,
+}
+
+/// A restorable trash entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub kind: Kind,
+    /// Project the data came from.
+    pub project: PathBuf,
+    /// RFC 3339, UTC, fixed width so entries sort as strings.
+    pub created_at: String,
+    /// One line for `sqrl undo` output.
+    pub summary: String,
+    /// Entry directory holding the payload.
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+/// Trash directory inside a project.
+pub fn project_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".sqrl").join("trash")
+}
+
+/// Trash directory for data whose project directory is gone.
+pub fn global_dir() -> Result<PathBuf, Error> {
+    Ok(GlobalConfig::dir()?.join("trash"))
+}
+
+impl Entry {
+    /// Create an empty entry; the caller moves the payload into `dir`.
+    pub fn create(
+        trash_dir: &Path,
+        kind: Kind,
+        project: &Path,
+        summary: impl Into<String>,
+    ) -> Result<Self, Error> {
+        purge(trash_dir);
+
+        let now = chrono::Utc::now();
+        let mut dir = trash_dir.join(now.timestamp_millis().to_string());
+        // Two entries in the same millisecond
+        for n in 1.. {
+            if !dir.exists() {
+                break;
+            }
+            dir = trash_dir.join(format!("{}-{}", now.timestamp_millis(), n));
+        }
+        fs::create_dir_all(&dir).map_err(Error::at(&dir))?;
+
+        let entry = Self {
+            kind,
+            project: project.to_path_buf(),
+            created_at: now.to_rfc3339_opts(SecondsFormat::Micros, true),
+            summary: summary.into(),
+            dir,
+        };
+        atomic::write(
+            &entry.dir.join(ENTRY_FILE),
+            serde_json::to_string_pretty(&entry)?,
+        )?;
+        Ok(entry)
+    }
+
+    /// Newest entry for `project` across the given trash directories.
+    pub fn latest(trash_dirs: &[PathBuf], project: &Path) -> Result<Option<Self>, Error> {
+        let mut newest: Option<Self> = None;
+        for trash_dir in trash_dirs {
+            let Ok(dirs) = fs::read_dir(trash_dir) else {
+                continue;
+            };
+            for dir in dirs.flatten().map(|e| e.path()) {
+                let Some(entry) = Self::read(&dir) else {
+                    continue;
+                };
+                let is_newer = newest
+                    .as_ref()
+                    .is_none_or(|n| entry.created_at > n.created_at);
+                if entry.project == project && is_newer {
+                    newest = Some(entry);
+                }
+            }
+        }
+        Ok(newest)
+    }
+
+    /// Path of a payload file or directory in this entry.
+    pub fn payload(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Delete the entry after a successful restore.
+    pub fn remove(self) -> Result<(), Error> {
+        fs::remove_dir_all(&self.dir).map_err(Error::at(&self.dir))
+    }
+
+    fn read(dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(dir.join(ENTRY_FILE)).ok()?;
+        let mut entry: Self = serde_json::from_str(&content).ok()?;
+        entry.dir = dir.to_path_buf();
+        Some(entry)
+    }
+}
+
+/// Move a file or directory, copying when a rename can't cross filesystems.
+pub fn move_path(from: &Path, to: &Path) -> Result<(), Error> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(Error::at(parent))?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from).map_err(Error::at(from))
+    } else {
+        fs::remove_file(from).map_err(Error::at(from))
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<(), Error> {
+    if from.is_dir() {
+        fs::create_dir_all(to).map_err(Error::at(to))?;
+        for entry in fs::read_dir(from).map_err(Error::at(from))? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to).map_err(Error::at(from))?;
+    }
+    Ok(())
+}
+
+/// Oldest time an entry can have been created and still be restored.
+pub fn restorable_since() -> chrono::DateTime<chrono::Utc> {
+    let retention_days = GlobalConfig::load()
+        .map(|c| c.trash)
+        .unwrap_or_else(|_| TrashConfig::default())
+        .retention_days;
+    chrono::Utc::now() - chrono::Duration::days(retention_days.into())
+}
+
+/// Remove entries older than the retention period. Best effort.
+fn purge(trash_dir: &Path) {
+    let cutoff = restorable_since();
+
+    let Ok(dirs) = fs::read_dir(trash_dir) else {
+        return;
+    };
+    for dir in dirs.flatten().map(|e| e.path()) {
+        let expired = Entry::read(&dir)
+            .and_then(|e| chrono::DateTime::parse_from_rfc3339(&e.created_at).ok())
+            .is_some_and(|created| created < cutoff);
+        if expired {
+            debug!(path = %dir.display(), "Purging trash entry");
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                warn!(path = %dir.display(), error = %e, "Failed to purge trash entry");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_latest_entry_per_project() {
+        let dir = TempDir::new().unwrap();
+        let trash = dir.path().join("trash");
+        let project = dir.path().join("p");
+
+        let first = Entry::create(&trash, Kind::MemoryDelete, &project, "first").unwrap();
+        fs::write(first.payload("memory.json"), "{}").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        Entry::create(&trash, Kind::Goaway, &project, "second").unwrap();
+        Entry::create(&trash, Kind::Goaway, &dir.path().join("other"), "other").unwrap();
+
+        let latest = Entry::latest(std::slice::from_ref(&trash), &project)
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.summary, "second");
+        latest.remove().unwrap();
+
+        let latest = Entry::latest(&[trash], &project).unwrap().unwrap();
+        assert_eq!(latest.kind, Kind::MemoryDelete);
+        assert!(latest.payload("memory.json").exists());
+    }
+}
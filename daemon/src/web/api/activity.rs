@@ -0,0 +1,114 @@
+//! Change feed, usage and health endpoints (project-specific).
+
+use axum::{extract::Query, http::header, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+
+use super::{ApiResponse, ProjectQuery};
+use crate::cli::health;
+use crate::config::Config;
+use crate::storage::changes::Change;
+use crate::storage::usage::{self, UsageDay, UsageStats};
+use crate::storage::Storage;
+
+#[derive(Deserialize)]
+pub struct ChangesQuery {
+    project: String,
+    #[serde(default)]
+    since: i64,
+}
+
+#[derive(Serialize)]
+struct ChangesResponse {
+    generation: i64,
+    changes: Vec<Change>,
+}
+
+/// Changes to memories, ratings and proposals after `since`, oldest first,
+/// with the generation to pass as `since` next time.
+pub async fn list_changes(Query(query): Query<ChangesQuery>) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    let feed = Storage::open(&db_path).and_then(|storage| {
+        // Read first: a change landing in between is listed now and again next poll
+        let generation = storage.generation()?;
+        let changes = storage.changes_since(query.since)?;
+        Ok(ChangesResponse {
+            generation,
+            changes,
+        })
+    });
+    match feed {
+        Ok(feed) => ApiResponse::ok(feed).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+#[derive(Serialize)]
+struct UsageResponse {
+    enabled: bool,
+    totals: Vec<UsageStats>,
+    daily: Vec<UsageDay>,
+}
+
+/// Usage analytics for charts: totals per command, hook and tool, and uses per day.
+pub async fn get_usage(Query(query): Query<ProjectQuery>) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    let usage = Storage::open(&db_path).and_then(|storage| {
+        Ok(UsageResponse {
+            enabled: usage::enabled(),
+            totals: storage.usage_stats()?,
+            daily: storage.usage_by_day()?,
+        })
+    });
+    match usage {
+        Ok(usage) => ApiResponse::ok(usage).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// Health score and its parts (CONFIG-011).
+pub async fn get_health(Query(query): Query<ProjectQuery>) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    if !Config::exists(&project_path) {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+    // Walks git history and opens the database; keep it off the async workers
+    match tokio::task::spawn_blocking(move || health::assess(&project_path)).await {
+        Ok(Ok(health)) => ApiResponse::ok(health).into_response(),
+        Ok(Err(e)) => ApiResponse::error(e.to_string()).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// Health score as an SVG badge, for dashboards. Never cached: the score
+/// changes with every commit.
+pub async fn get_health_badge(Query(query): Query<ProjectQuery>) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    if !Config::exists(&project_path) {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+    let score = tokio::task::spawn_blocking(move || health::assess(&project_path))
+        .await
+        .ok()
+        .and_then(|health| health.ok())
+        .and_then(|health| health.score);
+    (
+        [
+            (header::CONTENT_TYPE, "image/svg+xml"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        health::badge(score),
+    )
+        .into_response()
+}
@@ -0,0 +1,159 @@
+//! Global config, MCP and MCP template endpoints (~/.sqrl).
+
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use super::ApiResponse;
+use crate::global_config::managed::{DriftKind, ManagedEntry, Manifest};
+use crate::global_config::{catalog, GlobalConfig, McpConfig};
+
+pub async fn get_config() -> impl IntoResponse {
+    match GlobalConfig::load() {
+        Ok(config) => ApiResponse::ok(config).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn update_config(Json(config): Json<GlobalConfig>) -> impl IntoResponse {
+    match config.save() {
+        Ok(()) => ApiResponse::ok(config).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+// === MCP endpoints ===
+
+pub async fn list_mcps() -> impl IntoResponse {
+    match GlobalConfig::list_mcps() {
+        Ok(mcps) => ApiResponse::ok(mcps).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// 400 for a name that isn't an MCP config name, before it becomes a path.
+fn bad_mcp_name(name: &str) -> Option<(StatusCode, Json<ApiResponse<()>>)> {
+    (!McpConfig::valid_name(name))
+        .then(|| ApiResponse::error(format!("invalid MCP name '{}'", name)))
+}
+
+pub async fn get_mcp(Path(name): Path<String>) -> impl IntoResponse {
+    if let Some(bad) = bad_mcp_name(&name) {
+        return bad.into_response();
+    }
+    match GlobalConfig::get_mcp(&name) {
+        Ok(mcp) => ApiResponse::ok(mcp).into_response(),
+        Err(e) => ApiResponse::not_found(e.to_string()).into_response(),
+    }
+}
+
+pub async fn create_mcp(Json(mcp): Json<McpConfig>) -> impl IntoResponse {
+    match GlobalConfig::save_mcp(&mcp) {
+        Ok(()) => ApiResponse::ok(mcp).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn update_mcp(
+    Path(name): Path<String>,
+    Json(mut mcp): Json<McpConfig>,
+) -> impl IntoResponse {
+    if let Some(bad) = bad_mcp_name(&name) {
+        return bad.into_response();
+    }
+    mcp.name = name;
+    match GlobalConfig::save_mcp(&mcp) {
+        Ok(()) => ApiResponse::ok(mcp).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn delete_mcp(Path(name): Path<String>) -> impl IntoResponse {
+    if let Some(bad) = bad_mcp_name(&name) {
+        return bad.into_response();
+    }
+    match GlobalConfig::delete_mcp(&name) {
+        Ok(()) => ApiResponse::ok(()).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// A managed entry with how it compares to its file now.
+#[derive(Serialize)]
+struct AppliedEntry {
+    #[serde(flatten)]
+    entry: ManagedEntry,
+    /// `applied`, `modified` or `removed`.
+    state: &'static str,
+}
+
+/// Where `sqrl apply` registered each MCP (MANAGED-001), with drift.
+pub async fn list_applied() -> impl IntoResponse {
+    let manifest = match Manifest::load() {
+        Ok(manifest) => manifest,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+    let entries: Vec<AppliedEntry> = manifest
+        .entries
+        .into_iter()
+        .map(|entry| AppliedEntry {
+            state: match entry.drift() {
+                None => "applied",
+                Some(DriftKind::Modified) => "modified",
+                Some(DriftKind::Removed) => "removed",
+            },
+            entry,
+        })
+        .collect();
+    ApiResponse::ok(entries).into_response()
+}
+
+// === MCP template endpoints ===
+
+#[derive(Deserialize)]
+pub struct InstantiateTemplateRequest {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    params: std::collections::BTreeMap<String, String>,
+}
+
+pub async fn list_mcp_templates() -> impl IntoResponse {
+    ApiResponse::ok(catalog::templates()).into_response()
+}
+
+pub async fn instantiate_mcp_template(
+    Path(id): Path<String>,
+    Json(req): Json<InstantiateTemplateRequest>,
+) -> impl IntoResponse {
+    let Some(template) = catalog::get_template(&id) else {
+        return ApiResponse::not_found(format!("Unknown template: {}", id)).into_response();
+    };
+
+    let mcp = match catalog::instantiate(template, req.name.as_deref(), &req.params) {
+        Ok(mcp) => mcp,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    match GlobalConfig::save_mcp(&mcp) {
+        Ok(()) => ApiResponse::ok(mcp).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mcp_routes_traversal_name_bad_request() {
+        let name = || Path("../projects".to_string());
+        assert_eq!(
+            get_mcp(name()).await.into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            delete_mcp(name()).await.into_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Memory endpoints (project-specific, .sqrl/memory.db).
+
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+
+use super::{ApiResponse, ProjectQuery};
+use crate::error::Error;
+use crate::mcp;
+use crate::storage::Storage;
+
+#[derive(Deserialize)]
+pub struct CreateMemoryRequest {
+    memory_type: String,
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMemoryRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+}
+
+pub async fn list_memories(Query(query): Query<ProjectQuery>) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage.list_all_memories() {
+            Ok(memories) => ApiResponse::ok(memories).into_response(),
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_memory(
+    Path(id): Path<String>,
+    Query(query): Query<ProjectQuery>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage
+            .resolve_id(&id)
+            .and_then(|id| storage.get_memory(&id))
+        {
+            Ok(Some(memory)) => ApiResponse::ok(memory).into_response(),
+            Err(e @ Error::MemoryNotFound(_)) => {
+                ApiResponse::not_found(e.to_string()).into_response()
+            }
+            Ok(None) => ApiResponse::not_found("Memory not found").into_response(),
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// MCP calls that stored, reinforced, or rated a memory, oldest first.
+pub async fn get_memory_trace(
+    Path(id): Path<String>,
+    Query(query): Query<ProjectQuery>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    let events = Storage::open(&db_path).and_then(|storage| {
+        storage
+            .resolve_id(&id)
+            .and_then(|id| storage.memory_events(&id))
+    });
+    match events {
+        Ok(events) => ApiResponse::ok(events).into_response(),
+        Err(e @ Error::MemoryNotFound(_)) => ApiResponse::not_found(e.to_string()).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn create_memory(
+    Query(query): Query<ProjectQuery>,
+    Json(req): Json<CreateMemoryRequest>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    let _store = mcp::STORES.lock().unwrap_or_else(|e| e.into_inner());
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage.store_memory(&req.memory_type, &req.content, &req.tags) {
+            Ok(result) => ApiResponse::ok(result).into_response(),
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn update_memory(
+    Path(id): Path<String>,
+    Query(query): Query<ProjectQuery>,
+    Json(req): Json<UpdateMemoryRequest>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => {
+            let updated = storage.resolve_id(&id).and_then(|id| {
+                storage.update_memory(
+                    &id,
+                    req.memory_type.as_deref(),
+                    req.content.as_deref(),
+                    req.tags.as_deref(),
+                )
+            });
+            match updated {
+                Ok(()) => ApiResponse::ok(()).into_response(),
+                Err(e @ Error::MemoryNotFound(_)) => {
+                    ApiResponse::not_found(e.to_string()).into_response()
+                }
+                Err(e) => ApiResponse::error(e.to_string()).into_response(),
+            }
+        }
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn delete_memory(
+    Path(id): Path<String>,
+    Query(query): Query<ProjectQuery>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let db_path = project_path.join(".sqrl").join("memory.db");
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage
+            .resolve_id(&id)
+            .and_then(|id| storage.delete_memory(&id))
+        {
+            Ok(()) => ApiResponse::ok(()).into_response(),
+            Err(e @ Error::MemoryNotFound(_)) => {
+                ApiResponse::not_found(e.to_string()).into_response()
+            }
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,73 @@
+//! API endpoints for Squirrel web UI, one module per resource.
+
+mod activity;
+mod config;
+mod memories;
+mod preferences;
+mod rules;
+
+use axum::{http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+
+pub use activity::{get_health, get_health_badge, get_usage, list_changes};
+pub use config::{
+    create_mcp, delete_mcp, get_config, get_mcp, instantiate_mcp_template, list_applied,
+    list_mcp_templates, list_mcps, update_config, update_mcp,
+};
+pub use memories::{
+    create_memory, delete_memory, get_memory, get_memory_trace, list_memories, update_memory,
+};
+pub use preferences::{
+    create_preference, delete_preference, get_preference, list_preferences, update_preference,
+};
+pub use rules::{get_rules, preview_rules, update_rules};
+
+/// API response wrapper.
+#[derive(Serialize)]
+struct ApiResponse<T> {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    fn ok(data: T) -> Json<Self> {
+        Json(Self {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    }
+}
+
+impl ApiResponse<()> {
+    fn error(msg: impl Into<String>) -> (StatusCode, Json<Self>) {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(Self {
+                success: false,
+                data: None,
+                error: Some(msg.into()),
+            }),
+        )
+    }
+
+    fn not_found(msg: impl Into<String>) -> (StatusCode, Json<Self>) {
+        (
+            StatusCode::NOT_FOUND,
+            Json(Self {
+                success: false,
+                data: None,
+                error: Some(msg.into()),
+            }),
+        )
+    }
+}
+
+/// Query naming the project a request is about.
+#[derive(Deserialize)]
+pub struct ProjectQuery {
+    project: String,
+}
@@ -0,0 +1,183 @@
+//! Preference endpoints (global, ~/.sqrl/memory.db).
+
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+
+use super::ApiResponse;
+use crate::error::Error;
+use crate::global_config::GlobalConfig;
+use crate::mcp;
+use crate::storage::{self, Storage};
+
+#[derive(Deserialize)]
+pub struct PreferenceQuery {
+    profile: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatePreferenceRequest {
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Global profile the preference belongs to (`profile:<name>` tag).
+    profile: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdatePreferenceRequest {
+    content: Option<String>,
+    /// Replaces all tags, `profile:<name>` ones included.
+    tags: Option<Vec<String>>,
+}
+
+/// Preferences; with `profile`, only those that apply while it is active.
+pub async fn list_preferences(Query(query): Query<PreferenceQuery>) -> impl IntoResponse {
+    let db_path = match GlobalConfig::memory_db_path() {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    // Ensure parent dir exists
+    if let Some(parent) = db_path.parent() {
+        if !parent.exists() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                return ApiResponse::error(e.to_string()).into_response();
+            }
+        }
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage.list_all_memories() {
+            Ok(memories) => {
+                // Filter to only preference type
+                let prefs: Vec<_> = memories
+                    .into_iter()
+                    .filter(|m| m.memory_type == "preference")
+                    .filter(|m| {
+                        query.profile.is_none()
+                            || storage::visible_in_profile(m, query.profile.as_deref())
+                    })
+                    .collect();
+                ApiResponse::ok(prefs).into_response()
+            }
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn create_preference(Json(req): Json<CreatePreferenceRequest>) -> impl IntoResponse {
+    let db_path = match GlobalConfig::memory_db_path() {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    // Ensure parent dir exists
+    if let Some(parent) = db_path.parent() {
+        if !parent.exists() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                return ApiResponse::error(e.to_string()).into_response();
+            }
+        }
+    }
+
+    let mut tags = req.tags;
+    if let Some(profile) = req.profile.filter(|p| !p.is_empty()) {
+        tags.push(format!("{}{}", storage::PROFILE_TAG_PREFIX, profile));
+    }
+    let _store = mcp::STORES.lock().unwrap_or_else(|e| e.into_inner());
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage.store_memory("preference", &req.content, &tags) {
+            Ok(result) => ApiResponse::ok(result).into_response(),
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_preference(Path(id): Path<String>) -> impl IntoResponse {
+    let db_path = match GlobalConfig::memory_db_path() {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("No preferences database").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage
+            .resolve_id(&id)
+            .and_then(|id| storage.get_memory(&id))
+        {
+            Ok(Some(memory)) if memory.memory_type == "preference" => {
+                ApiResponse::ok(memory).into_response()
+            }
+            Err(e @ Error::MemoryNotFound(_)) => {
+                ApiResponse::not_found(e.to_string()).into_response()
+            }
+            Ok(_) => ApiResponse::not_found("Preference not found").into_response(),
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn update_preference(
+    Path(id): Path<String>,
+    Json(req): Json<UpdatePreferenceRequest>,
+) -> impl IntoResponse {
+    let db_path = match GlobalConfig::memory_db_path() {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("No preferences database").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => {
+            let updated = storage.resolve_id(&id).and_then(|id| {
+                storage.update_memory(&id, None, req.content.as_deref(), req.tags.as_deref())
+            });
+            match updated {
+                Ok(()) => ApiResponse::ok(()).into_response(),
+                Err(e @ Error::MemoryNotFound(_)) => {
+                    ApiResponse::not_found(e.to_string()).into_response()
+                }
+                Err(e) => ApiResponse::error(e.to_string()).into_response(),
+            }
+        }
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+pub async fn delete_preference(Path(id): Path<String>) -> impl IntoResponse {
+    let db_path = match GlobalConfig::memory_db_path() {
+        Ok(p) => p,
+        Err(e) => return ApiResponse::error(e.to_string()).into_response(),
+    };
+
+    if !db_path.exists() {
+        return ApiResponse::not_found("No preferences database").into_response();
+    }
+
+    match Storage::open(&db_path) {
+        Ok(storage) => match storage
+            .resolve_id(&id)
+            .and_then(|id| storage.delete_memory(&id))
+        {
+            Ok(()) => ApiResponse::ok(()).into_response(),
+            Err(e @ Error::MemoryNotFound(_)) => {
+                ApiResponse::not_found(e.to_string()).into_response()
+            }
+            Err(e) => ApiResponse::error(e.to_string()).into_response(),
+        },
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,117 @@
+//! Doc rule endpoints (project-specific, docs section of .sqrl/config.yaml).
+
+use axum::{extract::Query, response::IntoResponse, Json};
+
+use super::{ApiResponse, ProjectQuery};
+use crate::cli::rules;
+use crate::config::{Config, DocsConfig};
+use crate::error::Error;
+use crate::lock::SqrlLock;
+
+/// The project's `docs` settings as written in its config file.
+pub async fn get_rules(Query(query): Query<ProjectQuery>) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    match Config::load(&project_path) {
+        Ok(config) => ApiResponse::ok(config.docs).into_response(),
+        Err(e @ Error::ConfigNotFound(_)) => ApiResponse::not_found(e.to_string()).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// Replace the project's `docs` settings. Patterns must compile.
+pub async fn update_rules(
+    Query(query): Query<ProjectQuery>,
+    Json(docs): Json<DocsConfig>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    let saved = SqrlLock::project(&project_path).and_then(|_lock| {
+        let mut config = Config::load(&project_path)?;
+        config.docs = docs;
+        config.check()?;
+        config.save(&project_path)?;
+        Ok(config.docs)
+    });
+    match saved {
+        Ok(docs) => ApiResponse::ok(docs).into_response(),
+        Err(e @ Error::ConfigNotFound(_)) => ApiResponse::not_found(e.to_string()).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+/// Classify the project's files under proposed `docs` settings without saving them.
+pub async fn preview_rules(
+    Query(query): Query<ProjectQuery>,
+    Json(docs): Json<DocsConfig>,
+) -> impl IntoResponse {
+    let project_path = std::path::PathBuf::from(&query.project);
+    if !Config::exists(&project_path) {
+        return ApiResponse::not_found("Project not initialized").into_response();
+    }
+    match rules::preview(&project_path, &docs) {
+        Ok(preview) => ApiResponse::ok(preview).into_response(),
+        Err(e) => ApiResponse::error(e.to_string()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_preview_rules_classifies_matched_and_unmatched_paths() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let repo = Repository::init(root).unwrap();
+        std::fs::create_dir(root.join(".sqrl")).unwrap();
+        Config::default().save(root).unwrap();
+        let mut index = repo.index().unwrap();
+        for file in ["README.md", "docs/guide.md", "vendor/lib.md", "src/main.rs"] {
+            std::fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            std::fs::write(root.join(file), "x\n").unwrap();
+            index.add_path(std::path::Path::new(file)).unwrap();
+        }
+        index.write().unwrap();
+
+        let docs = DocsConfig {
+            exclude_paths: vec!["vendor/".to_string()],
+            ..Default::default()
+        };
+        let query = |project: &std::path::Path| {
+            Query(ProjectQuery {
+                project: project.to_string_lossy().into_owned(),
+            })
+        };
+        let response = preview_rules(query(root), Json(docs.clone()))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let paths = |key: &str| -> Vec<String> {
+            json["data"][key]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|o| o["path"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(json["data"]["tracked"], 4);
+        assert_eq!(paths("docs"), ["README.md", "docs/guide.md"]);
+        assert_eq!(paths("excluded"), ["vendor/lib.md"]);
+        assert_eq!(
+            json["data"]["excluded"][0]["rule"],
+            "docs.exclude_paths: vendor/"
+        );
+
+        let uninitialized = TempDir::new().unwrap();
+        let response = preview_rules(query(uninitialized.path()), Json(docs))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
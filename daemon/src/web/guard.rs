@@ -0,0 +1,90 @@
+//! Local-only access for every route (ARCH-004).
+//!
+//! The server listens on `127.0.0.1`, but a browser will still send it
+//! requests from any page the user visits. Two checks keep those out: an
+//! `Origin` header, which browsers add to cross-site requests, must name
+//! this machine, and so must the `Host` header, which a DNS rebinding page
+//! can't forge (its host is the attacker's domain).
+
+use axum::extract::Request;
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Host names that mean this machine.
+const LOCAL_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+
+/// Refuse requests from other origins or for other hosts with `403`.
+pub async fn local_only(request: Request, next: Next) -> Response {
+    if !local_origin(request.headers()) || !local_host(request.headers()) {
+        return (StatusCode::FORBIDDEN, "Only local requests are allowed").into_response();
+    }
+    next.run(request).await
+}
+
+/// No `Origin` (not a browser, or same-origin GET), or a local one.
+fn local_origin(headers: &HeaderMap) -> bool {
+    let Some(origin) = headers.get("origin") else {
+        return true;
+    };
+    let Ok(origin) = origin.to_str() else {
+        return false;
+    };
+    let authority = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    is_local(authority)
+}
+
+/// A `Host` naming this machine. HTTP/1.1 requires it.
+fn local_host(headers: &HeaderMap) -> bool {
+    headers
+        .get("host")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(is_local)
+}
+
+/// Whether `host[:port]` names this machine.
+fn is_local(authority: &str) -> bool {
+    let host = match authority.split_inclusive(']').next() {
+        Some(v6) if authority.starts_with('[') => v6,
+        _ => authority.split(':').next().unwrap_or(authority),
+    };
+    LOCAL_HOSTS.contains(&host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_only_local_origins_and_hosts() {
+        let headers = |pairs: &[(&'static str, &str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in pairs {
+                headers.insert(*name, HeaderValue::from_str(value).unwrap());
+            }
+            headers
+        };
+        let local = headers(&[("host", "localhost:3333")]);
+        assert!(local_origin(&local) && local_host(&local));
+        assert!(local_host(&headers(&[("host", "127.0.0.1")])));
+        assert!(local_host(&headers(&[("host", "[::1]:8080")])));
+        assert!(!local_host(&HeaderMap::new()));
+        // DNS rebinding: the page's own host resolves to 127.0.0.1
+        assert!(!local_host(&headers(&[("host", "evil.example.com:3333")])));
+        assert!(!local_host(&headers(&[(
+            "host",
+            "localhost.evil.example.com"
+        )])));
+
+        assert!(local_origin(&headers(&[(
+            "origin",
+            "http://localhost:3333"
+        )])));
+        assert!(!local_origin(&headers(&[(
+            "origin",
+            "https://evil.example.com"
+        )])));
+        assert!(!local_origin(&headers(&[("origin", "null")])));
+    }
+}
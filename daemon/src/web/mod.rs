@@ -2,27 +2,94 @@
 
 mod api;
 mod assets;
+mod guard;
 
 use std::net::SocketAddr;
 
-use axum::{routing::get, Router};
-use tower_http::cors::{Any, CorsLayer};
+use axum::{middleware, routing::get, Router};
 use tracing::info;
 
+use crate::cli::output::human;
 use crate::error::Error;
 use crate::global_config::GlobalConfig;
+use crate::mcp;
 
 /// Start the web server.
 pub async fn serve(open_browser: bool) -> Result<(), Error> {
     // Ensure global config exists
     GlobalConfig::init()?;
-    let config = GlobalConfig::load()?;
-    let port = config.ui.port;
+    let port = GlobalConfig::load()?.ui.port;
+    human!("Squirrel config UI: http://localhost:{}", port);
 
-    let app = Router::new()
+    if open_browser {
+        let url = format!("http://localhost:{}", port);
+        if let Err(e) = open::that(&url) {
+            eprintln!("Could not open browser: {}", e);
+        }
+    }
+    run(app(), port).await
+}
+
+/// Serve the web UI and API and MCP over HTTP (`/mcp`) on one port until
+/// interrupted (`sqrl serve`, CLI-034).
+pub async fn serve_all(port: Option<u16>) -> Result<(), Error> {
+    GlobalConfig::init()?;
+    let port = match port {
+        Some(port) => port,
+        None => GlobalConfig::load()?.ui.port,
+    };
+    human!("Squirrel config UI: http://localhost:{}", port);
+    human!("MCP (HTTP):         http://localhost:{}/mcp", port);
+    run(mcp::http::router().merge(app()), port).await
+}
+
+/// Listen on localhost until Ctrl-C or SIGTERM; requests in flight finish first.
+/// Every route only answers local requests (`guard`).
+async fn run(app: Router, port: u16) -> Result<(), Error> {
+    let app = app.layer(middleware::from_fn(guard::local_only));
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    info!("Starting web server at http://{}", addr);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown())
+        .await?;
+    info!("Web server stopped");
+    Ok(())
+}
+
+/// Resolves on Ctrl-C, or on SIGTERM where there is one.
+#[cfg(unix)]
+async fn shutdown() {
+    let mut terminate =
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).ok();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = async {
+            match terminate.as_mut() {
+                Some(signal) => signal.recv().await,
+                None => std::future::pending().await,
+            }
+        } => {}
+    }
+}
+
+#[cfg(not(unix))]
+async fn shutdown() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Web UI and API routes.
+fn app() -> Router {
+    Router::new()
         // API routes
         .route("/api/config", get(api::get_config).post(api::update_config))
         .route("/api/mcps", get(api::list_mcps).post(api::create_mcp))
+        .route("/api/mcps/templates", get(api::list_mcp_templates))
+        .route("/api/mcps/applied", get(api::list_applied))
+        .route(
+            "/api/mcps/templates/:id",
+            axum::routing::post(api::instantiate_mcp_template),
+        )
         .route(
             "/api/mcps/:name",
             get(api::get_mcp)
@@ -35,7 +102,9 @@ pub async fn serve(open_browser: bool) -> Result<(), Error> {
         )
         .route(
             "/api/preferences/:id",
-            axum::routing::delete(api::delete_preference),
+            get(api::get_preference)
+                .put(api::update_preference)
+                .delete(api::delete_preference),
         )
         .route(
             "/api/memories",
@@ -47,23 +116,16 @@ pub async fn serve(open_browser: bool) -> Result<(), Error> {
                 .put(api::update_memory)
                 .delete(api::delete_memory),
         )
+        .route("/api/memories/:id/trace", get(api::get_memory_trace))
+        .route("/api/changes", get(api::list_changes))
+        .route("/api/usage", get(api::get_usage))
+        .route("/api/health", get(api::get_health))
+        .route("/api/health/badge.svg", get(api::get_health_badge))
+        .route("/api/rules", get(api::get_rules).put(api::update_rules))
+        .route(
+            "/api/rules/preview",
+            axum::routing::post(api::preview_rules),
+        )
         // Static assets
         .fallback(assets::serve_static)
-        .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any));
-
-    let addr = SocketAddr::from(([127, 0, 0, 1], port));
-    info!("Starting web server at http://{}", addr);
-    println!("Squirrel config UI: http://localhost:{}", port);
-
-    if open_browser {
-        let url = format!("http://localhost:{}", port);
-        if let Err(e) = open::that(&url) {
-            eprintln!("Could not open browser: {}", e);
-        }
-    }
-
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
-
-    Ok(())
 }